@@ -1,7 +1,7 @@
 //! This crate is responsible for managing the clock pace.
 //!
 //! The Clock is responsible of giving informations about Epoch and Delta timing by exposing
-//! reference to the data but also by broadcasting `EpochChange` events.
+//! reference to the data but also by broadcasting `EpochChange` and `Checkpoint` events.
 
 use std::sync::{atomic::AtomicU64, Arc};
 
@@ -26,6 +26,8 @@ pub trait Clock {
 pub enum Event {
     /// Notify an Epoch change with the associated epoch_number
     EpochChange(u64),
+    /// Notify that a checkpoint is due within the current epoch
+    Checkpoint { epoch: u64, sequence_number: u64 },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -24,6 +24,7 @@ pub struct TimeClock {
     current_block: Arc<AtomicU64>,
     epoch_duration: u64,
     current_epoch: Arc<AtomicU64>,
+    checkpoint_interval: Option<u64>,
 }
 
 impl Clock for TimeClock {
@@ -56,6 +57,7 @@ impl TimeClock {
             current_block: Arc::new(AtomicU64::new(0)),
             epoch_duration,
             current_epoch: Arc::new(AtomicU64::new(0)),
+            checkpoint_interval: None,
         };
 
         clock.compute_block();
@@ -64,6 +66,14 @@ impl TimeClock {
         Ok(clock)
     }
 
+    /// Notify a checkpoint every `interval` blocks within the epochs, the sequence number of the
+    /// checkpoint being its rank in the epoch.
+    pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+        self.checkpoint_interval = Some(interval).filter(|interval| *interval > 0);
+
+        self
+    }
+
     async fn run(&mut self, sender: broadcast::Sender<Event>) {
         let mut interval = interval_at(Instant::now(), Duration::from_secs(1));
         loop {
@@ -71,11 +81,20 @@ impl TimeClock {
 
             let _previous_block = self.current_block.fetch_add(1, Ordering::Relaxed);
 
-            if self.current_block.load(Ordering::Relaxed) % self.epoch_duration == 0 {
+            let block_in_epoch = self.current_block.load(Ordering::Relaxed) % self.epoch_duration;
+            if block_in_epoch == 0 {
                 self.compute_epoch();
                 _ = sender.send(Event::EpochChange(
                     self.current_epoch.load(Ordering::Relaxed),
                 ));
+            } else if let Some(interval) = self
+                .checkpoint_interval
+                .filter(|interval| block_in_epoch % interval == 0)
+            {
+                _ = sender.send(Event::Checkpoint {
+                    epoch: self.current_epoch.load(Ordering::Relaxed),
+                    sequence_number: block_in_epoch / interval,
+                });
             }
         }
     }
@@ -147,4 +166,27 @@ mod tests {
         assert_eq!(current_epoch.load(std::sync::atomic::Ordering::Relaxed), 18);
         assert!(current_block.load(std::sync::atomic::Ordering::Relaxed) >= 35);
     }
+
+    #[tokio::test]
+    async fn test_time_clock_checkpoints() {
+        let genesis = Utc::now()
+            .checked_sub_signed(Duration::seconds(30))
+            .unwrap();
+
+        let clock = TimeClock::new(genesis, 4)
+            .unwrap()
+            .with_checkpoint_interval(2);
+
+        let mut recv = clock.spawn().unwrap();
+
+        assert_eq!(recv.recv().await, Ok(Event::EpochChange(8)));
+        assert_eq!(
+            recv.recv().await,
+            Ok(Event::Checkpoint {
+                epoch: 8,
+                sequence_number: 1
+            })
+        );
+        assert_eq!(recv.recv().await, Ok(Event::EpochChange(9)));
+    }
 }
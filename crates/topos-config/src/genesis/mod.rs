@@ -25,6 +25,9 @@ pub enum Error {
 
     #[error("Invalid genesis file on path {0}: {1}")]
     InvalidGenesisFile(String, String),
+
+    #[error("Failed to parse timestamp")]
+    ParseTimestamp,
 }
 
 impl Genesis {
@@ -61,6 +64,19 @@ impl Genesis {
         }
     }
 
+    /// Parse the hexadecimal `timestamp` field of the genesis file, as a unix timestamp in seconds.
+    /// A missing or zero timestamp is considered as unset
+    pub fn timestamp(&self) -> Result<Option<i64>, Error> {
+        let Some(timestamp) = self.json["genesis"]["timestamp"].as_str() else {
+            return Ok(None);
+        };
+
+        let timestamp = i64::from_str_radix(timestamp.trim_start_matches("0x"), 16)
+            .map_err(|_| Error::ParseTimestamp)?;
+
+        Ok(Some(timestamp).filter(|timestamp| *timestamp > 0))
+    }
+
     /// Parse the validators from the `extraData` field of the genesis file.
     /// The `extraData` is padded with 32 bytes, and the validators are RLP encoded.
     /// Each validator is 20 bytes, with a SEAL at the end of the whole list (8 bytes)
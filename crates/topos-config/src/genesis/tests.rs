@@ -24,6 +24,20 @@ pub fn test_correct_validator_count(genesis: &Genesis) {
     assert_eq!(validators.len(), 4);
 }
 
+#[rstest]
+pub fn test_unset_timestamp(genesis: &Genesis) {
+    assert_eq!(genesis.timestamp().unwrap(), None);
+}
+
+#[rstest]
+pub fn test_parse_timestamp() {
+    let genesis = Genesis {
+        json: serde_json::json!({ "genesis": { "timestamp": "0x65a8c2b0" } }),
+    };
+
+    assert_eq!(genesis.timestamp().unwrap(), Some(1_705_558_704));
+}
+
 #[rstest]
 pub fn test_parse_bootnodes(genesis: &Genesis) {
     let bootnodes = genesis.boot_peers(None);
@@ -14,12 +14,20 @@ use crate::Config;
 use topos_p2p::{Multiaddr, PeerId};
 
 use self::broadcast::ReliableBroadcastParams;
+use self::epoch::EpochConfig;
+use self::limits::LimitsConfig;
 use self::p2p::P2PConfig;
+use self::retention::RetentionConfig;
 use self::synchronization::SynchronizationConfig;
+use self::validation::ValidationConfig;
 
 pub mod broadcast;
+pub mod epoch;
+pub mod limits;
 pub mod p2p;
+pub mod retention;
 pub mod synchronization;
+pub mod validation;
 
 const DEFAULT_IP: std::net::Ipv4Addr = std::net::Ipv4Addr::new(0, 0, 0, 0);
 
@@ -54,9 +62,13 @@ pub struct TceConfig {
     #[serde(skip)]
     pub version: &'static str,
 
-    /// Storage database path, if not set RAM storage is used
+    /// Storage database path, unused if the storage is kept in memory
     #[serde(default = "default_db_path")]
     pub db_path: PathBuf,
+    /// Keep the storage in memory instead of persisting it in the database path, the node
+    /// starting from scratch on every restart
+    #[serde(default)]
+    pub in_memory_storage: bool,
     /// Array of extra boot nodes to connect to
     pub extra_boot_peers: Option<String>,
     /// Connection degree for the GossipSub overlay
@@ -74,12 +86,34 @@ pub struct TceConfig {
     #[serde(default)]
     pub synchronization: SynchronizationConfig,
 
+    /// Epoch configuration
+    #[serde(default)]
+    pub epoch: EpochConfig,
+
+    /// Certificate validation configuration
+    #[serde(default)]
+    pub validation: ValidationConfig,
+
+    /// Limits applied to the clients of the gRPC, GraphQL and REST APIs
+    #[serde(default)]
+    pub limits: LimitsConfig,
+
+    /// Retention of the pending data and of the proofs of the delivered certificates
+    #[serde(default)]
+    pub retention: RetentionConfig,
+
     /// gRPC API Addr
     #[serde(default = "default_grpc_api_addr")]
     pub grpc_api_addr: SocketAddr,
+    /// Console gRPC API Addr, exposing the admin commands
+    #[serde(default = "default_console_api_addr")]
+    pub console_api_addr: SocketAddr,
     /// GraphQL API Addr
     #[serde(default = "default_graphql_api_addr")]
     pub graphql_api_addr: SocketAddr,
+    /// REST API Addr
+    #[serde(default = "default_rest_api_addr")]
+    pub rest_api_addr: SocketAddr,
     /// Metrics server API Addr
     #[serde(default = "default_metrics_api_addr")]
     pub metrics_api_addr: SocketAddr,
@@ -109,10 +143,21 @@ const fn default_grpc_api_addr() -> SocketAddr {
     SocketAddr::V4(std::net::SocketAddrV4::new(DEFAULT_IP, 1340))
 }
 
+const fn default_console_api_addr() -> SocketAddr {
+    SocketAddr::V4(std::net::SocketAddrV4::new(
+        std::net::Ipv4Addr::LOCALHOST,
+        1341,
+    ))
+}
+
 const fn default_graphql_api_addr() -> SocketAddr {
     SocketAddr::V4(std::net::SocketAddrV4::new(DEFAULT_IP, 4030))
 }
 
+const fn default_rest_api_addr() -> SocketAddr {
+    SocketAddr::V4(std::net::SocketAddrV4::new(DEFAULT_IP, 4040))
+}
+
 const fn default_metrics_api_addr() -> SocketAddr {
     SocketAddr::V4(std::net::SocketAddrV4::new(DEFAULT_IP, 3000))
 }
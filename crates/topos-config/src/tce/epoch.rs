@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration of the TCE epochs
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct EpochConfig {
+    /// Unix timestamp in seconds at which the epoch 0 started, taken from the `timestamp` of the
+    /// genesis file if not set
+    pub genesis_timestamp: Option<i64>,
+
+    /// Duration of an epoch in seconds
+    #[serde(default = "EpochConfig::default_duration_seconds")]
+    pub duration_seconds: u64,
+
+    /// Interval in seconds between two checkpoints signed during an epoch
+    #[serde(default = "EpochConfig::default_checkpoint_interval_seconds")]
+    pub checkpoint_interval_seconds: u64,
+}
+
+impl Default for EpochConfig {
+    fn default() -> Self {
+        Self {
+            genesis_timestamp: None,
+            duration_seconds: EpochConfig::DURATION_SECONDS,
+            checkpoint_interval_seconds: EpochConfig::CHECKPOINT_INTERVAL_SECONDS,
+        }
+    }
+}
+
+impl EpochConfig {
+    pub const DURATION_SECONDS: u64 = 3600;
+    pub const CHECKPOINT_INTERVAL_SECONDS: u64 = 60;
+
+    const fn default_duration_seconds() -> u64 {
+        Self::DURATION_SECONDS
+    }
+
+    const fn default_checkpoint_interval_seconds() -> u64 {
+        Self::CHECKPOINT_INTERVAL_SECONDS
+    }
+}
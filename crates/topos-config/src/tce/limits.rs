@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration of the limits applied to the clients of the public APIs, a limit which isn't
+/// set being disabled
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct LimitsConfig {
+    /// Maximum number of certificates submitted per source subnet and per second
+    pub submissions_per_second: Option<u32>,
+
+    /// Maximum number of concurrent streams opened from the same peer address
+    pub max_streams_per_peer: Option<usize>,
+
+    /// Maximum number of pending certificates per source subnet
+    pub max_pending_certificates: Option<u64>,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration of the retention of the TCE storage, a policy which isn't set keeping the
+/// data forever
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct RetentionConfig {
+    /// Interval in seconds between two prunings of the storage
+    #[serde(default = "RetentionConfig::default_interval_seconds")]
+    pub interval_seconds: u64,
+
+    /// Duration in seconds after which a certificate still waiting for its previous certificate
+    /// in the precedence pool is evicted
+    pub precedence_pool_ttl_seconds: Option<u64>,
+
+    /// Number of latest certificates per source stream keeping their proof, the proof of the
+    /// older ones being dropped. Every proof is kept (archive mode) if not provided
+    pub proofs_per_source_stream: Option<u64>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: RetentionConfig::INTERVAL_SECONDS,
+            precedence_pool_ttl_seconds: None,
+            proofs_per_source_stream: None,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub const INTERVAL_SECONDS: u64 = 600;
+
+    const fn default_interval_seconds() -> u64 {
+        Self::INTERVAL_SECONDS
+    }
+}
@@ -11,6 +11,11 @@ pub struct SynchronizationConfig {
     /// Maximum number of Proof of delivery per query per subnet
     #[serde(default = "SynchronizationConfig::default_limit_per_subnet")]
     pub limit_per_subnet: usize,
+
+    /// Duration in seconds during which a peer that served an invalid checkpoint
+    /// is no longer used to synchronize
+    #[serde(default = "SynchronizationConfig::default_penalty_duration_seconds")]
+    pub penalty_duration_seconds: u64,
 }
 
 impl Default for SynchronizationConfig {
@@ -18,6 +23,7 @@ impl Default for SynchronizationConfig {
         Self {
             interval_seconds: SynchronizationConfig::INTERVAL_SECONDS,
             limit_per_subnet: SynchronizationConfig::LIMIT_PER_SUBNET,
+            penalty_duration_seconds: SynchronizationConfig::PENALTY_DURATION_SECONDS,
         }
     }
 }
@@ -25,6 +31,7 @@ impl Default for SynchronizationConfig {
 impl SynchronizationConfig {
     pub const INTERVAL_SECONDS: u64 = 10;
     pub const LIMIT_PER_SUBNET: usize = 100;
+    pub const PENALTY_DURATION_SECONDS: u64 = 600;
 
     const fn default_interval_seconds() -> u64 {
         Self::INTERVAL_SECONDS
@@ -33,4 +40,8 @@ impl SynchronizationConfig {
     const fn default_limit_per_subnet() -> usize {
         Self::LIMIT_PER_SUBNET
     }
+
+    const fn default_penalty_duration_seconds() -> u64 {
+        Self::PENALTY_DURATION_SECONDS
+    }
 }
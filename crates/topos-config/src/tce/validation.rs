@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use topos_core::uci::CertificateValidator;
+
+/// Configuration of the validation of the certificates before they enter the pending pool
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ValidationConfig {
+    /// Whether the signature of the source subnet has to be verified, the proof of the
+    /// certificates being verified regardless
+    #[serde(default = "ValidationConfig::default_verify_signature")]
+    pub verify_signature: bool,
+
+    /// Maximum size of a certificate proof in bytes
+    #[serde(default = "ValidationConfig::default_max_proof_size")]
+    pub max_proof_size: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            verify_signature: true,
+            max_proof_size: ValidationConfig::MAX_PROOF_SIZE,
+        }
+    }
+}
+
+impl ValidationConfig {
+    pub const MAX_PROOF_SIZE: usize = CertificateValidator::MAX_PROOF_SIZE;
+
+    const fn default_verify_signature() -> bool {
+        true
+    }
+
+    const fn default_max_proof_size() -> usize {
+        Self::MAX_PROOF_SIZE
+    }
+}
@@ -33,6 +33,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             ".topos.shared.v1.Positions.SourceStreamPosition",
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            ".topos.shared.v1.Positions.TargetStreamPosition",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
         .type_attribute(
             ".topos.tce.v1.ProofOfDelivery",
             "#[derive(serde::Deserialize, serde::Serialize)]",
@@ -49,6 +53,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             ".topos.tce.v1.CheckpointMapFieldEntry",
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            ".topos.tce.v1.CheckpointSummary",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .type_attribute(
+            ".topos.tce.v1.SignedCheckpoint",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .type_attribute(
+            ".topos.tce.v1.ValidatorSignature",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
         .type_attribute(
             ".topos.shared.v1.EcdsaSignature",
             "#[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]",
@@ -61,6 +77,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             ".topos.tce.v1.Gossip",
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            ".topos.tce.v1.PeerBinding",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
         .type_attribute(
             ".topos.tce.v1.Echo",
             "#[derive(serde::Deserialize, serde::Serialize)]",
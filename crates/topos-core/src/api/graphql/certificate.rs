@@ -1,9 +1,13 @@
-use async_graphql::{NewType, SimpleObject};
+use async_graphql::{Enum, InputObject, NewType, SimpleObject, Union};
 use serde::{Deserialize, Serialize};
 
 use crate::{types::CertificateDelivered, uci};
 
-use super::{checkpoint::SourceStreamPosition, subnet::SubnetId};
+use super::{
+    checkpoint::{SourceStreamPosition, TargetStreamPosition},
+    errors::GraphQLServerError,
+    subnet::SubnetId,
+};
 
 #[derive(Serialize, Deserialize, Debug, NewType)]
 pub struct CertificateId(String);
@@ -70,6 +74,126 @@ impl From<&uci::Certificate> for UndeliveredCertificate {
     }
 }
 
+/// A certificate submitted to the node, the binary fields being hex encoded
+#[derive(Debug, Serialize, Deserialize, InputObject)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInput {
+    pub id: CertificateId,
+    pub prev_id: CertificateId,
+    pub proof: String,
+    pub signature: String,
+    pub source_subnet_id: SubnetId,
+    pub state_root: String,
+    pub target_subnets: Vec<SubnetId>,
+    pub tx_root_hash: String,
+    pub receipts_root_hash: String,
+    pub verifier: u32,
+}
+
+fn decode_hex(field: &'static str, value: &str) -> Result<Vec<u8>, GraphQLServerError> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map_err(|_| GraphQLServerError::ParseCertificate(field))
+}
+
+fn decode_hash(field: &'static str, value: &str) -> Result<[u8; 32], GraphQLServerError> {
+    decode_hex(field, value)?
+        .try_into()
+        .map_err(|_| GraphQLServerError::ParseCertificate(field))
+}
+
+impl TryFrom<&CertificateInput> for uci::Certificate {
+    type Error = GraphQLServerError;
+
+    fn try_from(value: &CertificateInput) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: uci::CertificateId::try_from(value.id.0.as_bytes())
+                .map_err(|_| GraphQLServerError::ParseCertificateId)?,
+            prev_id: uci::CertificateId::try_from(value.prev_id.0.as_bytes())
+                .map_err(|_| GraphQLServerError::ParseCertificateId)?,
+            source_subnet_id: (&value.source_subnet_id)
+                .try_into()
+                .map_err(|_| GraphQLServerError::ParseSubnetId)?,
+            state_root: decode_hash("stateRoot", &value.state_root)?,
+            tx_root_hash: decode_hash("txRootHash", &value.tx_root_hash)?,
+            receipts_root_hash: decode_hash("receiptsRootHash", &value.receipts_root_hash)?,
+            target_subnets: value
+                .target_subnets
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()
+                .map_err(|_| GraphQLServerError::ParseSubnetId)?,
+            verifier: value.verifier,
+            proof: decode_hex("proof", &value.proof)?,
+            signature: decode_hex("signature", &value.signature)?,
+        })
+    }
+}
+
+/// Outcome of the submission of a certificate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum PendingResult {
+    /// The certificate has been inserted in the pending pool
+    InPending,
+    /// The certificate has been inserted in the precedence pool, waiting for its previous
+    /// certificate to be delivered
+    AwaitPrecedence,
+    AlreadyPending,
+    AlreadyDelivered,
+    /// The certificate has been rejected before entering the pending pool
+    Rejected,
+}
+
+/// Outcome of the submission of a certificate within a batch
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateSubmission {
+    pub certificate_id: CertificateId,
+    pub result: PendingResult,
+    /// Index of the certificate in the pending pool, when inserted in it
+    pub pending_id: Option<u64>,
+    /// Reason for which the certificate has been rejected
+    pub rejection: Option<String>,
+}
+
+/// A certificate in the pending pool, waiting to be broadcast
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingStatus {
+    pub pending_id: u64,
+}
+
+/// A certificate in the precedence pool, waiting for its previous certificate to be delivered
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct AwaitingPrecedenceStatus {
+    pub prev_id: CertificateId,
+}
+
+/// A certificate being broadcast
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct InBroadcastStatus {
+    pub echo_count: u64,
+    pub ready_count: u64,
+}
+
+/// A certificate that has been delivered
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveredStatus {
+    pub source: SourceStreamPosition,
+    pub targets: Vec<TargetStreamPosition>,
+}
+
+/// Where a certificate currently stands in the node
+#[derive(Debug, Serialize, Deserialize, Union)]
+pub enum CertificateStatus {
+    Pending(PendingStatus),
+    AwaitingPrecedence(AwaitingPrecedenceStatus),
+    InBroadcast(InBroadcastStatus),
+    Delivered(DeliveredStatus),
+}
+
 #[derive(Debug, Serialize, Deserialize, SimpleObject)]
 pub struct Ready {
     message: String,
@@ -20,6 +20,15 @@ pub struct SourceStreamPosition {
     pub certificate_id: CertificateId,
 }
 
+#[derive(Debug, Deserialize, Serialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetStreamPosition {
+    pub source_subnet_id: SubnetId,
+    pub target_subnet_id: SubnetId,
+    pub position: u64,
+    pub certificate_id: CertificateId,
+}
+
 impl From<&ProofOfDelivery> for SourceStreamPosition {
     fn from(value: &ProofOfDelivery) -> Self {
         Self {
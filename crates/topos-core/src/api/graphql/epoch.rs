@@ -0,0 +1,12 @@
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use super::checkpoint::SourceStreamPosition;
+
+#[derive(Debug, Deserialize, Serialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct Epoch {
+    pub id: u64,
+    /// Position of each source stream when the epoch started
+    pub start_checkpoint: Vec<SourceStreamPosition>,
+}
@@ -1,3 +1,5 @@
+use async_graphql::ErrorExtensions;
+
 #[derive(Debug, thiserror::Error)]
 pub enum GraphQLServerError {
     #[error("The provided data layer is invalid")]
@@ -9,15 +11,40 @@ pub enum GraphQLServerError {
     #[error("The provided certificate_id is not a proper HEX value")]
     ParseCertificateId,
 
+    #[error("The provided certificate has an invalid {0}")]
+    ParseCertificate(&'static str),
+
+    #[error("The certificate has been rejected: {0}")]
+    CertificateRejected(String),
+
+    #[error("Unable to submit more than {0} certificates at once")]
+    TooManyCertificates(usize),
+
+    #[error("Resource exhausted: {0}")]
+    ResourceExhausted(String),
+
     #[error("Internal Server Error")]
     StorageError,
 
     #[error("Certificate not found")]
     CertificateNotFound,
 
+    #[error("The proof of the certificate {0} has been pruned")]
+    ProofPruned(String),
+
     #[error("Unable to create transient stream: {0}")]
     TransientStream(String),
 
     #[error("Internal API error: {0}")]
     InternalError(&'static str),
 }
+
+impl ErrorExtensions for GraphQLServerError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(self.to_string()).extend_with(|_, extensions| {
+            if let GraphQLServerError::ResourceExhausted(_) = self {
+                extensions.set("code", "RESOURCE_EXHAUSTED");
+            }
+        })
+    }
+}
@@ -5,3 +5,10 @@ pub enum SubnetFilter {
     Source(SubnetId),
     Target(SubnetId),
 }
+
+/// Inclusive range of positions in a stream, unbounded on the sides that aren't provided
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, async_graphql::InputObject)]
+pub struct PositionRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
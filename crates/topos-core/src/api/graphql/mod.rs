@@ -1,6 +1,8 @@
 pub mod certificate;
 pub mod checkpoint;
+pub mod epoch;
 pub mod errors;
 pub mod filter;
+pub mod network;
 pub mod query;
 pub mod subnet;
@@ -0,0 +1,72 @@
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use super::certificate::CertificateId;
+
+/// Validator set of an epoch
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSet {
+    pub epoch: u64,
+    pub validators: Vec<String>,
+}
+
+/// Peer connected to the node
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct Peer {
+    pub peer_id: String,
+    /// Remote addresses of the established connections
+    pub addresses: Vec<String>,
+    /// Whether at least one of the connections was dialed by the node
+    pub outbound: bool,
+    /// Whether the Echo and Ready messages of the peer are dropped
+    pub denied: bool,
+}
+
+/// Latency between the submission of a certificate to the node and its delivery
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryLatency {
+    /// Number of certificates submitted to the node and delivered since it started
+    pub count: u64,
+    pub total_seconds: f64,
+    pub average_seconds: Option<f64>,
+}
+
+/// Progress of the broadcast of a certificate
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastProgress {
+    pub certificate_id: CertificateId,
+    /// Number of distinct validators from which an Echo has been received
+    pub echo_count: u64,
+    /// Number of distinct validators from which a Ready has been received
+    pub ready_count: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum ProtocolEventKind {
+    /// The broadcast of the certificate started
+    Broadcast,
+    /// The certificate is gossiped to the network
+    Gossip,
+    /// The number of Echo or Ready received for the certificate changed
+    Progress,
+    BroadcastFailed,
+    AlreadyDelivered,
+}
+
+/// Event emitted by the reliable broadcast of the node
+#[derive(Debug, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolEvent {
+    pub kind: ProtocolEventKind,
+    pub certificate_id: CertificateId,
+    /// Number of distinct validators from which an Echo has been received, set on the
+    /// progress events
+    pub echo_count: Option<u64>,
+    /// Number of distinct validators from which a Ready has been received, set on the
+    /// progress events
+    pub ready_count: Option<u64>,
+}
@@ -10,6 +10,14 @@ pub enum TargetCheckpointError {
     ParseError,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum SourceCheckpointError {
+    #[error("Subnet format is invalid")]
+    InvalidSubnetFormat,
+    #[error("Invalid source stream position")]
+    InvalidSourceStreamPosition,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum StreamPositionError {
     #[error("The target_subnet_id field is missing")]
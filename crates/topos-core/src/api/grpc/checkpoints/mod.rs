@@ -46,3 +46,43 @@ impl From<TargetCheckpoint> for shared_v1::checkpoints::TargetCheckpoint {
         }
     }
 }
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SourceCheckpoint {
+    pub source_subnet_ids: Vec<SubnetId>,
+    pub positions: Vec<SourceStreamPosition>,
+}
+
+impl TryFrom<shared_v1::checkpoints::SourceCheckpoint> for SourceCheckpoint {
+    type Error = SourceCheckpointError;
+
+    fn try_from(value: shared_v1::checkpoints::SourceCheckpoint) -> Result<Self, Self::Error> {
+        Ok(SourceCheckpoint {
+            source_subnet_ids: value
+                .source_subnet_ids
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<SubnetId>, _>>()
+                .map_err(|_| SourceCheckpointError::InvalidSubnetFormat)?,
+            positions: value
+                .positions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<SourceStreamPosition>, _>>()
+                .map_err(|_| SourceCheckpointError::InvalidSourceStreamPosition)?,
+        })
+    }
+}
+
+impl From<SourceCheckpoint> for shared_v1::checkpoints::SourceCheckpoint {
+    fn from(value: SourceCheckpoint) -> Self {
+        Self {
+            source_subnet_ids: value
+                .source_subnet_ids
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            positions: value.positions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
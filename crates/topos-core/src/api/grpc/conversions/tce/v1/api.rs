@@ -1,8 +1,10 @@
 use crate::api::grpc::tce::v1::{
-    watch_certificates_request::{Command, OpenStream},
+    submit_certificate_response::{Rejection, RejectionReason},
+    watch_certificates_request::{Ack, Command, OpenStream},
     watch_certificates_response::{CertificatePushed, Event, StreamOpened},
-    WatchCertificatesRequest, WatchCertificatesResponse,
+    SubmitCertificateResponse, WatchCertificatesRequest, WatchCertificatesResponse,
 };
+use crate::uci::ValidationError;
 
 macro_rules! impl_command_conversion {
     ($type: ident) => {
@@ -31,6 +33,32 @@ macro_rules! impl_event_conversion {
 }
 
 impl_command_conversion!(OpenStream);
+impl_command_conversion!(Ack);
 
 impl_event_conversion!(StreamOpened);
 impl_event_conversion!(CertificatePushed);
+
+impl From<ValidationError> for Rejection {
+    fn from(error: ValidationError) -> Self {
+        let reason = match error {
+            ValidationError::InvalidSignature(_) => RejectionReason::InvalidSignature,
+            ValidationError::InvalidProof(_) => RejectionReason::InvalidProof,
+            ValidationError::SelfTarget => RejectionReason::SelfTarget,
+            ValidationError::DuplicatedTarget(_) => RejectionReason::DuplicatedTarget,
+            ValidationError::ProofTooLarge { .. } => RejectionReason::ProofTooLarge,
+        };
+
+        Self {
+            reason: reason.into(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<ValidationError> for SubmitCertificateResponse {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            rejection: Some(error.into()),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use prost::{bytes::Bytes, Message};
 
 use crate::api::grpc::tce::v1::{
     CheckpointRequest, CheckpointResponse, FetchCertificatesRequest, FetchCertificatesResponse,
+    SignedCheckpointRequest, SignedCheckpointResponse,
 };
 
 use crate::api::grpc::ConversionError;
@@ -37,12 +38,16 @@ impl_to_vec_conversion!(
     CheckpointRequest,
     CheckpointResponse,
     FetchCertificatesRequest,
-    FetchCertificatesResponse
+    FetchCertificatesResponse,
+    SignedCheckpointRequest,
+    SignedCheckpointResponse
 );
 
 impl_from_vec_conversion!(
     CheckpointResponse,
     CheckpointRequest,
     FetchCertificatesRequest,
-    FetchCertificatesResponse
+    FetchCertificatesResponse,
+    SignedCheckpointRequest,
+    SignedCheckpointResponse
 );
@@ -79,6 +79,7 @@ pub mod positions {
     }
     /// TargetStreamPosition represents a single point in a target stream regarding a source subnet.
     /// It is defined by a target_subnet_id, source_subnet_id and a position, resolving to a certificate_id
+    #[derive(serde::Deserialize, serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct TargetStreamPosition {
@@ -94,30 +95,30 @@ pub mod positions {
         pub certificate_id: ::core::option::Option<super::CertificateId>,
     }
 }
+/// A signature using the ECDSA algorithm.
+/// Used to sign double echo protocol messages.
 #[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Frost {
+pub struct EcdsaSignature {
     #[prost(bytes = "vec", tag = "1")]
-    pub value: ::prost::alloc::vec::Vec<u8>,
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub v: u64,
 }
 #[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct StarkProof {
+pub struct Frost {
     #[prost(bytes = "vec", tag = "1")]
     pub value: ::prost::alloc::vec::Vec<u8>,
 }
-/// A signature using the ECDSA algorithm.
-/// Used to sign double echo protocol messages.
 #[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct EcdsaSignature {
+pub struct StarkProof {
     #[prost(bytes = "vec", tag = "1")]
-    pub r: ::prost::alloc::vec::Vec<u8>,
-    #[prost(bytes = "vec", tag = "2")]
-    pub s: ::prost::alloc::vec::Vec<u8>,
-    #[prost(uint64, tag = "3")]
-    pub v: u64,
+    pub value: ::prost::alloc::vec::Vec<u8>,
 }
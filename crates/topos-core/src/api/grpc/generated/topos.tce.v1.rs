@@ -60,14 +60,74 @@ pub struct ProofOfDelivery {
     #[prost(uint64, tag = "3")]
     pub threshold: u64,
 }
+/// Ready message received from a validator during the broadcast of a certificate.
+/// The signature covers the certificate id followed by the validator id.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignedReady {
-    #[prost(string, tag = "1")]
-    pub ready: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub signature: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "1")]
+    pub validator_id: ::core::option::Option<super::super::shared::v1::ValidatorId>,
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<super::super::shared::v1::EcdsaSignature>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedCheckpointRequest {
+    /// Provide a request_id to track response
+    #[prost(message, optional, tag = "1")]
+    pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedCheckpointResponse {
+    /// Provide a request_id to track response
+    #[prost(message, optional, tag = "1")]
+    pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
+    /// Latest checkpoint signed by a quorum of validators, if any
+    #[prost(message, optional, tag = "2")]
+    pub checkpoint: ::core::option::Option<SignedCheckpoint>,
+    /// Proofs of delivery of the certificates at the source and target heads of the checkpoint
+    #[prost(message, repeated, tag = "3")]
+    pub heads: ::prost::alloc::vec::Vec<ProofOfDelivery>,
+}
+/// Heads of the source and target streams at some point of an epoch
+#[derive(serde::Deserialize, serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointSummary {
+    #[prost(uint64, tag = "1")]
+    pub epoch: u64,
+    #[prost(uint64, tag = "2")]
+    pub sequence_number: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub heads: ::prost::alloc::vec::Vec<
+        super::super::shared::v1::positions::SourceStreamPosition,
+    >,
+    #[prost(message, repeated, tag = "4")]
+    pub target_heads: ::prost::alloc::vec::Vec<
+        super::super::shared::v1::positions::TargetStreamPosition,
+    >,
+}
+/// Checkpoint along with the signatures of the validators attesting it.
+/// The signatures cover the epoch, the sequence number and every head of the checkpoint.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedCheckpoint {
+    #[prost(message, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<CheckpointSummary>,
+    #[prost(message, repeated, tag = "2")]
+    pub signatures: ::prost::alloc::vec::Vec<ValidatorSignature>,
+}
+#[derive(serde::Deserialize, serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidatorSignature {
+    #[prost(message, optional, tag = "1")]
+    pub validator_id: ::core::option::Option<super::super::shared::v1::ValidatorId>,
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<super::super::shared::v1::EcdsaSignature>,
 }
 /// Generated client implementations.
 pub mod synchronizer_service_client {
@@ -214,6 +274,36 @@ pub mod synchronizer_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn fetch_signed_checkpoint(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SignedCheckpointRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SignedCheckpointResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.SynchronizerService/fetch_signed_checkpoint",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "topos.tce.v1.SynchronizerService",
+                        "fetch_signed_checkpoint",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -237,6 +327,13 @@ pub mod synchronizer_service_server {
             tonic::Response<super::FetchCertificatesResponse>,
             tonic::Status,
         >;
+        async fn fetch_signed_checkpoint(
+            &self,
+            request: tonic::Request<super::SignedCheckpointRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SignedCheckpointResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct SynchronizerServiceServer<T: SynchronizerService> {
@@ -417,6 +514,58 @@ pub mod synchronizer_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/topos.tce.v1.SynchronizerService/fetch_signed_checkpoint" => {
+                    #[allow(non_camel_case_types)]
+                    struct fetch_signed_checkpointSvc<T: SynchronizerService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: SynchronizerService,
+                    > tonic::server::UnaryService<super::SignedCheckpointRequest>
+                    for fetch_signed_checkpointSvc<T> {
+                        type Response = super::SignedCheckpointResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SignedCheckpointRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SynchronizerService>::fetch_signed_checkpoint(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = fetch_signed_checkpointSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -467,7 +616,158 @@ pub struct SubmitCertificateRequest {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SubmitCertificateResponse {}
+pub struct SubmitCertificateResponse {
+    /// Set when the certificate has been rejected before entering the pending pool
+    #[prost(message, optional, tag = "1")]
+    pub rejection: ::core::option::Option<submit_certificate_response::Rejection>,
+}
+/// Nested message and enum types in `SubmitCertificateResponse`.
+pub mod submit_certificate_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Rejection {
+        #[prost(enumeration = "RejectionReason", tag = "1")]
+        pub reason: i32,
+        /// Human readable details about the rejection
+        #[prost(string, tag = "2")]
+        pub message: ::prost::alloc::string::String,
+    }
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum RejectionReason {
+        Unspecified = 0,
+        InvalidSignature = 1,
+        InvalidProof = 2,
+        SelfTarget = 3,
+        DuplicatedTarget = 4,
+        ProofTooLarge = 5,
+    }
+    impl RejectionReason {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                RejectionReason::Unspecified => "REJECTION_REASON_UNSPECIFIED",
+                RejectionReason::InvalidSignature => "REJECTION_REASON_INVALID_SIGNATURE",
+                RejectionReason::InvalidProof => "REJECTION_REASON_INVALID_PROOF",
+                RejectionReason::SelfTarget => "REJECTION_REASON_SELF_TARGET",
+                RejectionReason::DuplicatedTarget => "REJECTION_REASON_DUPLICATED_TARGET",
+                RejectionReason::ProofTooLarge => "REJECTION_REASON_PROOF_TOO_LARGE",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "REJECTION_REASON_UNSPECIFIED" => Some(Self::Unspecified),
+                "REJECTION_REASON_INVALID_SIGNATURE" => Some(Self::InvalidSignature),
+                "REJECTION_REASON_INVALID_PROOF" => Some(Self::InvalidProof),
+                "REJECTION_REASON_SELF_TARGET" => Some(Self::SelfTarget),
+                "REJECTION_REASON_DUPLICATED_TARGET" => Some(Self::DuplicatedTarget),
+                "REJECTION_REASON_PROOF_TOO_LARGE" => Some(Self::ProofTooLarge),
+                _ => None,
+            }
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitCertificatesRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub certificates: ::prost::alloc::vec::Vec<super::super::uci::v1::Certificate>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitCertificatesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub submissions: ::prost::alloc::vec::Vec<
+        submit_certificates_response::CertificateSubmission,
+    >,
+}
+/// Nested message and enum types in `SubmitCertificatesResponse`.
+pub mod submit_certificates_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct CertificateSubmission {
+        #[prost(message, optional, tag = "1")]
+        pub certificate_id: ::core::option::Option<
+            super::super::super::shared::v1::CertificateId,
+        >,
+        #[prost(enumeration = "PendingResult", tag = "2")]
+        pub result: i32,
+        /// Index of the certificate in the pending pool, set when the result is IN_PENDING
+        #[prost(uint64, tag = "3")]
+        pub pending_id: u64,
+        /// Set when the result is REJECTED
+        #[prost(message, optional, tag = "4")]
+        pub rejection: ::core::option::Option<
+            super::submit_certificate_response::Rejection,
+        >,
+    }
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum PendingResult {
+        Unspecified = 0,
+        /// The certificate has been inserted in the pending pool
+        InPending = 1,
+        /// The certificate has been inserted in the precedence pool, waiting for its previous
+        /// certificate to be delivered
+        AwaitPrecedence = 2,
+        AlreadyPending = 3,
+        AlreadyDelivered = 4,
+        /// The certificate has been rejected before entering the pending pool
+        Rejected = 5,
+    }
+    impl PendingResult {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                PendingResult::Unspecified => "PENDING_RESULT_UNSPECIFIED",
+                PendingResult::InPending => "PENDING_RESULT_IN_PENDING",
+                PendingResult::AwaitPrecedence => "PENDING_RESULT_AWAIT_PRECEDENCE",
+                PendingResult::AlreadyPending => "PENDING_RESULT_ALREADY_PENDING",
+                PendingResult::AlreadyDelivered => "PENDING_RESULT_ALREADY_DELIVERED",
+                PendingResult::Rejected => "PENDING_RESULT_REJECTED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "PENDING_RESULT_UNSPECIFIED" => Some(Self::Unspecified),
+                "PENDING_RESULT_IN_PENDING" => Some(Self::InPending),
+                "PENDING_RESULT_AWAIT_PRECEDENCE" => Some(Self::AwaitPrecedence),
+                "PENDING_RESULT_ALREADY_PENDING" => Some(Self::AlreadyPending),
+                "PENDING_RESULT_ALREADY_DELIVERED" => Some(Self::AlreadyDelivered),
+                "PENDING_RESULT_REJECTED" => Some(Self::Rejected),
+                _ => None,
+            }
+        }
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetSourceHeadRequest {
@@ -512,17 +812,87 @@ pub struct GetLastPendingCertificatesResponse {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCertificateStatusRequest {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCertificateStatusResponse {
+    #[prost(oneof = "get_certificate_status_response::Status", tags = "1, 2, 3, 4")]
+    pub status: ::core::option::Option<get_certificate_status_response::Status>,
+}
+/// Nested message and enum types in `GetCertificateStatusResponse`.
+pub mod get_certificate_status_response {
+    /// The certificate is in the pending pool, waiting to be broadcast
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Pending {
+        #[prost(uint64, tag = "1")]
+        pub pending_id: u64,
+    }
+    /// The certificate is in the precedence pool, waiting for its previous certificate
+    /// to be delivered
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct AwaitingPrecedence {
+        #[prost(message, optional, tag = "1")]
+        pub prev_id: ::core::option::Option<
+            super::super::super::shared::v1::CertificateId,
+        >,
+    }
+    /// The certificate is being broadcast
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InBroadcast {
+        #[prost(uint64, tag = "1")]
+        pub echo_count: u64,
+        #[prost(uint64, tag = "2")]
+        pub ready_count: u64,
+    }
+    /// The certificate has been delivered
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Delivered {
+        #[prost(message, optional, tag = "1")]
+        pub source_position: ::core::option::Option<
+            super::super::super::shared::v1::positions::SourceStreamPosition,
+        >,
+        #[prost(message, repeated, tag = "2")]
+        pub target_positions: ::prost::alloc::vec::Vec<
+            super::super::super::shared::v1::positions::TargetStreamPosition,
+        >,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Status {
+        #[prost(message, tag = "1")]
+        Pending(Pending),
+        #[prost(message, tag = "2")]
+        AwaitingPrecedence(AwaitingPrecedence),
+        #[prost(message, tag = "3")]
+        InBroadcast(InBroadcast),
+        #[prost(message, tag = "4")]
+        Delivered(Delivered),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WatchCertificatesRequest {
     /// Provide a request_id to track response
+    /// The request_id of the OpenStream also identifies the stream across reconnections: the
+    /// positions acknowledged under it are kept by the TCE and used to resume the stream
     #[prost(message, optional, tag = "1")]
     pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
     /// Define which command needs to be performed
-    #[prost(oneof = "watch_certificates_request::Command", tags = "2")]
+    #[prost(oneof = "watch_certificates_request::Command", tags = "2, 3")]
     pub command: ::core::option::Option<watch_certificates_request::Command>,
 }
 /// Nested message and enum types in `WatchCertificatesRequest`.
 pub mod watch_certificates_request {
     /// Sent to start receiving events and being able to send further command
+    /// A stream either watches the certificates delivered to target subnets, or the certificates
+    /// emitted by source subnets, so exactly one of the checkpoints must be provided
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct OpenStream {
@@ -534,6 +904,26 @@ pub mod watch_certificates_request {
         pub source_checkpoint: ::core::option::Option<
             super::super::super::shared::v1::checkpoints::SourceCheckpoint,
         >,
+        /// Maximum number of pushed certificates waiting for an Ack before the stream is paused,
+        /// 0 disables the flow control. Only applies to target subscriptions
+        #[prost(uint32, tag = "3")]
+        pub max_unacknowledged: u32,
+        /// Only push the certificates of the source subscription targeting one of these subnets,
+        /// every certificate of the source subnets is pushed if empty
+        #[prost(message, repeated, tag = "4")]
+        pub target_subnet_filter: ::prost::alloc::vec::Vec<
+            super::super::super::shared::v1::SubnetId,
+        >,
+    }
+    /// Sent to acknowledge the consumption of a certificate, along with every previous
+    /// certificate of the same target stream
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Ack {
+        #[prost(message, optional, tag = "1")]
+        pub position: ::core::option::Option<
+            super::super::super::shared::v1::positions::TargetStreamPosition,
+        >,
     }
     /// Define which command needs to be performed
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -541,6 +931,8 @@ pub mod watch_certificates_request {
     pub enum Command {
         #[prost(message, tag = "2")]
         OpenStream(OpenStream),
+        #[prost(message, tag = "3")]
+        Ack(Ack),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -576,6 +968,11 @@ pub mod watch_certificates_response {
         pub positions: ::prost::alloc::vec::Vec<
             super::super::super::shared::v1::positions::TargetStreamPosition,
         >,
+        /// Position of the certificate in the stream of its source subnet
+        #[prost(message, optional, tag = "3")]
+        pub source_position: ::core::option::Option<
+            super::super::super::shared::v1::positions::SourceStreamPosition,
+        >,
     }
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
@@ -696,6 +1093,36 @@ pub mod api_service_client {
                 .insert(GrpcMethod::new("topos.tce.v1.APIService", "SubmitCertificate"));
             self.inner.unary(req, path, codec).await
         }
+        /// Submit a chain of certificates, inserted in order in the pending pool
+        ///
+        /// Returns the result of the submission of each certificate, in the order of the request
+        pub async fn submit_certificates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubmitCertificatesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SubmitCertificatesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.APIService/SubmitCertificates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("topos.tce.v1.APIService", "SubmitCertificates"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn get_source_head(
             &mut self,
             request: impl tonic::IntoRequest<super::GetSourceHeadRequest>,
@@ -756,6 +1183,35 @@ pub mod api_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Returns where a certificate currently stands: pending, awaiting its previous certificate,
+        /// being broadcast or delivered
+        pub async fn get_certificate_status(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCertificateStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCertificateStatusResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.APIService/GetCertificateStatus",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("topos.tce.v1.APIService", "GetCertificateStatus"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         /// This RPC allows a client to open a bidirectional stream with a TCE
         pub async fn watch_certificates(
             &mut self,
@@ -800,6 +1256,16 @@ pub mod api_service_server {
             tonic::Response<super::SubmitCertificateResponse>,
             tonic::Status,
         >;
+        /// Submit a chain of certificates, inserted in order in the pending pool
+        ///
+        /// Returns the result of the submission of each certificate, in the order of the request
+        async fn submit_certificates(
+            &self,
+            request: tonic::Request<super::SubmitCertificatesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SubmitCertificatesResponse>,
+            tonic::Status,
+        >;
         async fn get_source_head(
             &self,
             request: tonic::Request<super::GetSourceHeadRequest>,
@@ -819,6 +1285,15 @@ pub mod api_service_server {
             tonic::Response<super::GetLastPendingCertificatesResponse>,
             tonic::Status,
         >;
+        /// Returns where a certificate currently stands: pending, awaiting its previous certificate,
+        /// being broadcast or delivered
+        async fn get_certificate_status(
+            &self,
+            request: tonic::Request<super::GetCertificateStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCertificateStatusResponse>,
+            tonic::Status,
+        >;
         /// Server streaming response type for the WatchCertificates method.
         type WatchCertificatesStream: tonic::codegen::tokio_stream::Stream<
                 Item = std::result::Result<
@@ -962,25 +1437,26 @@ pub mod api_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/topos.tce.v1.APIService/GetSourceHead" => {
+                "/topos.tce.v1.APIService/SubmitCertificates" => {
                     #[allow(non_camel_case_types)]
-                    struct GetSourceHeadSvc<T: ApiService>(pub Arc<T>);
+                    struct SubmitCertificatesSvc<T: ApiService>(pub Arc<T>);
                     impl<
                         T: ApiService,
-                    > tonic::server::UnaryService<super::GetSourceHeadRequest>
-                    for GetSourceHeadSvc<T> {
-                        type Response = super::GetSourceHeadResponse;
+                    > tonic::server::UnaryService<super::SubmitCertificatesRequest>
+                    for SubmitCertificatesSvc<T> {
+                        type Response = super::SubmitCertificatesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetSourceHeadRequest>,
+                            request: tonic::Request<super::SubmitCertificatesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as ApiService>::get_source_head(&inner, request).await
+                                <T as ApiService>::submit_certificates(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -992,7 +1468,7 @@ pub mod api_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetSourceHeadSvc(inner);
+                        let method = SubmitCertificatesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1008,7 +1484,53 @@ pub mod api_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/topos.tce.v1.APIService/GetLastPendingCertificates" => {
+                "/topos.tce.v1.APIService/GetSourceHead" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSourceHeadSvc<T: ApiService>(pub Arc<T>);
+                    impl<
+                        T: ApiService,
+                    > tonic::server::UnaryService<super::GetSourceHeadRequest>
+                    for GetSourceHeadSvc<T> {
+                        type Response = super::GetSourceHeadResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSourceHeadRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ApiService>::get_source_head(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSourceHeadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.APIService/GetLastPendingCertificates" => {
                     #[allow(non_camel_case_types)]
                     struct GetLastPendingCertificatesSvc<T: ApiService>(pub Arc<T>);
                     impl<
@@ -1061,6 +1583,53 @@ pub mod api_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/topos.tce.v1.APIService/GetCertificateStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCertificateStatusSvc<T: ApiService>(pub Arc<T>);
+                    impl<
+                        T: ApiService,
+                    > tonic::server::UnaryService<super::GetCertificateStatusRequest>
+                    for GetCertificateStatusSvc<T> {
+                        type Response = super::GetCertificateStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCertificateStatusRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ApiService>::get_certificate_status(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetCertificateStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/topos.tce.v1.APIService/WatchCertificates" => {
                     #[allow(non_camel_case_types)]
                     struct WatchCertificatesSvc<T: ApiService>(pub Arc<T>);
@@ -1159,7 +1728,102 @@ pub struct StatusRequest {}
 pub struct StatusResponse {
     #[prost(bool, tag = "1")]
     pub has_active_sample: bool,
+    /// Current epoch of the node
+    #[prost(uint64, tag = "2")]
+    pub epoch: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPeersRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPeersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub peers: ::prost::alloc::vec::Vec<get_peers_response::Peer>,
+}
+/// Nested message and enum types in `GetPeersResponse`.
+pub mod get_peers_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Peer {
+        #[prost(string, tag = "1")]
+        pub peer_id: ::prost::alloc::string::String,
+        /// Remote addresses of the established connections
+        #[prost(string, repeated, tag = "2")]
+        pub addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+        /// Whether at least one of the connections was dialed by the node
+        #[prost(bool, tag = "3")]
+        pub outbound: bool,
+        /// Whether the Echo and Ready messages of the peer are dropped
+        #[prost(bool, tag = "4")]
+        pub denied: bool,
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPoolsRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPoolsResponse {
+    #[prost(uint64, tag = "1")]
+    pub pending_pool_size: u64,
+    #[prost(uint64, tag = "2")]
+    pub precedence_pool_size: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSourceHeadsRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSourceHeadsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub source_heads: ::prost::alloc::vec::Vec<
+        super::super::shared::v1::positions::SourceStreamPosition,
+    >,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBroadcastTasksRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBroadcastTasksResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub tasks: ::prost::alloc::vec::Vec<get_broadcast_tasks_response::BroadcastTask>,
+}
+/// Nested message and enum types in `GetBroadcastTasksResponse`.
+pub mod get_broadcast_tasks_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct BroadcastTask {
+        #[prost(message, optional, tag = "1")]
+        pub certificate_id: ::core::option::Option<
+            super::super::super::shared::v1::CertificateId,
+        >,
+        #[prost(uint64, tag = "2")]
+        pub echo_count: u64,
+        #[prost(uint64, tag = "3")]
+        pub ready_count: u64,
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLogLevelRequest {
+    /// Log filter using the `RUST_LOG` syntax, e.g. `warn,topos=debug`
+    #[prost(string, tag = "1")]
+    pub filter: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLogLevelResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvictPendingCertificateRequest {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvictPendingCertificateResponse {}
 /// Generated client implementations.
 pub mod console_service_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -1267,70 +1931,283 @@ pub mod console_service_client {
                 .insert(GrpcMethod::new("topos.tce.v1.ConsoleService", "Status"));
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod console_service_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with ConsoleServiceServer.
-    #[async_trait]
-    pub trait ConsoleService: Send + Sync + 'static {
-        async fn status(
-            &self,
-            request: tonic::Request<super::StatusRequest>,
-        ) -> std::result::Result<tonic::Response<super::StatusResponse>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct ConsoleServiceServer<T: ConsoleService> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    struct _Inner<T>(Arc<T>);
-    impl<T: ConsoleService> ConsoleServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
+        /// Returns the connected peers along with their connections
+        pub async fn get_peers(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPeersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPeersResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.ConsoleService/GetPeers",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.ConsoleService", "GetPeers"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        /// Returns the number of certificates in the pending and precedence pools
+        pub async fn get_pools(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPoolsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPoolsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.ConsoleService/GetPools",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.ConsoleService", "GetPools"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        /// Returns the latest delivered certificate of every known source subnet
+        pub async fn get_source_heads(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSourceHeadsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSourceHeadsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.ConsoleService/GetSourceHeads",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("topos.tce.v1.ConsoleService", "GetSourceHeads"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
+        /// Returns the certificates being broadcast with the number of Echo and Ready received
+        pub async fn get_broadcast_tasks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBroadcastTasksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBroadcastTasksResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.ConsoleService/GetBroadcastTasks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("topos.tce.v1.ConsoleService", "GetBroadcastTasks"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Replace the log filter of the node at runtime
+        pub async fn set_log_level(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetLogLevelRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetLogLevelResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.ConsoleService/SetLogLevel",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.ConsoleService", "SetLogLevel"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Remove a stuck certificate from the pending pool and stop its broadcast
+        pub async fn evict_pending_certificate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EvictPendingCertificateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EvictPendingCertificateResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.ConsoleService/EvictPendingCertificate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "topos.tce.v1.ConsoleService",
+                        "EvictPendingCertificate",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod console_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ConsoleServiceServer.
+    #[async_trait]
+    pub trait ConsoleService: Send + Sync + 'static {
+        async fn status(
+            &self,
+            request: tonic::Request<super::StatusRequest>,
+        ) -> std::result::Result<tonic::Response<super::StatusResponse>, tonic::Status>;
+        /// Returns the connected peers along with their connections
+        async fn get_peers(
+            &self,
+            request: tonic::Request<super::GetPeersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPeersResponse>,
+            tonic::Status,
+        >;
+        /// Returns the number of certificates in the pending and precedence pools
+        async fn get_pools(
+            &self,
+            request: tonic::Request<super::GetPoolsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPoolsResponse>,
+            tonic::Status,
+        >;
+        /// Returns the latest delivered certificate of every known source subnet
+        async fn get_source_heads(
+            &self,
+            request: tonic::Request<super::GetSourceHeadsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSourceHeadsResponse>,
+            tonic::Status,
+        >;
+        /// Returns the certificates being broadcast with the number of Echo and Ready received
+        async fn get_broadcast_tasks(
+            &self,
+            request: tonic::Request<super::GetBroadcastTasksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBroadcastTasksResponse>,
+            tonic::Status,
+        >;
+        /// Replace the log filter of the node at runtime
+        async fn set_log_level(
+            &self,
+            request: tonic::Request<super::SetLogLevelRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetLogLevelResponse>,
+            tonic::Status,
+        >;
+        /// Remove a stuck certificate from the pending pool and stop its broadcast
+        async fn evict_pending_certificate(
+            &self,
+            request: tonic::Request<super::EvictPendingCertificateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EvictPendingCertificateResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct ConsoleServiceServer<T: ConsoleService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ConsoleService> ConsoleServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
             self
         }
         /// Limits the maximum size of an encoded message.
@@ -1406,6 +2283,290 @@ pub mod console_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/topos.tce.v1.ConsoleService/GetPeers" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPeersSvc<T: ConsoleService>(pub Arc<T>);
+                    impl<
+                        T: ConsoleService,
+                    > tonic::server::UnaryService<super::GetPeersRequest>
+                    for GetPeersSvc<T> {
+                        type Response = super::GetPeersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPeersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ConsoleService>::get_peers(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPeersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.ConsoleService/GetPools" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPoolsSvc<T: ConsoleService>(pub Arc<T>);
+                    impl<
+                        T: ConsoleService,
+                    > tonic::server::UnaryService<super::GetPoolsRequest>
+                    for GetPoolsSvc<T> {
+                        type Response = super::GetPoolsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPoolsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ConsoleService>::get_pools(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPoolsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.ConsoleService/GetSourceHeads" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSourceHeadsSvc<T: ConsoleService>(pub Arc<T>);
+                    impl<
+                        T: ConsoleService,
+                    > tonic::server::UnaryService<super::GetSourceHeadsRequest>
+                    for GetSourceHeadsSvc<T> {
+                        type Response = super::GetSourceHeadsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSourceHeadsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ConsoleService>::get_source_heads(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSourceHeadsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.ConsoleService/GetBroadcastTasks" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBroadcastTasksSvc<T: ConsoleService>(pub Arc<T>);
+                    impl<
+                        T: ConsoleService,
+                    > tonic::server::UnaryService<super::GetBroadcastTasksRequest>
+                    for GetBroadcastTasksSvc<T> {
+                        type Response = super::GetBroadcastTasksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBroadcastTasksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ConsoleService>::get_broadcast_tasks(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBroadcastTasksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.ConsoleService/SetLogLevel" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetLogLevelSvc<T: ConsoleService>(pub Arc<T>);
+                    impl<
+                        T: ConsoleService,
+                    > tonic::server::UnaryService<super::SetLogLevelRequest>
+                    for SetLogLevelSvc<T> {
+                        type Response = super::SetLogLevelResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetLogLevelRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ConsoleService>::set_log_level(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetLogLevelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.ConsoleService/EvictPendingCertificate" => {
+                    #[allow(non_camel_case_types)]
+                    struct EvictPendingCertificateSvc<T: ConsoleService>(pub Arc<T>);
+                    impl<
+                        T: ConsoleService,
+                    > tonic::server::UnaryService<super::EvictPendingCertificateRequest>
+                    for EvictPendingCertificateSvc<T> {
+                        type Response = super::EvictPendingCertificateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::EvictPendingCertificateRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ConsoleService>::evict_pending_certificate(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = EvictPendingCertificateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -1454,6 +2615,18 @@ pub struct Gossip {
     #[prost(message, optional, tag = "1")]
     pub certificate: ::core::option::Option<super::super::uci::v1::Certificate>,
 }
+/// Statement of a validator that the peer publishing its messages acts on its behalf
+#[derive(serde::Deserialize, serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PeerBinding {
+    /// Epoch at which the statement has been signed
+    #[prost(uint64, tag = "1")]
+    pub epoch_id: u64,
+    /// Signature of the peer id followed by the epoch id by the validator
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<super::super::shared::v1::EcdsaSignature>,
+}
 #[derive(serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1464,6 +2637,8 @@ pub struct Echo {
     pub signature: ::core::option::Option<super::super::shared::v1::EcdsaSignature>,
     #[prost(message, optional, tag = "3")]
     pub validator_id: ::core::option::Option<super::super::shared::v1::ValidatorId>,
+    #[prost(message, optional, tag = "4")]
+    pub peer_binding: ::core::option::Option<PeerBinding>,
 }
 #[derive(serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1475,12 +2650,14 @@ pub struct Ready {
     pub signature: ::core::option::Option<super::super::shared::v1::EcdsaSignature>,
     #[prost(message, optional, tag = "3")]
     pub validator_id: ::core::option::Option<super::super::shared::v1::ValidatorId>,
+    #[prost(message, optional, tag = "4")]
+    pub peer_binding: ::core::option::Option<PeerBinding>,
 }
 #[derive(serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DoubleEchoRequest {
-    #[prost(oneof = "double_echo_request::Request", tags = "1, 2, 3")]
+    #[prost(oneof = "double_echo_request::Request", tags = "1, 2, 3, 4")]
     pub request: ::core::option::Option<double_echo_request::Request>,
 }
 /// Nested message and enum types in `DoubleEchoRequest`.
@@ -1495,6 +2672,9 @@ pub mod double_echo_request {
         Echo(super::Echo),
         #[prost(message, tag = "3")]
         Ready(super::Ready),
+        /// Checkpoint signed by the sending validator
+        #[prost(message, tag = "4")]
+        Checkpoint(super::SignedCheckpoint),
     }
 }
 #[derive(serde::Deserialize, serde::Serialize)]
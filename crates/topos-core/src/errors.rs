@@ -1,4 +1,5 @@
 use crate::api::grpc::checkpoints::StreamPositionError;
+use crate::types::ValidatorIdConversionError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum GrpcParsingError {
@@ -6,4 +7,6 @@ pub enum GrpcParsingError {
     GrpcMalformedType(&'static str),
     #[error(transparent)]
     PositionParsing(#[from] StreamPositionError),
+    #[error(transparent)]
+    ValidatorIdParsing(#[from] ValidatorIdConversionError),
 }
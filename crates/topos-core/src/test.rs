@@ -1,4 +1,10 @@
-use crate::types::stream::Position;
+use std::collections::HashSet;
+
+use topos_crypto::messages::MessageSigner;
+
+use crate::types::stream::{CertificateSourceStreamPosition, Position};
+use crate::types::{CertificateDelivered, ProofOfDelivery, ProofOfDeliveryError, ValidatorId};
+use crate::uci::{Certificate, CertificateId, SubnetId};
 
 #[test]
 fn test_position() {
@@ -25,3 +31,129 @@ fn position_from_integer() {
 
     assert_eq!(*position, 0);
 }
+
+fn signed_proof(
+    certificate_id: CertificateId,
+    signers: &[MessageSigner],
+    threshold: u64,
+) -> ProofOfDelivery {
+    ProofOfDelivery {
+        certificate_id,
+        delivery_position: CertificateSourceStreamPosition {
+            subnet_id: SubnetId::from_array([2u8; 32]),
+            position: Position::ZERO,
+        },
+        readies: signers
+            .iter()
+            .map(|signer| {
+                let validator_id = ValidatorId::from(signer.public_address);
+                let payload = ProofOfDelivery::ready_payload(&certificate_id, &validator_id);
+
+                (validator_id, signer.sign_message(&payload).unwrap())
+            })
+            .collect(),
+        threshold,
+    }
+}
+
+#[test]
+fn proof_of_delivery_verification() {
+    let signers: Vec<_> = (1..=4u8)
+        .map(|i| MessageSigner::new(&[i; 32]).unwrap())
+        .collect();
+    let validators: HashSet<ValidatorId> = signers
+        .iter()
+        .map(|signer| signer.public_address.into())
+        .collect();
+
+    let proof = signed_proof(CertificateId::from_array([1u8; 32]), &signers[..3], 3);
+    assert_eq!(proof.verify(&validators, 3), Ok(()));
+
+    assert_eq!(
+        proof.verify(&validators, 4),
+        Err(ProofOfDeliveryError::ThresholdTooLow {
+            provided: 3,
+            expected: 4
+        })
+    );
+
+    let proof = signed_proof(CertificateId::from_array([1u8; 32]), &signers[..2], 3);
+    assert_eq!(
+        proof.verify(&validators, 3),
+        Err(ProofOfDeliveryError::NotEnoughReadies {
+            received: 2,
+            threshold: 3
+        })
+    );
+
+    let outsider = MessageSigner::new(&[42u8; 32]).unwrap();
+    let outsider_id: ValidatorId = outsider.public_address.into();
+    let proof = signed_proof(CertificateId::from_array([1u8; 32]), &[outsider], 1);
+    assert_eq!(
+        proof.verify(&validators, 1),
+        Err(ProofOfDeliveryError::UnknownValidator(outsider_id))
+    );
+
+    let mut proof = signed_proof(CertificateId::from_array([1u8; 32]), &signers[..3], 3);
+    let forged = proof.readies[1].1;
+    proof.readies[0].1 = forged;
+    assert_eq!(
+        proof.verify(&validators, 3),
+        Err(ProofOfDeliveryError::InvalidSignature(proof.readies[0].0))
+    );
+}
+
+#[test]
+fn certificate_delivered_verification() {
+    let signers: Vec<_> = (1..=4u8)
+        .map(|i| MessageSigner::new(&[i; 32]).unwrap())
+        .collect();
+    let validators: HashSet<ValidatorId> = signers
+        .iter()
+        .map(|signer| signer.public_address.into())
+        .collect();
+
+    let source_subnet_id = SubnetId::from_array([2u8; 32]);
+    let certificate = Certificate::new_with_default_fields(
+        CertificateId::from_array([0u8; 32]),
+        source_subnet_id,
+        &[],
+    )
+    .unwrap();
+
+    let delivered = CertificateDelivered {
+        certificate: certificate.clone(),
+        proof_of_delivery: signed_proof(certificate.id, &signers[..3], 3),
+    };
+    assert_eq!(delivered.verify(&validators, 3), Ok(()));
+
+    let mut tampered = delivered.clone();
+    tampered.certificate.verifier = 1;
+    assert_eq!(
+        tampered.verify(&validators, 3),
+        Err(ProofOfDeliveryError::InvalidCertificateId(certificate.id))
+    );
+
+    let other_proof = signed_proof(CertificateId::from_array([1u8; 32]), &signers[..3], 3);
+    let mismatch = CertificateDelivered {
+        certificate: certificate.clone(),
+        proof_of_delivery: other_proof.clone(),
+    };
+    assert_eq!(
+        mismatch.verify(&validators, 3),
+        Err(ProofOfDeliveryError::CertificateMismatch {
+            certificate: certificate.id,
+            proof: other_proof.certificate_id,
+        })
+    );
+
+    let mut wrong_subnet = delivered;
+    wrong_subnet.proof_of_delivery.delivery_position.subnet_id = SubnetId::from_array([3u8; 32]);
+    assert_eq!(
+        wrong_subnet.verify(&validators, 3),
+        Err(ProofOfDeliveryError::SubnetMismatch {
+            certificate: source_subnet_id,
+            proof: SubnetId::from_array([3u8; 32]),
+        })
+    );
+}
@@ -1,5 +1,6 @@
-use crate::uci::{Certificate, CertificateId};
+use crate::uci::{Certificate, CertificateId, SubnetId};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 use crate::errors::GrpcParsingError;
 
@@ -11,9 +12,7 @@ use crate::api::grpc::{
 
 pub mod stream;
 
-pub type Ready = String;
-pub type Signature = String;
-
+pub use topos_crypto::messages::Signature;
 pub use topos_crypto::validator_id::Error as ValidatorIdConversionError;
 pub use topos_crypto::validator_id::ValidatorId;
 
@@ -29,6 +28,42 @@ impl AsRef<CertificateDelivered> for CertificateDelivered {
     }
 }
 
+impl CertificateDelivered {
+    /// Verify that the certificate has been delivered by the given set of validators.
+    ///
+    /// The proof of delivery needs to be bound to the certificate, both on its id and on
+    /// its source subnet, and needs to be valid according to [`ProofOfDelivery::verify`].
+    /// This check doesn't require any access to a TCE node.
+    pub fn verify(
+        &self,
+        validators: &HashSet<ValidatorId>,
+        expected_threshold: u64,
+    ) -> Result<(), ProofOfDeliveryError> {
+        let certificate = &self.certificate;
+        let proof = &self.proof_of_delivery;
+
+        certificate
+            .check_id()
+            .map_err(|_| ProofOfDeliveryError::InvalidCertificateId(certificate.id))?;
+
+        if proof.certificate_id != certificate.id {
+            return Err(ProofOfDeliveryError::CertificateMismatch {
+                certificate: certificate.id,
+                proof: proof.certificate_id,
+            });
+        }
+
+        if proof.delivery_position.subnet_id != certificate.source_subnet_id {
+            return Err(ProofOfDeliveryError::SubnetMismatch {
+                certificate: certificate.source_subnet_id,
+                proof: proof.delivery_position.subnet_id,
+            });
+        }
+
+        proof.verify(validators, expected_threshold)
+    }
+}
+
 /// Certificate's Proof of Delivery
 ///
 /// This structure is used to prove that a certificate has been delivered.
@@ -49,12 +84,103 @@ pub struct ProofOfDelivery {
     pub certificate_id: CertificateId,
     /// The position of the certificate in the source stream
     pub delivery_position: CertificateSourceStreamPosition,
-    /// The list of Ready messages used to proove the certificate's delivery
-    pub readies: Vec<(Ready, Signature)>,
+    /// The list of signed Ready messages used to prove the certificate's delivery,
+    /// identified by the validator who emitted them
+    pub readies: Vec<(ValidatorId, Signature)>,
     /// The threshold of Ready messages required to consider the certificate as delivered
     pub threshold: u64,
 }
 
+/// Errors raised when a [`ProofOfDelivery`] fails to prove the delivery of a certificate
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ProofOfDeliveryError {
+    #[error("The threshold of the proof ({provided}) is lower than the expected one ({expected})")]
+    ThresholdTooLow { provided: u64, expected: u64 },
+
+    #[error("Ready message signed by an unknown validator: {0}")]
+    UnknownValidator(ValidatorId),
+
+    #[error("Ready message from validator {0} appears more than once")]
+    DuplicatedReady(ValidatorId),
+
+    #[error("Invalid Ready signature from validator {0}")]
+    InvalidSignature(ValidatorId),
+
+    #[error("Not enough Ready messages to reach the threshold: {received}/{threshold}")]
+    NotEnoughReadies { received: usize, threshold: u64 },
+
+    #[error("The certificate id {0} doesn't match the certificate content")]
+    InvalidCertificateId(CertificateId),
+
+    #[error("The proof of delivery is for certificate {proof} instead of {certificate}")]
+    CertificateMismatch {
+        certificate: CertificateId,
+        proof: CertificateId,
+    },
+
+    #[error("The proof of delivery is positioned on subnet {proof} instead of {certificate}")]
+    SubnetMismatch {
+        certificate: SubnetId,
+        proof: SubnetId,
+    },
+}
+
+impl ProofOfDelivery {
+    /// Returns the payload signed by a validator when sending a Ready message
+    /// for the given certificate.
+    pub fn ready_payload(certificate_id: &CertificateId, validator_id: &ValidatorId) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(certificate_id.as_array());
+        payload.extend_from_slice(validator_id.as_bytes());
+
+        payload
+    }
+
+    /// Verify that the proof is backed by enough valid Ready messages.
+    ///
+    /// Every Ready message needs to be signed by a member of `validators` and the number
+    /// of Ready messages must reach the `threshold` of the proof. The `threshold` of the proof
+    /// can't be lower than the `expected_threshold`, which prevents a proof to lower its
+    /// own requirement.
+    pub fn verify(
+        &self,
+        validators: &HashSet<ValidatorId>,
+        expected_threshold: u64,
+    ) -> Result<(), ProofOfDeliveryError> {
+        if self.threshold < expected_threshold {
+            return Err(ProofOfDeliveryError::ThresholdTooLow {
+                provided: self.threshold,
+                expected: expected_threshold,
+            });
+        }
+
+        let mut signers = HashSet::with_capacity(self.readies.len());
+        for (validator_id, signature) in &self.readies {
+            if !validators.contains(validator_id) {
+                return Err(ProofOfDeliveryError::UnknownValidator(*validator_id));
+            }
+
+            if !signers.insert(validator_id) {
+                return Err(ProofOfDeliveryError::DuplicatedReady(*validator_id));
+            }
+
+            let payload = Self::ready_payload(&self.certificate_id, validator_id);
+            signature
+                .verify(payload, validator_id.address())
+                .map_err(|_| ProofOfDeliveryError::InvalidSignature(*validator_id))?;
+        }
+
+        if (signers.len() as u64) < self.threshold {
+            return Err(ProofOfDeliveryError::NotEnoughReadies {
+                received: signers.len(),
+                threshold: self.threshold,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl From<SourceStreamPosition> for CertificateSourceStreamPosition {
     fn from(value: SourceStreamPosition) -> Self {
         Self {
@@ -82,8 +208,19 @@ impl TryFrom<GrpcProofOfDelivery> for ProofOfDelivery {
             readies: value
                 .readies
                 .into_iter()
-                .map(|v| (v.ready, v.signature))
-                .collect(),
+                .map(|v| {
+                    let validator_id = v
+                        .validator_id
+                        .ok_or(GrpcParsingError::GrpcMalformedType("readies.validator_id"))?
+                        .try_into()?;
+                    let signature = v
+                        .signature
+                        .ok_or(GrpcParsingError::GrpcMalformedType("readies.signature"))?
+                        .into();
+
+                    Ok((validator_id, signature))
+                })
+                .collect::<Result<_, GrpcParsingError>>()?,
             threshold: value.threshold,
         })
     }
@@ -103,9 +240,9 @@ impl From<ProofOfDelivery> for GrpcProofOfDelivery {
             readies: value
                 .readies
                 .into_iter()
-                .map(|v| SignedReady {
-                    ready: v.0,
-                    signature: v.1,
+                .map(|(validator_id, signature)| SignedReady {
+                    validator_id: Some(validator_id.into()),
+                    signature: Some(signature.into()),
                 })
                 .collect(),
             threshold: value.threshold,
@@ -4,7 +4,7 @@ use std::fmt::Debug;
 
 use super::{
     CertificateId, Error, Frost, ReceiptsRootHash, StarkProof, StateRoot, SubnetId, TxRootHash,
-    CERTIFICATE_ID_LENGTH, DUMMY_FROST_VERIF_DELAY, DUMMY_STARK_DELAY,
+    VerifierRegistry, CERTIFICATE_ID_LENGTH,
 };
 
 /// Certificate - main exchange item
@@ -115,14 +115,32 @@ impl Certificate {
         Ok(cert)
     }
 
+    /// Check that the certificate payload is signed by the source subnet
     pub fn check_signature(&self) -> Result<(), Error> {
-        std::thread::sleep(DUMMY_FROST_VERIF_DELAY);
+        topos_crypto::signatures::verify(
+            &self.source_subnet_id.to_secp256k1_public_key(),
+            self.get_payload().as_slice(),
+            self.signature.as_slice(),
+        )?;
+
         Ok(())
     }
 
-    pub fn check_proof(&self) -> Result<(), Error> {
-        std::thread::sleep(DUMMY_STARK_DELAY);
-        Ok(())
+    /// Check the proof of the certificate with the verifier it designates
+    pub fn check_proof(&self, verifiers: &VerifierRegistry) -> Result<(), Error> {
+        verifiers.verify(self)
+    }
+
+    /// Check that the certificate id matches the content of the certificate
+    pub fn check_id(&self) -> Result<(), Error> {
+        if Self::calculate_cert_id(self)? == *self.id.as_array() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "certificate id {} doesn't match the certificate content",
+                self.id
+            )))
+        }
     }
 
     /// Signs the hash of the certificate payload
@@ -218,6 +236,32 @@ mod tests {
         .expect("valid signature check")
     }
 
+    #[test]
+    fn check_proof_with_registered_verifier() {
+        let private_test_key = hex::decode(PRIVATE_TEST_KEY).unwrap();
+        let mut dummy_cert = generate_dummy_cert(&private_test_key);
+        dummy_cert
+            .update_signature(private_test_key.as_slice())
+            .expect("valid signature update");
+
+        assert!(matches!(
+            dummy_cert.check_proof(&VerifierRegistry::default()),
+            Err(Error::UnknownVerifier(2))
+        ));
+
+        let verifiers = VerifierRegistry::empty()
+            .with_verifier(dummy_cert.verifier, crate::uci::Secp256k1SignatureVerifier);
+        dummy_cert
+            .check_proof(&verifiers)
+            .expect("valid proof check");
+
+        dummy_cert.state_root[0] = 0xff;
+        assert!(matches!(
+            dummy_cert.check_proof(&verifiers),
+            Err(Error::CryptoError(_))
+        ));
+    }
+
     #[test]
     #[should_panic]
     fn signature_verification_failed_corrupt_data() {
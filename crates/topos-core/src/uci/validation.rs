@@ -0,0 +1,188 @@
+//! Validation of the certificates before they enter the pending pool
+//!
+//! The [`CertificateValidator`] runs the checks that don't depend on the state of the node:
+//! the size of the proof, the sanity of the target subnets, the signature of the source subnet
+//! and the proof through the [`VerifierRegistry`].
+
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use super::{Certificate, SubnetId, VerifierRegistry};
+
+/// Reason for which a certificate has been rejected by the [`CertificateValidator`]
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("invalid proof: {0}")]
+    InvalidProof(String),
+
+    #[error("the certificate targets its own source subnet")]
+    SelfTarget,
+
+    #[error("the target subnet {0} is duplicated")]
+    DuplicatedTarget(SubnetId),
+
+    #[error("the proof is too large: {size} bytes, maximum is {max}")]
+    ProofTooLarge { size: usize, max: usize },
+}
+
+/// Pipeline of checks run on every certificate submitted to or gossiped to the node
+pub struct CertificateValidator {
+    verifiers: VerifierRegistry,
+    verify_signature: bool,
+    max_proof_size: usize,
+}
+
+impl Default for CertificateValidator {
+    fn default() -> Self {
+        Self {
+            verifiers: VerifierRegistry::default(),
+            verify_signature: true,
+            max_proof_size: Self::MAX_PROOF_SIZE,
+        }
+    }
+}
+
+impl CertificateValidator {
+    /// Default maximum size of a proof, in bytes
+    pub const MAX_PROOF_SIZE: usize = 1024 * 1024;
+
+    pub fn with_verifiers(mut self, verifiers: VerifierRegistry) -> Self {
+        self.verifiers = verifiers;
+
+        self
+    }
+
+    pub fn with_signature_verification(mut self, verify_signature: bool) -> Self {
+        self.verify_signature = verify_signature;
+
+        self
+    }
+
+    pub fn with_max_proof_size(mut self, max_proof_size: usize) -> Self {
+        self.max_proof_size = max_proof_size;
+
+        self
+    }
+
+    /// Run the checks on the certificate, the cheapest ones first
+    pub fn validate(&self, certificate: &Certificate) -> Result<(), ValidationError> {
+        if certificate.proof.len() > self.max_proof_size {
+            return Err(ValidationError::ProofTooLarge {
+                size: certificate.proof.len(),
+                max: self.max_proof_size,
+            });
+        }
+
+        let mut targets = HashSet::with_capacity(certificate.target_subnets.len());
+        for target in &certificate.target_subnets {
+            if *target == certificate.source_subnet_id {
+                return Err(ValidationError::SelfTarget);
+            }
+
+            if !targets.insert(target) {
+                return Err(ValidationError::DuplicatedTarget(*target));
+            }
+        }
+
+        if self.verify_signature {
+            certificate
+                .check_signature()
+                .map_err(|error| ValidationError::InvalidSignature(error.to_string()))?;
+        }
+
+        certificate
+            .check_proof(&self.verifiers)
+            .map_err(|error| ValidationError::InvalidProof(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uci::{SignatureOnlyVerifier, CERTIFICATE_ID_LENGTH, SUBNET_ID_LENGTH};
+
+    const SOURCE_SUBNET_ID: SubnetId = SubnetId::from_array([1u8; SUBNET_ID_LENGTH]);
+    const TARGET_SUBNET_ID: SubnetId = SubnetId::from_array([2u8; SUBNET_ID_LENGTH]);
+
+    fn certificate(target_subnets: &[SubnetId], proof: Vec<u8>) -> Certificate {
+        Certificate::new(
+            [0u8; CERTIFICATE_ID_LENGTH],
+            SOURCE_SUBNET_ID,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            target_subnets,
+            0,
+            proof,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validation_pipeline() {
+        let validator = CertificateValidator::default()
+            .with_signature_verification(false)
+            .with_verifiers(VerifierRegistry::empty().with_verifier(0, SignatureOnlyVerifier))
+            .with_max_proof_size(4);
+
+        assert_eq!(
+            validator.validate(&certificate(&[TARGET_SUBNET_ID], vec![0; 4])),
+            Ok(())
+        );
+        assert_eq!(
+            validator.validate(&certificate(&[TARGET_SUBNET_ID], vec![0; 5])),
+            Err(ValidationError::ProofTooLarge { size: 5, max: 4 })
+        );
+        assert_eq!(
+            validator.validate(&certificate(&[SOURCE_SUBNET_ID], vec![])),
+            Err(ValidationError::SelfTarget)
+        );
+        assert_eq!(
+            validator.validate(&certificate(&[TARGET_SUBNET_ID, TARGET_SUBNET_ID], vec![])),
+            Err(ValidationError::DuplicatedTarget(TARGET_SUBNET_ID))
+        );
+
+        let mut unknown_verifier = certificate(&[TARGET_SUBNET_ID], vec![]);
+        unknown_verifier.verifier = 1;
+        assert!(matches!(
+            validator.validate(&unknown_verifier),
+            Err(ValidationError::InvalidProof(_))
+        ));
+
+        assert!(matches!(
+            validator
+                .with_signature_verification(true)
+                .validate(&certificate(&[TARGET_SUBNET_ID], vec![])),
+            Err(ValidationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn signature_verification_can_be_disabled() {
+        let unsigned = certificate(&[TARGET_SUBNET_ID], vec![]);
+
+        assert!(matches!(
+            CertificateValidator::default().validate(&unsigned),
+            Err(ValidationError::InvalidSignature(_))
+        ));
+
+        // Disabling the signature verification leaves the proof verification untouched
+        assert!(matches!(
+            CertificateValidator::default()
+                .with_signature_verification(false)
+                .validate(&unsigned),
+            Err(ValidationError::InvalidProof(_))
+        ));
+        assert_eq!(
+            CertificateValidator::default()
+                .with_signature_verification(false)
+                .with_verifiers(VerifierRegistry::empty().with_verifier(0, SignatureOnlyVerifier))
+                .validate(&unsigned),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Verification of the proofs carried by the certificates
+//!
+//! Each certificate designates, through its `verifier` field, the way its proof has to be
+//! verified. A [`VerifierRegistry`] maps those identifiers to [`CertificateVerifier`]
+//! implementations, certificates using an unknown verifier being rejected.
+
+use std::collections::HashMap;
+
+use super::{Certificate, Error};
+
+/// Identifier of the certificates whose proof is the secp256k1 signature of their source subnet
+pub const SECP256K1_SIGNATURE_VERIFIER: u32 = 0;
+
+/// Verifies the proof of a certificate
+pub trait CertificateVerifier: Send + Sync {
+    fn verify(&self, certificate: &Certificate) -> Result<(), Error>;
+}
+
+/// Verifies that the certificate is signed by its source subnet
+///
+/// The signature is checked against the secp256k1 public key derived from the source subnet id.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Secp256k1SignatureVerifier;
+
+impl CertificateVerifier for Secp256k1SignatureVerifier {
+    fn verify(&self, certificate: &Certificate) -> Result<(), Error> {
+        certificate.check_signature()
+    }
+}
+
+/// Accepts every certificate, leaving the signature of the source subnet to the
+/// [`CertificateValidator`](super::CertificateValidator)
+///
+/// Meant for the setups whose certificates aren't signed, such as tests, the proof of the
+/// certificates being otherwise left unchecked.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SignatureOnlyVerifier;
+
+impl CertificateVerifier for SignatureOnlyVerifier {
+    fn verify(&self, _: &Certificate) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Registry of the [`CertificateVerifier`] indexed by their identifier
+///
+/// The default registry contains the [`Secp256k1SignatureVerifier`] as
+/// [`SECP256K1_SIGNATURE_VERIFIER`].
+pub struct VerifierRegistry {
+    verifiers: HashMap<u32, Box<dyn CertificateVerifier>>,
+}
+
+impl Default for VerifierRegistry {
+    fn default() -> Self {
+        Self::empty().with_verifier(SECP256K1_SIGNATURE_VERIFIER, Secp256k1SignatureVerifier)
+    }
+}
+
+impl VerifierRegistry {
+    /// Creates a registry without any verifier, rejecting every certificate
+    pub fn empty() -> Self {
+        Self {
+            verifiers: HashMap::new(),
+        }
+    }
+
+    /// Register a verifier, replacing the one previously registered with the same identifier
+    pub fn with_verifier<V: CertificateVerifier + 'static>(mut self, id: u32, verifier: V) -> Self {
+        self.verifiers.insert(id, Box::new(verifier));
+
+        self
+    }
+
+    /// Verify the proof of the certificate with the verifier it designates
+    pub fn verify(&self, certificate: &Certificate) -> Result<(), Error> {
+        self.verifiers
+            .get(&certificate.verifier)
+            .ok_or(Error::UnknownVerifier(certificate.verifier))?
+            .verify(certificate)
+    }
+}
@@ -16,9 +16,11 @@ use topos_core::api::grpc::tce::v1::api_service_server::{ApiService, ApiServiceS
 use topos_core::api::grpc::tce::v1::synchronizer_service_client::SynchronizerServiceClient;
 use topos_core::api::grpc::tce::v1::watch_certificates_request::{Command, OpenStream};
 use topos_core::api::grpc::tce::v1::{
-    GetLastPendingCertificatesRequest, GetLastPendingCertificatesResponse, GetSourceHeadRequest,
-    GetSourceHeadResponse, LastPendingCertificate, SubmitCertificateRequest,
-    SubmitCertificateResponse, WatchCertificatesRequest, WatchCertificatesResponse,
+    GetCertificateStatusRequest, GetCertificateStatusResponse, GetLastPendingCertificatesRequest,
+    GetLastPendingCertificatesResponse, GetSourceHeadRequest, GetSourceHeadResponse,
+    LastPendingCertificate, SubmitCertificateRequest, SubmitCertificateResponse,
+    SubmitCertificatesRequest, SubmitCertificatesResponse, WatchCertificatesRequest,
+    WatchCertificatesResponse,
 };
 use topos_core::api::grpc::uci::v1::Certificate;
 use topos_core::api::grpc::{shared, GrpcClient};
@@ -40,7 +42,14 @@ async fn create_tce_layer() {
             &self,
             _request: Request<SubmitCertificateRequest>,
         ) -> Result<Response<SubmitCertificateResponse>, tonic::Status> {
-            Ok(Response::new(SubmitCertificateResponse {}))
+            Ok(Response::new(SubmitCertificateResponse { rejection: None }))
+        }
+
+        async fn submit_certificates(
+            &self,
+            _request: Request<SubmitCertificatesRequest>,
+        ) -> Result<Response<SubmitCertificatesResponse>, tonic::Status> {
+            Err(Status::unimplemented("submit_certificates"))
         }
 
         async fn get_source_head(
@@ -97,6 +106,13 @@ async fn create_tce_layer() {
             }))
         }
 
+        async fn get_certificate_status(
+            &self,
+            _request: Request<GetCertificateStatusRequest>,
+        ) -> Result<Response<GetCertificateStatusResponse>, Status> {
+            Err(Status::unimplemented("get_certificate_status"))
+        }
+
         async fn watch_certificates(
             &self,
             request: Request<tonic::Streaming<WatchCertificatesRequest>>,
@@ -173,7 +189,7 @@ async fn create_tce_layer() {
         .await
         .map(|r| r.into_inner())
         .unwrap();
-    assert_eq!(response, SubmitCertificateResponse {});
+    assert_eq!(response, SubmitCertificateResponse { rejection: None });
 
     // Test get source head certificate
     let response = client
@@ -222,6 +238,8 @@ async fn create_tce_layer() {
             positions: Vec::new(),
         }),
         source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
     }));
     let request_id: shared::v1::Uuid = Uuid::new_v4().into();
     let first_request = WatchCertificatesRequest {
@@ -235,6 +253,8 @@ async fn create_tce_layer() {
             positions: Vec::new(),
         }),
         source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
     }
     .into();
     first_request_short.request_id = Some(request_id);
@@ -1,4 +1,7 @@
-use prometheus::{register_int_counter_with_registry, IntCounter};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_counter_with_registry, IntCounter,
+    IntCounterVec,
+};
 
 use lazy_static::lazy_static;
 
@@ -12,4 +15,12 @@ lazy_static! {
             TOPOS_METRIC_REGISTRY
         )
         .unwrap();
+    pub static ref API_LIMITED_REQUESTS_TOTAL: IntCounterVec =
+        register_int_counter_vec_with_registry!(
+            "api_limited_requests_total",
+            "Number of requests refused by the public APIs because a client limit was reached.",
+            &["limit"],
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
 }
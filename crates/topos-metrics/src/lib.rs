@@ -90,10 +90,12 @@ pub fn gather_metrics() -> String {
 
 pub fn init_metrics() {
     API_GRPC_CERTIFICATE_RECEIVED_TOTAL.reset();
+    API_LIMITED_REQUESTS_TOTAL.reset();
     P2P_EVENT_STREAM_CAPACITY_TOTAL.reset();
     P2P_MESSAGE_RECEIVED_ON_GOSSIP_TOTAL.reset();
     P2P_MESSAGE_RECEIVED_ON_ECHO_TOTAL.reset();
     P2P_MESSAGE_RECEIVED_ON_READY_TOTAL.reset();
+    P2P_MESSAGE_RECEIVED_ON_CHECKPOINT_TOTAL.reset();
     P2P_MESSAGE_SENT_ON_GOSSIPSUB_TOTAL.reset();
     DOUBLE_ECHO_ACTIVE_TASKS_COUNT.set(0);
     DOUBLE_ECHO_COMMAND_CHANNEL_CAPACITY_TOTAL.reset();
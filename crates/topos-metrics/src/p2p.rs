@@ -43,6 +43,13 @@ lazy_static! {
             TOPOS_METRIC_REGISTRY
         )
         .unwrap();
+    pub static ref P2P_MESSAGE_RECEIVED_ON_CHECKPOINT_TOTAL: IntCounter =
+        register_int_counter_with_registry!(
+            "p2p_checkpoint_message_total",
+            "Number of checkpoint message received.",
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
     pub static ref P2P_MESSAGE_SENT_ON_GOSSIPSUB_TOTAL: IntCounter =
         register_int_counter_with_registry!(
             "p2p_gossipsub_message_sent_total",
@@ -75,6 +75,9 @@ pub(crate) fn spawn_tce_process(
     };
 
     config.validators = genesis.validators().expect("Cannot parse validators");
+    if config.epoch.genesis_timestamp.is_none() {
+        config.epoch.genesis_timestamp = genesis.timestamp().expect("Cannot parse timestamp");
+    }
     config.tce_params = ReliableBroadcastParams::new(config.validators.len());
 
     if let Some(socket) = config.libp2p_api_addr {
@@ -93,7 +96,11 @@ pub(crate) fn spawn_tce_process(
     }
 
     config.version = env!("TOPOS_VERSION");
-    config.storage = StorageConfiguration::RocksDB(Some(config.db_path.clone()));
+    config.storage = if config.in_memory_storage {
+        StorageConfiguration::RAM
+    } else {
+        StorageConfiguration::RocksDB(Some(config.db_path.clone()))
+    };
 
     debug!("TCE args: {config:?}");
     spawn(async move {
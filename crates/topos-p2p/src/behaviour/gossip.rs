@@ -21,7 +21,9 @@ use topos_metrics::P2P_GOSSIP_BATCH_SIZE;
 use tracing::{debug, error, warn};
 
 use crate::error::P2PError;
-use crate::{constants, event::ComposedEvent, TOPOS_ECHO, TOPOS_GOSSIP, TOPOS_READY};
+use crate::{
+    constants, event::ComposedEvent, TOPOS_CHECKPOINT, TOPOS_ECHO, TOPOS_GOSSIP, TOPOS_READY,
+};
 
 use super::HealthStatus;
 
@@ -45,9 +47,9 @@ impl Behaviour {
         message: Vec<u8>,
     ) -> Result<usize, &'static str> {
         match topic {
-            TOPOS_GOSSIP => {
+            TOPOS_GOSSIP | TOPOS_CHECKPOINT => {
                 if let Ok(msg_id) = self.gossipsub.publish(IdentTopic::new(topic), message) {
-                    debug!("Published on topos_gossip: {:?}", msg_id);
+                    debug!("Published on {}: {:?}", topic, msg_id);
                 }
             }
             TOPOS_ECHO | TOPOS_READY => self.pending.entry(topic).or_default().push_back(message),
@@ -67,6 +69,9 @@ impl Behaviour {
         self.gossipsub
             .subscribe(&gossipsub::IdentTopic::new(TOPOS_READY))?;
 
+        self.gossipsub
+            .subscribe(&gossipsub::IdentTopic::new(TOPOS_CHECKPOINT))?;
+
         Ok(())
     }
 
@@ -252,6 +257,15 @@ impl NetworkBehaviour for Behaviour {
                             },
                         )))
                     }
+                    TOPOS_CHECKPOINT => {
+                        return Poll::Ready(ToSwarm::GenerateEvent(ComposedEvent::Gossipsub(
+                            crate::event::GossipEvent::Message {
+                                topic: TOPOS_CHECKPOINT,
+                                message: data,
+                                source,
+                            },
+                        )))
+                    }
                     _ => {}
                 },
                 gossipsub::Event::Subscribed { peer_id, topic } => {
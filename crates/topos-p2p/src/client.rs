@@ -10,7 +10,7 @@ use topos_core::api::grpc::GrpcClient;
 use crate::{
     error::{CommandExecutionError, P2PError},
     utils::GrpcOverP2P,
-    Command,
+    Command, PeerInfo,
 };
 
 #[derive(Clone)]
@@ -29,6 +29,13 @@ impl NetworkClient {
             .await
     }
 
+    /// Returns the connected peers along with their connections
+    pub async fn peers_info(&self) -> Result<Vec<PeerInfo>, P2PError> {
+        let (sender, receiver) = oneshot::channel();
+        Self::send_command_with_receiver(&self.sender, Command::PeersInfo { sender }, receiver)
+            .await
+    }
+
     pub async fn random_known_peer(&self) -> Result<PeerId, P2PError> {
         let (sender, receiver) = oneshot::channel();
         Self::send_command_with_receiver(
@@ -56,6 +63,17 @@ impl NetworkClient {
         })
     }
 
+    /// Deny peers from publishing on the echo and ready topics, lifting the denial of others
+    pub async fn update_denied_peers(
+        &self,
+        denied: Vec<PeerId>,
+        allowed: Vec<PeerId>,
+    ) -> Result<(), SendError<Command>> {
+        self.sender
+            .send(Command::UpdateDeniedPeers { denied, allowed })
+            .await
+    }
+
     async fn send_command_with_receiver<
         T,
         E: From<oneshot::error::RecvError> + From<CommandExecutionError>,
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use libp2p::PeerId;
+use libp2p::{Multiaddr, PeerId};
 use tokio::sync::oneshot;
 
 use crate::{behaviour::grpc::connection::OutboundConnection, error::P2PError};
@@ -28,19 +28,43 @@ pub enum Command {
         response: oneshot::Sender<OutboundConnection>,
     },
 
+    /// Ask for the connected peers along with their connections
+    PeersInfo {
+        sender: oneshot::Sender<Result<Vec<PeerInfo>, P2PError>>,
+    },
+
     /// Ask for a random known peer
     RandomKnownPeer {
         sender: oneshot::Sender<Result<PeerId, P2PError>>,
     },
+
+    /// Update the peers whose messages are dropped on the echo and ready topics
+    UpdateDeniedPeers {
+        denied: Vec<PeerId>,
+        allowed: Vec<PeerId>,
+    },
 }
 
 impl Display for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Command::ConnectedPeers { .. } => write!(f, "ConnectedPeers"),
+            Command::PeersInfo { .. } => write!(f, "PeersInfo"),
             Command::RandomKnownPeer { .. } => write!(f, "RandomKnownPeer"),
             Command::Gossip { .. } => write!(f, "GossipMessage"),
             Command::NewProxiedQuery { .. } => write!(f, "NewProxiedQuery"),
+            Command::UpdateDeniedPeers { .. } => write!(f, "UpdateDeniedPeers"),
         }
     }
 }
+
+/// Connected peer and the remote addresses of its connections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerInfo {
+    pub peer_id: PeerId,
+    pub addresses: Vec<Multiaddr>,
+    /// Whether at least one of the connections was dialed by the local node
+    pub outbound: bool,
+    /// Whether the Echo and Ready messages of the peer are dropped
+    pub denied: bool,
+}
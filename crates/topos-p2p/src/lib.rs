@@ -16,7 +16,7 @@ use std::convert::Infallible;
 pub(crate) use behaviour::Behaviour;
 pub use client::NetworkClient;
 pub use client::RetryPolicy;
-pub use command::Command;
+pub use command::{Command, PeerInfo};
 pub use event::Event;
 use http::Request;
 use http::Response;
@@ -37,6 +37,7 @@ pub mod network;
 pub const TOPOS_GOSSIP: &str = "topos_gossip";
 pub const TOPOS_ECHO: &str = "topos_echo";
 pub const TOPOS_READY: &str = "topos_ready";
+pub const TOPOS_CHECKPOINT: &str = "topos_checkpoint";
 
 #[macro_export]
 macro_rules! protocol_name {
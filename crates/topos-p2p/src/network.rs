@@ -241,6 +241,8 @@ impl<'a> NetworkBuilder<'a> {
                     ..Default::default()
                 },
                 health_status: HealthStatus::Initializing,
+                denied_peers: HashSet::new(),
+                connections: HashMap::new(),
             },
         ))
     }
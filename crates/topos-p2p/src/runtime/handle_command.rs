@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use crate::{
     error::{CommandExecutionError, P2PError},
-    protocol_name, Command, Runtime,
+    protocol_name, Command, PeerInfo, Runtime,
 };
+use libp2p::PeerId;
 
 use rand::{thread_rng, Rng};
 use topos_metrics::P2P_MESSAGE_SENT_ON_GOSSIPSUB_TOTAL;
@@ -37,6 +40,24 @@ impl Runtime {
                     warn!("Unable to notify ConnectedPeers response: initiator is dropped");
                 }
             }
+            Command::PeersInfo { sender } => {
+                let mut peers: HashMap<PeerId, PeerInfo> = HashMap::new();
+                for (peer_id, endpoint) in self.connections.values() {
+                    let peer = peers.entry(*peer_id).or_insert_with(|| PeerInfo {
+                        peer_id: *peer_id,
+                        addresses: Vec::new(),
+                        outbound: false,
+                        denied: self.denied_peers.contains(peer_id),
+                    });
+
+                    peer.addresses.push(endpoint.get_remote_address().clone());
+                    peer.outbound |= endpoint.is_dialer();
+                }
+
+                if sender.send(Ok(peers.into_values().collect())).is_err() {
+                    warn!("Unable to notify PeersInfo response: initiator is dropped");
+                }
+            }
             Command::RandomKnownPeer { sender } => {
                 if self.peer_set.is_empty() {
                     let _ = sender.send(Err(P2PError::CommandError(
@@ -71,6 +92,17 @@ impl Runtime {
                 }
                 Err(err) => error!("Failed to publish message to {topic}: {err}"),
             },
+
+            Command::UpdateDeniedPeers { denied, allowed } => {
+                for peer in allowed {
+                    self.denied_peers.remove(&peer);
+                }
+
+                for peer in denied {
+                    debug!("Denying {peer} from publishing on the echo and ready topics");
+                    self.denied_peers.insert(peer);
+                }
+            }
         }
     }
 }
@@ -42,6 +42,22 @@ impl EventHandler<ComposedEvent> for Runtime {
 #[async_trait::async_trait]
 impl EventHandler<SwarmEvent<ComposedEvent>> for Runtime {
     async fn handle(&mut self, event: SwarmEvent<ComposedEvent>) -> EventResult {
+        match &event {
+            SwarmEvent::ConnectionEstablished {
+                peer_id,
+                connection_id,
+                endpoint,
+                ..
+            } => {
+                self.connections
+                    .insert(*connection_id, (*peer_id, endpoint.clone()));
+            }
+            SwarmEvent::ConnectionClosed { connection_id, .. } => {
+                self.connections.remove(connection_id);
+            }
+            _ => {}
+        }
+
         match event {
             SwarmEvent::NewListenAddr {
                 listener_id,
@@ -1,11 +1,14 @@
 use topos_metrics::{
     P2P_EVENT_STREAM_CAPACITY_TOTAL, P2P_MESSAGE_DESERIALIZE_FAILURE_TOTAL,
-    P2P_MESSAGE_RECEIVED_ON_ECHO_TOTAL, P2P_MESSAGE_RECEIVED_ON_GOSSIP_TOTAL,
-    P2P_MESSAGE_RECEIVED_ON_READY_TOTAL,
+    P2P_MESSAGE_RECEIVED_ON_CHECKPOINT_TOTAL, P2P_MESSAGE_RECEIVED_ON_ECHO_TOTAL,
+    P2P_MESSAGE_RECEIVED_ON_GOSSIP_TOTAL, P2P_MESSAGE_RECEIVED_ON_READY_TOTAL,
 };
 use tracing::{debug, error};
 
-use crate::{constants, event::GossipEvent, Event, Runtime, TOPOS_ECHO, TOPOS_GOSSIP, TOPOS_READY};
+use crate::{
+    constants, event::GossipEvent, Event, Runtime, TOPOS_CHECKPOINT, TOPOS_ECHO, TOPOS_GOSSIP,
+    TOPOS_READY,
+};
 use prost::Message;
 use topos_core::api::grpc::tce::v1::Batch;
 
@@ -26,8 +29,12 @@ impl EventHandler<GossipEvent> for Runtime {
 
             debug!("Received message from {:?} on topic {:?}", source, topic);
             match topic {
-                TOPOS_GOSSIP => {
-                    P2P_MESSAGE_RECEIVED_ON_GOSSIP_TOTAL.inc();
+                TOPOS_GOSSIP | TOPOS_CHECKPOINT => {
+                    if topic == TOPOS_GOSSIP {
+                        P2P_MESSAGE_RECEIVED_ON_GOSSIP_TOTAL.inc();
+                    } else {
+                        P2P_MESSAGE_RECEIVED_ON_CHECKPOINT_TOTAL.inc();
+                    }
 
                     if let Err(e) = self
                         .event_sender
@@ -41,6 +48,12 @@ impl EventHandler<GossipEvent> for Runtime {
                     }
                 }
                 TOPOS_ECHO | TOPOS_READY => {
+                    if self.denied_peers.contains(&source) {
+                        debug!("Dropping message from denied peer {source} on topic {topic}");
+
+                        return Ok(());
+                    }
+
                     if topic == TOPOS_ECHO {
                         P2P_MESSAGE_RECEIVED_ON_ECHO_TOTAL.inc();
                     } else {
@@ -8,7 +8,10 @@ use crate::{
     Behaviour, Command, Event,
 };
 use libp2p::{
-    core::transport::ListenerId, kad::QueryId, swarm::ConnectionId, Multiaddr, PeerId, Swarm,
+    core::{transport::ListenerId, ConnectedPoint},
+    kad::QueryId,
+    swarm::ConnectionId,
+    Multiaddr, PeerId, Swarm,
 };
 use tokio::{
     spawn,
@@ -46,6 +49,12 @@ pub struct Runtime {
 
     /// Health status of the p2p layer
     pub(crate) health_status: HealthStatus,
+
+    /// Peers whose messages are dropped on the echo and ready topics
+    pub(crate) denied_peers: HashSet<PeerId>,
+
+    /// Established connections with their peer
+    pub(crate) connections: HashMap<ConnectionId, (PeerId, ConnectedPoint)>,
 }
 
 mod handle_command;
@@ -74,6 +74,9 @@ impl AppContext {
                             info!("Shutdown finished, restarting sequencer...");
                             return AppContextStatus::Restarting;
                         },
+                        TceProxyEvent::CertificateRejected { certificate_id, reason } => {
+                            error!("Certificate {certificate_id} has been rejected by the TCE: {reason}");
+                        },
                         _ => self.on_tce_proxy_event(tce_evt).await,
                     }
                 },
@@ -1,19 +1,25 @@
 use std::{net::SocketAddr, sync::Arc};
 
-use async_graphql::{EmptyMutation, Schema};
-use async_graphql_axum::GraphQLSubscription;
-use axum::{extract::Extension, routing::get, Router, Server};
+use async_graphql::Schema;
+use axum::{
+    extract::{connect_info::IntoMakeServiceWithConnectInfo, Extension},
+    routing::get,
+    Router, Server,
+};
 use http::{header, Method};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tower_http::cors::{Any, CorsLayer};
 
 use crate::{
     graphql::{
+        mutation::MutationRoot,
         query::{QueryRoot, ServiceSchema},
-        routes::{graphql_playground, health},
+        routes::{graphql_playground, graphql_subscription, health},
     },
+    limits::Limiter,
     runtime::InternalRuntimeCommand,
 };
+use topos_tce_broadcast::event::ProtocolEvents;
 use topos_tce_storage::validator::ValidatorStore;
 
 use super::query::SubscriptionRoot;
@@ -23,6 +29,8 @@ pub struct ServerBuilder {
     store: Option<Arc<ValidatorStore>>,
     serve_addr: Option<SocketAddr>,
     runtime: Option<mpsc::Sender<InternalRuntimeCommand>>,
+    protocol_events: Option<broadcast::Sender<ProtocolEvents>>,
+    limiter: Limiter,
 }
 
 impl ServerBuilder {
@@ -34,6 +42,20 @@ impl ServerBuilder {
 
         self
     }
+    /// Sets the channel of the events of the reliable broadcast, used by subscriptions
+    pub(crate) fn protocol_events(mut self, sender: broadcast::Sender<ProtocolEvents>) -> Self {
+        self.protocol_events = Some(sender);
+
+        self
+    }
+
+    /// Sets the limiter of the submissions and subscriptions of the clients
+    pub(crate) fn limiter(mut self, limiter: Limiter) -> Self {
+        self.limiter = limiter;
+
+        self
+    }
+
     pub(crate) fn store(mut self, store: Arc<ValidatorStore>) -> Self {
         self.store = Some(store);
 
@@ -48,7 +70,8 @@ impl ServerBuilder {
 
     pub async fn build(
         mut self,
-    ) -> Server<hyper::server::conn::AddrIncoming, axum::routing::IntoMakeService<Router>> {
+    ) -> Server<hyper::server::conn::AddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>
+    {
         let cors = CorsLayer::new()
             // allow `GET` and `POST` when accessing the resource
             .allow_methods([Method::GET, Method::POST])
@@ -67,11 +90,17 @@ impl ServerBuilder {
             .runtime
             .take()
             .expect("Cannot build GraphQL server without the internal runtime channel");
+        let protocol_events = self
+            .protocol_events
+            .take()
+            .expect("Cannot build GraphQL server without the protocol events channel");
 
-        let schema: ServiceSchema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        let schema: ServiceSchema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
             .data(store)
             .data(fullnode_store)
             .data(runtime)
+            .data(protocol_events)
+            .data(self.limiter)
             .finish();
 
         let app = Router::new()
@@ -80,12 +109,12 @@ impl ServerBuilder {
                 get(graphql_playground)
                     .post_service(async_graphql_axum::GraphQL::new(schema.clone())),
             )
-            .route_service("/ws", GraphQLSubscription::new(schema.clone()))
+            .route("/ws", get(graphql_subscription))
             .route("/health", get(health))
             .layer(cors)
             .layer(Extension(schema));
 
         let serve_addr = self.serve_addr.take().expect("Server address is not set");
-        Server::bind(&serve_addr).serve(app.into_make_service())
+        Server::bind(&serve_addr).serve(app.into_make_service_with_connect_info::<SocketAddr>())
     }
 }
@@ -1,4 +1,35 @@
+use topos_core::api::graphql::errors::GraphQLServerError;
+use topos_core::api::graphql::filter::SubnetFilter;
+use topos_core::uci::{Certificate, SubnetId};
+
 pub(crate) enum FilterIs {
     Source,
     Target,
 }
+
+/// Parse the [`SubnetFilter`] of a GraphQL request
+pub(crate) fn parse_filter(
+    filter: Option<SubnetFilter>,
+) -> Result<Option<(FilterIs, SubnetId)>, GraphQLServerError> {
+    filter
+        .map(|value| match value {
+            SubnetFilter::Target(ref id) => id.try_into().map(|v| (FilterIs::Target, v)),
+            SubnetFilter::Source(ref id) => id.try_into().map(|v| (FilterIs::Source, v)),
+        })
+        .map_or(Ok(None), |v| v.map(Some))
+        .map_err(|_| GraphQLServerError::ParseSubnetId)
+}
+
+/// Check whether a certificate matches a parsed [`SubnetFilter`], no filter matches everything
+pub(crate) fn matches_filter(
+    filter: &Option<(FilterIs, SubnetId)>,
+    certificate: &Certificate,
+) -> bool {
+    filter
+        .as_ref()
+        .map(|v| match v {
+            (FilterIs::Source, id) => id == &certificate.source_subnet_id,
+            (FilterIs::Target, id) => certificate.target_subnets.contains(id),
+        })
+        .unwrap_or(true)
+}
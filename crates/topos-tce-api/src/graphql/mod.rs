@@ -1,6 +1,8 @@
 pub mod builder;
 mod filter;
+mod mutation;
 mod query;
 mod routes;
+mod streams;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,133 @@
+use async_graphql::{Context, ErrorExtensions, Object};
+use tokio::sync::{mpsc, oneshot};
+use topos_core::api::graphql::certificate::{
+    CertificateInput, CertificateSubmission, PendingResult,
+};
+use topos_core::api::graphql::errors::GraphQLServerError;
+use topos_core::uci::{Certificate, CertificateId};
+
+use crate::constants::MAX_SUBMITTED_CERTIFICATES;
+use crate::limits::{Limiter, SubmissionPermit};
+use crate::runtime::InternalRuntimeCommand;
+use crate::submission::certificate_submission;
+
+pub struct MutationRoot;
+
+impl MutationRoot {
+    /// Check that the certificates can be submitted, reporting the reached limit with the
+    /// `RESOURCE_EXHAUSTED` code
+    fn check_submissions(
+        ctx: &Context<'_>,
+        certificates: &[Certificate],
+    ) -> async_graphql::Result<SubmissionPermit> {
+        let limiter = ctx.data::<Limiter>().map_err(|_| {
+            tracing::error!("Failed to get the limiter from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        limiter
+            .check_submissions(certificates)
+            .map_err(|error| GraphQLServerError::ResourceExhausted(error.to_string()).extend())
+    }
+}
+
+#[Object]
+impl MutationRoot {
+    /// This endpoint is used to submit a certificate, going through the same validation as
+    /// the gRPC submission before entering the pending pool.
+    async fn submit_certificate(
+        &self,
+        ctx: &Context<'_>,
+        certificate: CertificateInput,
+    ) -> async_graphql::Result<PendingResult> {
+        let runtime = ctx
+            .data::<mpsc::Sender<InternalRuntimeCommand>>()
+            .map_err(|_| {
+                tracing::error!("Failed to get the runtime client from context");
+
+                GraphQLServerError::ParseDataConnector
+            })?;
+
+        let certificate: Certificate = (&certificate).try_into()?;
+        let certificate_id = certificate.id;
+        let permit = Self::check_submissions(ctx, std::slice::from_ref(&certificate))?;
+
+        let (sender, receiver) = oneshot::channel();
+        runtime
+            .send(InternalRuntimeCommand::CertificateSubmitted {
+                certificate: Box::new(certificate),
+                sender,
+            })
+            .await
+            .map_err(|_| GraphQLServerError::InternalError("Unable to submit the certificate"))?;
+
+        let result = receiver.await.map_err(|_| {
+            GraphQLServerError::InternalError("Unable to receive the submission result")
+        })?;
+        permit.settle([&result]);
+
+        let submission = certificate_submission(certificate_id, result)
+            .map_err(|_| GraphQLServerError::InternalError("Unable to submit the certificate"))?;
+
+        match submission {
+            CertificateSubmission {
+                rejection: Some(rejection),
+                ..
+            } => Err(GraphQLServerError::CertificateRejected(rejection).into()),
+            submission => Ok(submission.result),
+        }
+    }
+
+    /// This endpoint is used to submit a chain of certificates, inserted in order in the
+    /// pending pool. It returns the outcome of the submission of each certificate.
+    async fn submit_certificates(
+        &self,
+        ctx: &Context<'_>,
+        certificates: Vec<CertificateInput>,
+    ) -> async_graphql::Result<Vec<CertificateSubmission>> {
+        if certificates.len() > MAX_SUBMITTED_CERTIFICATES {
+            return Err(GraphQLServerError::TooManyCertificates(MAX_SUBMITTED_CERTIFICATES).into());
+        }
+
+        let runtime = ctx
+            .data::<mpsc::Sender<InternalRuntimeCommand>>()
+            .map_err(|_| {
+                tracing::error!("Failed to get the runtime client from context");
+
+                GraphQLServerError::ParseDataConnector
+            })?;
+
+        let certificates = certificates
+            .iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<Certificate>, _>>()?;
+        let permit = Self::check_submissions(ctx, &certificates)?;
+
+        let certificate_ids: Vec<CertificateId> = certificates
+            .iter()
+            .map(|certificate| certificate.id)
+            .collect();
+
+        let (sender, receiver) = oneshot::channel();
+        runtime
+            .send(InternalRuntimeCommand::CertificatesSubmitted {
+                certificates,
+                sender,
+            })
+            .await
+            .map_err(|_| GraphQLServerError::InternalError("Unable to submit the certificates"))?;
+
+        let results = receiver.await.map_err(|_| {
+            GraphQLServerError::InternalError("Unable to receive the submission results")
+        })?;
+        permit.settle(&results);
+
+        Ok(certificate_ids
+            .into_iter()
+            .zip(results)
+            .map(|(certificate_id, result)| certificate_submission(certificate_id, result))
+            .collect::<Result<_, _>>()
+            .map_err(|_| GraphQLServerError::InternalError("Unable to submit the certificate"))?)
+    }
+}
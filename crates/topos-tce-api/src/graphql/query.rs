@@ -1,34 +1,59 @@
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use async_graphql::{Context, EmptyMutation, Object, Schema, Subscription};
+use async_graphql::connection::{query, Connection};
+use async_graphql::{Context, ErrorExtensions, Object, Schema, Subscription};
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use futures::{Stream, StreamExt};
-use tokio::sync::{mpsc, oneshot};
-use topos_core::api::graphql::certificate::UndeliveredCertificate;
-use topos_core::api::graphql::checkpoint::SourceStreamPosition;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use topos_core::api::graphql::certificate::{
+    AwaitingPrecedenceStatus, CertificateStatus, DeliveredStatus, InBroadcastStatus, PendingStatus,
+    UndeliveredCertificate,
+};
+use topos_core::api::graphql::checkpoint::{SourceStreamPosition, TargetStreamPosition};
+use topos_core::api::graphql::epoch::Epoch;
 use topos_core::api::graphql::errors::GraphQLServerError;
-use topos_core::api::graphql::filter::SubnetFilter;
+use topos_core::api::graphql::filter::{PositionRange, SubnetFilter};
+use topos_core::api::graphql::network::{
+    BroadcastProgress, DeliveryLatency, Peer, ProtocolEvent, ProtocolEventKind, ValidatorSet,
+};
+use topos_core::api::graphql::subnet::SubnetId;
 use topos_core::api::graphql::{
     certificate::{Certificate, CertificateId},
     checkpoint::SourceCheckpointInput,
     query::CertificateQuery,
 };
-use topos_core::types::stream::CertificateSourceStreamPosition;
-use topos_metrics::{STORAGE_PENDING_POOL_COUNT, STORAGE_PRECEDENCE_POOL_COUNT};
+use topos_core::types::stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition};
+use topos_metrics::{
+    CERTIFICATE_DELIVERY_LATENCY, STORAGE_PENDING_POOL_COUNT, STORAGE_PRECEDENCE_POOL_COUNT,
+};
+use topos_tce_broadcast::event::ProtocolEvents;
+use topos_tce_storage::errors::{InternalStorageError, StorageError};
 use topos_tce_storage::fullnode::FullNodeStore;
 use topos_tce_storage::store::ReadStore;
 
 use topos_tce_storage::validator::ValidatorStore;
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::runtime::InternalRuntimeCommand;
+use crate::limits::{Limiter, StreamPermit};
+use crate::runtime::{self, error::RuntimeError, InternalRuntimeCommand};
 use crate::stream::TransientStream;
 
-use super::filter::FilterIs;
+use super::filter::{matches_filter, parse_filter};
+use super::mutation::MutationRoot;
+use super::streams::{
+    checkpoint_positions, collect_page, replay_source_streams, SentPositions,
+    StreamConnectionFields, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
+
+/// Interval at which the protocol events subscriptions poll the progress of the broadcast
+const PROTOCOL_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct QueryRoot;
-pub(crate) type ServiceSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+pub(crate) type ServiceSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
 
 #[async_trait]
 impl CertificateQuery for QueryRoot {
@@ -61,7 +86,7 @@ impl CertificateQuery for QueryRoot {
                     },
                     first,
                 )
-                .map_err(|_| GraphQLServerError::StorageError)?;
+                .map_err(storage_error)?;
 
             debug!("Returned from storage: {certificates_with_position:?}");
             certificates.extend(
@@ -90,10 +115,53 @@ impl CertificateQuery for QueryRoot {
                     .try_into()
                     .map_err(|_| GraphQLServerError::ParseCertificateId)?,
             )
-            .map_err(|_| GraphQLServerError::StorageError)
-            .and_then(|c| {
-                c.map(|ref c| c.into())
-                    .ok_or(GraphQLServerError::StorageError)
+            .map_err(|_| GraphQLServerError::StorageError)?
+            .ok_or(GraphQLServerError::StorageError)
+            .and_then(|ref c| {
+                store.check_proof_kept(c).map_err(storage_error)?;
+
+                Ok(c.into())
+            })
+    }
+}
+
+/// Surface the certificates whose proof has been pruned, any other storage error being internal
+fn storage_error(error: StorageError) -> GraphQLServerError {
+    match error {
+        StorageError::InternalStorage(InternalStorageError::ProofPruned(certificate_id)) => {
+            GraphQLServerError::ProofPruned(certificate_id.to_string())
+        }
+        _ => GraphQLServerError::StorageError,
+    }
+}
+
+impl QueryRoot {
+    /// Send a command to the runtime and wait for its response
+    async fn request_runtime<T>(
+        ctx: &Context<'_>,
+        command: impl FnOnce(oneshot::Sender<Result<T, RuntimeError>>) -> InternalRuntimeCommand,
+    ) -> Result<T, GraphQLServerError> {
+        let runtime = ctx
+            .data::<mpsc::Sender<InternalRuntimeCommand>>()
+            .map_err(|_| {
+                tracing::error!("Failed to get the runtime client from context");
+
+                GraphQLServerError::ParseDataConnector
+            })?;
+
+        let (sender, receiver) = oneshot::channel();
+        runtime
+            .send(command(sender))
+            .await
+            .map_err(|_| GraphQLServerError::InternalError("Unable to reach the runtime"))?;
+
+        receiver
+            .await
+            .map_err(|_| GraphQLServerError::InternalError("Unable to receive from the runtime"))?
+            .map_err(|error| {
+                warn!("The runtime failed to answer a GraphQL query: {error}");
+
+                GraphQLServerError::InternalError("The runtime failed to answer")
             })
     }
 }
@@ -118,6 +186,153 @@ impl QueryRoot {
         Self::certificate_by_id(ctx, certificate_id).await
     }
 
+    /// This endpoint is used to paginate over the certificates emitted by a source subnet.
+    /// The cursor of a certificate is its position in the source stream. The certificates can
+    /// be restricted to the ones targeting a subnet and to a range of positions. As the
+    /// number of certificates scanned by a page is capped, a page restricted to a target subnet
+    /// can be incomplete, in which case its `continuationCursor` resumes the scan.
+    async fn source_stream_certificates(
+        &self,
+        ctx: &Context<'_>,
+        source_subnet_id: SubnetId,
+        target_subnet_id: Option<SubnetId>,
+        positions: Option<PositionRange>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> async_graphql::Result<Connection<u64, Certificate, StreamConnectionFields>> {
+        let store = ctx.data::<Arc<FullNodeStore>>().map_err(|_| {
+            tracing::error!("Failed to get store from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        let source_subnet_id: topos_core::uci::SubnetId = (&source_subnet_id)
+            .try_into()
+            .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+        let target_subnet_id: Option<topos_core::uci::SubnetId> = target_subnet_id
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+        let positions = positions.unwrap_or_default();
+
+        query(
+            after,
+            None,
+            first,
+            None,
+            |after: Option<u64>, _: Option<u64>, first, _| async move {
+                let lower_bound = positions.from.unwrap_or(0);
+                let start = after
+                    .map_or(0, |after| after.saturating_add(1))
+                    .max(lower_bound);
+
+                let page = collect_page(
+                    |position, limit| {
+                        store
+                            .get_source_stream_certificates_from_position(
+                                CertificateSourceStreamPosition::new(source_subnet_id, position),
+                                limit,
+                            )
+                            .map(|certificates| {
+                                certificates
+                                    .into_iter()
+                                    .map(|(certificate, position)| {
+                                        (certificate, *position.position)
+                                    })
+                                    .collect()
+                            })
+                    },
+                    start,
+                    positions.to,
+                    first.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+                    |certificate| {
+                        target_subnet_id.as_ref().map_or(true, |target_subnet_id| {
+                            certificate
+                                .certificate
+                                .target_subnets
+                                .contains(target_subnet_id)
+                        })
+                    },
+                )
+                .map_err(storage_error)?;
+
+                Ok::<_, GraphQLServerError>(page.into_connection(start > lower_bound))
+            },
+        )
+        .await
+    }
+
+    /// This endpoint is used to paginate over the certificates delivered to a target subnet by
+    /// a source subnet. The cursor of a certificate is its position in the target stream. The
+    /// certificates can be restricted to a range of positions.
+    async fn target_stream_certificates(
+        &self,
+        ctx: &Context<'_>,
+        target_subnet_id: SubnetId,
+        source_subnet_id: SubnetId,
+        positions: Option<PositionRange>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> async_graphql::Result<Connection<u64, Certificate, StreamConnectionFields>> {
+        let store = ctx.data::<Arc<FullNodeStore>>().map_err(|_| {
+            tracing::error!("Failed to get store from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        let target_subnet_id: topos_core::uci::SubnetId = (&target_subnet_id)
+            .try_into()
+            .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+        let source_subnet_id: topos_core::uci::SubnetId = (&source_subnet_id)
+            .try_into()
+            .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+        let positions = positions.unwrap_or_default();
+
+        query(
+            after,
+            None,
+            first,
+            None,
+            |after: Option<u64>, _: Option<u64>, first, _| async move {
+                let lower_bound = positions.from.unwrap_or(0);
+                let start = after
+                    .map_or(0, |after| after.saturating_add(1))
+                    .max(lower_bound);
+
+                let page = collect_page(
+                    |position, limit| {
+                        store
+                            .get_target_stream_certificates_from_position(
+                                CertificateTargetStreamPosition::new(
+                                    target_subnet_id,
+                                    source_subnet_id,
+                                    position,
+                                ),
+                                limit,
+                            )
+                            .map(|certificates| {
+                                certificates
+                                    .into_iter()
+                                    .map(|(certificate, position)| {
+                                        (certificate, *position.position)
+                                    })
+                                    .collect()
+                            })
+                    },
+                    start,
+                    positions.to,
+                    first.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+                    |_| true,
+                )
+                .map_err(storage_error)?;
+
+                Ok::<_, GraphQLServerError>(page.into_connection(start > lower_bound))
+            },
+        )
+        .await
+    }
+
     /// This endpoint is used to get the current storage pool stats.
     /// It returns the number of certificates in the pending and precedence pools.
     /// The values are estimated as having a precise count is costly.
@@ -205,6 +420,38 @@ impl QueryRoot {
             .collect())
     }
 
+    /// This endpoint is used to get the current epoch.
+    /// It returns the epoch id with the checkpoint of the source streams at the start of the epoch.
+    async fn get_current_epoch(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Epoch>, GraphQLServerError> {
+        let store = ctx.data::<Arc<FullNodeStore>>().map_err(|_| {
+            tracing::error!("Failed to get store from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        let summary = store
+            .get_latest_epoch_summary()
+            .map_err(|_| GraphQLServerError::StorageError)?;
+
+        Ok(summary.map(|summary| Epoch {
+            id: summary.epoch_id,
+            start_checkpoint: summary
+                .start_checkpoint
+                .0
+                .checkpoint_data
+                .iter()
+                .map(|head| SourceStreamPosition {
+                    source_subnet_id: (&head.subnet_id).into(),
+                    position: *head.position,
+                    certificate_id: head.certificate_id.into(),
+                })
+                .collect(),
+        }))
+    }
+
     /// This endpoint is used to get the current pending pool.
     /// It returns [`CertificateId`] and the [`PendingCertificateId`]
     async fn get_pending_pool(
@@ -224,6 +471,158 @@ impl QueryRoot {
             .collect())
     }
 
+    /// This endpoint is used to know where a certificate currently stands in the node.
+    /// It returns whether the certificate is pending, awaiting its previous certificate,
+    /// being broadcast or delivered.
+    async fn certificate_status(
+        &self,
+        ctx: &Context<'_>,
+        certificate_id: CertificateId,
+    ) -> Result<CertificateStatus, GraphQLServerError> {
+        let runtime = ctx
+            .data::<mpsc::Sender<InternalRuntimeCommand>>()
+            .map_err(|_| {
+                tracing::error!("Failed to get the runtime client from context");
+
+                GraphQLServerError::ParseDataConnector
+            })?;
+
+        let certificate_id: topos_core::uci::CertificateId = certificate_id
+            .try_into()
+            .map_err(|_| GraphQLServerError::ParseCertificateId)?;
+
+        let (sender, receiver) = oneshot::channel();
+        runtime
+            .send(InternalRuntimeCommand::GetCertificateStatus {
+                certificate_id,
+                sender,
+            })
+            .await
+            .map_err(|_| {
+                GraphQLServerError::InternalError("Unable to request the certificate status")
+            })?;
+
+        let status = receiver
+            .await
+            .map_err(|_| {
+                GraphQLServerError::InternalError("Unable to receive the certificate status")
+            })?
+            .map_err(|_| GraphQLServerError::StorageError)?
+            .ok_or(GraphQLServerError::CertificateNotFound)?;
+
+        Ok(match status {
+            runtime::CertificateStatus::Pending { pending_id } => {
+                CertificateStatus::Pending(PendingStatus { pending_id })
+            }
+            runtime::CertificateStatus::AwaitingPrecedence { prev_id } => {
+                CertificateStatus::AwaitingPrecedence(AwaitingPrecedenceStatus {
+                    prev_id: prev_id.into(),
+                })
+            }
+            runtime::CertificateStatus::InBroadcast {
+                echo_count,
+                ready_count,
+            } => CertificateStatus::InBroadcast(InBroadcastStatus {
+                echo_count: echo_count as u64,
+                ready_count: ready_count as u64,
+            }),
+            runtime::CertificateStatus::Delivered(positions) => {
+                CertificateStatus::Delivered(DeliveredStatus {
+                    source: SourceStreamPosition {
+                        source_subnet_id: (&positions.source.subnet_id).into(),
+                        position: *positions.source.position,
+                        certificate_id: certificate_id.into(),
+                    },
+                    targets: positions
+                        .targets
+                        .values()
+                        .map(|target| TargetStreamPosition {
+                            source_subnet_id: (&target.source_subnet_id).into(),
+                            target_subnet_id: (&target.target_subnet_id).into(),
+                            position: *target.position,
+                            certificate_id: certificate_id.into(),
+                        })
+                        .collect(),
+                })
+            }
+        })
+    }
+
+    /// This endpoint is used to get the validator set of the latest known epoch.
+    async fn get_validators(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<ValidatorSet>, GraphQLServerError> {
+        let store = ctx.data::<Arc<FullNodeStore>>().map_err(|_| {
+            tracing::error!("Failed to get store from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        let latest = store
+            .epoch_validators_store()
+            .get_latest_validators()
+            .map_err(|_| GraphQLServerError::StorageError)?;
+
+        Ok(latest.map(|(epoch, validators)| {
+            let mut validators: Vec<String> = validators.iter().map(ToString::to_string).collect();
+            validators.sort();
+
+            ValidatorSet { epoch, validators }
+        }))
+    }
+
+    /// This endpoint is used to get the peers connected to the node along with their connections.
+    async fn get_peers(&self, ctx: &Context<'_>) -> Result<Vec<Peer>, GraphQLServerError> {
+        let peers =
+            Self::request_runtime(ctx, |sender| InternalRuntimeCommand::GetPeers { sender })
+                .await?;
+
+        Ok(peers
+            .into_iter()
+            .map(|peer| Peer {
+                peer_id: peer.peer_id.to_string(),
+                addresses: peer.addresses.iter().map(ToString::to_string).collect(),
+                outbound: peer.outbound,
+                denied: peer.denied,
+            })
+            .collect())
+    }
+
+    /// This endpoint is used to get the latency between the submission of a certificate to the
+    /// node and its delivery.
+    async fn get_delivery_latency(&self) -> DeliveryLatency {
+        let count = CERTIFICATE_DELIVERY_LATENCY.get_sample_count();
+        let total_seconds = CERTIFICATE_DELIVERY_LATENCY.get_sample_sum();
+
+        DeliveryLatency {
+            count,
+            total_seconds,
+            average_seconds: (count > 0).then(|| total_seconds / count as f64),
+        }
+    }
+
+    /// This endpoint is used to get the progress of the certificates currently being broadcast.
+    /// It returns the number of Echo and Ready received for each of them.
+    async fn get_broadcast_progress(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<BroadcastProgress>, GraphQLServerError> {
+        let progress = Self::request_runtime(ctx, |sender| {
+            InternalRuntimeCommand::GetBroadcastProgress { sender }
+        })
+        .await?;
+
+        Ok(progress
+            .into_iter()
+            .map(|progress| BroadcastProgress {
+                certificate_id: progress.certificate_id.into(),
+                echo_count: progress.echo_count as u64,
+                ready_count: progress.ready_count as u64,
+            })
+            .collect())
+    }
+
     /// This endpoint is used to check if a certificate has any child certificate in the precedence pool.
     async fn check_precedence(
         &self,
@@ -250,47 +649,185 @@ impl QueryRoot {
 pub struct SubscriptionRoot;
 
 impl SubscriptionRoot {
+    /// Register the stream opened by the peer of the WebSocket connection, reporting the reached
+    /// limit with the `RESOURCE_EXHAUSTED` code
+    fn acquire_stream(ctx: &Context<'_>) -> async_graphql::Result<StreamPermit> {
+        let limiter = ctx.data::<Limiter>().map_err(|_| {
+            tracing::error!("Failed to get the limiter from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        limiter
+            .acquire_stream(ctx.data_opt::<SocketAddr>().copied())
+            .map_err(|error| GraphQLServerError::ResourceExhausted(error.to_string()).extend())
+    }
+
     /// Try to create a new [`Stream`] of delivered [`Certificate`]s to be used in a GraphQL subscription.
     pub(crate) async fn new_transient_stream(
         &self,
         register: &mpsc::Sender<InternalRuntimeCommand>,
         filter: Option<SubnetFilter>,
     ) -> Result<impl Stream<Item = Certificate>, GraphQLServerError> {
+        let filter = parse_filter(filter)?;
+        let stream = Self::open_transient_stream(register).await?;
+
+        Ok(stream
+            .filter(move |c| futures::future::ready(matches_filter(&filter, &c.certificate)))
+            .map(|c| c.as_ref().into()))
+    }
+
+    /// Try to create a new [`Stream`] of delivered [`Certificate`]s which first replays the
+    /// source streams of the checkpoint from the storage before going live.
+    pub(crate) async fn new_replaying_stream(
+        &self,
+        register: &mpsc::Sender<InternalRuntimeCommand>,
+        store: Arc<FullNodeStore>,
+        filter: Option<SubnetFilter>,
+        from_source_checkpoint: SourceCheckpointInput,
+    ) -> Result<impl Stream<Item = Certificate>, GraphQLServerError> {
+        let filter = parse_filter(filter)?;
+        let positions = checkpoint_positions(&from_source_checkpoint)?;
+
+        // The transient stream is opened before the replay starts, so that the certificates
+        // delivered in the meantime are received twice instead of being missed
+        let live = Self::open_transient_stream(register).await?;
+
+        let mut sent_positions: HashMap<topos_core::uci::SubnetId, SentPositions> = positions
+            .iter()
+            .map(|(subnet_id, position)| (*subnet_id, SentPositions::new(*position)))
+            .collect();
+
+        Ok(replay_source_streams(store, positions)
+            .map(Arc::new)
+            .chain(live)
+            .filter_map(move |certificate| {
+                let delivery_position = &certificate.proof_of_delivery.delivery_position;
+                let position = *delivery_position.position;
+
+                let already_sent = sent_positions
+                    .get_mut(&delivery_position.subnet_id)
+                    .is_some_and(|sent_positions| !sent_positions.insert(position));
+
+                futures::future::ready(
+                    (!already_sent && matches_filter(&filter, &certificate.certificate))
+                        .then(|| certificate.as_ref().into()),
+                )
+            }))
+    }
+
+    /// Create a new [`Stream`] of the [`ProtocolEvent`]s of the reliable broadcast, optionally
+    /// restricted to a single certificate. Instead of the Echo and Ready messages, the number of
+    /// them received for each certificate is emitted whenever it changes.
+    pub(crate) fn new_protocol_event_stream(
+        &self,
+        register: mpsc::Sender<InternalRuntimeCommand>,
+        mut receiver: broadcast::Receiver<ProtocolEvents>,
+        certificate_id: Option<topos_core::uci::CertificateId>,
+    ) -> impl Stream<Item = ProtocolEvent> {
+        async_stream::stream! {
+            let mut progress_interval = tokio::time::interval(PROTOCOL_PROGRESS_INTERVAL);
+            // Echo and Ready counts last emitted for the certificates being broadcast
+            let mut emitted_counts: HashMap<topos_core::uci::CertificateId, (usize, usize)> =
+                HashMap::new();
+
+            loop {
+                let events = tokio::select! {
+                    event = receiver.recv() => match event {
+                        Ok(event) => match event {
+                            ProtocolEvents::Broadcast { certificate_id } => {
+                                vec![(ProtocolEventKind::Broadcast, certificate_id, None)]
+                            }
+                            ProtocolEvents::Gossip { cert } => {
+                                vec![(ProtocolEventKind::Gossip, cert.id, None)]
+                            }
+                            ProtocolEvents::BroadcastFailed { certificate_id } => {
+                                vec![(ProtocolEventKind::BroadcastFailed, certificate_id, None)]
+                            }
+                            ProtocolEvents::AlreadyDelivered { certificate_id } => {
+                                vec![(ProtocolEventKind::AlreadyDelivered, certificate_id, None)]
+                            }
+                            // Accounted for in the progress of the certificate
+                            ProtocolEvents::Echo { .. } | ProtocolEvents::Ready { .. } => continue,
+                        },
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Protocol events subscription lagged, {skipped} events skipped");
+
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = progress_interval.tick() => {
+                        let (sender, progress) = oneshot::channel();
+                        if register
+                            .send(InternalRuntimeCommand::GetBroadcastProgress { sender })
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+
+                        let progress = match progress.await {
+                            Ok(Ok(progress)) => progress,
+                            Ok(Err(error)) => {
+                                warn!("Unable to get the broadcast progress: {error}");
+
+                                continue;
+                            }
+                            Err(_) => break,
+                        };
+
+                        let previous_counts = std::mem::take(&mut emitted_counts);
+                        let mut events = Vec::new();
+                        for progress in progress {
+                            let counts = (progress.echo_count, progress.ready_count);
+                            if previous_counts.get(&progress.certificate_id) != Some(&counts) {
+                                events.push((
+                                    ProtocolEventKind::Progress,
+                                    progress.certificate_id,
+                                    Some(counts),
+                                ));
+                            }
+
+                            emitted_counts.insert(progress.certificate_id, counts);
+                        }
+
+                        events
+                    }
+                };
+
+                for (kind, event_certificate_id, counts) in events {
+                    if certificate_id.is_some_and(|certificate_id| certificate_id != event_certificate_id) {
+                        continue;
+                    }
+
+                    yield ProtocolEvent {
+                        kind,
+                        certificate_id: event_certificate_id.into(),
+                        echo_count: counts.map(|(echo_count, _)| echo_count as u64),
+                        ready_count: counts.map(|(_, ready_count)| ready_count as u64),
+                    };
+                }
+            }
+        }
+    }
+
+    async fn open_transient_stream(
+        register: &mpsc::Sender<InternalRuntimeCommand>,
+    ) -> Result<TransientStream, GraphQLServerError> {
         let (sender, receiver) = oneshot::channel();
         _ = register
             .send(InternalRuntimeCommand::NewTransientStream { sender })
             .await;
 
-        let stream: TransientStream = receiver
+        receiver
             .await
             .map_err(|_| {
                 GraphQLServerError::InternalError(
                     "Communication error trying to create a new transient stream",
                 )
             })?
-            .map_err(|e| GraphQLServerError::TransientStream(e.to_string()))?;
-
-        let filter: Option<(FilterIs, topos_core::uci::SubnetId)> = filter
-            .map(|value| match value {
-                SubnetFilter::Target(ref id) => id.try_into().map(|v| (FilterIs::Target, v)),
-                SubnetFilter::Source(ref id) => id.try_into().map(|v| (FilterIs::Source, v)),
-            })
-            .map_or(Ok(None), |v| v.map(Some))
-            .map_err(|_| GraphQLServerError::ParseSubnetId)?;
-
-        Ok(stream
-            .filter(move |c| {
-                futures::future::ready(
-                    filter
-                        .as_ref()
-                        .map(|v| match v {
-                            (FilterIs::Source, id) => id == &c.certificate.source_subnet_id,
-                            (FilterIs::Target, id) => c.certificate.target_subnets.contains(id),
-                        })
-                        .unwrap_or(true),
-                )
-            })
-            .map(|c| c.as_ref().into()))
+            .map_err(|e| GraphQLServerError::TransientStream(e.to_string()))
     }
 }
 
@@ -301,12 +838,16 @@ impl SubscriptionRoot {
     ///
     /// Closing the connection will close the stream.
     /// Starting a new connection will start a new stream and the client will not receive
-    /// any certificates that were delivered before the connection was started.
+    /// any certificates that were delivered before the connection was started, unless a
+    /// source checkpoint is provided: the source streams are then replayed from its positions
+    /// before receiving the live certificates.
     async fn watch_delivered_certificates(
         &self,
         ctx: &Context<'_>,
         filter: Option<SubnetFilter>,
-    ) -> Result<impl Stream<Item = Certificate>, GraphQLServerError> {
+        from_source_checkpoint: Option<SourceCheckpointInput>,
+    ) -> async_graphql::Result<BoxStream<'static, Certificate>> {
+        let permit = Self::acquire_stream(ctx)?;
         let register = ctx
             .data::<mpsc::Sender<InternalRuntimeCommand>>()
             .map_err(|_| {
@@ -315,6 +856,75 @@ impl SubscriptionRoot {
                 GraphQLServerError::ParseDataConnector
             })?;
 
-        self.new_transient_stream(register, filter).await
+        let Some(from_source_checkpoint) = from_source_checkpoint else {
+            return Ok(self
+                .new_transient_stream(register, filter)
+                .await?
+                .map(move |certificate| {
+                    let _ = &permit;
+
+                    certificate
+                })
+                .boxed());
+        };
+
+        let store = ctx.data::<Arc<FullNodeStore>>().map_err(|_| {
+            tracing::error!("Failed to get store from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        Ok(self
+            .new_replaying_stream(register, store.clone(), filter, from_source_checkpoint)
+            .await?
+            .map(move |certificate| {
+                let _ = &permit;
+
+                certificate
+            })
+            .boxed())
+    }
+
+    /// This endpoint is used to receive the events of the reliable broadcast of the node,
+    /// such as the number of Echo and Ready received for a certificate, optionally for a
+    /// single certificate.
+    async fn watch_protocol_events(
+        &self,
+        ctx: &Context<'_>,
+        certificate_id: Option<CertificateId>,
+    ) -> async_graphql::Result<BoxStream<'static, ProtocolEvent>> {
+        let permit = Self::acquire_stream(ctx)?;
+        let register = ctx
+            .data::<mpsc::Sender<InternalRuntimeCommand>>()
+            .map_err(|_| {
+                tracing::error!("Failed to get the runtime client from context");
+
+                GraphQLServerError::ParseDataConnector
+            })?;
+        let protocol_events = ctx
+            .data::<broadcast::Sender<ProtocolEvents>>()
+            .map_err(|_| {
+                tracing::error!("Failed to get the protocol events channel from context");
+
+                GraphQLServerError::ParseDataConnector
+            })?;
+
+        let certificate_id = certificate_id
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|_| GraphQLServerError::ParseCertificateId)?;
+
+        Ok(self
+            .new_protocol_event_stream(
+                register.clone(),
+                protocol_events.subscribe(),
+                certificate_id,
+            )
+            .map(move |event| {
+                let _ = &permit;
+
+                event
+            })
+            .boxed())
     }
 }
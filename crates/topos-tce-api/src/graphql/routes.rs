@@ -1,11 +1,18 @@
-use async_graphql::http::GraphiQLSource;
+use std::net::SocketAddr;
+
+use async_graphql::http::{GraphiQLSource, ALL_WEBSOCKET_PROTOCOLS};
+use async_graphql::Data;
+use async_graphql_axum::{GraphQLProtocol, GraphQLWebSocket};
 use axum::{
+    extract::{ConnectInfo, Extension, WebSocketUpgrade},
     http::StatusCode,
     response::{Html, IntoResponse},
     Json,
 };
 use serde::Serialize;
 
+use super::query::ServiceSchema;
+
 #[derive(Serialize)]
 struct Health {
     healthy: bool,
@@ -25,3 +32,23 @@ pub async fn graphql_playground() -> impl IntoResponse {
             .finish(),
     )
 }
+
+/// Serve the GraphQL subscriptions, exposing the address of the peer to the subscriptions so
+/// that their number can be limited
+pub(crate) async fn graphql_subscription(
+    Extension(schema): Extension<ServiceSchema>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let mut data = Data::default();
+    data.insert(peer);
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
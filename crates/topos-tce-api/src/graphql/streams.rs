@@ -0,0 +1,237 @@
+//! Walk the source and target streams of the storage, to paginate over them or to replay them
+//! before a subscription goes live.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use async_graphql::connection::{Connection, CursorType, Edge};
+use async_graphql::SimpleObject;
+use futures::{stream, Stream, StreamExt};
+use topos_core::api::graphql::certificate::Certificate;
+use topos_core::api::graphql::checkpoint::SourceCheckpointInput;
+use topos_core::api::graphql::errors::GraphQLServerError;
+use topos_core::types::stream::CertificateSourceStreamPosition;
+use topos_core::types::CertificateDelivered;
+use topos_core::uci::SubnetId;
+use topos_tce_storage::errors::StorageError;
+use topos_tce_storage::fullnode::FullNodeStore;
+use topos_tce_storage::store::ReadStore;
+use tracing::error;
+
+/// Number of certificates returned by a page when the client doesn't ask for a size
+pub(crate) const DEFAULT_PAGE_SIZE: usize = 10;
+/// Maximum number of certificates returned by a page
+pub(crate) const MAX_PAGE_SIZE: usize = 100;
+/// Number of certificates fetched at once while walking a stream
+const BATCH_SIZE: usize = 100;
+/// Maximum number of certificates scanned to build a page, so that a filter matching few of
+/// them doesn't walk the whole stream in a single query
+pub(crate) const MAX_SCANNED_CERTIFICATES: usize = 10 * BATCH_SIZE;
+/// Maximum number of positions sent ahead of a gap in a replayed stream before giving up on it
+const MAX_SENT_AHEAD: usize = 1024;
+
+/// Additional fields of the connections over a stream
+#[derive(SimpleObject)]
+pub(crate) struct StreamConnectionFields {
+    /// Cursor of the last scanned position when the page stopped before being filled because
+    /// too many certificates were scanned. Giving it as `after` continues the scan.
+    continuation_cursor: Option<String>,
+}
+
+/// Page of certificates along with their position in the walked stream
+pub(crate) struct Page {
+    pub(crate) certificates: Vec<(u64, CertificateDelivered)>,
+    pub(crate) has_next_page: bool,
+    /// Last scanned position when the scan was interrupted before the page was filled
+    pub(crate) scanned_until: Option<u64>,
+}
+
+impl Page {
+    /// Convert the page into a GraphQL connection, using the positions as cursors
+    pub(crate) fn into_connection(
+        self,
+        has_previous_page: bool,
+    ) -> Connection<u64, Certificate, StreamConnectionFields> {
+        let mut connection = Connection::with_additional_fields(
+            has_previous_page,
+            self.has_next_page,
+            StreamConnectionFields {
+                continuation_cursor: self.scanned_until.map(|position| position.encode_cursor()),
+            },
+        );
+        connection.edges.extend(
+            self.certificates
+                .iter()
+                .map(|(position, certificate)| Edge::new(*position, certificate.into())),
+        );
+
+        connection
+    }
+}
+
+/// Walk a stream from the position `start` and collect the certificates matching the
+/// predicate, until `size` of them are collected or the position `end` is passed. The walk
+/// stops early once [`MAX_SCANNED_CERTIFICATES`] are scanned, reporting where it stopped.
+///
+/// `fetch` returns the certificates of the stream from a position, with their position.
+pub(crate) fn collect_page<F, P>(
+    mut fetch: F,
+    start: u64,
+    end: Option<u64>,
+    size: usize,
+    predicate: P,
+) -> Result<Page, StorageError>
+where
+    F: FnMut(u64, usize) -> Result<Vec<(CertificateDelivered, u64)>, StorageError>,
+    P: Fn(&CertificateDelivered) -> bool,
+{
+    let mut certificates = Vec::new();
+    let mut position = start;
+    let mut scanned = 0;
+
+    while end.map_or(true, |end| position <= end) {
+        let batch = fetch(position, BATCH_SIZE)?;
+        let fetched = batch.len();
+        // The certificates whose proof is pruned are skipped, resume after the last one returned
+        let next_position = batch.last().map(|(_, position)| position + 1);
+
+        for (certificate, certificate_position) in batch {
+            if end.is_some_and(|end| certificate_position > end) {
+                break;
+            }
+
+            if predicate(&certificate) {
+                if certificates.len() == size {
+                    return Ok(Page {
+                        certificates,
+                        has_next_page: true,
+                        scanned_until: None,
+                    });
+                }
+
+                certificates.push((certificate_position, certificate));
+            }
+        }
+
+        let Some(next_position) = next_position.filter(|_| fetched == BATCH_SIZE) else {
+            break;
+        };
+
+        scanned += fetched;
+        position = next_position;
+
+        if scanned >= MAX_SCANNED_CERTIFICATES && end.map_or(true, |end| position <= end) {
+            return Ok(Page {
+                certificates,
+                has_next_page: true,
+                scanned_until: Some(position - 1),
+            });
+        }
+    }
+
+    Ok(Page {
+        certificates,
+        has_next_page: false,
+        scanned_until: None,
+    })
+}
+
+/// Positions of a source stream which were sent to a subscriber, to skip the certificates
+/// both replayed and delivered live without skipping the ones delivered out of order
+pub(crate) struct SentPositions {
+    /// Every position below it was sent
+    next: u64,
+    /// Positions sent past `next`
+    ahead: BTreeSet<u64>,
+}
+
+impl SentPositions {
+    pub(crate) fn new(next: u64) -> Self {
+        Self {
+            next,
+            ahead: BTreeSet::new(),
+        }
+    }
+
+    /// Record the position as sent, returning `false` if it already was
+    pub(crate) fn insert(&mut self, position: u64) -> bool {
+        if position < self.next || !self.ahead.insert(position) {
+            return false;
+        }
+
+        // A gap which never fills up (e.g. an interrupted replay) is given up on instead of
+        // holding every following position
+        if self.ahead.len() > MAX_SENT_AHEAD {
+            self.next = self.ahead.pop_first().unwrap_or(self.next) + 1;
+        }
+
+        while self.ahead.remove(&self.next) {
+            self.next += 1;
+        }
+
+        true
+    }
+}
+
+/// Collect the position of every source subnet of a checkpoint, starting from the beginning of
+/// the streams which don't have any
+pub(crate) fn checkpoint_positions(
+    checkpoint: &SourceCheckpointInput,
+) -> Result<Vec<(SubnetId, u64)>, GraphQLServerError> {
+    let mut positions = checkpoint
+        .source_subnet_ids
+        .iter()
+        .map(|subnet_id| subnet_id.try_into().map(|subnet_id| (subnet_id, 0)))
+        .collect::<Result<HashMap<SubnetId, u64>, _>>()
+        .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+
+    for position in &checkpoint.positions {
+        let subnet_id = (&position.source_subnet_id)
+            .try_into()
+            .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+
+        positions.insert(subnet_id, position.position);
+    }
+
+    Ok(positions.into_iter().collect())
+}
+
+/// Replay the streams of the source subnets from the given positions until their current end
+pub(crate) fn replay_source_streams(
+    store: Arc<FullNodeStore>,
+    positions: Vec<(SubnetId, u64)>,
+) -> impl Stream<Item = CertificateDelivered> {
+    stream::iter(positions).flat_map(move |(subnet_id, position)| {
+        let store = store.clone();
+
+        stream::unfold(Some(position), move |position| {
+            let store = store.clone();
+
+            async move {
+                let position = position?;
+
+                match store.get_source_stream_certificates_from_position(
+                    CertificateSourceStreamPosition::new(subnet_id, position),
+                    BATCH_SIZE,
+                ) {
+                    Ok(batch) => {
+                        // The certificates whose proof is pruned are skipped, the next batch
+                        // starts after the last one returned
+                        let next_position = batch
+                            .last()
+                            .filter(|_| batch.len() == BATCH_SIZE)
+                            .map(|(_, position)| *position.position + 1);
+
+                        Some((batch, next_position))
+                    }
+                    Err(error) => {
+                        error!("Unable to replay the source stream {subnet_id}: {error}");
+
+                        None
+                    }
+                }
+            }
+        })
+        .flat_map(|batch| stream::iter(batch.into_iter().map(|(certificate, _)| certificate)))
+    })
+}
@@ -1,22 +1,31 @@
 use std::{sync::Arc, time::Duration};
 
 use crate::{
-    graphql::query::{QueryRoot, SubscriptionRoot},
+    graphql::{
+        mutation::MutationRoot,
+        query::{QueryRoot, SubscriptionRoot},
+        streams::{collect_page, SentPositions, MAX_SCANNED_CERTIFICATES},
+    },
+    limits::{ClientLimits, Limiter},
     runtime::InternalRuntimeCommand,
     stream::TransientStream,
+    RuntimeError,
 };
 use async_graphql::{http, value, EmptyMutation, Schema};
 use futures::{SinkExt, StreamExt};
 use rstest::rstest;
 use test_log::test;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use topos_core::{
-    types::stream::Position,
-    uci::{SubnetId, INITIAL_CERTIFICATE_ID},
+    types::{stream::Position, Signature, ValidatorId},
+    uci::{Certificate, SubnetId, ValidationError, INITIAL_CERTIFICATE_ID},
 };
+use topos_tce_broadcast::{event::ProtocolEvents, BroadcastProgress};
+use topos_tce_storage::types::PendingResult;
 use topos_test_sdk::{
-    certificates::{create_certificate, create_certificate_at_position},
+    certificates::{create_certificate, create_certificate_at_position, create_certificate_chain},
     constants::{SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_3},
+    storage::create_fullnode_store,
 };
 use uuid::Uuid;
 
@@ -88,6 +97,7 @@ async fn open_watch_certificate_delivered() {
     });
     let subscription = SubscriptionRoot {};
     let schema = Schema::build(QueryRoot, EmptyMutation, subscription)
+        .data(Limiter::default())
         .data(sender)
         .finish();
 
@@ -162,3 +172,566 @@ async fn open_watch_certificate_delivered() {
         }),
     );
 }
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn watch_delivered_certificates_replays_from_checkpoint() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 6);
+    let store = create_fullnode_store::partial_1(&certificates[..5]).await;
+
+    let live_certificates = vec![certificates[3].clone(), certificates[5].clone()];
+    let (sender, mut receiver): (mpsc::Sender<InternalRuntimeCommand>, _) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        let mut v = Vec::new();
+        while let Some(query) = receiver.recv().await {
+            if let InternalRuntimeCommand::NewTransientStream { sender } = query {
+                let (notifier, notifier_receiver) = oneshot::channel();
+                v.push(notifier_receiver);
+
+                let (notify, inner) = mpsc::channel(10);
+                _ = sender.send(Ok(TransientStream {
+                    stream_id: Uuid::new_v4(),
+                    notifier: Some(notifier),
+                    inner,
+                }));
+
+                // Delivered while the replay is running, the first one is replayed as well
+                for certificate in &live_certificates {
+                    _ = notify.send(Arc::new(certificate.clone())).await;
+                }
+            }
+        }
+    });
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(Limiter::default())
+        .data(sender)
+        .data(store)
+        .finish();
+
+    let mut stream = schema.execute_stream(format!(
+        r#"subscription {{
+              watchDeliveredCertificates(
+                fromSourceCheckpoint: {{
+                  sourceSubnetIds: ["{SOURCE_SUBNET_ID_2}"],
+                  positions: [{{ sourceSubnetId: "{SOURCE_SUBNET_ID_2}", position: 2 }}]
+                }}
+              ) {{
+                id
+              }}
+            }}"#
+    ));
+
+    for certificate in &certificates[2..] {
+        let response = stream.next().await.unwrap();
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        assert_eq!(
+            response.data,
+            value!({
+                "watchDeliveredCertificates": {
+                    "id": certificate.certificate.id.to_string(),
+                },
+            })
+        );
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn watch_delivered_certificates_keeps_late_deliveries() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 8);
+    let store = create_fullnode_store::partial_1(&certificates[..5]).await;
+
+    // The certificate at position 5 is delivered after the one at position 6
+    let live_certificates = vec![
+        certificates[3].clone(),
+        certificates[6].clone(),
+        certificates[5].clone(),
+        certificates[6].clone(),
+        certificates[7].clone(),
+    ];
+    let (sender, mut receiver): (mpsc::Sender<InternalRuntimeCommand>, _) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        let mut v = Vec::new();
+        while let Some(query) = receiver.recv().await {
+            if let InternalRuntimeCommand::NewTransientStream { sender } = query {
+                let (notifier, notifier_receiver) = oneshot::channel();
+                v.push(notifier_receiver);
+
+                let (notify, inner) = mpsc::channel(10);
+                _ = sender.send(Ok(TransientStream {
+                    stream_id: Uuid::new_v4(),
+                    notifier: Some(notifier),
+                    inner,
+                }));
+
+                for certificate in &live_certificates {
+                    _ = notify.send(Arc::new(certificate.clone())).await;
+                }
+            }
+        }
+    });
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(Limiter::default())
+        .data(sender)
+        .data(store)
+        .finish();
+
+    let mut stream = schema.execute_stream(format!(
+        r#"subscription {{
+              watchDeliveredCertificates(
+                fromSourceCheckpoint: {{
+                  sourceSubnetIds: ["{SOURCE_SUBNET_ID_2}"],
+                  positions: [{{ sourceSubnetId: "{SOURCE_SUBNET_ID_2}", position: 2 }}]
+                }}
+              ) {{
+                id
+              }}
+            }}"#
+    ));
+
+    for index in [2, 3, 4, 6, 5, 7] {
+        let response = stream.next().await.unwrap();
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        assert_eq!(
+            response.data,
+            value!({
+                "watchDeliveredCertificates": {
+                    "id": certificates[index].certificate.id.to_string(),
+                },
+            })
+        );
+    }
+}
+
+#[test]
+fn sent_positions_skip_the_positions_already_sent() {
+    let mut sent_positions = SentPositions::new(2);
+
+    assert!(!sent_positions.insert(1));
+    assert!(sent_positions.insert(2));
+    assert!(sent_positions.insert(4));
+    assert!(!sent_positions.insert(4));
+    assert!(sent_positions.insert(3));
+    assert!(!sent_positions.insert(3));
+    assert!(sent_positions.insert(5));
+}
+
+#[test]
+fn stream_pages_stop_after_scanning_too_many_certificates() {
+    let certificate = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 1)
+        .pop()
+        .unwrap();
+    let stream_length = 5 * MAX_SCANNED_CERTIFICATES as u64 / 2;
+
+    let fetch = |position: u64, limit: usize| {
+        Ok((position..stream_length.min(position + limit as u64))
+            .map(|position| (certificate.clone(), position))
+            .collect())
+    };
+
+    // None of the certificates match
+    let page = collect_page(fetch, 0, None, 10, |_| false).unwrap();
+
+    assert!(page.certificates.is_empty());
+    assert!(page.has_next_page);
+    assert_eq!(
+        page.scanned_until,
+        Some(MAX_SCANNED_CERTIFICATES as u64 - 1)
+    );
+
+    // The scan isn't interrupted when the range ends before the cap
+    let page = collect_page(fetch, 0, Some(10), 10, |_| false).unwrap();
+
+    assert!(!page.has_next_page);
+    assert_eq!(page.scanned_until, None);
+
+    // Resuming from the continuation reaches the end of the stream
+    let mut start = 0;
+    let mut scans = 0;
+    loop {
+        let page = collect_page(fetch, start, None, 10, |_| false).unwrap();
+        scans += 1;
+
+        match page.scanned_until {
+            Some(scanned_until) => start = scanned_until + 1,
+            None => {
+                assert!(!page.has_next_page);
+                break;
+            }
+        }
+    }
+
+    assert_eq!(scans, 3);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn watch_protocol_events_of_a_certificate() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 2);
+    let watched = certificates[0].certificate.id;
+    let other = certificates[1].certificate.id;
+
+    let (protocol_events, _) = broadcast::channel(10);
+    let (sender, mut receiver): (mpsc::Sender<InternalRuntimeCommand>, _) = mpsc::channel(1);
+
+    // The counts of the watched certificate only change on the third poll
+    tokio::spawn(async move {
+        let mut polls = 0;
+        while let Some(command) = receiver.recv().await {
+            if let InternalRuntimeCommand::GetBroadcastProgress { sender } = command {
+                polls += 1;
+                let (echo_count, ready_count) = if polls < 3 { (1, 0) } else { (3, 1) };
+
+                _ = sender.send(Ok(vec![
+                    BroadcastProgress {
+                        certificate_id: watched,
+                        echo_count,
+                        ready_count,
+                    },
+                    BroadcastProgress {
+                        certificate_id: other,
+                        echo_count: polls,
+                        ready_count: 0,
+                    },
+                ]));
+            }
+        }
+    });
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(Limiter::default())
+        .data(sender)
+        .data(protocol_events.clone())
+        .finish();
+
+    let mut stream = schema.execute_stream(format!(
+        r#"subscription {{
+              watchProtocolEvents(certificateId: "{watched}") {{
+                kind
+                certificateId
+                echoCount
+                readyCount
+              }}
+            }}"#
+    ));
+
+    tokio::spawn(async move {
+        // The subscription only starts listening once the stream is polled
+        while protocol_events.receiver_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        for event in [
+            ProtocolEvents::Broadcast {
+                certificate_id: other,
+            },
+            ProtocolEvents::Broadcast {
+                certificate_id: watched,
+            },
+            ProtocolEvents::Echo {
+                certificate_id: watched,
+                signature: Signature {
+                    r: Default::default(),
+                    s: Default::default(),
+                    v: 0,
+                },
+                validator_id: ValidatorId::default(),
+            },
+            ProtocolEvents::BroadcastFailed {
+                certificate_id: other,
+            },
+            ProtocolEvents::AlreadyDelivered {
+                certificate_id: watched,
+            },
+        ] {
+            _ = protocol_events.send(event);
+        }
+    });
+
+    let mut kinds = Vec::new();
+    let mut counts = Vec::new();
+    while counts.len() < 2 || kinds.len() < 2 {
+        let response = stream.next().await.unwrap();
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let mut event = response.data.into_json().unwrap()["watchProtocolEvents"].take();
+        assert_eq!(event["certificateId"], watched.to_string());
+
+        if event["kind"] == "PROGRESS" {
+            counts.push((event["echoCount"].take(), event["readyCount"].take()));
+        } else {
+            assert!(event["echoCount"].is_null() && event["readyCount"].is_null());
+            kinds.push(event["kind"].take());
+        }
+    }
+
+    assert_eq!(kinds, ["BROADCAST", "ALREADY_DELIVERED"]);
+    assert_eq!(counts, [(1.into(), 0.into()), (3.into(), 1.into())]);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn get_validators_of_the_latest_epoch() {
+    let store = create_fullnode_store::default().await;
+    let validator: ValidatorId = "0x1111111111111111111111111111111111111111"
+        .parse()
+        .unwrap();
+    let next_validator: ValidatorId = "0x2222222222222222222222222222222222222222"
+        .parse()
+        .unwrap();
+
+    let validators_store = store.epoch_validators_store();
+    validators_store
+        .insert_validators(0, [validator].into_iter().collect())
+        .unwrap();
+    validators_store
+        .insert_validators(1, [validator, next_validator].into_iter().collect())
+        .unwrap();
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(Limiter::default())
+        .data(store)
+        .finish();
+
+    let response = schema
+        .execute("{ getValidators { epoch validators } }")
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    assert_eq!(
+        response.data,
+        value!({
+            "getValidators": {
+                "epoch": 1,
+                "validators": [validator.to_string(), next_validator.to_string()],
+            },
+        })
+    );
+}
+
+/// Format a certificate as a GraphQL `CertificateInput`
+fn certificate_input(certificate: &Certificate) -> String {
+    format!(
+        r#"{{
+          id: "{}",
+          prevId: "{}",
+          proof: "{}",
+          signature: "{}",
+          sourceSubnetId: "{}",
+          stateRoot: "0x{}",
+          targetSubnets: [{}],
+          txRootHash: "0x{}",
+          receiptsRootHash: "0x{}",
+          verifier: {}
+        }}"#,
+        certificate.id,
+        certificate.prev_id,
+        hex::encode(&certificate.proof),
+        hex::encode(&certificate.signature),
+        certificate.source_subnet_id,
+        hex::encode(certificate.state_root),
+        certificate
+            .target_subnets
+            .iter()
+            .map(|subnet_id| format!("\"{subnet_id}\""))
+            .collect::<Vec<_>>()
+            .join(", "),
+        hex::encode(certificate.tx_root_hash),
+        hex::encode(certificate.receipts_root_hash),
+        certificate.verifier,
+    )
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submit_certificates_through_graphql() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 2);
+    let (sender, mut receiver): (mpsc::Sender<InternalRuntimeCommand>, _) = mpsc::channel(1);
+
+    let expected = certificates
+        .iter()
+        .map(|certificate| certificate.certificate.clone())
+        .collect::<Vec<_>>();
+    tokio::spawn(async move {
+        if let Some(InternalRuntimeCommand::CertificatesSubmitted {
+            certificates,
+            sender,
+        }) = receiver.recv().await
+        {
+            assert_eq!(certificates, expected);
+
+            _ = sender.send(vec![
+                Ok(PendingResult::InPending(1)),
+                Err(RuntimeError::InvalidCertificate(
+                    ValidationError::SelfTarget,
+                )),
+            ]);
+        }
+    });
+
+    let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(Limiter::default())
+        .data(sender)
+        .finish();
+
+    let inputs = certificates
+        .iter()
+        .map(|certificate| certificate_input(&certificate.certificate))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let response = schema
+        .execute(format!(
+            "mutation {{ submitCertificates(certificates: [{inputs}]) {{ certificateId result \
+             pendingId }} }}"
+        ))
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    assert_eq!(
+        response.data,
+        value!({
+            "submitCertificates": [
+                {
+                    "certificateId": certificates[0].certificate.id.to_string(),
+                    "result": "IN_PENDING",
+                    "pendingId": 1,
+                },
+                {
+                    "certificateId": certificates[1].certificate.id.to_string(),
+                    "result": "REJECTED",
+                    "pendingId": null,
+                },
+            ],
+        })
+    );
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submit_certificate_through_graphql() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 2);
+    let accepted = certificates[0].certificate.clone();
+    let (sender, mut receiver): (mpsc::Sender<InternalRuntimeCommand>, _) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        while let Some(InternalRuntimeCommand::CertificateSubmitted {
+            certificate,
+            sender,
+        }) = receiver.recv().await
+        {
+            _ = sender.send(if *certificate == accepted {
+                Ok(PendingResult::InPending(0))
+            } else {
+                Err(RuntimeError::InvalidCertificate(
+                    ValidationError::SelfTarget,
+                ))
+            });
+        }
+    });
+
+    let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(Limiter::default())
+        .data(sender)
+        .finish();
+
+    let response = schema
+        .execute(format!(
+            "mutation {{ submitCertificate(certificate: {}) }}",
+            certificate_input(&certificates[0].certificate)
+        ))
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(response.data, value!({ "submitCertificate": "IN_PENDING" }));
+
+    let response = schema
+        .execute(format!(
+            "mutation {{ submitCertificate(certificate: {}) }}",
+            certificate_input(&certificates[1].certificate)
+        ))
+        .await;
+    assert_eq!(
+        response.errors[0].message,
+        "The certificate has been rejected: the certificate targets its own source subnet"
+    );
+
+    let response = schema
+        .execute(format!(
+            "mutation {{ submitCertificate(certificate: {}) }}",
+            certificate_input(&certificates[1].certificate)
+                .replace(r#"stateRoot: "0x"#, r#"stateRoot: "0xzz"#)
+        ))
+        .await;
+    assert_eq!(
+        response.errors[0].message,
+        "The provided certificate has an invalid stateRoot"
+    );
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submissions_through_graphql_are_rate_limited() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_3], 2);
+    let (sender, mut receiver): (mpsc::Sender<InternalRuntimeCommand>, _) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        while let Some(InternalRuntimeCommand::CertificateSubmitted { sender, .. }) =
+            receiver.recv().await
+        {
+            _ = sender.send(Ok(PendingResult::InPending(0)));
+        }
+    });
+
+    let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(Limiter::new(
+            ClientLimits {
+                submissions_per_second: Some(1),
+                ..Default::default()
+            },
+            None,
+        ))
+        .data(sender)
+        .finish();
+
+    let response = schema
+        .execute(format!(
+            "mutation {{ submitCertificate(certificate: {}) }}",
+            certificate_input(&certificates[0].certificate)
+        ))
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let response = schema
+        .execute(format!(
+            "mutation {{ submitCertificate(certificate: {}) }}",
+            certificate_input(&certificates[1].certificate)
+        ))
+        .await;
+    assert_eq!(
+        response.errors[0].message,
+        format!(
+            "Resource exhausted: The source subnet {SOURCE_SUBNET_ID_2} can't submit more than 1 \
+             certificates per second"
+        )
+    );
+    assert_eq!(
+        response.errors[0]
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get("code")),
+        Some(&value!("RESOURCE_EXHAUSTED"))
+    );
+}
@@ -9,6 +9,7 @@ use topos_core::api::grpc::tce::v1::{
 };
 use topos_tce_storage::validator::ValidatorStore;
 
+use crate::limits::Limiter;
 use crate::runtime::InternalRuntimeCommand;
 
 use super::{console::TceConsoleService, TceGrpcService};
@@ -19,6 +20,7 @@ pub struct ServerBuilder {
     local_peer_id: String,
     command_sender: Option<Sender<InternalRuntimeCommand>>,
     serve_addr: Option<SocketAddr>,
+    limiter: Limiter,
 }
 
 impl ServerBuilder {
@@ -34,6 +36,12 @@ impl ServerBuilder {
         self
     }
 
+    pub(crate) fn with_limiter(mut self, limiter: Limiter) -> Self {
+        self.limiter = limiter;
+
+        self
+    }
+
     pub(crate) fn command_sender(mut self, sender: Sender<InternalRuntimeCommand>) -> Self {
         self.command_sender = Some(sender);
 
@@ -63,13 +71,9 @@ impl ServerBuilder {
         // So as soon as the node starts it is ready to send and receive ECHO messages.
         let status = Arc::new(RwLock::new(StatusResponse {
             has_active_sample: true,
+            epoch: 0,
         }));
 
-        let console = ConsoleServiceServer::new(TceConsoleService {
-            command_sender: command_sender.clone(),
-            status: status.clone(),
-        });
-
         let store = self
             .store
             .take()
@@ -78,6 +82,7 @@ impl ServerBuilder {
         let service = ApiServiceServer::new(TceGrpcService {
             store,
             command_sender,
+            limiter: self.limiter,
         });
 
         let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
@@ -99,7 +104,6 @@ impl ServerBuilder {
         let grpc = tonic::transport::Server::builder()
             .add_service(health_service)
             .add_service(service)
-            .add_service(console)
             .add_service(reflexion)
             .serve(serve_addr)
             .boxed();
@@ -107,3 +111,65 @@ impl ServerBuilder {
         (health_reporter, status, grpc)
     }
 }
+
+/// Builder of the console gRPC server, served on its own address as it exposes admin commands
+#[derive(Default)]
+pub struct ConsoleServerBuilder {
+    store: Option<Arc<ValidatorStore>>,
+    command_sender: Option<Sender<InternalRuntimeCommand>>,
+    status: Option<Arc<RwLock<StatusResponse>>>,
+    serve_addr: Option<SocketAddr>,
+}
+
+impl ConsoleServerBuilder {
+    pub(crate) fn with_store(mut self, store: Arc<ValidatorStore>) -> Self {
+        self.store = Some(store);
+
+        self
+    }
+
+    pub(crate) fn command_sender(mut self, sender: Sender<InternalRuntimeCommand>) -> Self {
+        self.command_sender = Some(sender);
+
+        self
+    }
+
+    pub(crate) fn status(mut self, status: Arc<RwLock<StatusResponse>>) -> Self {
+        self.status = Some(status);
+
+        self
+    }
+
+    pub(crate) fn serve_addr(mut self, addr: Option<SocketAddr>) -> Self {
+        self.serve_addr = addr;
+
+        self
+    }
+
+    pub fn build(mut self) -> BoxFuture<'static, Result<(), tonic::transport::Error>> {
+        let console = ConsoleServiceServer::new(TceConsoleService {
+            command_sender: self
+                .command_sender
+                .take()
+                .expect("Cannot build the console without an InternalRuntimeCommand sender"),
+            status: self
+                .status
+                .take()
+                .expect("Cannot build the console without the TCE status"),
+            store: self
+                .store
+                .take()
+                .expect("Cannot build the console without a Validator store"),
+        });
+
+        let serve_addr = self
+            .serve_addr
+            .take()
+            .expect("Cannot build the console without a valid serve_addr");
+
+        tonic::transport::Server::builder()
+            .add_service(console)
+            .serve(serve_addr)
+            .boxed()
+    }
+}
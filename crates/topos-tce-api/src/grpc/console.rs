@@ -1,20 +1,54 @@
 use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot;
 
 use crate::runtime::InternalRuntimeCommand;
+use crate::RuntimeError;
 use tokio::sync::RwLock;
 use tonic::{Request, Response, Status};
+use topos_core::api::grpc::shared::v1::positions::SourceStreamPosition;
 use topos_core::api::grpc::tce::v1::{
-    console_service_server::ConsoleService, StatusRequest, StatusResponse,
+    console_service_server::ConsoleService, get_broadcast_tasks_response::BroadcastTask,
+    get_peers_response::Peer, EvictPendingCertificateRequest, EvictPendingCertificateResponse,
+    GetBroadcastTasksRequest, GetBroadcastTasksResponse, GetPeersRequest, GetPeersResponse,
+    GetPoolsRequest, GetPoolsResponse, GetSourceHeadsRequest, GetSourceHeadsResponse,
+    SetLogLevelRequest, SetLogLevelResponse, StatusRequest, StatusResponse,
 };
+use topos_tce_storage::store::ReadStore;
+use topos_tce_storage::validator::ValidatorStore;
+use topos_telemetry::tracing::LogFilterError;
+use tracing::{info, warn};
 
 pub(crate) struct TceConsoleService {
-    // We want to allow this unused command_sender, because we need it in the future again.
-    // We keep it so the architecture is already obvious where to put a command_sender
-    // One example will be changing validators during the uptime of the network
-    #[allow(dead_code)]
     pub(crate) command_sender: Sender<InternalRuntimeCommand>,
     pub(crate) status: Arc<RwLock<StatusResponse>>,
+    pub(crate) store: Arc<ValidatorStore>,
+}
+
+impl TceConsoleService {
+    /// Send a command to the runtime and wait for its response
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<Result<T, RuntimeError>>) -> InternalRuntimeCommand,
+    ) -> Result<T, Status> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.command_sender
+            .send(command(sender))
+            .await
+            .map_err(|_| Status::internal("Can't reach the runtime: sender dropped"))?;
+
+        match receiver.await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(RuntimeError::CertificateNotPending(certificate_id))) => Err(Status::not_found(
+                format!("The certificate {certificate_id} isn't pending"),
+            )),
+            Ok(Err(error)) => Err(Status::internal(error.to_string())),
+            Err(_) => Err(Status::internal(
+                "Can't reach the runtime: receiver dropped",
+            )),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -27,4 +61,128 @@ impl ConsoleService for TceConsoleService {
 
         Ok(Response::new(status.clone()))
     }
+
+    async fn get_peers(
+        &self,
+        _request: Request<GetPeersRequest>,
+    ) -> Result<Response<GetPeersResponse>, Status> {
+        let peers = self
+            .request(|sender| InternalRuntimeCommand::GetPeers { sender })
+            .await?;
+
+        Ok(Response::new(GetPeersResponse {
+            peers: peers
+                .into_iter()
+                .map(|peer| Peer {
+                    peer_id: peer.peer_id.to_string(),
+                    addresses: peer.addresses.iter().map(ToString::to_string).collect(),
+                    outbound: peer.outbound,
+                    denied: peer.denied,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn get_pools(
+        &self,
+        _request: Request<GetPoolsRequest>,
+    ) -> Result<Response<GetPoolsResponse>, Status> {
+        let pending_pool_size = self
+            .store
+            .pending_pool_size()
+            .map_err(|error| Status::internal(error.to_string()))?;
+        let precedence_pool_size = self
+            .store
+            .precedence_pool_size()
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        Ok(Response::new(GetPoolsResponse {
+            pending_pool_size,
+            precedence_pool_size,
+        }))
+    }
+
+    async fn get_source_heads(
+        &self,
+        _request: Request<GetSourceHeadsRequest>,
+    ) -> Result<Response<GetSourceHeadsResponse>, Status> {
+        let source_heads = self
+            .store
+            .get_checkpoint()
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        Ok(Response::new(GetSourceHeadsResponse {
+            source_heads: source_heads
+                .into_values()
+                .map(|head| SourceStreamPosition {
+                    source_subnet_id: Some(head.subnet_id.into()),
+                    certificate_id: Some(head.certificate_id.into()),
+                    position: *head.position,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn get_broadcast_tasks(
+        &self,
+        _request: Request<GetBroadcastTasksRequest>,
+    ) -> Result<Response<GetBroadcastTasksResponse>, Status> {
+        let progress = self
+            .request(|sender| InternalRuntimeCommand::GetBroadcastProgress { sender })
+            .await?;
+
+        Ok(Response::new(GetBroadcastTasksResponse {
+            tasks: progress
+                .into_iter()
+                .map(|progress| BroadcastTask {
+                    certificate_id: Some(progress.certificate_id.into()),
+                    echo_count: progress.echo_count as u64,
+                    ready_count: progress.ready_count as u64,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn set_log_level(
+        &self,
+        request: Request<SetLogLevelRequest>,
+    ) -> Result<Response<SetLogLevelResponse>, Status> {
+        let filter = request.into_inner().filter;
+
+        match topos_telemetry::tracing::set_log_filter(&filter) {
+            Ok(()) => {
+                info!("Log filter changed to {filter}");
+
+                Ok(Response::new(SetLogLevelResponse {}))
+            }
+            Err(error @ LogFilterError::InvalidFilter(_)) => {
+                Err(Status::invalid_argument(error.to_string()))
+            }
+            Err(error) => {
+                warn!("Unable to change the log filter: {error}");
+
+                Err(Status::failed_precondition(error.to_string()))
+            }
+        }
+    }
+
+    async fn evict_pending_certificate(
+        &self,
+        request: Request<EvictPendingCertificateRequest>,
+    ) -> Result<Response<EvictPendingCertificateResponse>, Status> {
+        let certificate_id = request
+            .into_inner()
+            .certificate_id
+            .ok_or_else(|| Status::invalid_argument("Missing certificate id"))?
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Invalid certificate id"))?;
+
+        self.request(|sender| InternalRuntimeCommand::EvictPendingCertificate {
+            certificate_id,
+            sender,
+        })
+        .await?;
+
+        Ok(Response::new(EvictPendingCertificateResponse {}))
+    }
 }
@@ -1,5 +1,8 @@
 use tonic::Status;
-use topos_core::api::grpc::checkpoints::{TargetCheckpoint, TargetStreamPosition};
+use topos_core::api::grpc::checkpoints::{
+    SourceCheckpoint, SourceStreamPosition, TargetCheckpoint, TargetStreamPosition,
+};
+use topos_core::api::grpc::tce::v1::watch_certificates_request::Ack as GrpcAck;
 use topos_core::api::grpc::tce::v1::watch_certificates_request::Command;
 use topos_core::api::grpc::tce::v1::watch_certificates_request::OpenStream as GrpcOpenStream;
 use topos_core::api::grpc::tce::v1::watch_certificates_response::CertificatePushed as GrpcCertificatePushed;
@@ -10,10 +13,29 @@ use topos_core::uci::SubnetId;
 
 pub enum InboundMessage {
     OpenStream(OpenStream),
+    Ack(Ack),
 }
 
 pub struct OpenStream {
-    pub(crate) target_checkpoint: TargetCheckpoint,
+    pub(crate) subscription: Subscription,
+    /// Maximum number of pushed certificates waiting for an [`Ack`], 0 disables the flow control
+    pub(crate) max_unacknowledged: usize,
+}
+
+/// Certificates watched by a stream
+pub enum Subscription {
+    /// The certificates delivered to the target subnets of the checkpoint
+    Target(TargetCheckpoint),
+    /// The certificates emitted by the source subnets of the checkpoint, restricted to the
+    /// ones targeting the subnets of the filter if it isn't empty
+    Source {
+        checkpoint: SourceCheckpoint,
+        target_subnet_filter: Vec<SubnetId>,
+    },
+}
+
+pub struct Ack {
+    pub(crate) position: TargetStreamPosition,
 }
 
 #[derive(Debug)]
@@ -39,6 +61,7 @@ impl TryFrom<Command> for InboundMessage {
     fn try_from(command: Command) -> Result<Self, Self::Error> {
         match command {
             Command::OpenStream(value) => Ok(OpenStream::try_from(value)?.into()),
+            Command::Ack(value) => Ok(Ack::try_from(value)?.into()),
         }
     }
 }
@@ -47,15 +70,63 @@ impl TryFrom<GrpcOpenStream> for OpenStream {
     type Error = Status;
 
     fn try_from(value: GrpcOpenStream) -> Result<Self, Self::Error> {
+        let subscription = match (value.target_checkpoint, value.source_checkpoint) {
+            (Some(target_checkpoint), None) => Subscription::Target(
+                target_checkpoint
+                    .try_into()
+                    .map_err(|_| Status::invalid_argument("invalid checkpoint"))?,
+            ),
+            (None, Some(source_checkpoint)) => Subscription::Source {
+                checkpoint: source_checkpoint
+                    .try_into()
+                    .map_err(|_| Status::invalid_argument("invalid checkpoint"))?,
+                target_subnet_filter: value
+                    .target_subnet_filter
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| Status::invalid_argument("invalid target_subnet_filter"))?,
+            },
+            (None, None) => {
+                return Err(Status::invalid_argument(
+                    "missing target_checkpoint or source_checkpoint",
+                ))
+            }
+            (Some(_), Some(_)) => {
+                return Err(Status::invalid_argument(
+                    "target_checkpoint and source_checkpoint are mutually exclusive",
+                ))
+            }
+        };
+
+        Ok(Self {
+            subscription,
+            max_unacknowledged: value.max_unacknowledged as usize,
+        })
+    }
+}
+
+impl TryFrom<GrpcAck> for Ack {
+    type Error = Status;
+
+    fn try_from(value: GrpcAck) -> Result<Self, Self::Error> {
         Ok(Self {
-            target_checkpoint: value.target_checkpoint.map(TryInto::try_into).map_or(
-                Err(Status::invalid_argument("missing target_checkpoint")),
-                |value| value.map_err(|_| Status::invalid_argument("invalid checkpoint")),
-            )?,
+            position: value
+                .position
+                .map(TryInto::try_into)
+                .map_or(Err(Status::invalid_argument("missing position")), |value| {
+                    value.map_err(|_| Status::invalid_argument("invalid position"))
+                })?,
         })
     }
 }
 
+impl From<Ack> for InboundMessage {
+    fn from(value: Ack) -> Self {
+        Self::Ack(value)
+    }
+}
+
 impl From<OpenStream> for InboundMessage {
     fn from(value: OpenStream) -> Self {
         Self::OpenStream(value)
@@ -71,6 +142,16 @@ impl From<OutboundMessage> for Event {
                 })
             }
             OutboundMessage::CertificatePushed(certificate_pushed) => {
+                let delivery_position = &certificate_pushed
+                    .certificate
+                    .proof_of_delivery
+                    .delivery_position;
+                let source_position = SourceStreamPosition {
+                    source_subnet_id: delivery_position.subnet_id,
+                    position: *delivery_position.position,
+                    certificate_id: Some(certificate_pushed.certificate.certificate.id),
+                };
+
                 Self::CertificatePushed(GrpcCertificatePushed {
                     certificate: Some(certificate_pushed.certificate.certificate.into()),
                     positions: certificate_pushed
@@ -78,6 +159,7 @@ impl From<OutboundMessage> for Event {
                         .into_iter()
                         .map(Into::into)
                         .collect(),
+                    source_position: Some(source_position.into()),
                 })
             }
         }
@@ -5,22 +5,34 @@ use std::sync::Arc;
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status, Streaming};
+use topos_core::api::grpc::shared::v1::positions::{SourceStreamPosition, TargetStreamPosition};
+use topos_core::api::grpc::tce::v1::get_certificate_status_response::{
+    AwaitingPrecedence, Delivered, InBroadcast, Pending, Status as CertificateStatusKind,
+};
+use topos_core::api::grpc::tce::v1::submit_certificates_response::{
+    CertificateSubmission, PendingResult as SubmissionResult,
+};
 use topos_core::api::grpc::tce::v1::LastPendingCertificate;
 use topos_core::api::grpc::tce::v1::{
-    api_service_server::ApiService, GetLastPendingCertificatesRequest,
-    GetLastPendingCertificatesResponse, GetSourceHeadRequest, GetSourceHeadResponse,
-    SubmitCertificateRequest, SubmitCertificateResponse, WatchCertificatesRequest,
+    api_service_server::ApiService, GetCertificateStatusRequest, GetCertificateStatusResponse,
+    GetLastPendingCertificatesRequest, GetLastPendingCertificatesResponse, GetSourceHeadRequest,
+    GetSourceHeadResponse, SubmitCertificateRequest, SubmitCertificateResponse,
+    SubmitCertificatesRequest, SubmitCertificatesResponse, WatchCertificatesRequest,
     WatchCertificatesResponse,
 };
-use topos_core::uci::SubnetId;
+use topos_core::uci::{CertificateId, SubnetId};
 use topos_metrics::API_GRPC_CERTIFICATE_RECEIVED_TOTAL;
+use topos_tce_storage::types::PendingResult;
 use topos_tce_storage::validator::ValidatorStore;
 use tracing::{error, info, Span};
 use uuid::Uuid;
 
 use crate::{
-    runtime::InternalRuntimeCommand,
+    constants::MAX_SUBMITTED_CERTIFICATES,
+    limits::Limiter,
+    runtime::{CertificateStatus, InternalRuntimeCommand},
     stream::{Stream, StreamError, StreamErrorKind},
+    RuntimeError,
 };
 
 use self::messaging::{InboundMessage, OutboundMessage};
@@ -37,6 +49,7 @@ pub(crate) mod messaging;
 pub(crate) struct TceGrpcService {
     store: Arc<ValidatorStore>,
     command_sender: mpsc::Sender<InternalRuntimeCommand>,
+    limiter: Limiter,
 }
 
 impl TceGrpcService {
@@ -53,6 +66,87 @@ impl TceGrpcService {
         }))
     }
 
+    fn certificate_status(
+        certificate_id: CertificateId,
+        status: CertificateStatus,
+    ) -> CertificateStatusKind {
+        match status {
+            CertificateStatus::Pending { pending_id } => {
+                CertificateStatusKind::Pending(Pending { pending_id })
+            }
+            CertificateStatus::AwaitingPrecedence { prev_id } => {
+                CertificateStatusKind::AwaitingPrecedence(AwaitingPrecedence {
+                    prev_id: Some(prev_id.into()),
+                })
+            }
+            CertificateStatus::InBroadcast {
+                echo_count,
+                ready_count,
+            } => CertificateStatusKind::InBroadcast(InBroadcast {
+                echo_count: echo_count as u64,
+                ready_count: ready_count as u64,
+            }),
+            CertificateStatus::Delivered(positions) => {
+                CertificateStatusKind::Delivered(Delivered {
+                    source_position: Some(SourceStreamPosition {
+                        source_subnet_id: Some(positions.source.subnet_id.into()),
+                        certificate_id: Some(certificate_id.into()),
+                        position: *positions.source.position,
+                    }),
+                    target_positions: positions
+                        .targets
+                        .into_values()
+                        .map(|target| TargetStreamPosition {
+                            source_subnet_id: Some(target.source_subnet_id.into()),
+                            target_subnet_id: Some(target.target_subnet_id.into()),
+                            certificate_id: Some(certificate_id.into()),
+                            position: *target.position,
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+
+    fn certificate_submission(
+        certificate_id: CertificateId,
+        result: Result<PendingResult, RuntimeError>,
+    ) -> Result<CertificateSubmission, Status> {
+        let mut submission = CertificateSubmission {
+            certificate_id: Some(certificate_id.into()),
+            ..Default::default()
+        };
+
+        match result {
+            Ok(PendingResult::InPending(pending_id)) => {
+                submission.set_result(SubmissionResult::InPending);
+                submission.pending_id = pending_id;
+            }
+            Ok(PendingResult::AwaitPrecedence) => {
+                submission.set_result(SubmissionResult::AwaitPrecedence)
+            }
+            Ok(PendingResult::AlreadyPending) => {
+                submission.set_result(SubmissionResult::AlreadyPending)
+            }
+            Ok(PendingResult::AlreadyDelivered) => {
+                submission.set_result(SubmissionResult::AlreadyDelivered)
+            }
+            Err(RuntimeError::InvalidCertificate(error)) => {
+                submission.set_result(SubmissionResult::Rejected);
+                submission.rejection = Some(error.into());
+            }
+            Err(error) => {
+                error!("Unable to submit the certificate {certificate_id}: {error}");
+
+                return Err(Status::internal(format!(
+                    "Can't submit certificate {certificate_id}"
+                )));
+            }
+        }
+
+        Ok(submission)
+    }
+
     pub fn parse_stream(
         message: Result<WatchCertificatesRequest, Status>,
         stream_id: Uuid,
@@ -103,6 +197,11 @@ impl ApiService for TceGrpcService {
                         }
                     };
 
+                    let permit = self
+                        .limiter
+                        .check_submissions([&certificate])
+                        .map_err(|error| Status::resource_exhausted(error.to_string()))?;
+
                     if self
                         .command_sender
                         .send(InternalRuntimeCommand::CertificateSubmitted {
@@ -117,13 +216,21 @@ impl ApiService for TceGrpcService {
                         API_GRPC_CERTIFICATE_RECEIVED_TOTAL.inc();
                     }
 
-                    receiver
-                        .map(|value| match value {
-                            Ok(Ok(_)) => Ok(Response::new(SubmitCertificateResponse {})),
-                            Ok(Err(_)) => Err(Status::internal("Can't submit certificate")),
-                            Err(_) => Err(Status::internal("Can't submit certificate")),
-                        })
-                        .await
+                    let result = receiver.await;
+                    if let Ok(result) = &result {
+                        permit.settle([result]);
+                    }
+
+                    match result {
+                        Ok(Ok(_)) => {
+                            Ok(Response::new(SubmitCertificateResponse { rejection: None }))
+                        }
+                        Ok(Err(crate::RuntimeError::InvalidCertificate(error))) => {
+                            Ok(Response::new(error.into()))
+                        }
+                        Ok(Err(_)) => Err(Status::internal("Can't submit certificate")),
+                        Err(_) => Err(Status::internal("Can't submit certificate")),
+                    }
                 } else {
                     error!("No certificate id provided");
                     Err(Status::invalid_argument("Certificate is malformed"))
@@ -135,6 +242,61 @@ impl ApiService for TceGrpcService {
         .await
     }
 
+    async fn submit_certificates(
+        &self,
+        request: Request<SubmitCertificatesRequest>,
+    ) -> Result<Response<SubmitCertificatesResponse>, Status> {
+        let certificates = request.into_inner().certificates;
+        if certificates.len() > MAX_SUBMITTED_CERTIFICATES {
+            return Err(Status::invalid_argument(format!(
+                "Can't submit more than {MAX_SUBMITTED_CERTIFICATES} certificates at once"
+            )));
+        }
+
+        let certificates = certificates
+            .into_iter()
+            .map(|certificate| {
+                certificate.try_into().map_err(|error| {
+                    Status::invalid_argument(format!("Can't submit invalid certificate: {error}"))
+                })
+            })
+            .collect::<Result<Vec<topos_core::uci::Certificate>, _>>()?;
+        let permit = self
+            .limiter
+            .check_submissions(&certificates)
+            .map_err(|error| Status::resource_exhausted(error.to_string()))?;
+
+        let certificate_ids: Vec<CertificateId> = certificates
+            .iter()
+            .map(|certificate| certificate.id)
+            .collect();
+
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(InternalRuntimeCommand::CertificatesSubmitted {
+                certificates,
+                sender,
+            })
+            .await
+            .map_err(|_| Status::internal("Can't submit certificates: sender dropped"))?;
+        API_GRPC_CERTIFICATE_RECEIVED_TOTAL.inc_by(certificate_ids.len() as u64);
+
+        let results = receiver
+            .await
+            .map_err(|_| Status::internal("Can't submit certificates: receiver dropped"))?;
+        permit.settle(&results);
+
+        Ok(Response::new(SubmitCertificatesResponse {
+            submissions: certificate_ids
+                .into_iter()
+                .zip(results)
+                .map(|(certificate_id, result)| {
+                    Self::certificate_submission(certificate_id, result)
+                })
+                .collect::<Result<_, _>>()?,
+        }))
+    }
+
     /// This RPC allows a client to get last delivered source certificate
     /// for particular subnet
     async fn get_source_head(
@@ -250,6 +412,49 @@ impl ApiService for TceGrpcService {
         }))
     }
 
+    async fn get_certificate_status(
+        &self,
+        request: Request<GetCertificateStatusRequest>,
+    ) -> Result<Response<GetCertificateStatusResponse>, Status> {
+        let certificate_id = request
+            .into_inner()
+            .certificate_id
+            .ok_or_else(|| Status::invalid_argument("Missing certificate id"))?
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Invalid certificate id"))?;
+
+        let (sender, receiver) = oneshot::channel();
+
+        if self
+            .command_sender
+            .send(InternalRuntimeCommand::GetCertificateStatus {
+                certificate_id,
+                sender,
+            })
+            .await
+            .is_err()
+        {
+            return Err(Status::internal(
+                "Can't get the certificate status: sender dropped",
+            ));
+        }
+
+        match receiver.await {
+            Ok(Ok(Some(status))) => Ok(Response::new(GetCertificateStatusResponse {
+                status: Some(Self::certificate_status(certificate_id, status)),
+            })),
+            Ok(Ok(None)) => Err(Status::not_found(format!(
+                "Unknown certificate {certificate_id}"
+            ))),
+            Ok(Err(e)) => Err(Status::internal(format!(
+                "Can't get the certificate status: {e}"
+            ))),
+            Err(e) => Err(Status::internal(format!(
+                "Can't get the certificate status: {e}"
+            ))),
+        }
+    }
+
     ///Server streaming response type for the WatchCertificates method.
     type WatchCertificatesStream = Pin<
         Box<dyn FutureStream<Item = Result<WatchCertificatesResponse, Status>> + Send + 'static>,
@@ -264,6 +469,11 @@ impl ApiService for TceGrpcService {
             Some(addr) => info!(client.addr = %addr, "Starting a new stream"),
             None => info!(client.addr = %"<unknown>", "Starting a new stream"),
         }
+        let permit = self
+            .limiter
+            .acquire_stream(request.remote_addr())
+            .map_err(|error| Status::resource_exhausted(error.to_string()))?;
+
         // TODO: Use Cow
         let stream_id = Uuid::new_v4();
 
@@ -298,8 +508,13 @@ impl ApiService for TceGrpcService {
             return Err(Status::internal("Can't submit certificate: sender dropped"));
         }
 
+        // The permit is released once the client drops the stream
         Ok(Response::new(
-            Self::create_stream(rx) as Self::WatchCertificatesStream
+            Box::pin(Self::create_stream(rx).map(move |response| {
+                let _ = &permit;
+
+                response
+            })) as Self::WatchCertificatesStream,
         ))
     }
 }
@@ -1,8 +1,11 @@
 mod graphql;
 mod grpc;
+mod limits;
 mod metrics;
+mod rest;
 mod runtime;
 mod stream;
+mod submission;
 
 #[cfg(test)]
 mod tests;
@@ -13,7 +16,25 @@ pub(crate) mod constants {
 
     /// Constant size of every transient stream channel in the crate
     pub(crate) const TRANSIENT_STREAM_CHANNEL_SIZE: usize = 1024;
+
+    /// Maximum number of certificates submitted at once
+    pub(crate) const MAX_SUBMITTED_CERTIFICATES: usize = 1024;
+
+    /// Maximum number of certificates queued by a stream, the certificates of the target
+    /// streams falling behind being replayed from the storage once the queue is drained
+    pub(crate) const MAX_QUEUED_CERTIFICATES: usize = 1024;
+
+    /// Maximum number of certificates of a source subnet held by a stream ahead of the next
+    /// position to queue, the others being replayed from the storage
+    pub(crate) const MAX_AHEAD_CERTIFICATES: usize = 1024;
+
+    /// Time during which the acknowledged positions of a closed stream are kept, for a new
+    /// stream opened with the same request id to resume from them
+    pub(crate) const RESUME_POSITIONS_TTL: std::time::Duration =
+        std::time::Duration::from_secs(300);
 }
+pub use limits::ClientLimits;
 pub use runtime::{
-    error::RuntimeError, Runtime, RuntimeClient, RuntimeCommand, RuntimeContext, RuntimeEvent,
+    error::RuntimeError, CertificateStatus, Runtime, RuntimeClient, RuntimeCommand, RuntimeContext,
+    RuntimeEvent,
 };
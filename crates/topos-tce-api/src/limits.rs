@@ -0,0 +1,574 @@
+//! Limits applied to the clients of the public APIs
+//!
+//! The [`Limiter`] is shared by the gRPC, GraphQL and REST servers so that a client can't bypass
+//! the limits by switching from one API to another.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+use topos_core::uci::{Certificate, SubnetId};
+use topos_metrics::API_LIMITED_REQUESTS_TOTAL;
+use topos_tce_storage::types::PendingResult;
+use topos_tce_storage::validator::ValidatorStore;
+use tracing::warn;
+
+use crate::RuntimeError;
+
+/// Duration of the window in which the submissions of a source subnet are counted
+const SUBMISSION_WINDOW: Duration = Duration::from_secs(1);
+/// Number of tracked source subnets above which the expired windows and counts are dropped
+const MAX_TRACKED_SUBNETS: usize = 1024;
+/// Duration after which the pending certificates of a source subnet are counted again from the
+/// store, to account for the ones delivered or evicted since
+const PENDING_COUNT_VALIDITY: Duration = Duration::from_secs(1);
+
+/// Limits applied to the clients of the public APIs, `None` meaning unlimited
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClientLimits {
+    /// Maximum number of certificates submitted per source subnet and per second
+    pub submissions_per_second: Option<u32>,
+    /// Maximum number of concurrent streams opened from the same peer address
+    pub max_streams_per_peer: Option<usize>,
+    /// Maximum number of pending certificates per source subnet, including the ones awaiting
+    /// their precedence
+    pub max_pending_certificates: Option<u64>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum LimitError {
+    #[error("The source subnet {0} can't submit more than {1} certificates per second")]
+    SubmissionRate(SubnetId, u32),
+
+    #[error("The source subnet {0} can't have more than {1} pending certificates")]
+    PendingCertificates(SubnetId, u64),
+
+    #[error("Unable to count the pending certificates of the source subnet {0}")]
+    PendingCount(SubnetId),
+
+    #[error("The peer {0} can't open more than {1} concurrent streams")]
+    Streams(IpAddr, usize),
+}
+
+impl LimitError {
+    fn label(&self) -> &'static str {
+        match self {
+            LimitError::SubmissionRate(..) => "submission_rate",
+            LimitError::PendingCertificates(..) | LimitError::PendingCount(..) => {
+                "pending_certificates"
+            }
+            LimitError::Streams(..) => "streams",
+        }
+    }
+}
+
+struct SubmissionWindow {
+    started_at: Instant,
+    count: u64,
+}
+
+struct PendingCount {
+    /// Pending certificates of the subnet counted from the store, increased by the submissions
+    /// inserted in the pools since
+    stored: u64,
+    /// Submissions whose outcome isn't known yet
+    reserved: u64,
+    counted_at: Instant,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Limiter {
+    limits: ClientLimits,
+    store: Option<Arc<ValidatorStore>>,
+    submissions: Arc<Mutex<HashMap<SubnetId, SubmissionWindow>>>,
+    pending: Arc<Mutex<HashMap<SubnetId, PendingCount>>>,
+    streams: Arc<Mutex<HashMap<IpAddr, usize>>>,
+}
+
+impl Limiter {
+    pub(crate) fn new(limits: ClientLimits, store: Option<Arc<ValidatorStore>>) -> Self {
+        Self {
+            limits,
+            store,
+            ..Default::default()
+        }
+    }
+
+    /// Check that the certificates can be submitted, counting them in the submission rate and
+    /// the pending certificates of their source subnet if they can.
+    ///
+    /// The returned permit is to be settled with the outcome of the submissions: the
+    /// certificates failing their validation aren't counted in the submission rate, and only
+    /// the ones inserted in the pools keep being counted as pending.
+    pub(crate) fn check_submissions<'a>(
+        &self,
+        certificates: impl IntoIterator<Item = &'a Certificate>,
+    ) -> Result<SubmissionPermit, LimitError> {
+        let subnets: Vec<SubnetId> = certificates
+            .into_iter()
+            .map(|certificate| certificate.source_subnet_id)
+            .collect();
+
+        let mut submitted: HashMap<SubnetId, u64> = HashMap::new();
+        for subnet_id in &subnets {
+            *submitted.entry(*subnet_id).or_default() += 1;
+        }
+
+        let now = Instant::now();
+        // Both are checked and counted at once so that concurrent submissions can't exceed
+        // the limits
+        let mut pending = self.pending.lock().unwrap();
+        let mut windows = self.submissions.lock().unwrap();
+
+        let reserve_pending = self
+            .check_pending_certificates(&mut pending, &submitted, now)
+            .map_err(Self::limited)?;
+        self.check_submission_rate(&mut windows, &submitted, now)
+            .map_err(Self::limited)?;
+
+        for (subnet_id, count) in &submitted {
+            if reserve_pending {
+                if let Some(pending) = pending.get_mut(subnet_id) {
+                    pending.reserved += count;
+                }
+            }
+
+            if self.limits.submissions_per_second.is_some() {
+                let window = windows
+                    .entry(*subnet_id)
+                    .or_insert_with(|| SubmissionWindow {
+                        started_at: now,
+                        count: 0,
+                    });
+
+                if now.duration_since(window.started_at) >= SUBMISSION_WINDOW {
+                    window.started_at = now;
+                    window.count = 0;
+                }
+
+                window.count += count;
+            }
+        }
+
+        Ok(SubmissionPermit {
+            limiter: self.clone(),
+            charged_at: now,
+            subnets,
+            reserved_pending: reserve_pending,
+            settled: false,
+        })
+    }
+
+    /// Register a new stream opened by the peer, the returned permit releasing it once dropped
+    pub(crate) fn acquire_stream(
+        &self,
+        peer: Option<SocketAddr>,
+    ) -> Result<StreamPermit, LimitError> {
+        let (Some(max_streams), Some(peer)) = (self.limits.max_streams_per_peer, peer) else {
+            return Ok(StreamPermit {
+                streams: self.streams.clone(),
+                peer: None,
+            });
+        };
+
+        let peer = peer.ip();
+        let mut streams = self.streams.lock().unwrap();
+        let count = streams.entry(peer).or_default();
+        if *count >= max_streams {
+            return Err(Self::limited(LimitError::Streams(peer, max_streams)));
+        }
+
+        *count += 1;
+
+        Ok(StreamPermit {
+            streams: self.streams.clone(),
+            peer: Some(peer),
+        })
+    }
+
+    /// Check the pending certificates of the subnets, counting them again from the store when
+    /// their count is outdated. Returns whether the submissions are to be reserved.
+    fn check_pending_certificates(
+        &self,
+        pending: &mut HashMap<SubnetId, PendingCount>,
+        submitted: &HashMap<SubnetId, u64>,
+        now: Instant,
+    ) -> Result<bool, LimitError> {
+        let (Some(max_pending), Some(store)) = (self.limits.max_pending_certificates, &self.store)
+        else {
+            return Ok(false);
+        };
+
+        if pending.len() > MAX_TRACKED_SUBNETS {
+            pending.retain(|_, count| {
+                count.reserved > 0 || now.duration_since(count.counted_at) < PENDING_COUNT_VALIDITY
+            });
+        }
+
+        let outdated: Vec<SubnetId> = submitted
+            .keys()
+            .filter(|subnet_id| {
+                pending.get(subnet_id).map_or(true, |count| {
+                    now.duration_since(count.counted_at) >= PENDING_COUNT_VALIDITY
+                })
+            })
+            .copied()
+            .collect();
+
+        if !outdated.is_empty() {
+            let counts = store
+                .get_pending_certificates_for_subnets(&outdated)
+                .map_err(|error| {
+                    warn!("Unable to count the pending certificates of {outdated:?}: {error}");
+
+                    LimitError::PendingCount(outdated[0])
+                })?;
+
+            for subnet_id in outdated {
+                let stored = counts.get(&subnet_id).map_or(0, |(count, _)| *count);
+                let count = pending.entry(subnet_id).or_insert(PendingCount {
+                    stored,
+                    reserved: 0,
+                    counted_at: now,
+                });
+
+                count.stored = stored;
+                count.counted_at = now;
+            }
+        }
+
+        for (subnet_id, count) in submitted {
+            let pending_count = pending
+                .get(subnet_id)
+                .map_or(0, |pending| pending.stored + pending.reserved);
+
+            if pending_count + count > max_pending {
+                return Err(LimitError::PendingCertificates(*subnet_id, max_pending));
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn check_submission_rate(
+        &self,
+        windows: &mut HashMap<SubnetId, SubmissionWindow>,
+        submitted: &HashMap<SubnetId, u64>,
+        now: Instant,
+    ) -> Result<(), LimitError> {
+        let Some(max_submissions) = self.limits.submissions_per_second else {
+            return Ok(());
+        };
+
+        if windows.len() > MAX_TRACKED_SUBNETS {
+            windows.retain(|_, window| now.duration_since(window.started_at) < SUBMISSION_WINDOW);
+        }
+
+        for (subnet_id, count) in submitted {
+            let submissions = windows
+                .get(subnet_id)
+                .filter(|window| now.duration_since(window.started_at) < SUBMISSION_WINDOW)
+                .map_or(0, |window| window.count);
+
+            if submissions + count > u64::from(max_submissions) {
+                return Err(LimitError::SubmissionRate(*subnet_id, max_submissions));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release the submissions of a permit: the `inserted` ones are counted as pending and the
+    /// `invalid` ones are removed from the submission rate
+    fn release_submissions(
+        &self,
+        permit: &SubmissionPermit,
+        inserted: &HashMap<SubnetId, u64>,
+        invalid: &HashMap<SubnetId, u64>,
+    ) {
+        let mut pending = self.pending.lock().unwrap();
+        let mut windows = self.submissions.lock().unwrap();
+
+        if permit.reserved_pending {
+            for subnet_id in &permit.subnets {
+                if let Some(count) = pending.get_mut(subnet_id) {
+                    count.reserved = count.reserved.saturating_sub(1);
+                }
+            }
+
+            for (subnet_id, inserted) in inserted {
+                if let Some(count) = pending.get_mut(subnet_id) {
+                    count.stored += inserted;
+                }
+            }
+        }
+
+        for (subnet_id, invalid) in invalid {
+            // A new window started since the submission doesn't count it
+            if let Some(window) = windows
+                .get_mut(subnet_id)
+                .filter(|window| window.started_at <= permit.charged_at)
+            {
+                window.count = window.count.saturating_sub(*invalid);
+            }
+        }
+    }
+
+    fn limited(error: LimitError) -> LimitError {
+        API_LIMITED_REQUESTS_TOTAL
+            .with_label_values(&[error.label()])
+            .inc();
+
+        error
+    }
+}
+
+/// Submissions counted by the [`Limiter`], to be settled with their outcome. The submissions
+/// of a permit dropped without being settled are considered as failed.
+pub(crate) struct SubmissionPermit {
+    limiter: Limiter,
+    charged_at: Instant,
+    /// Source subnet of every submitted certificate, in order
+    subnets: Vec<SubnetId>,
+    reserved_pending: bool,
+    settled: bool,
+}
+
+impl SubmissionPermit {
+    /// Settle the submissions with the outcome of each certificate, in order
+    pub(crate) fn settle<'a>(
+        mut self,
+        results: impl IntoIterator<Item = &'a Result<PendingResult, RuntimeError>>,
+    ) {
+        let mut inserted: HashMap<SubnetId, u64> = HashMap::new();
+        let mut invalid: HashMap<SubnetId, u64> = HashMap::new();
+        let mut results = results.into_iter();
+
+        for subnet_id in &self.subnets {
+            match results.next() {
+                Some(Ok(PendingResult::InPending(_) | PendingResult::AwaitPrecedence)) => {
+                    *inserted.entry(*subnet_id).or_default() += 1;
+                }
+                Some(Ok(PendingResult::AlreadyPending | PendingResult::AlreadyDelivered)) => {}
+                // Not validated, or not submitted at all
+                Some(Err(_)) | None => *invalid.entry(*subnet_id).or_default() += 1,
+            }
+        }
+
+        self.limiter.release_submissions(&self, &inserted, &invalid);
+        self.settled = true;
+    }
+}
+
+impl Drop for SubmissionPermit {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+
+        let mut invalid: HashMap<SubnetId, u64> = HashMap::new();
+        for subnet_id in &self.subnets {
+            *invalid.entry(*subnet_id).or_default() += 1;
+        }
+
+        self.limiter
+            .release_submissions(self, &HashMap::new(), &invalid);
+    }
+}
+
+/// Stream registered by the [`Limiter`] for a peer, released once dropped
+pub(crate) struct StreamPermit {
+    streams: Arc<Mutex<HashMap<IpAddr, usize>>>,
+    peer: Option<IpAddr>,
+}
+
+impl Drop for StreamPermit {
+    fn drop(&mut self) {
+        let Some(peer) = self.peer else {
+            return;
+        };
+
+        let mut streams = self.streams.lock().unwrap();
+        if let Some(count) = streams.get_mut(&peer) {
+            *count -= 1;
+
+            if *count == 0 {
+                streams.remove(&peer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+    use topos_core::uci::ValidationError;
+    use topos_test_sdk::certificates::create_certificate_chain;
+    use topos_test_sdk::constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1};
+    use topos_test_sdk::storage::{create_fullnode_store, create_validator_store};
+
+    #[test]
+    fn submissions_are_limited_per_source_subnet() {
+        let limiter = Limiter::new(
+            ClientLimits {
+                submissions_per_second: Some(2),
+                ..Default::default()
+            },
+            None,
+        );
+
+        let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+        let other = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 1);
+
+        assert_eq!(
+            limiter
+                .check_submissions(certificates.iter().map(|c| &c.certificate))
+                .err(),
+            Some(LimitError::SubmissionRate(SOURCE_SUBNET_ID_1, 2))
+        );
+        limiter
+            .check_submissions(certificates[..2].iter().map(|c| &c.certificate))
+            .unwrap()
+            .settle(&[
+                Ok(PendingResult::InPending(0)),
+                Ok(PendingResult::InPending(1)),
+            ]);
+        assert_eq!(
+            limiter
+                .check_submissions([&certificates[2].certificate])
+                .err(),
+            Some(LimitError::SubmissionRate(SOURCE_SUBNET_ID_1, 2))
+        );
+        assert!(limiter.check_submissions([&other[0].certificate]).is_ok());
+    }
+
+    #[test]
+    fn invalid_submissions_are_not_counted() {
+        let limiter = Limiter::new(
+            ClientLimits {
+                submissions_per_second: Some(2),
+                ..Default::default()
+            },
+            None,
+        );
+
+        let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+        let certificates: Vec<&Certificate> = certificates.iter().map(|c| &c.certificate).collect();
+
+        // Forged certificates of the subnet don't consume its submission rate
+        limiter
+            .check_submissions(certificates.iter().copied())
+            .unwrap()
+            .settle(&[
+                Err(RuntimeError::InvalidCertificate(
+                    ValidationError::SelfTarget,
+                )),
+                Err(RuntimeError::InvalidCertificate(
+                    ValidationError::SelfTarget,
+                )),
+            ]);
+
+        // Neither do the submissions which never got an outcome
+        drop(
+            limiter
+                .check_submissions(certificates.iter().copied())
+                .unwrap(),
+        );
+
+        let permit = limiter
+            .check_submissions(certificates.iter().copied())
+            .unwrap();
+
+        // Counted until their outcome is known
+        assert_eq!(
+            limiter.check_submissions([certificates[0]]).err(),
+            Some(LimitError::SubmissionRate(SOURCE_SUBNET_ID_1, 2))
+        );
+
+        permit.settle(&[
+            Ok(PendingResult::AlreadyPending),
+            Err(RuntimeError::InvalidCertificate(
+                ValidationError::SelfTarget,
+            )),
+        ]);
+
+        limiter
+            .check_submissions([certificates[0]])
+            .unwrap()
+            .settle(&[Ok(PendingResult::InPending(0))]);
+        assert_eq!(
+            limiter.check_submissions([certificates[0]]).err(),
+            Some(LimitError::SubmissionRate(SOURCE_SUBNET_ID_1, 2))
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn pending_certificates_are_limited_per_source_subnet() {
+        let store = create_validator_store(&[], create_fullnode_store(&[])).await;
+        let limiter = Limiter::new(
+            ClientLimits {
+                max_pending_certificates: Some(2),
+                ..Default::default()
+            },
+            Some(store),
+        );
+
+        let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+        let certificates: Vec<&Certificate> = certificates.iter().map(|c| &c.certificate).collect();
+
+        // The submissions in progress are counted, so that concurrent ones can't exceed the limit
+        let permit = limiter
+            .check_submissions(certificates[..2].iter().copied())
+            .unwrap();
+        assert_eq!(
+            limiter.check_submissions([certificates[2]]).err(),
+            Some(LimitError::PendingCertificates(SOURCE_SUBNET_ID_1, 2))
+        );
+
+        // Only the certificates inserted in the pools keep being counted
+        permit.settle(&[
+            Ok(PendingResult::InPending(0)),
+            Ok(PendingResult::AlreadyPending),
+        ]);
+
+        let permit = limiter.check_submissions([certificates[2]]).unwrap();
+        assert_eq!(
+            limiter.check_submissions([certificates[1]]).err(),
+            Some(LimitError::PendingCertificates(SOURCE_SUBNET_ID_1, 2))
+        );
+
+        drop(permit);
+
+        assert!(limiter.check_submissions([certificates[2]]).is_ok());
+    }
+
+    #[test]
+    fn streams_are_limited_per_peer_address() {
+        let limiter = Limiter::new(
+            ClientLimits {
+                max_streams_per_peer: Some(1),
+                ..Default::default()
+            },
+            None,
+        );
+
+        let peer: SocketAddr = "127.0.0.1:1000".parse().unwrap();
+        let permit = limiter.acquire_stream(Some(peer)).unwrap();
+
+        assert!(matches!(
+            limiter.acquire_stream(Some("127.0.0.1:1001".parse().unwrap())),
+            Err(LimitError::Streams(_, 1))
+        ));
+        assert!(limiter
+            .acquire_stream(Some("127.0.0.2:1000".parse().unwrap()))
+            .is_ok());
+        assert!(limiter.acquire_stream(None).is_ok());
+
+        drop(permit);
+
+        assert!(limiter.acquire_stream(Some(peer)).is_ok());
+    }
+}
@@ -0,0 +1,94 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract::connect_info::IntoMakeServiceWithConnectInfo,
+    routing::{get, post},
+    Router, Server,
+};
+use http::{header, Method};
+use tokio::sync::mpsc;
+use topos_tce_storage::StorageClient;
+use tower_http::cors::{Any, CorsLayer};
+
+use crate::limits::Limiter;
+use crate::runtime::InternalRuntimeCommand;
+
+use super::handlers::{
+    get_certificate, get_source_head, get_target_stream, openapi_document, submit_certificate,
+    watch_delivered_certificates, RestState, GET_CERTIFICATE, GET_SOURCE_HEAD, GET_TARGET_STREAM,
+    SUBMIT_CERTIFICATE, WATCH_DELIVERED_CERTIFICATES,
+};
+
+#[derive(Default)]
+pub struct ServerBuilder {
+    storage: Option<StorageClient>,
+    serve_addr: Option<SocketAddr>,
+    runtime: Option<mpsc::Sender<InternalRuntimeCommand>>,
+    limiter: Limiter,
+}
+
+impl ServerBuilder {
+    /// Sets the runtime command channel, used to submit certificates and open transient streams
+    pub(crate) fn runtime(mut self, runtime: mpsc::Sender<InternalRuntimeCommand>) -> Self {
+        self.runtime = Some(runtime);
+
+        self
+    }
+
+    /// Sets the limiter of the submissions and streams of the clients
+    pub(crate) fn limiter(mut self, limiter: Limiter) -> Self {
+        self.limiter = limiter;
+
+        self
+    }
+
+    pub(crate) fn storage(mut self, storage: StorageClient) -> Self {
+        self.storage = Some(storage);
+
+        self
+    }
+
+    pub(crate) fn serve_addr(mut self, addr: Option<SocketAddr>) -> Self {
+        self.serve_addr = addr;
+
+        self
+    }
+
+    pub fn build(
+        mut self,
+    ) -> Server<hyper::server::conn::AddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>
+    {
+        let cors = CorsLayer::new()
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([header::CONTENT_TYPE])
+            .allow_origin(Any);
+
+        let state = RestState {
+            storage: self
+                .storage
+                .take()
+                .expect("Cannot build REST server without a storage client"),
+            runtime: self
+                .runtime
+                .take()
+                .expect("Cannot build REST server without the internal runtime channel"),
+            limiter: self.limiter,
+        };
+
+        let app = Router::new()
+            .route(GET_CERTIFICATE.path, get(get_certificate))
+            .route(GET_SOURCE_HEAD.path, get(get_source_head))
+            .route(GET_TARGET_STREAM.path, get(get_target_stream))
+            .route(SUBMIT_CERTIFICATE.path, post(submit_certificate))
+            .route(
+                WATCH_DELIVERED_CERTIFICATES.path,
+                get(watch_delivered_certificates),
+            )
+            .route("/openapi.json", get(openapi_document))
+            .with_state(state)
+            .layer(cors);
+
+        let serve_addr = self.serve_addr.take().expect("Server address is not set");
+        Server::bind(&serve_addr).serve(app.into_make_service_with_connect_info::<SocketAddr>())
+    }
+}
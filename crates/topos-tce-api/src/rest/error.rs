@@ -0,0 +1,35 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub(crate) enum RestError {
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Gone(String),
+
+    #[error("{0}")]
+    TooManyRequests(String),
+
+    #[error("Internal error: {0}")]
+    Internal(&'static str),
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> axum::response::Response {
+        let status = match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Gone(_) => StatusCode::GONE,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
@@ -0,0 +1,392 @@
+//! Handlers of the REST API, each of them described by an [`Operation`] from which the
+//! OpenAPI document is generated.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use async_graphql::{InputType, OutputType, SimpleObject};
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{mpsc, oneshot};
+use topos_core::api::graphql::certificate::{
+    Certificate, CertificateInput, CertificateSubmission, UndeliveredCertificate,
+};
+use topos_core::api::graphql::errors::GraphQLServerError;
+use topos_core::types::stream::{CertificateTargetStreamPosition, Position};
+use topos_core::uci::{CertificateId, SubnetId};
+use topos_tce_storage::{
+    errors::{InternalStorageError, StorageError},
+    FetchCertificatesFilter, FetchCertificatesPosition, StorageClient,
+};
+use tracing::error;
+
+use crate::limits::Limiter;
+use crate::runtime::InternalRuntimeCommand;
+use crate::submission::certificate_submission;
+
+use super::error::RestError;
+use super::openapi::{self, Operation, QueryParameter};
+
+/// Number of certificates returned when listing a stream without limit
+const DEFAULT_LIMIT: usize = 10;
+/// Maximum number of certificates returned when listing a stream
+const MAX_LIMIT: usize = 100;
+
+#[derive(Clone)]
+pub(crate) struct RestState {
+    pub(crate) storage: StorageClient,
+    pub(crate) runtime: mpsc::Sender<InternalRuntimeCommand>,
+    pub(crate) limiter: Limiter,
+}
+
+fn parse_certificate_id(value: &str) -> Result<CertificateId, RestError> {
+    CertificateId::try_from(value.as_bytes())
+        .map_err(|_| RestError::BadRequest(format!("Invalid certificate id {value}")))
+}
+
+fn parse_subnet_id(value: &str) -> Result<SubnetId, RestError> {
+    SubnetId::from_str(value)
+        .map_err(|_| RestError::BadRequest(format!("Invalid subnet id {value}")))
+}
+
+/// A certificate whose proof has been pruned is gone, any other storage error being internal
+fn storage_error(error: StorageError, message: &'static str) -> RestError {
+    match error {
+        StorageError::InternalStorage(InternalStorageError::ProofPruned(certificate_id)) => {
+            RestError::Gone(format!(
+                "The proof of the certificate {certificate_id} has been pruned"
+            ))
+        }
+        _ => RestError::Internal(message),
+    }
+}
+
+pub(crate) const GET_CERTIFICATE: Operation = Operation {
+    method: "get",
+    path: "/v1/certificates/:certificate_id",
+    summary: "Get a delivered certificate by its id",
+    query: &[],
+    request_body: None,
+    content_type: "application/json",
+    responses: &[
+        (
+            200,
+            "The delivered certificate",
+            Some(<Certificate as OutputType>::create_type_info),
+        ),
+        (400, "Invalid certificate id", None),
+        (404, "The certificate is not delivered", None),
+        (410, "The proof of the certificate has been pruned", None),
+    ],
+};
+
+pub(crate) async fn get_certificate(
+    State(state): State<RestState>,
+    Path(certificate_id): Path<String>,
+) -> Result<Json<Certificate>, RestError> {
+    let certificate_id = parse_certificate_id(&certificate_id)?;
+
+    let certificate = state
+        .storage
+        .get_certificate(certificate_id)
+        .await
+        .map_err(|error| {
+            error!("Unable to get the certificate {certificate_id}: {error}");
+
+            storage_error(error, "Unable to get the certificate")
+        })?
+        .ok_or_else(|| {
+            RestError::NotFound(format!("The certificate {certificate_id} is not delivered"))
+        })?;
+
+    Ok(Json((&certificate).into()))
+}
+
+#[derive(Serialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SourceHead {
+    position: u64,
+    certificate: UndeliveredCertificate,
+}
+
+pub(crate) const GET_SOURCE_HEAD: Operation = Operation {
+    method: "get",
+    path: "/v1/subnets/:subnet_id/source-head",
+    summary: "Get the latest delivered certificate of a source subnet",
+    query: &[],
+    request_body: None,
+    content_type: "application/json",
+    responses: &[
+        (
+            200,
+            "The position and the latest delivered certificate of the source stream",
+            Some(<SourceHead as OutputType>::create_type_info),
+        ),
+        (400, "Invalid subnet id", None),
+        (404, "No certificate of the subnet is delivered", None),
+    ],
+};
+
+pub(crate) async fn get_source_head(
+    State(state): State<RestState>,
+    Path(subnet_id): Path<String>,
+) -> Result<Json<SourceHead>, RestError> {
+    let subnet_id = parse_subnet_id(&subnet_id)?;
+
+    let (position, certificate) = state
+        .storage
+        .get_source_head(subnet_id)
+        .await
+        .map_err(|error| {
+            error!("Unable to get the source head of {subnet_id}: {error}");
+
+            RestError::Internal("Unable to get the source head")
+        })?
+        .ok_or_else(|| {
+            RestError::NotFound(format!(
+                "No certificate of the subnet {subnet_id} is delivered"
+            ))
+        })?;
+
+    Ok(Json(SourceHead {
+        position,
+        certificate: (&certificate).into(),
+    }))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct StreamQuery {
+    from: Option<u64>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreamCertificate {
+    position: u64,
+    certificate: Certificate,
+}
+
+pub(crate) const GET_TARGET_STREAM: Operation = Operation {
+    method: "get",
+    path: "/v1/subnets/:target_subnet_id/target-streams/:source_subnet_id",
+    summary: "List the certificates of a target stream from a position",
+    query: &[
+        QueryParameter {
+            name: "from",
+            description: "Position of the first certificate, 0 by default",
+        },
+        QueryParameter {
+            name: "limit",
+            description: "Maximum number of certificates, 10 by default and at most 100",
+        },
+    ],
+    request_body: None,
+    content_type: "application/json",
+    responses: &[
+        (
+            200,
+            "The certificates of the stream along with their position",
+            Some(<Vec<StreamCertificate> as OutputType>::create_type_info),
+        ),
+        (400, "Invalid subnet id", None),
+        (
+            410,
+            "The proof of a certificate of the stream has been pruned",
+            None,
+        ),
+    ],
+};
+
+pub(crate) async fn get_target_stream(
+    State(state): State<RestState>,
+    Path((target_subnet_id, source_subnet_id)): Path<(String, String)>,
+    Query(query): Query<StreamQuery>,
+) -> Result<Json<Vec<StreamCertificate>>, RestError> {
+    let target_subnet_id = parse_subnet_id(&target_subnet_id)?;
+    let source_subnet_id = parse_subnet_id(&source_subnet_id)?;
+
+    let certificates = state
+        .storage
+        .fetch_certificates(FetchCertificatesFilter::Target {
+            target_stream_position: CertificateTargetStreamPosition::new(
+                target_subnet_id,
+                source_subnet_id,
+                Position::from(query.from.unwrap_or_default()),
+            ),
+            limit: query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+        })
+        .await
+        .map_err(|error| {
+            error!(
+                "Unable to fetch the target stream {target_subnet_id}/{source_subnet_id}: {error}"
+            );
+
+            storage_error(error, "Unable to fetch the target stream")
+        })?;
+
+    Ok(Json(
+        certificates
+            .into_iter()
+            .filter_map(|(certificate, position)| match position {
+                FetchCertificatesPosition::Target(position) => Some(StreamCertificate {
+                    position: *position.position,
+                    certificate: (&certificate).into(),
+                }),
+                FetchCertificatesPosition::Source(_) => None,
+            })
+            .collect(),
+    ))
+}
+
+pub(crate) const SUBMIT_CERTIFICATE: Operation = Operation {
+    method: "post",
+    path: "/v1/certificates",
+    summary: "Submit a certificate",
+    query: &[],
+    request_body: Some((
+        "The certificate, its binary fields being hex encoded",
+        <CertificateInput as InputType>::create_type_info,
+    )),
+    content_type: "application/json",
+    responses: &[
+        (
+            202,
+            "The certificate has been accepted",
+            Some(<CertificateSubmission as OutputType>::create_type_info),
+        ),
+        (400, "Malformed certificate", None),
+        (
+            422,
+            "The certificate has been rejected",
+            Some(<CertificateSubmission as OutputType>::create_type_info),
+        ),
+        (429, "A limit of the source subnet has been reached", None),
+    ],
+};
+
+pub(crate) async fn submit_certificate(
+    State(state): State<RestState>,
+    Json(certificate): Json<CertificateInput>,
+) -> Result<(StatusCode, Json<CertificateSubmission>), RestError> {
+    let certificate: topos_core::uci::Certificate = (&certificate)
+        .try_into()
+        .map_err(|error: GraphQLServerError| RestError::BadRequest(error.to_string()))?;
+    let certificate_id = certificate.id;
+
+    let permit = state
+        .limiter
+        .check_submissions([&certificate])
+        .map_err(|error| RestError::TooManyRequests(error.to_string()))?;
+
+    let (sender, receiver) = oneshot::channel();
+    state
+        .runtime
+        .send(InternalRuntimeCommand::CertificateSubmitted {
+            certificate: Box::new(certificate),
+            sender,
+        })
+        .await
+        .map_err(|_| RestError::Internal("Unable to submit the certificate"))?;
+
+    let result = receiver
+        .await
+        .map_err(|_| RestError::Internal("Unable to receive the submission result"))?;
+    permit.settle([&result]);
+
+    let submission = certificate_submission(certificate_id, result)
+        .map_err(|_| RestError::Internal("Unable to submit the certificate"))?;
+
+    let status = if submission.rejection.is_some() {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::ACCEPTED
+    };
+
+    Ok((status, Json(submission)))
+}
+
+pub(crate) const WATCH_DELIVERED_CERTIFICATES: Operation = Operation {
+    method: "get",
+    path: "/v1/streams/delivered-certificates",
+    summary: "Stream the certificates delivered from now on as server-sent events",
+    query: &[],
+    request_body: None,
+    content_type: "text/event-stream",
+    responses: &[
+        (
+            200,
+            "A `certificate` event for every delivered certificate, its data being a `Certificate`",
+            None,
+        ),
+        (
+            429,
+            "Too many streams are opened from the same address",
+            None,
+        ),
+    ],
+};
+
+pub(crate) async fn watch_delivered_certificates(
+    State(state): State<RestState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, RestError> {
+    let permit = state
+        .limiter
+        .acquire_stream(Some(peer))
+        .map_err(|error| RestError::TooManyRequests(error.to_string()))?;
+
+    let (sender, receiver) = oneshot::channel();
+    state
+        .runtime
+        .send(InternalRuntimeCommand::NewTransientStream { sender })
+        .await
+        .map_err(|_| RestError::Internal("Unable to open a transient stream"))?;
+
+    let stream = receiver
+        .await
+        .map_err(|_| RestError::Internal("Unable to open a transient stream"))?
+        .map_err(|error| {
+            error!("Unable to open a transient stream: {error}");
+
+            RestError::Internal("Unable to open a transient stream")
+        })?;
+
+    Ok(Sse::new(stream.filter_map(move |certificate| {
+        let _ = &permit;
+
+        async move {
+            let certificate: Certificate = certificate.as_ref().into();
+
+            match Event::default().event("certificate").json_data(certificate) {
+                Ok(event) => Some(Ok(event)),
+                Err(error) => {
+                    error!("Unable to serialize a delivered certificate: {error}");
+
+                    None
+                }
+            }
+        }
+    }))
+    .keep_alive(KeepAlive::default()))
+}
+
+pub(crate) const OPERATIONS: &[&Operation] = &[
+    &GET_CERTIFICATE,
+    &GET_SOURCE_HEAD,
+    &GET_TARGET_STREAM,
+    &SUBMIT_CERTIFICATE,
+    &WATCH_DELIVERED_CERTIFICATES,
+];
+
+pub(crate) async fn openapi_document() -> Json<Value> {
+    Json(openapi::document(OPERATIONS))
+}
@@ -0,0 +1,4 @@
+pub mod builder;
+mod error;
+mod handlers;
+mod openapi;
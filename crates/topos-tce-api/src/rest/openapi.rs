@@ -0,0 +1,276 @@
+//! Generation of the OpenAPI document describing the REST API from the [`Operation`] of
+//! each handler.
+//!
+//! The bodies of the REST API are the serialization of the types of the GraphQL API, their
+//! schemas are generated from the GraphQL registry in which the handlers register them.
+
+use async_graphql::registry::{MetaType, Registry};
+use serde_json::{json, Map, Value};
+
+/// Register the type of a body in the GraphQL registry, returning its GraphQL type name,
+/// e.g. `<Certificate as OutputType>::create_type_info`
+pub(crate) type BodyType = fn(&mut Registry) -> String;
+
+/// Parameter of an operation passed in the query string
+pub(crate) struct QueryParameter {
+    pub(crate) name: &'static str,
+    pub(crate) description: &'static str,
+}
+
+/// Description of a handler of the REST API
+pub(crate) struct Operation {
+    /// HTTP method, in lower case
+    pub(crate) method: &'static str,
+    /// Route of the handler using the axum syntax, e.g. `/v1/certificates/:certificate_id`
+    pub(crate) path: &'static str,
+    pub(crate) summary: &'static str,
+    pub(crate) query: &'static [QueryParameter],
+    /// Description and type of the JSON body of the request, if any
+    pub(crate) request_body: Option<(&'static str, BodyType)>,
+    /// Content type of the successful responses
+    pub(crate) content_type: &'static str,
+    /// Status, description and body type of the responses. The error responses without body
+    /// type carry an [`ERROR_SCHEMA`] message.
+    pub(crate) responses: &'static [(u16, &'static str, Option<BodyType>)],
+}
+
+/// Name of the schema of the body of the error responses
+const ERROR_SCHEMA: &str = "Error";
+
+impl Operation {
+    /// Route of the handler using the OpenAPI syntax, e.g. `/v1/certificates/{certificate_id}`
+    fn openapi_path(&self) -> String {
+        self.path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{name}}}"),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn to_openapi(&self, registry: &mut Registry) -> Value {
+        let path_parameters = self
+            .path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            });
+        let query_parameters = self.query.iter().map(|parameter| {
+            json!({
+                "name": parameter.name,
+                "in": "query",
+                "required": false,
+                "description": parameter.description,
+                "schema": { "type": "integer", "minimum": 0 },
+            })
+        });
+
+        let responses: Map<String, Value> = self
+            .responses
+            .iter()
+            .map(|(status, description, body)| {
+                let mut response = json!({ "description": description });
+                match body {
+                    Some(body) => {
+                        let ty = body(registry);
+                        response["content"] =
+                            json!({ self.content_type: { "schema": type_schema(registry, &ty) } });
+                    }
+                    None if *status >= 400 => {
+                        response["content"] = json!({
+                            "application/json": { "schema": schema_ref(ERROR_SCHEMA) },
+                        });
+                    }
+                    None => response["content"] = json!({ self.content_type: {} }),
+                }
+
+                (status.to_string(), response)
+            })
+            .collect();
+
+        let mut operation = json!({
+            "summary": self.summary,
+            "parameters": path_parameters.chain(query_parameters).collect::<Vec<_>>(),
+            "responses": responses,
+        });
+
+        if let Some((description, body)) = self.request_body {
+            let ty = body(registry);
+            operation["requestBody"] = json!({
+                "required": true,
+                "description": description,
+                "content": { "application/json": { "schema": type_schema(registry, &ty) } },
+            });
+        }
+
+        operation
+    }
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// Convert a GraphQL type name, e.g. `[SubnetId!]!`, to a JSON schema
+fn type_schema(registry: &Registry, ty: &str) -> Value {
+    let (ty, nullable) = match ty.strip_suffix('!') {
+        Some(ty) => (ty, false),
+        None => (ty, true),
+    };
+
+    let schema = match ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+        Some(item) => json!({ "type": "array", "items": type_schema(registry, item) }),
+        None => match (ty, registry.types.get(ty)) {
+            ("Int", _) => json!({ "type": "integer" }),
+            ("Float", _) => json!({ "type": "number" }),
+            ("Boolean", _) => json!({ "type": "boolean" }),
+            (_, Some(MetaType::Scalar { .. }) | None) => json!({ "type": "string" }),
+            // A reference can't have siblings, it is wrapped to be marked as nullable
+            (_, Some(_)) if nullable => json!({ "allOf": [schema_ref(ty)] }),
+            (_, Some(_)) => schema_ref(ty),
+        },
+    };
+
+    match schema {
+        Value::Object(mut schema) if nullable => {
+            schema.insert("nullable".to_string(), true.into());
+
+            Value::Object(schema)
+        }
+        schema => schema,
+    }
+}
+
+/// Build the schema of an object from the name, description and GraphQL type of its fields
+fn object_schema<'a>(
+    registry: &Registry,
+    description: &Option<String>,
+    fields: impl Iterator<Item = (&'a String, &'a Option<String>, &'a String)>,
+) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, description, ty) in fields {
+        let mut schema = type_schema(registry, ty);
+        if let Some(description) = description {
+            schema["description"] = description.as_str().into();
+        }
+        if ty.ends_with('!') {
+            required.push(name.clone());
+        }
+
+        properties.insert(name.clone(), schema);
+    }
+
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    if let Some(description) = description {
+        schema["description"] = description.as_str().into();
+    }
+
+    schema
+}
+
+/// Build the schemas of the objects and enums of the registry
+fn component_schemas(registry: &Registry) -> Map<String, Value> {
+    let mut schemas: Map<String, Value> = registry
+        .types
+        .iter()
+        .filter_map(|(name, meta_type)| {
+            let schema = match meta_type {
+                MetaType::Object {
+                    description,
+                    fields,
+                    ..
+                } => object_schema(
+                    registry,
+                    description,
+                    fields
+                        .values()
+                        .map(|field| (&field.name, &field.description, &field.ty)),
+                ),
+                MetaType::InputObject {
+                    description,
+                    input_fields,
+                    ..
+                } => object_schema(
+                    registry,
+                    description,
+                    input_fields
+                        .values()
+                        .map(|field| (&field.name, &field.description, &field.ty)),
+                ),
+                // The enums are serialized with the name of their variants, e.g. `InPending`
+                // for the GraphQL value `IN_PENDING`
+                MetaType::Enum { enum_values, .. } => json!({
+                    "type": "string",
+                    "enum": enum_values
+                        .keys()
+                        .map(|value| variant_name(value))
+                        .collect::<Vec<_>>(),
+                }),
+                _ => return None,
+            };
+
+            Some((name.clone(), schema))
+        })
+        .collect();
+
+    schemas.insert(
+        ERROR_SCHEMA.to_string(),
+        json!({
+            "type": "object",
+            "properties": { "error": { "type": "string" } },
+            "required": ["error"],
+        }),
+    );
+
+    schemas
+}
+
+/// Convert the name of a GraphQL enum value to the name of its Rust variant
+fn variant_name(value: &str) -> String {
+    value
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .chain(chars.flat_map(char::to_lowercase))
+        })
+        .collect()
+}
+
+/// Build the OpenAPI document of the given operations
+pub(crate) fn document(operations: &[&Operation]) -> Value {
+    let mut registry = Registry::default();
+    let mut paths = Map::new();
+    for operation in operations {
+        let path = paths
+            .entry(operation.openapi_path())
+            .or_insert_with(|| json!({}));
+
+        path[operation.method] = operation.to_openapi(&mut registry);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "TCE REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": { "schemas": component_schemas(&registry) },
+    })
+}
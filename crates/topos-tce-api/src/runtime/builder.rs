@@ -12,9 +12,13 @@ use topos_tce_storage::{
 use tracing::Instrument;
 
 use crate::{
-    constants::CHANNEL_SIZE, graphql::builder::ServerBuilder as GraphQLBuilder,
-    grpc::builder::ServerBuilder, metrics::builder::ServerBuilder as MetricsBuilder, Runtime,
-    RuntimeClient, RuntimeEvent,
+    constants::CHANNEL_SIZE,
+    graphql::builder::ServerBuilder as GraphQLBuilder,
+    grpc::builder::{ConsoleServerBuilder, ServerBuilder},
+    limits::{ClientLimits, Limiter},
+    metrics::builder::ServerBuilder as MetricsBuilder,
+    rest::builder::ServerBuilder as RestBuilder,
+    Runtime, RuntimeClient, RuntimeEvent,
 };
 
 #[derive(Default)]
@@ -25,8 +29,11 @@ pub struct RuntimeBuilder {
     local_peer_id: String,
     grpc_socket_addr: Option<SocketAddr>,
     graphql_socket_addr: Option<SocketAddr>,
+    rest_socket_addr: Option<SocketAddr>,
     metrics_socket_addr: Option<SocketAddr>,
+    console_socket_addr: Option<SocketAddr>,
     status: Option<RwLock<StatusResponse>>,
+    client_limits: ClientLimits,
 }
 
 impl RuntimeBuilder {
@@ -57,12 +64,31 @@ impl RuntimeBuilder {
         self
     }
 
+    pub fn serve_rest_addr(mut self, addr: SocketAddr) -> Self {
+        self.rest_socket_addr = Some(addr);
+
+        self
+    }
+
     pub fn serve_metrics_addr(mut self, addr: SocketAddr) -> Self {
         self.metrics_socket_addr = Some(addr);
 
         self
     }
 
+    pub fn serve_console_addr(mut self, addr: SocketAddr) -> Self {
+        self.console_socket_addr = Some(addr);
+
+        self
+    }
+
+    /// Sets the limits applied to the clients of the gRPC, GraphQL and REST APIs
+    pub fn with_client_limits(mut self, limits: ClientLimits) -> Self {
+        self.client_limits = limits;
+
+        self
+    }
+
     pub fn tce_status(mut self, status: RwLock<StatusResponse>) -> Self {
         self.status = Some(status);
 
@@ -91,6 +117,8 @@ impl RuntimeBuilder {
         let (internal_runtime_command_sender, internal_runtime_command_receiver) =
             mpsc::channel(CHANNEL_SIZE);
         let (api_event_sender, api_event_receiver) = mpsc::channel(CHANNEL_SIZE);
+        let (protocol_events, _) = broadcast::channel(CHANNEL_SIZE);
+        let limiter = Limiter::new(self.client_limits, self.store.clone());
 
         let (health_reporter, tce_status, grpc) = ServerBuilder::default()
             .with_store(
@@ -100,6 +128,7 @@ impl RuntimeBuilder {
                     .expect("Unable to build gRPC Server, Store is missing"),
             )
             .with_peer_id(self.local_peer_id)
+            .with_limiter(limiter.clone())
             .command_sender(internal_runtime_command_sender.clone())
             .serve_addr(self.grpc_socket_addr)
             .build()
@@ -111,6 +140,27 @@ impl RuntimeBuilder {
 
         let grpc_handler = spawn(grpc.in_current_span());
 
+        let console_handler = if let Some(console_addr) = self.console_socket_addr {
+            tracing::info!("Serving the console on {}", console_addr);
+
+            let console = ConsoleServerBuilder::default()
+                .with_store(
+                    self.store
+                        .clone()
+                        .expect("Unable to build the console, Store is missing"),
+                )
+                .command_sender(internal_runtime_command_sender.clone())
+                .status(tce_status.clone())
+                .serve_addr(Some(console_addr))
+                .build();
+            spawn(console.in_current_span())
+        } else {
+            spawn(async move {
+                tracing::info!("Not serving the console");
+                Ok(())
+            })
+        };
+
         let graphql_handler = if let Some(graphql_addr) = self.graphql_socket_addr {
             tracing::info!("Serving GraphQL on {}", graphql_addr);
 
@@ -121,6 +171,8 @@ impl RuntimeBuilder {
                         .expect("Unable to build GraphQL Server, Store is missing"),
                 )
                 .runtime(internal_runtime_command_sender.clone())
+                .protocol_events(protocol_events.clone())
+                .limiter(limiter.clone())
                 .serve_addr(Some(graphql_addr))
                 .build()
                 .in_current_span();
@@ -132,6 +184,27 @@ impl RuntimeBuilder {
             })
         };
 
+        let rest_handler = if let Some(rest_addr) = self.rest_socket_addr {
+            tracing::info!("Serving the REST API on {}", rest_addr);
+
+            let rest = RestBuilder::default()
+                .storage(
+                    self.storage
+                        .clone()
+                        .expect("Unable to build the REST server, Storage is missing"),
+                )
+                .runtime(internal_runtime_command_sender.clone())
+                .limiter(limiter)
+                .serve_addr(Some(rest_addr))
+                .build();
+            spawn(rest.in_current_span())
+        } else {
+            spawn(async move {
+                tracing::info!("Not serving the REST API");
+                Ok(())
+            })
+        };
+
         let metrics_handler = if let Some(metrics_addr) = self.metrics_socket_addr {
             tracing::info!("Serving metrics on {}", metrics_addr);
 
@@ -160,6 +233,9 @@ impl RuntimeBuilder {
             active_streams: HashMap::new(),
             pending_streams: HashMap::new(),
             subnet_subscriptions: HashMap::new(),
+            source_subnet_subscriptions: HashMap::new(),
+            resume_positions: HashMap::new(),
+            stream_request_ids: HashMap::new(),
             internal_runtime_command_receiver,
             runtime_command_receiver,
             health_reporter,
@@ -175,11 +251,14 @@ impl RuntimeBuilder {
                 command_sender,
                 tce_status,
                 shutdown_channel,
+                protocol_events,
             },
             ReceiverStream::new(api_event_receiver),
             RuntimeContext {
                 grpc_handler,
+                console_handler,
                 graphql_handler,
+                rest_handler,
                 metrics_handler,
                 runtime_handler,
             },
@@ -196,7 +275,9 @@ impl RuntimeBuilder {
 #[derive(Debug)]
 pub struct RuntimeContext {
     grpc_handler: tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
+    console_handler: tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
     graphql_handler: tokio::task::JoinHandle<Result<(), hyper::Error>>,
+    rest_handler: tokio::task::JoinHandle<Result<(), hyper::Error>>,
     metrics_handler: tokio::task::JoinHandle<Result<(), hyper::Error>>,
     runtime_handler: tokio::task::JoinHandle<()>,
 }
@@ -205,7 +286,9 @@ impl Drop for RuntimeContext {
     fn drop(&mut self) {
         tracing::warn!("Dropping RuntimeContext");
         self.grpc_handler.abort();
+        self.console_handler.abort();
         self.graphql_handler.abort();
+        self.rest_handler.abort();
         self.metrics_handler.abort();
         self.runtime_handler.abort();
     }
@@ -3,11 +3,12 @@ use std::sync::Arc;
 
 use super::RuntimeCommand;
 use futures::Future;
-use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
 use topos_core::api::grpc::tce::v1::StatusResponse;
 use topos_core::types::CertificateDelivered;
 use topos_core::uci::SubnetId;
+use topos_tce_broadcast::event::ProtocolEvents;
 use tracing::error;
 
 #[derive(Clone, Debug)]
@@ -15,6 +16,7 @@ pub struct RuntimeClient {
     pub(crate) command_sender: mpsc::Sender<RuntimeCommand>,
     pub(crate) tce_status: Arc<RwLock<StatusResponse>>,
     pub(crate) shutdown_channel: mpsc::Sender<oneshot::Sender<()>>,
+    pub(crate) protocol_events: broadcast::Sender<ProtocolEvents>,
 }
 
 impl RuntimeClient {
@@ -38,6 +40,13 @@ impl RuntimeClient {
         }
     }
 
+    /// Forward an event of the reliable broadcast to the API subscribers, if any
+    pub fn dispatch_protocol_event(&self, event: &ProtocolEvents) {
+        if self.protocol_events.receiver_count() > 0 {
+            _ = self.protocol_events.send(event.clone());
+        }
+    }
+
     pub async fn has_active_sample(&self) -> bool {
         self.tce_status.read().await.has_active_sample
     }
@@ -48,6 +57,16 @@ impl RuntimeClient {
         status.has_active_sample = value;
     }
 
+    pub async fn epoch(&self) -> u64 {
+        self.tce_status.read().await.epoch
+    }
+
+    pub async fn set_epoch(&self, epoch: u64) {
+        let mut status = self.tce_status.write().await;
+
+        status.epoch = epoch;
+    }
+
     pub async fn shutdown(&self) -> Result<(), Box<dyn std::error::Error>> {
         let (sender, receiver) = oneshot::channel();
         self.shutdown_channel.send(sender).await?;
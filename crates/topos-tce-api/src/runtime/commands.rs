@@ -1,14 +1,17 @@
 use std::collections::HashMap;
 use tokio::sync::{mpsc::Sender, oneshot};
-use topos_core::api::grpc::checkpoints::TargetStreamPosition;
+use topos_core::api::grpc::checkpoints::{SourceStreamPosition, TargetStreamPosition};
 use topos_core::types::CertificateDelivered;
-use topos_core::uci::{Certificate, SubnetId};
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
+use topos_p2p::PeerInfo;
+use topos_tce_broadcast::BroadcastProgress;
 use topos_tce_storage::types::PendingResult;
 use uuid::Uuid;
 
 use crate::stream::{Stream, StreamCommand, TransientStream};
 
 use super::error::RuntimeError;
+use super::events::CertificateStatus;
 
 #[derive(Debug)]
 pub enum RuntimeCommand {
@@ -31,11 +34,30 @@ pub(crate) enum InternalRuntimeCommand {
     /// Commands or certificates pointing to one of the subnet will be forward using the given Sender
     Register {
         stream_id: Uuid,
+        /// Request id of the OpenStream, used to resume from the acknowledged positions
+        request_id: Option<Uuid>,
         #[allow(dead_code)]
         target_subnet_stream_positions: HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>,
+        /// Position from which the certificates of each watched source subnet are replayed
+        source_subnet_stream_positions: HashMap<SubnetId, SourceStreamPosition>,
         sender: oneshot::Sender<Result<(), RuntimeError>>,
     },
 
+    /// Notify that the client of a stream consumed the certificate at the given position,
+    /// along with every previous certificate of the same target stream
+    Acknowledge {
+        stream_id: Uuid,
+        position: TargetStreamPosition,
+    },
+
+    /// Replay from the storage the target and source streams on which a stream fell behind,
+    /// from the given positions
+    Replay {
+        stream_id: Uuid,
+        target_subnet_stream_positions: HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>,
+        source_subnet_stream_positions: HashMap<SubnetId, SourceStreamPosition>,
+    },
+
     /// Notify that a Stream has successfully handshake with the server
     Handshaked { stream_id: Uuid },
 
@@ -46,12 +68,41 @@ pub(crate) enum InternalRuntimeCommand {
         sender: oneshot::Sender<Result<PendingResult, RuntimeError>>,
     },
 
+    /// Dispatch when a chain of certificates has been submitted to the TCE.
+    /// The certificates are inserted in order, each of them getting its own result.
+    CertificatesSubmitted {
+        certificates: Vec<Certificate>,
+        sender: oneshot::Sender<Vec<Result<PendingResult, RuntimeError>>>,
+    },
+
     /// Get source head certificate by source subnet id
     GetSourceHead {
         subnet_id: SubnetId,
         sender: oneshot::Sender<Result<Option<(u64, Certificate)>, RuntimeError>>,
     },
 
+    /// Get where a certificate currently stands in the node
+    GetCertificateStatus {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Result<Option<CertificateStatus>, RuntimeError>>,
+    },
+
+    /// Get the connected peers along with their connections
+    GetPeers {
+        sender: oneshot::Sender<Result<Vec<PeerInfo>, RuntimeError>>,
+    },
+
+    /// Get the progress of the certificates currently being broadcast
+    GetBroadcastProgress {
+        sender: oneshot::Sender<Result<Vec<BroadcastProgress>, RuntimeError>>,
+    },
+
+    /// Remove a certificate from the pending pool and stop its broadcast
+    EvictPendingCertificate {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Result<(), RuntimeError>>,
+    },
+
     /// Ask for the creation of a new TransientStream
     NewTransientStream {
         sender: oneshot::Sender<Result<TransientStream, RuntimeError>>,
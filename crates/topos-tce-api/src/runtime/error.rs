@@ -1,5 +1,5 @@
 use thiserror::Error;
-use topos_core::uci::SubnetId;
+use topos_core::uci::{CertificateId, SubnetId, ValidationError};
 use topos_tce_storage::errors::StorageError;
 use uuid::Uuid;
 
@@ -19,4 +19,10 @@ pub enum RuntimeError {
 
     #[error("Communication error: {0}")]
     CommunicationError(String),
+
+    #[error("The certificate {0} isn't pending")]
+    CertificateNotPending(CertificateId),
+
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(#[from] ValidationError),
 }
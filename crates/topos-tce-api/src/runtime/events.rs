@@ -1,22 +1,68 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use tokio::sync::oneshot;
-use topos_core::uci::{Certificate, SubnetId};
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
+use topos_p2p::PeerInfo;
+use topos_tce_broadcast::BroadcastProgress;
 use topos_tce_storage::types::PendingResult;
+use topos_tce_storage::{CertificatePositions, PendingCertificateId};
 
 use super::error::RuntimeError;
 
+/// Where a certificate currently stands in the node
+#[derive(Debug, Clone)]
+pub enum CertificateStatus {
+    /// The certificate is in the pending pool, waiting to be broadcast
+    Pending { pending_id: PendingCertificateId },
+
+    /// The certificate is in the precedence pool, waiting for its previous certificate
+    /// to be delivered
+    AwaitingPrecedence { prev_id: CertificateId },
+
+    /// The certificate is being broadcast
+    InBroadcast {
+        echo_count: usize,
+        ready_count: usize,
+    },
+
+    /// The certificate has been delivered at the given positions
+    Delivered(CertificatePositions),
+}
+
 pub enum RuntimeEvent {
     CertificateSubmitted {
         certificate: Box<Certificate>,
         sender: oneshot::Sender<Result<PendingResult, RuntimeError>>,
     },
 
+    CertificatesSubmitted {
+        certificates: Vec<Certificate>,
+        sender: oneshot::Sender<Vec<Result<PendingResult, RuntimeError>>>,
+    },
+
     GetSourceHead {
         subnet_id: SubnetId,
         sender: oneshot::Sender<Result<Option<(u64, Certificate)>, RuntimeError>>,
     },
 
+    GetCertificateStatus {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Result<Option<CertificateStatus>, RuntimeError>>,
+    },
+
+    GetPeers {
+        sender: oneshot::Sender<Result<Vec<PeerInfo>, RuntimeError>>,
+    },
+
+    GetBroadcastProgress {
+        sender: oneshot::Sender<Result<Vec<BroadcastProgress>, RuntimeError>>,
+    },
+
+    EvictPendingCertificate {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Result<(), RuntimeError>>,
+    },
+
     GetLastPendingCertificates {
         subnet_ids: HashSet<SubnetId>,
         #[allow(clippy::type_complexity)]
@@ -1,10 +1,10 @@
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt, TryFutureExt};
 use std::future::{Future, IntoFuture};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet},
     pin::Pin,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::mpsc::{self, Receiver, Sender},
@@ -18,11 +18,11 @@ use topos_core::types::CertificateDelivered;
 use topos_core::uci::SubnetId;
 use topos_tce_storage::{types::CertificateDeliveredWithPositions, StorageClient};
 
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::{
-    constants::TRANSIENT_STREAM_CHANNEL_SIZE,
+    constants::{RESUME_POSITIONS_TTL, TRANSIENT_STREAM_CHANNEL_SIZE},
     grpc::TceGrpcService,
     stream::{StreamCommand, StreamError, StreamErrorKind, TransientStream},
 };
@@ -44,13 +44,26 @@ use self::builder::RuntimeBuilder;
 pub(crate) use self::commands::InternalRuntimeCommand;
 
 pub use self::commands::RuntimeCommand;
-pub use self::events::RuntimeEvent;
+pub use self::events::{CertificateStatus, RuntimeEvent};
 
-use crate::runtime::sync_task::{RunningTasks, SyncTask};
+use crate::runtime::sync_task::{
+    ReplayTask, RunningTasks, SyncTask, SyncTaskStatus, TargetSubnetStreamPositions,
+};
 
 pub(crate) type Streams =
     FuturesUnordered<Pin<Box<dyn Future<Output = Result<Uuid, StreamError>> + Send>>>;
 
+/// Positions acknowledged by the client of a stream opened with a request id, from which a
+/// new stream opened with the same request id resumes
+pub(crate) struct ResumePositions {
+    /// Stream acknowledging the positions, the latest one opened with the request id
+    pub(crate) stream_id: Uuid,
+    pub(crate) positions: TargetSubnetStreamPositions,
+    /// Time at which the stream closed, the positions being dropped after
+    /// [`RESUME_POSITIONS_TTL`] unless a new stream resumes from them
+    pub(crate) closed_at: Option<Instant>,
+}
+
 pub struct Runtime {
     /// Map of sync tasks and their stream id, so we can cancel them when a new stream
     /// with the same id is registered
@@ -68,6 +81,13 @@ pub struct Runtime {
     pub(crate) pending_streams: HashMap<Uuid, Sender<StreamCommand>>,
     /// Mapping between a subnet_id and streams that are subscribed to it
     pub(crate) subnet_subscriptions: HashMap<SubnetId, HashSet<Uuid>>,
+    /// Mapping between a source subnet_id and streams that watch the certificates it emits
+    pub(crate) source_subnet_subscriptions: HashMap<SubnetId, HashSet<Uuid>>,
+    /// Positions from which the streams resume after a reconnection, built from their
+    /// acknowledgements and keyed by the request_id of their OpenStream
+    pub(crate) resume_positions: HashMap<Uuid, ResumePositions>,
+    /// Request id of the OpenStream of the streams, keyed by stream
+    pub(crate) stream_request_ids: HashMap<Uuid, Uuid>,
     /// Receiver for Internal API command
     pub(crate) internal_runtime_command_receiver: Receiver<InternalRuntimeCommand>,
     /// Receiver for Outside API command
@@ -97,6 +117,7 @@ impl Runtime {
 
                 _ = health_update.tick() => {
                     self.health_reporter.set_serving::<ApiServiceServer<TceGrpcService>>().await;
+                    self.expire_resume_positions();
                 }
 
                 Ok(certificate_delivered) = self.broadcast_stream.recv() => {
@@ -141,6 +162,10 @@ impl Runtime {
 
                 Some(result) = self.running_sync_tasks.next() => {
                     debug!("SyncTask with StreamId: {:?} resulted in {:?}", result.0, result.1);
+
+                    if let (stream_id, SyncTaskStatus::Error(_)) = result {
+                        self.close_failed_stream(stream_id).await;
+                    }
                 }
             }
         };
@@ -158,19 +183,23 @@ impl Runtime {
 
                 self.active_streams.remove(&stream_id);
                 self.pending_streams.remove(&stream_id);
+                self.release_resume_positions(stream_id);
             }
             Err(StreamError { stream_id, kind }) => match kind {
                 StreamErrorKind::HandshakeFailed(_)
                 | StreamErrorKind::InvalidCommand
                 | StreamErrorKind::MalformedTargetCheckpoint
+                | StreamErrorKind::MalformedSourceCheckpoint
                 | StreamErrorKind::Transport(_)
                 | StreamErrorKind::PreStartError
                 | StreamErrorKind::StreamClosed
+                | StreamErrorKind::SyncFailed
                 | StreamErrorKind::Timeout => {
                     error!("Stream {stream_id} error: {kind:?}");
 
                     self.active_streams.remove(&stream_id);
                     self.pending_streams.remove(&stream_id);
+                    self.release_resume_positions(stream_id);
                 }
             },
         }
@@ -240,6 +269,33 @@ impl Runtime {
                         }
                     }
                 }
+
+                // Streams watching the source subnet are notified even before the end of their
+                // handshake, so that no certificate falls between their replay and the live
+                // delivery
+                if let Some(stream_list) = self
+                    .source_subnet_subscriptions
+                    .get(&certificate.certificate.source_subnet_id)
+                {
+                    for uuid in stream_list {
+                        if let Some(sender) = self
+                            .active_streams
+                            .get(uuid)
+                            .or_else(|| self.pending_streams.get(uuid))
+                        {
+                            info!("Sending certificate to {uuid}");
+                            if let Err(error) = sender
+                                .send(StreamCommand::PushCertificate {
+                                    certificate: certificate.clone(),
+                                    positions: Vec::new(),
+                                })
+                                .await
+                            {
+                                error!(%error, "Can't push certificate because the receiver is dropped");
+                            }
+                        }
+                    }
+                }
             }
         }
     }
@@ -297,11 +353,21 @@ impl Runtime {
 
             InternalRuntimeCommand::Register {
                 stream_id,
+                request_id,
                 sender,
-                target_subnet_stream_positions,
+                mut target_subnet_stream_positions,
+                source_subnet_stream_positions,
             } => {
                 info!("Stream {stream_id} is registered as subscriber");
 
+                if let Some(request_id) = request_id {
+                    self.claim_resume_positions(
+                        stream_id,
+                        request_id,
+                        &mut target_subnet_stream_positions,
+                    );
+                }
+
                 if let Some(cancel_token) = self.sync_tasks.remove(&stream_id) {
                     // Cancel the previous task
                     cancel_token.cancel();
@@ -330,6 +396,13 @@ impl Runtime {
                             .insert(stream_id);
                     }
 
+                    for source_subnet_id in source_subnet_stream_positions.keys() {
+                        self.source_subnet_subscriptions
+                            .entry(*source_subnet_id)
+                            .or_default()
+                            .insert(stream_id);
+                    }
+
                     let cancel_token = CancellationToken::new();
 
                     let cloned_cancel_token = cancel_token.clone();
@@ -337,6 +410,7 @@ impl Runtime {
                     let task = SyncTask::new(
                         stream_id,
                         target_subnet_stream_positions,
+                        source_subnet_stream_positions,
                         storage,
                         notifier,
                         cancel_token,
@@ -348,6 +422,80 @@ impl Runtime {
                 }
             }
 
+            InternalRuntimeCommand::Acknowledge {
+                stream_id,
+                position,
+            } => {
+                let Some(next_position) = position.position.checked_add(1) else {
+                    warn!("Stream {stream_id} acknowledged an invalid position, ignoring it");
+
+                    return;
+                };
+
+                let Some(resume_positions) = self
+                    .stream_request_ids
+                    .get(&stream_id)
+                    .and_then(|request_id| self.resume_positions.get_mut(request_id))
+                    .filter(|resume_positions| resume_positions.stream_id == stream_id)
+                else {
+                    return;
+                };
+
+                let next_position = TargetStreamPosition {
+                    position: next_position,
+                    certificate_id: None,
+                    ..position
+                };
+
+                match resume_positions
+                    .positions
+                    .entry(position.target_subnet_id)
+                    .or_default()
+                    .entry(position.source_subnet_id)
+                {
+                    Entry::Occupied(mut entry) => {
+                        if entry.get().position < next_position.position {
+                            entry.insert(next_position);
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(next_position);
+                    }
+                }
+            }
+
+            InternalRuntimeCommand::Replay {
+                stream_id,
+                target_subnet_stream_positions,
+                source_subnet_stream_positions,
+            } => {
+                let Some(notifier) = self
+                    .active_streams
+                    .get(&stream_id)
+                    .or_else(|| self.pending_streams.get(&stream_id))
+                    .cloned()
+                else {
+                    return;
+                };
+
+                debug!("Stream {stream_id} fell behind, replaying its streams");
+
+                // The replay is cancelled along with the sync task of the stream
+                let cancel_token = self.sync_tasks.entry(stream_id).or_default().child_token();
+
+                self.running_sync_tasks.push(
+                    ReplayTask {
+                        stream_id,
+                        target_subnet_stream_positions,
+                        source_subnet_stream_positions,
+                        store: self.storage.clone(),
+                        notifier,
+                        cancel_token,
+                    }
+                    .into_future(),
+                );
+            }
+
             InternalRuntimeCommand::CertificateSubmitted {
                 certificate,
                 sender,
@@ -374,6 +522,83 @@ impl Runtime {
                 .await
             }
 
+            InternalRuntimeCommand::CertificatesSubmitted {
+                certificates,
+                sender,
+            } => {
+                info!(
+                    "A batch of {} certificates has been submitted to the TCE",
+                    certificates.len()
+                );
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::CertificatesSubmitted {
+                        certificates,
+                        sender,
+                    })
+                    .await
+                {
+                    error!(
+                        %error,
+                        "Can't send certificates submission to runtime, receiver is dropped"
+                    );
+                }
+            }
+
+            InternalRuntimeCommand::GetCertificateStatus {
+                certificate_id,
+                sender,
+            } => {
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::GetCertificateStatus {
+                        certificate_id,
+                        sender,
+                    })
+                    .await
+                {
+                    error!(%error, "Can't request the certificate status, receiver is dropped");
+                }
+            }
+
+            InternalRuntimeCommand::GetPeers { sender } => {
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::GetPeers { sender })
+                    .await
+                {
+                    error!(%error, "Can't request the peers, receiver is dropped");
+                }
+            }
+
+            InternalRuntimeCommand::GetBroadcastProgress { sender } => {
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::GetBroadcastProgress { sender })
+                    .await
+                {
+                    error!(%error, "Can't request the broadcast progress, receiver is dropped");
+                }
+            }
+
+            InternalRuntimeCommand::EvictPendingCertificate {
+                certificate_id,
+                sender,
+            } => {
+                info!("Eviction of the pending certificate {certificate_id} has been requested");
+
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::EvictPendingCertificate {
+                        certificate_id,
+                        sender,
+                    })
+                    .await
+                {
+                    error!(%error, "Can't request the eviction of a certificate, receiver is dropped");
+                }
+            }
+
             InternalRuntimeCommand::GetSourceHead { subnet_id, sender } => {
                 info!("Source head certificate has been requested for subnet id: {subnet_id}");
 
@@ -390,4 +615,94 @@ impl Runtime {
             }
         }
     }
+
+    /// Close a stream whose certificates can't be replayed from the storage, as it would
+    /// miss some of them
+    async fn close_failed_stream(&mut self, stream_id: Uuid) {
+        if let Some(sender) = self
+            .active_streams
+            .get(&stream_id)
+            .or_else(|| self.pending_streams.get(&stream_id))
+        {
+            if let Err(error) = sender.send(StreamCommand::SyncFailed).await {
+                error!(%error, "Can't close the stream {stream_id}, receiver is dropped");
+            }
+        }
+    }
+
+    /// Bind the positions acknowledged with a request id to a new stream, moving the positions
+    /// requested by the stream forward to them
+    fn claim_resume_positions(
+        &mut self,
+        stream_id: Uuid,
+        request_id: Uuid,
+        positions: &mut TargetSubnetStreamPositions,
+    ) {
+        match self.resume_positions.entry(request_id) {
+            Entry::Occupied(mut entry) => {
+                let resume_positions = entry.get_mut();
+                Self::resume_from(positions, &resume_positions.positions);
+
+                // The previous stream doesn't acknowledge for the request id anymore
+                self.stream_request_ids.remove(&resume_positions.stream_id);
+                resume_positions.stream_id = stream_id;
+                resume_positions.closed_at = None;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(ResumePositions {
+                    stream_id,
+                    positions: HashMap::new(),
+                    closed_at: None,
+                });
+            }
+        }
+
+        self.stream_request_ids.insert(stream_id, request_id);
+    }
+
+    /// Start the expiration of the positions acknowledged by a closed stream
+    fn release_resume_positions(&mut self, stream_id: Uuid) {
+        if let Some(resume_positions) = self
+            .stream_request_ids
+            .remove(&stream_id)
+            .and_then(|request_id| self.resume_positions.get_mut(&request_id))
+        {
+            resume_positions.closed_at = Some(Instant::now());
+        }
+    }
+
+    /// Drop the positions of the streams closed for longer than [`RESUME_POSITIONS_TTL`]
+    fn expire_resume_positions(&mut self) {
+        self.resume_positions.retain(|_, resume_positions| {
+            resume_positions
+                .closed_at
+                .map_or(true, |closed_at| closed_at.elapsed() < RESUME_POSITIONS_TTL)
+        });
+    }
+
+    /// Move the positions requested by a stream forward to the positions it acknowledged
+    /// before reconnecting, for the target subnets it still subscribes to
+    fn resume_from(
+        positions: &mut TargetSubnetStreamPositions,
+        resume_positions: &TargetSubnetStreamPositions,
+    ) {
+        for (target_subnet_id, sources) in positions.iter_mut() {
+            let Some(resume_sources) = resume_positions.get(target_subnet_id) else {
+                continue;
+            };
+
+            for (source_subnet_id, resume_position) in resume_sources {
+                match sources.entry(*source_subnet_id) {
+                    Entry::Occupied(mut entry) => {
+                        if entry.get().position < resume_position.position {
+                            entry.insert(resume_position.clone());
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(resume_position.clone());
+                    }
+                }
+            }
+        }
+    }
 }
@@ -7,15 +7,17 @@ use std::pin::Pin;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::Sender;
 use tokio_util::sync::CancellationToken;
-use topos_core::api::grpc::checkpoints::TargetStreamPosition;
-use topos_core::types::stream::CertificateTargetStreamPosition;
+use topos_core::api::grpc::checkpoints::{SourceStreamPosition, TargetStreamPosition};
+use topos_core::types::stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition};
 use topos_core::types::CertificateDelivered;
 use topos_core::uci::SubnetId;
 use topos_tce_storage::{FetchCertificatesFilter, FetchCertificatesPosition, StorageClient};
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
-type TargetSubnetStreamPositions = HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>;
+pub(crate) type TargetSubnetStreamPositions =
+    HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>;
+pub(crate) type SourceSubnetStreamPositions = HashMap<SubnetId, SourceStreamPosition>;
 pub(crate) type RunningTasks =
     FuturesUnordered<Pin<Box<dyn Future<Output = (Uuid, SyncTaskStatus)> + Send>>>;
 
@@ -42,6 +44,8 @@ pub(crate) enum SyncTaskError {
     },
     /// Invalid certificate position was being fetched
     InvalidCertificatePosition,
+    /// The certificates couldn't be fetched from the storage
+    Storage,
 }
 
 /// When registering a stream, a [`SyncTask`] is started to fetch certificates from the storage
@@ -59,6 +63,8 @@ pub(crate) struct SyncTask {
     /// A map of subnet and the subnet pair (target and source subnet id), its position and the
     /// last certificate id delivered to the stream
     pub(crate) target_subnet_stream_positions: TargetSubnetStreamPositions,
+    /// A map of the watched source subnets and the position from which they are replayed
+    pub(crate) source_subnet_stream_positions: SourceSubnetStreamPositions,
     /// The connection to the database layer through a StorageClient
     pub(crate) store: StorageClient,
     /// The notifier is used to send certificates to the stream
@@ -68,10 +74,14 @@ pub(crate) struct SyncTask {
 }
 
 impl SyncTask {
+    /// Number of certificates fetched at once when replaying a source stream
+    const SOURCE_BATCH_SIZE: usize = 100;
+
     /// Creating a new SyncTask which will fetch certificates from the storage and pushes them to the stream
     pub(crate) fn new(
         stream_id: Uuid,
         target_subnet_stream_positions: TargetSubnetStreamPositions,
+        source_subnet_stream_positions: SourceSubnetStreamPositions,
         store: StorageClient,
         notifier: Sender<StreamCommand>,
         cancel_token: CancellationToken,
@@ -80,6 +90,7 @@ impl SyncTask {
             status: SyncTaskStatus::Running,
             stream_id,
             target_subnet_stream_positions,
+            source_subnet_stream_positions,
             store,
             notifier,
             cancel_token,
@@ -192,9 +203,229 @@ impl IntoFuture for SyncTask {
                 }
             }
 
+            // The source streams are replayed until their end, the certificates delivered after
+            // are pushed by the live delivery and the stream drops the ones seen twice
+            for (source_subnet_id, source_position) in &self.source_subnet_stream_positions {
+                let mut position = source_position.position;
+
+                loop {
+                    if self.cancel_token.is_cancelled() {
+                        self.status = SyncTaskStatus::Cancelled;
+                        return (self.stream_id, self.status);
+                    }
+
+                    let certificates = match self
+                        .store
+                        .fetch_certificates(FetchCertificatesFilter::Source {
+                            source_stream_position: CertificateSourceStreamPosition::new(
+                                *source_subnet_id,
+                                position,
+                            ),
+                            limit: Self::SOURCE_BATCH_SIZE,
+                        })
+                        .await
+                    {
+                        Ok(certificates) => certificates,
+                        Err(error) => {
+                            error!(
+                                "Unable to replay the source stream {} for stream {}: {}",
+                                source_subnet_id, self.stream_id, error
+                            );
+                            self.status = SyncTaskStatus::Error(Box::new(SyncTaskError::Storage));
+                            return (self.stream_id, self.status);
+                        }
+                    };
+
+                    let fetched = certificates.len();
+                    // The certificates whose proof is pruned are skipped, the next batch starts
+                    // after the last one returned
+                    if let Some((_, FetchCertificatesPosition::Source(last))) = certificates.last()
+                    {
+                        position = *last.position + 1;
+                    }
+
+                    for (certificate, _) in certificates {
+                        debug!(
+                            "Stream sync task for {} is sending {}",
+                            self.stream_id, certificate.certificate.id
+                        );
+
+                        if let Err(error) = self
+                            .notifier
+                            .send(StreamCommand::PushCertificate {
+                                certificate,
+                                positions: Vec::new(),
+                            })
+                            .await
+                        {
+                            error!("Error sending certificate to stream: {}", error);
+                            self.status =
+                                SyncTaskStatus::Error(Box::new(SyncTaskError::SendingToStream {
+                                    error: Box::new(error),
+                                }));
+                            return (self.stream_id, self.status);
+                        }
+                    }
+
+                    if fetched < Self::SOURCE_BATCH_SIZE {
+                        break;
+                    }
+                }
+            }
+
             info!("The sync task for stream {} is done", self.stream_id);
             self.status = SyncTaskStatus::Done;
             (self.stream_id, self.status)
         })
     }
 }
+
+/// When a stream falls behind on some target or source streams, a [`ReplayTask`] fetches
+/// their next certificates from the storage and pushes them at once to the stream.
+pub(crate) struct ReplayTask {
+    /// The stream which fell behind
+    pub(crate) stream_id: Uuid,
+    /// Positions from which the target streams are replayed
+    pub(crate) target_subnet_stream_positions: TargetSubnetStreamPositions,
+    /// Positions from which the source streams are replayed
+    pub(crate) source_subnet_stream_positions: SourceSubnetStreamPositions,
+    /// The connection to the database layer through a StorageClient
+    pub(crate) store: StorageClient,
+    /// The notifier is used to send certificates to the stream
+    pub(crate) notifier: Sender<StreamCommand>,
+    /// Cancelled along with the [`SyncTask`] of the stream
+    pub(crate) cancel_token: CancellationToken,
+}
+
+impl ReplayTask {
+    /// Number of certificates replayed at once for each target and source stream
+    const BATCH_SIZE: usize = 100;
+}
+
+impl IntoFuture for ReplayTask {
+    type Output = (Uuid, SyncTaskStatus);
+
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            debug!("Replay task started for stream {}", self.stream_id);
+            let mut target_certificates = Vec::new();
+
+            for position in self
+                .target_subnet_stream_positions
+                .values()
+                .flat_map(HashMap::values)
+            {
+                if self.cancel_token.is_cancelled() {
+                    return (self.stream_id, SyncTaskStatus::Cancelled);
+                }
+
+                let fetched = match self
+                    .store
+                    .fetch_certificates(FetchCertificatesFilter::Target {
+                        target_stream_position: CertificateTargetStreamPosition::new(
+                            position.target_subnet_id,
+                            position.source_subnet_id,
+                            position.position,
+                        ),
+                        limit: Self::BATCH_SIZE,
+                    })
+                    .await
+                {
+                    Ok(fetched) => fetched,
+                    Err(error) => {
+                        error!(
+                            "Unable to replay the target stream {} -> {} for stream {}: {}",
+                            position.source_subnet_id,
+                            position.target_subnet_id,
+                            self.stream_id,
+                            error
+                        );
+
+                        return (
+                            self.stream_id,
+                            SyncTaskStatus::Error(Box::new(SyncTaskError::Storage)),
+                        );
+                    }
+                };
+
+                for (certificate, fetched_position) in fetched {
+                    let FetchCertificatesPosition::Target(target_position) = fetched_position
+                    else {
+                        return (
+                            self.stream_id,
+                            SyncTaskStatus::Error(Box::new(
+                                SyncTaskError::InvalidCertificatePosition,
+                            )),
+                        );
+                    };
+
+                    let certificate_id = certificate.certificate.id;
+                    target_certificates.push((
+                        certificate,
+                        TargetStreamPosition {
+                            target_subnet_id: target_position.target_subnet_id,
+                            source_subnet_id: target_position.source_subnet_id,
+                            position: *target_position.position,
+                            certificate_id: Some(certificate_id),
+                        },
+                    ));
+                }
+            }
+
+            let mut source_certificates = Vec::new();
+            for position in self.source_subnet_stream_positions.values() {
+                if self.cancel_token.is_cancelled() {
+                    return (self.stream_id, SyncTaskStatus::Cancelled);
+                }
+
+                match self
+                    .store
+                    .fetch_certificates(FetchCertificatesFilter::Source {
+                        source_stream_position: CertificateSourceStreamPosition::new(
+                            position.source_subnet_id,
+                            position.position,
+                        ),
+                        limit: Self::BATCH_SIZE,
+                    })
+                    .await
+                {
+                    Ok(fetched) => source_certificates
+                        .extend(fetched.into_iter().map(|(certificate, _)| certificate)),
+                    Err(error) => {
+                        error!(
+                            "Unable to replay the source stream {} for stream {}: {}",
+                            position.source_subnet_id, self.stream_id, error
+                        );
+
+                        return (
+                            self.stream_id,
+                            SyncTaskStatus::Error(Box::new(SyncTaskError::Storage)),
+                        );
+                    }
+                }
+            }
+
+            if let Err(error) = self
+                .notifier
+                .send(StreamCommand::Replayed {
+                    target_certificates,
+                    source_certificates,
+                })
+                .await
+            {
+                error!("Error sending replayed certificates to stream: {}", error);
+
+                return (
+                    self.stream_id,
+                    SyncTaskStatus::Error(Box::new(SyncTaskError::SendingToStream {
+                        error: Box::new(error),
+                    })),
+                );
+            }
+
+            (self.stream_id, SyncTaskStatus::Done)
+        })
+    }
+}
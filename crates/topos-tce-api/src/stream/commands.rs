@@ -7,4 +7,12 @@ pub enum StreamCommand {
         certificate: CertificateDelivered,
         positions: Vec<TargetStreamPosition>,
     },
+    /// Certificates replayed from the storage, ordered by position in each target and
+    /// source stream
+    Replayed {
+        target_certificates: Vec<(CertificateDelivered, TargetStreamPosition)>,
+        source_certificates: Vec<CertificateDelivered>,
+    },
+    /// The certificates of the stream can't be replayed from the storage
+    SyncFailed,
 }
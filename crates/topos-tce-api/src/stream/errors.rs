@@ -20,6 +20,10 @@ pub(crate) enum StreamErrorKind {
     Transport(Code),
     #[error("The submitted TargetCheckpoint is ill-formed")]
     MalformedTargetCheckpoint,
+    #[error("The submitted SourceCheckpoint is ill-formed")]
+    MalformedSourceCheckpoint,
+    #[error("Unable to replay the certificates from the storage")]
+    SyncFailed,
 }
 
 #[derive(Debug)]
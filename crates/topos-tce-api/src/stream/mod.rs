@@ -1,6 +1,10 @@
 use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use std::sync::Arc;
-use std::{collections::HashMap, fmt::Debug, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    time::Duration,
+};
 use tokio::{
     sync::{
         mpsc::{self, Receiver, Sender},
@@ -9,7 +13,9 @@ use tokio::{
     time::timeout,
 };
 use tonic::Status;
-use topos_core::api::grpc::checkpoints::{TargetCheckpoint, TargetStreamPosition};
+use topos_core::api::grpc::checkpoints::{
+    SourceCheckpoint, SourceStreamPosition, TargetCheckpoint, TargetStreamPosition,
+};
 use topos_core::types::CertificateDelivered;
 use topos_core::uci::SubnetId;
 use tracing::{debug, error, info, trace, warn};
@@ -22,8 +28,10 @@ pub mod errors;
 mod tests;
 
 use crate::{
+    constants::{MAX_AHEAD_CERTIFICATES, MAX_QUEUED_CERTIFICATES},
     grpc::messaging::{
-        CertificatePushed, InboundMessage, OpenStream, OutboundMessage, StreamOpened,
+        Ack, CertificatePushed, InboundMessage, OpenStream, OutboundMessage, StreamOpened,
+        Subscription,
     },
     runtime::InternalRuntimeCommand,
     RuntimeError,
@@ -69,15 +77,59 @@ impl Drop for TransientStream {
     }
 }
 
+/// Progress of a stream in the stream of a source subnet it watches
+///
+/// The certificates of a source subnet are received both from the replay of the storage and
+/// from the live delivery, the listener drops the ones already pushed and holds the ones
+/// received ahead of their position until the replay catches up.
+#[derive(Debug, Default)]
+pub(crate) struct SourceListener {
+    /// Position in the source stream of the next certificate to push
+    pub(crate) next_position: u64,
+    /// Certificates received ahead of the next position, indexed by their position, up to
+    /// [`MAX_AHEAD_CERTIFICATES`]
+    pub(crate) ahead: BTreeMap<u64, (CertificateDelivered, Vec<TargetStreamPosition>)>,
+    /// Whether certificates ahead of the next position were dropped and have to be replayed
+    pub(crate) dropped: bool,
+}
+
 pub struct Stream {
     pub(crate) stream_id: Uuid,
 
+    /// Request id of the OpenStream, used by the runtime to keep the acknowledged positions
+    /// of the stream across reconnections
+    pub(crate) request_id: Option<Uuid>,
+
     /// Mapping for each target subnet to the set of position per source subnet
     pub(crate) target_subnet_listeners: HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>,
 
+    /// Mapping for each source subnet to the progress of the stream in it
+    pub(crate) source_subnet_listeners: HashMap<SubnetId, SourceListener>,
+    /// Target subnets on which the certificates of the source subnets are filtered,
+    /// no filtering if empty
+    pub(crate) target_subnet_filter: HashSet<SubnetId>,
+
     pub(crate) command_receiver: Receiver<StreamCommand>,
     pub(crate) internal_runtime_command_sender: Sender<InternalRuntimeCommand>,
 
+    /// Maximum number of unacknowledged certificates before pausing the stream,
+    /// 0 disables the flow control
+    pub(crate) max_unacknowledged: usize,
+    /// Certificates waiting to be pushed to the client, up to [`MAX_QUEUED_CERTIFICATES`]
+    pub(crate) queued: VecDeque<(CertificateDelivered, Vec<TargetStreamPosition>)>,
+    /// Positions pushed to the client and not yet acknowledged
+    pub(crate) unacknowledged: Vec<TargetStreamPosition>,
+    /// Last position pushed to the client for each target and source subnets
+    pub(crate) last_pushed: HashMap<(SubnetId, SubnetId), u64>,
+
+    /// Next position to queue of the target streams on which the stream fell behind, their
+    /// certificates being only queued in order once the queue overflowed
+    pub(crate) lagging: HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>,
+    /// Whether certificates of the lagging target streams were dropped and have to be replayed
+    pub(crate) replay_needed: bool,
+    /// Whether a replay of the lagging target streams has been requested to the runtime
+    pub(crate) replaying: bool,
+
     /// gRPC outbound stream
     pub(crate) outbound_stream: Sender<Result<(Option<Uuid>, OutboundMessage), Status>>,
     /// gRPC inbound stream
@@ -89,7 +141,15 @@ impl Debug for Stream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Stream")
             .field("stream_id", &self.stream_id)
+            .field("request_id", &self.request_id)
             .field("target_subnet_listeners", &self.target_subnet_listeners)
+            .field("source_subnet_listeners", &self.source_subnet_listeners)
+            .field("target_subnet_filter", &self.target_subnet_filter)
+            .field("max_unacknowledged", &self.max_unacknowledged)
+            .field("queued", &self.queued.len())
+            .field("unacknowledged", &self.unacknowledged.len())
+            .field("lagging", &self.lagging)
+            .field("replaying", &self.replaying)
             .finish()
     }
 }
@@ -104,23 +164,50 @@ impl Stream {
     ) -> Self {
         Self {
             stream_id,
+            request_id: None,
             target_subnet_listeners: HashMap::new(),
+            source_subnet_listeners: HashMap::new(),
+            target_subnet_filter: HashSet::new(),
             command_receiver,
+            max_unacknowledged: 0,
+            queued: VecDeque::new(),
+            unacknowledged: Vec::new(),
+            last_pushed: HashMap::new(),
+            lagging: HashMap::new(),
+            replay_needed: false,
+            replaying: false,
             outbound_stream,
             inbound_stream,
             internal_runtime_command_sender,
         }
     }
 
+    /// A stream is paused while the client has too many certificates to acknowledge
+    fn is_paused(&self) -> bool {
+        self.max_unacknowledged > 0 && self.unacknowledged.len() >= self.max_unacknowledged
+    }
+
     pub async fn run(mut self) -> Result<Uuid, StreamError> {
         // Prestart is the phase that waits for a particular message to being able to process the
         // handshake. For now we do not have authentication nor authorization.
-        let (request_id, checkpoint) = self.pre_start().await?;
+        let (request_id, subscription) = self.pre_start().await?;
 
         // The handshake is preparing the stream to broadcast certificates to the client.
         // Notifying the manager about the subscriptions and defining everything related to
         // the stream management.
-        self.handshake(checkpoint)
+        if let Err(error) = self.subscribe(subscription) {
+            if let Err(send_error) = self
+                .outbound_stream
+                .send(Err(Status::invalid_argument(error.kind.to_string())))
+                .await
+            {
+                warn!(%send_error, "Can't notify stream of an ill-formed subscription");
+            }
+
+            return Err(error);
+        }
+
+        self.handshake()
             .await
             .map_err(|error| StreamError::new(self.stream_id, StreamErrorKind::from(error)))?;
 
@@ -129,7 +216,12 @@ impl Stream {
             .send(Ok((
                 request_id,
                 OutboundMessage::StreamOpened(StreamOpened {
-                    subnet_ids: self.target_subnet_listeners.keys().copied().collect(),
+                    subnet_ids: self
+                        .target_subnet_listeners
+                        .keys()
+                        .chain(self.source_subnet_listeners.keys())
+                        .copied()
+                        .collect(),
                 }),
             )))
             .await
@@ -143,6 +235,9 @@ impl Stream {
         }
 
         loop {
+            // The handlers below need the stream mutably while the outbound slot is reserved
+            let outbound_stream = self.outbound_stream.clone();
+
             tokio::select! {
                 Some(command) = self.command_receiver.recv() => {
                     if self.handle_command(command).await? {
@@ -150,12 +245,58 @@ impl Stream {
                     }
                 }
 
-                // We currently open the stream, but no other message from the client is getting processed.
-                // We are using this open connection to communicate `delivered_certificates` to the client.
+                // Certificates are queued by the stream, so that a slow or paused client never
+                // blocks the runtime nor loses certificates.
+                permit = outbound_stream.reserve(), if !self.queued.is_empty() && !self.is_paused() => {
+                    let Ok(permit) = permit else {
+                        error!("Can't forward WatchCertificatesResponse to stream {}, channel seems dropped", self.stream_id);
+
+                        return Err(StreamError::new(self.stream_id, StreamErrorKind::StreamClosed));
+                    };
+
+                    if let Some((certificate, positions)) = self.queued.pop_front() {
+                        let certificate_id = certificate.certificate.id;
+                        for position in &positions {
+                            self.last_pushed.insert(
+                                (position.target_subnet_id, position.source_subnet_id),
+                                position.position,
+                            );
+                        }
+                        if self.max_unacknowledged > 0 {
+                            self.unacknowledged.extend(positions.iter().cloned());
+                        }
+
+                        permit.send(Ok((
+                            None,
+                            OutboundMessage::CertificatePushed(Box::new(CertificatePushed {
+                                certificate,
+                                positions,
+                            })),
+                        )));
+
+                        info!(
+                            "Certificate {} sent to gRPC stream {}",
+                            certificate_id, self.stream_id
+                        );
+
+                        if self.is_paused() {
+                            debug!("Stream {} is paused until the client acknowledges its certificates", self.stream_id);
+                        }
+                    }
+
+                    self.release_source_certificates();
+                    if self.queued.is_empty() {
+                        self.request_replay().await?;
+                    }
+                }
+
                 Some(stream_packet) = self.inbound_stream.next() => {
                     match stream_packet {
-                        Ok((_request_id, _message)) => {
-                            trace!("Received message from stream_id: {:?}", self.stream_id);
+                        Ok((_request_id, InboundMessage::Ack(Ack { position }))) => {
+                            self.acknowledge(position).await;
+                        }
+                        Ok((_request_id, InboundMessage::OpenStream(_))) => {
+                            warn!("Stream {} is already opened, ignoring the OpenStream", self.stream_id);
                         }
                         Err(error) => {
                             match error.kind {
@@ -193,46 +334,342 @@ impl Stream {
                 certificate,
                 positions,
             } => {
-                let certificate_id = certificate.certificate.id;
+                if self.source_subnet_listeners.is_empty() {
+                    self.queue_target_certificate(certificate, positions, false);
+                } else {
+                    self.sequence_source_certificate(certificate, positions);
+                }
+
+                if self.queued.is_empty() {
+                    self.request_replay().await?;
+                }
+            }
+            StreamCommand::Replayed {
+                target_certificates,
+                source_certificates,
+            } => {
+                self.replaying = false;
+
+                for (certificate, position) in target_certificates {
+                    self.queue_target_certificate(certificate, vec![position], true);
+                }
+
+                for certificate in source_certificates {
+                    self.sequence_source_certificate(certificate, Vec::new());
+                }
+
+                if self.queued.is_empty() {
+                    self.request_replay().await?;
+                }
+            }
+            StreamCommand::SyncFailed => {
+                error!(
+                    "Stream {} is closed as its certificates can't be replayed",
+                    self.stream_id
+                );
+
+                let kind = StreamErrorKind::SyncFailed;
                 if let Err(error) = self
                     .outbound_stream
-                    .send(Ok((
-                        None,
-                        OutboundMessage::CertificatePushed(Box::new(CertificatePushed {
-                            certificate,
-                            positions,
-                        })),
-                    )))
+                    .send(Err(Status::internal(kind.to_string())))
                     .await
                 {
-                    error!(%error, "Can't forward WatchCertificatesResponse to stream, channel seems dropped certificate {certificate_id}");
+                    warn!(%error, "Can't notify stream of the failure of its replay");
+                }
 
-                    return Err(StreamError::new(
-                        self.stream_id,
-                        StreamErrorKind::StreamClosed,
-                    ));
-                } else {
-                    info!(
-                        "Certificate {} sent to gRPC stream {}",
-                        certificate_id, self.stream_id
+                return Err(StreamError::new(self.stream_id, kind));
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Queue a certificate of a target stream, unless the queue is full in which case the
+    /// target stream falls behind and its certificates are replayed from the storage
+    ///
+    /// The certificates of a lagging target stream are only queued in order, from the
+    /// position following the last queued one. The replayed certificates, fetched from the
+    /// storage in order, can move the position forward over the gaps of the target stream.
+    fn queue_target_certificate(
+        &mut self,
+        certificate: CertificateDelivered,
+        positions: Vec<TargetStreamPosition>,
+        replayed: bool,
+    ) {
+        let Some(position) = positions.first() else {
+            if self.queued.len() < MAX_QUEUED_CERTIFICATES {
+                self.queued.push_back((certificate, positions));
+            } else {
+                warn!(
+                    "Certificate {} without target position dropped, the queue of gRPC stream {} \
+                     is full",
+                    certificate.certificate.id, self.stream_id
+                );
+            }
+
+            return;
+        };
+
+        let next_position = self
+            .lagging
+            .get(&position.target_subnet_id)
+            .and_then(|sources| sources.get(&position.source_subnet_id))
+            .map(|next_position| next_position.position);
+
+        match next_position {
+            Some(next_position) if position.position < next_position => {
+                trace!(
+                    "Certificate {} already queued for gRPC stream {}",
+                    certificate.certificate.id,
+                    self.stream_id
+                );
+
+                return;
+            }
+            Some(next_position) if position.position > next_position && !replayed => {
+                trace!(
+                    "Certificate {} dropped for gRPC stream {}, it will be replayed",
+                    certificate.certificate.id,
+                    self.stream_id
+                );
+                self.replay_needed = true;
+
+                return;
+            }
+            _ => {}
+        }
+
+        if self.queued.len() >= MAX_QUEUED_CERTIFICATES {
+            if next_position.is_none() {
+                debug!(
+                    "gRPC stream {} fell behind on {} -> {}, replaying from position {}",
+                    self.stream_id,
+                    position.source_subnet_id,
+                    position.target_subnet_id,
+                    position.position
+                );
+            }
+
+            self.set_lagging_position(position, position.position);
+            self.replay_needed = true;
+
+            return;
+        }
+
+        if next_position.is_some() {
+            self.set_lagging_position(position, position.position.saturating_add(1));
+        }
+
+        trace!(
+            "Certificate {} queued for gRPC stream {}",
+            certificate.certificate.id,
+            self.stream_id
+        );
+
+        self.queued.push_back((certificate, positions));
+    }
+
+    fn set_lagging_position(&mut self, position: &TargetStreamPosition, next_position: u64) {
+        self.lagging
+            .entry(position.target_subnet_id)
+            .or_default()
+            .insert(
+                position.source_subnet_id,
+                TargetStreamPosition {
+                    position: next_position,
+                    certificate_id: None,
+                    ..position.clone()
+                },
+            );
+    }
+
+    /// Ask the runtime to replay the lagging target streams and the source streams whose
+    /// certificates were dropped, once the queue is drained
+    async fn request_replay(&mut self) -> Result<(), StreamError> {
+        if !self.replay_needed || self.replaying {
+            return Ok(());
+        }
+
+        self.replay_needed = false;
+        self.replaying = true;
+
+        let source_subnet_stream_positions = self
+            .source_subnet_listeners
+            .iter_mut()
+            .filter(|(_, listener)| listener.dropped)
+            .map(|(source_subnet_id, listener)| {
+                listener.dropped = false;
+
+                (
+                    *source_subnet_id,
+                    SourceStreamPosition {
+                        source_subnet_id: *source_subnet_id,
+                        position: listener.next_position,
+                        certificate_id: None,
+                    },
+                )
+            })
+            .collect();
+
+        self.internal_runtime_command_sender
+            .send(InternalRuntimeCommand::Replay {
+                stream_id: self.stream_id,
+                target_subnet_stream_positions: self.lagging.clone(),
+                source_subnet_stream_positions,
+            })
+            .await
+            .map_err(|_| StreamError::new(self.stream_id, StreamErrorKind::StreamClosed))
+    }
+
+    /// Queue the certificates of the watched source subnets in the order of their source
+    /// stream, whether they come from the replay or from the live delivery
+    fn sequence_source_certificate(
+        &mut self,
+        certificate: CertificateDelivered,
+        positions: Vec<TargetStreamPosition>,
+    ) {
+        let delivery_position = &certificate.proof_of_delivery.delivery_position;
+        let position = *delivery_position.position;
+
+        let Some(listener) = self
+            .source_subnet_listeners
+            .get_mut(&delivery_position.subnet_id)
+        else {
+            warn!(
+                "Stream {} received the certificate {} of an unwatched source subnet",
+                self.stream_id, certificate.certificate.id
+            );
+
+            return;
+        };
+
+        if position < listener.next_position {
+            trace!(
+                "Certificate {} already pushed to gRPC stream {}",
+                certificate.certificate.id,
+                self.stream_id
+            );
+
+            return;
+        }
+
+        // The next certificate is always held, the ones further ahead are dropped once too
+        // many are held and replayed from the storage later on
+        if position != listener.next_position
+            && listener.ahead.len() >= MAX_AHEAD_CERTIFICATES
+            && !listener.ahead.contains_key(&position)
+        {
+            trace!(
+                "Certificate {} dropped for gRPC stream {}, it will be replayed",
+                certificate.certificate.id,
+                self.stream_id
+            );
+            listener.dropped = true;
+            self.replay_needed = true;
+
+            return;
+        }
+
+        listener.ahead.insert(position, (certificate, positions));
+
+        self.release_source_certificates();
+    }
+
+    /// Queue the certificates of the watched source subnets which follow the last queued ones,
+    /// as long as the queue isn't full
+    fn release_source_certificates(&mut self) {
+        for listener in self.source_subnet_listeners.values_mut() {
+            while self.queued.len() < MAX_QUEUED_CERTIFICATES {
+                let Some((certificate, positions)) = listener.ahead.remove(&listener.next_position)
+                else {
+                    break;
+                };
+
+                listener.next_position += 1;
+
+                if self.target_subnet_filter.is_empty()
+                    || certificate
+                        .certificate
+                        .target_subnets
+                        .iter()
+                        .any(|target| self.target_subnet_filter.contains(target))
+                {
+                    trace!(
+                        "Certificate {} queued for gRPC stream {}",
+                        certificate.certificate.id,
+                        self.stream_id
                     );
+
+                    self.queued.push_back((certificate, positions));
                 }
             }
         }
+    }
 
-        Ok(false)
+    /// Release the pushed certificates up to the acknowledged position and let the runtime
+    /// know where to resume the stream from
+    async fn acknowledge(&mut self, position: TargetStreamPosition) {
+        trace!(
+            "Stream {} acknowledged position {} of {} -> {}",
+            self.stream_id,
+            position.position,
+            position.source_subnet_id,
+            position.target_subnet_id
+        );
+
+        match self
+            .last_pushed
+            .get(&(position.target_subnet_id, position.source_subnet_id))
+        {
+            Some(last_pushed) if position.position <= *last_pushed => {}
+            _ => {
+                warn!(
+                    "Stream {} acknowledged the position {} of {} -> {} which isn't pushed yet, \
+                     ignoring it",
+                    self.stream_id,
+                    position.position,
+                    position.source_subnet_id,
+                    position.target_subnet_id
+                );
+
+                return;
+            }
+        }
+
+        self.unacknowledged.retain(|pushed| {
+            pushed.target_subnet_id != position.target_subnet_id
+                || pushed.source_subnet_id != position.source_subnet_id
+                || pushed.position > position.position
+        });
+
+        if self.request_id.is_some() {
+            if let Err(error) = self
+                .internal_runtime_command_sender
+                .send(InternalRuntimeCommand::Acknowledge {
+                    stream_id: self.stream_id,
+                    position,
+                })
+                .await
+            {
+                error!(%error, "Can't notify the runtime of an acknowledgement, receiver is dropped");
+            }
+        }
     }
 
-    async fn pre_start(&mut self) -> Result<(Option<Uuid>, TargetCheckpoint), StreamError> {
+    async fn pre_start(&mut self) -> Result<(Option<Uuid>, Subscription), StreamError> {
         let waiting_for_open_stream = async {
             if let Ok(Some((
                 request_id,
                 InboundMessage::OpenStream(OpenStream {
-                    target_checkpoint, ..
+                    subscription,
+                    max_unacknowledged,
                 }),
             ))) = self.inbound_stream.try_next().await
             {
-                Ok((request_id, target_checkpoint))
+                self.request_id = request_id;
+                self.max_unacknowledged = max_unacknowledged;
+
+                Ok((request_id, subscription))
             } else {
                 Err(())
             }
@@ -269,14 +706,39 @@ impl Stream {
         }
     }
 
-    async fn handshake(&mut self, checkpoint: TargetCheckpoint) -> Result<(), HandshakeError> {
-        _ = self.handle_checkpoint(checkpoint);
+    /// Set up the listeners of the stream from the subscription of its OpenStream
+    fn subscribe(&mut self, subscription: Subscription) -> Result<(), StreamError> {
+        match subscription {
+            Subscription::Target(checkpoint) => self.handle_checkpoint(checkpoint),
+            Subscription::Source {
+                checkpoint,
+                target_subnet_filter,
+            } => self.handle_source_checkpoint(checkpoint, target_subnet_filter),
+        }
+    }
+
+    async fn handshake(&mut self) -> Result<(), HandshakeError> {
         let (sender, receiver) = oneshot::channel::<Result<(), RuntimeError>>();
 
         self.internal_runtime_command_sender
             .send(InternalRuntimeCommand::Register {
                 stream_id: self.stream_id,
+                request_id: self.request_id,
                 target_subnet_stream_positions: self.target_subnet_listeners.clone(),
+                source_subnet_stream_positions: self
+                    .source_subnet_listeners
+                    .iter()
+                    .map(|(source_subnet_id, listener)| {
+                        (
+                            *source_subnet_id,
+                            SourceStreamPosition {
+                                source_subnet_id: *source_subnet_id,
+                                position: listener.next_position,
+                                certificate_id: None,
+                            },
+                        )
+                    })
+                    .collect(),
                 sender,
             })
             .await
@@ -322,4 +784,34 @@ impl Stream {
 
         Ok(())
     }
+
+    fn handle_source_checkpoint(
+        &mut self,
+        checkpoint: SourceCheckpoint,
+        target_subnet_filter: Vec<SubnetId>,
+    ) -> Result<(), StreamError> {
+        self.source_subnet_listeners.clear();
+        self.target_subnet_filter = target_subnet_filter.into_iter().collect();
+
+        for source in checkpoint.source_subnet_ids {
+            self.source_subnet_listeners
+                .insert(source, Default::default());
+        }
+
+        for position in checkpoint.positions {
+            if let Some(listener) = self
+                .source_subnet_listeners
+                .get_mut(&position.source_subnet_id)
+            {
+                listener.next_position = position.position;
+            } else {
+                return Err(StreamError::new(
+                    self.stream_id,
+                    StreamErrorKind::MalformedSourceCheckpoint,
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
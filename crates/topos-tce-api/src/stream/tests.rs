@@ -4,10 +4,11 @@ use tokio::sync::{mpsc, oneshot};
 use tokio_stream::StreamExt;
 use topos_core::uci::SUBNET_ID_LENGTH;
 use topos_test_sdk::certificates::create_certificate_chain;
-use topos_test_sdk::constants::{SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1};
+use topos_test_sdk::constants::{SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1, TARGET_SUBNET_ID_2};
 use uuid::Uuid;
 
 use self::utils::StreamBuilder;
+use crate::constants::{MAX_AHEAD_CERTIFICATES, MAX_QUEUED_CERTIFICATES};
 use crate::grpc::messaging::{OutboundMessage, StreamOpened};
 use crate::runtime::InternalRuntimeCommand;
 use crate::stream::{StreamError, StreamErrorKind, TransientStream};
@@ -15,9 +16,11 @@ use crate::tests::encode;
 use crate::wait_for_command;
 use test_log::test;
 use tokio::spawn;
-use topos_core::api::grpc::shared::v1::checkpoints::TargetCheckpoint;
-use topos_core::api::grpc::shared::v1::positions::TargetStreamPosition;
-use topos_core::api::grpc::tce::v1::watch_certificates_request::OpenStream as GrpcOpenStream;
+use topos_core::api::grpc::shared::v1::checkpoints::{SourceCheckpoint, TargetCheckpoint};
+use topos_core::api::grpc::shared::v1::positions::{SourceStreamPosition, TargetStreamPosition};
+use topos_core::api::grpc::tce::v1::watch_certificates_request::{
+    Ack as GrpcAck, Command, OpenStream as GrpcOpenStream,
+};
 use topos_core::api::grpc::tce::v1::WatchCertificatesRequest;
 
 mod utils;
@@ -59,6 +62,8 @@ pub async fn sending_open_stream_message() -> Result<(), Box<dyn std::error::Err
             positions: Vec::new(),
         }),
         source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
     }
     .into();
 
@@ -94,6 +99,8 @@ async fn subscribing_to_one_target_with_position() -> Result<(), Box<dyn std::er
             }],
         }),
         source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
     }
     .into();
 
@@ -128,6 +135,8 @@ async fn receive_expected_certificate_from_zero() -> Result<(), Box<dyn std::err
             positions: vec![],
         }),
         source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
     }
     .into();
 
@@ -184,21 +193,522 @@ async fn receive_expected_certificate_from_zero() -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+#[rstest]
+#[timeout(Duration::from_millis(500))]
 #[test(tokio::test)]
-#[ignore = "not yet implemented"]
-async fn pausing_all_subscription() {}
+async fn pausing_and_resuming_all_subscription() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let expected_certificates =
+        create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 2);
+
+    let join = spawn(stream.run());
+
+    let request_id = Uuid::new_v4();
+    let msg = WatchCertificatesRequest {
+        request_id: Some(request_id.into()),
+        command: Some(Command::OpenStream(GrpcOpenStream {
+            target_checkpoint: Some(TargetCheckpoint {
+                target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+                positions: vec![],
+            }),
+            source_checkpoint: None,
+            max_unacknowledged: 1,
+            target_subnet_filter: vec![],
+        })),
+    };
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, request_id: Some(id), sender, .. } if stream_id == expected_stream_id && id == request_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Ok((_, OutboundMessage::StreamOpened(_))))),
+        "Expected StreamOpened, received: {msg:?}"
+    );
+
+    let positions: Vec<_> = expected_certificates
+        .iter()
+        .enumerate()
+        .map(
+            |(index, certificate)| topos_core::api::grpc::checkpoints::TargetStreamPosition {
+                position: index as u64,
+                certificate_id: Some(certificate.certificate.id),
+                target_subnet_id: TARGET_SUBNET_ID_1,
+                source_subnet_id: SOURCE_SUBNET_ID_2,
+            },
+        )
+        .collect();
+
+    for (certificate, position) in expected_certificates.iter().zip(&positions) {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificate.clone(),
+                positions: vec![position.clone()],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(
+            msg,
+            Some(Ok((_, OutboundMessage::CertificatePushed(ref pushed)))) if pushed.certificate == expected_certificates[0]
+        ),
+        "Expected the first CertificatePushed, received: {msg:?}"
+    );
+
+    // The stream is paused until the first certificate is acknowledged
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), context.stream_receiver.recv())
+            .await
+            .is_err()
+    );
+
+    // The second certificate isn't pushed yet, its acknowledgement is ignored
+    let msg = WatchCertificatesRequest {
+        request_id: Some(request_id.into()),
+        command: Some(Command::Ack(GrpcAck {
+            position: Some(positions[1].clone().into()),
+        })),
+    };
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), context.stream_receiver.recv())
+            .await
+            .is_err()
+    );
+    while let Ok(command) = context.runtime_receiver.try_recv() {
+        assert!(
+            !matches!(command, InternalRuntimeCommand::Acknowledge { .. }),
+            "Unexpected acknowledgement: {command:?}"
+        );
+    }
+
+    let msg = WatchCertificatesRequest {
+        request_id: Some(request_id.into()),
+        command: Some(Command::Ack(GrpcAck {
+            position: Some(positions[0].clone().into()),
+        })),
+    };
 
+    _ = tx.send_data(encode(&msg)?).await;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Acknowledge { stream_id, ref position } if stream_id == expected_stream_id && position == &positions[0]
+    );
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(
+            msg,
+            Some(Ok((_, OutboundMessage::CertificatePushed(ref pushed)))) if pushed.certificate == expected_certificates[1]
+        ),
+        "Expected the second CertificatePushed, received: {msg:?}"
+    );
+
+    join.abort();
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(2))]
 #[test(tokio::test)]
-#[ignore = "not yet implemented"]
-async fn pausing_one_subscription() {}
+async fn stream_falling_behind_is_replayed_from_the_storage(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let certificates = create_certificate_chain(
+        SOURCE_SUBNET_ID_2,
+        &[TARGET_SUBNET_ID_1],
+        MAX_QUEUED_CERTIFICATES + 20,
+    );
+    let positions: Vec<_> = certificates
+        .iter()
+        .enumerate()
+        .map(
+            |(index, certificate)| topos_core::api::grpc::checkpoints::TargetStreamPosition {
+                position: index as u64,
+                certificate_id: Some(certificate.certificate.id),
+                target_subnet_id: TARGET_SUBNET_ID_1,
+                source_subnet_id: SOURCE_SUBNET_ID_2,
+            },
+        )
+        .collect();
+
+    let join = spawn(stream.run());
+
+    let msg = WatchCertificatesRequest {
+        request_id: Some(Uuid::new_v4().into()),
+        command: Some(Command::OpenStream(GrpcOpenStream {
+            target_checkpoint: Some(TargetCheckpoint {
+                target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+                positions: vec![],
+            }),
+            source_checkpoint: None,
+            max_unacknowledged: 0,
+            target_subnet_filter: vec![],
+        })),
+    };
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, sender, .. } if stream_id == expected_stream_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Ok((_, OutboundMessage::StreamOpened(_))))),
+        "Expected StreamOpened, received: {msg:?}"
+    );
+
+    // The client doesn't consume the certificates, the queue of the stream overflows
+    for (certificate, position) in certificates.iter().zip(&positions) {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificate.clone(),
+                positions: vec![position.clone()],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    let mut received = Vec::new();
+    while let Ok(Some(Ok((_, OutboundMessage::CertificatePushed(pushed))))) =
+        tokio::time::timeout(Duration::from_millis(50), context.stream_receiver.recv()).await
+    {
+        received.push(pushed.certificate);
+    }
+
+    assert!(received.len() < certificates.len());
+
+    // Once drained, the stream replays the target stream from the first dropped position
+    let from = received.len();
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Replay { stream_id, ref target_subnet_stream_positions, .. }
+            if stream_id == expected_stream_id
+            && target_subnet_stream_positions[&TARGET_SUBNET_ID_1][&SOURCE_SUBNET_ID_2].position
+                == from as u64
+    );
+
+    context
+        .command_sender
+        .send(crate::stream::StreamCommand::Replayed {
+            target_certificates: certificates[from..]
+                .iter()
+                .cloned()
+                .zip(positions[from..].iter().cloned())
+                .collect(),
+            source_certificates: Vec::new(),
+        })
+        .await
+        .expect("Unable to send the replayed certificates during test");
+
+    while received.len() < certificates.len() {
+        let msg = context.stream_receiver.recv().await;
+        let Some(Ok((_, OutboundMessage::CertificatePushed(pushed)))) = msg else {
+            panic!("Expected CertificatePushed, received: {msg:?}");
+        };
+        received.push(pushed.certificate);
+    }
+
+    assert_eq!(received, certificates);
+
+    join.abort();
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(500))]
+#[test(tokio::test)]
+async fn source_subscription_is_sequenced_and_filtered() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 4);
+    certificates[2].certificate.target_subnets = vec![TARGET_SUBNET_ID_2];
+
+    let join = spawn(stream.run());
+
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: None,
+        source_checkpoint: Some(SourceCheckpoint {
+            source_subnet_ids: vec![SOURCE_SUBNET_ID_2.into()],
+            positions: vec![SourceStreamPosition {
+                source_subnet_id: Some(SOURCE_SUBNET_ID_2.into()),
+                position: 1,
+                certificate_id: None,
+            }],
+        }),
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![TARGET_SUBNET_ID_1.into()],
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, ref source_subnet_stream_positions, sender, .. }
+            if stream_id == expected_stream_id
+            && source_subnet_stream_positions[&SOURCE_SUBNET_ID_2].position == 1 => {
+            sender.send(Ok(()))
+        }
+    );
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Ok((_, OutboundMessage::StreamOpened(StreamOpened { ref subnet_ids })))) if subnet_ids == &[SOURCE_SUBNET_ID_2]),
+        "Expected StreamOpened, received: {msg:?}"
+    );
+
+    // The live delivery is ahead of the replay, which then pushes certificates already queued
+    for index in [3, 1, 0, 2, 1] {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificates[index].clone(),
+                positions: vec![],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    for expected_certificate in [&certificates[1], &certificates[3]] {
+        let msg = context.stream_receiver.recv().await;
+        assert!(
+            matches!(
+                msg,
+                Some(Ok((_, OutboundMessage::CertificatePushed(ref pushed)))) if &pushed.certificate == expected_certificate
+            ),
+            "Expected CertificatePushed with {}, received: {msg:?}",
+            expected_certificate.certificate.id
+        );
+    }
+
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), context.stream_receiver.recv())
+            .await
+            .is_err()
+    );
+
+    join.abort();
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(500))]
+#[test(tokio::test)]
+async fn malformed_source_checkpoint_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let join = spawn(stream.run());
+
+    // The position is given for a source subnet which isn't watched
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: None,
+        source_checkpoint: Some(SourceCheckpoint {
+            source_subnet_ids: vec![SOURCE_SUBNET_ID_2.into()],
+            positions: vec![SourceStreamPosition {
+                source_subnet_id: Some(TARGET_SUBNET_ID_1.into()),
+                position: 1,
+                certificate_id: None,
+            }],
+        }),
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Err(ref status)) if status.code() == tonic::Code::InvalidArgument),
+        "Expected an InvalidArgument status, received: {msg:?}"
+    );
+
+    let result = join.await?;
+    assert!(
+        matches!(result, Err(StreamError { stream_id, kind: StreamErrorKind::MalformedSourceCheckpoint }) if stream_id == context.stream_id),
+        "Doesn't match {result:?}",
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(2))]
+#[test(tokio::test)]
+async fn source_certificates_held_ahead_are_bounded() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let certificates = create_certificate_chain(
+        SOURCE_SUBNET_ID_2,
+        &[TARGET_SUBNET_ID_1],
+        MAX_AHEAD_CERTIFICATES + 3,
+    );
+
+    let join = spawn(stream.run());
+
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: None,
+        source_checkpoint: Some(SourceCheckpoint {
+            source_subnet_ids: vec![SOURCE_SUBNET_ID_2.into()],
+            positions: vec![],
+        }),
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, sender, .. } if stream_id == expected_stream_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Ok((_, OutboundMessage::StreamOpened(_))))),
+        "Expected StreamOpened, received: {msg:?}"
+    );
+
+    // The first certificate is missing, the ones after it are held until too many are held
+    for certificate in &certificates[1..] {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificate.clone(),
+                positions: vec![],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Replay { stream_id, ref source_subnet_stream_positions, .. }
+            if stream_id == expected_stream_id
+            && source_subnet_stream_positions[&SOURCE_SUBNET_ID_2].position == 0
+    );
+
+    context
+        .command_sender
+        .send(crate::stream::StreamCommand::Replayed {
+            target_certificates: Vec::new(),
+            source_certificates: certificates.clone(),
+        })
+        .await
+        .expect("Unable to send the replayed certificates during test");
+
+    for expected_certificate in &certificates {
+        let msg = context.stream_receiver.recv().await;
+        assert!(
+            matches!(
+                msg,
+                Some(Ok((_, OutboundMessage::CertificatePushed(ref pushed)))) if &pushed.certificate == expected_certificate
+            ),
+            "Expected CertificatePushed with {}, received: {msg:?}",
+            expected_certificate.certificate.id
+        );
+    }
+
+    join.abort();
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(500))]
+#[test(tokio::test)]
+async fn failed_sync_closes_the_stream() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let join = spawn(stream.run());
+
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: Some(TargetCheckpoint {
+            target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+            positions: vec![],
+        }),
+        source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, sender, .. } if stream_id == expected_stream_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Ok((_, OutboundMessage::StreamOpened(_))))),
+        "Expected StreamOpened, received: {msg:?}"
+    );
+
+    context
+        .command_sender
+        .send(crate::stream::StreamCommand::SyncFailed)
+        .await
+        .expect("Unable to send the sync failure during test");
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(msg, Some(Err(ref status)) if status.code() == tonic::Code::Internal),
+        "Expected an Internal status, received: {msg:?}"
+    );
+
+    let result = join.await?;
+    assert!(
+        matches!(result, Err(StreamError { stream_id, kind: StreamErrorKind::SyncFailed }) if stream_id == context.stream_id),
+        "Doesn't match {result:?}",
+    );
+
+    Ok(())
+}
 
 #[test(tokio::test)]
 #[ignore = "not yet implemented"]
-async fn resuming_one_subscription() {}
+async fn pausing_one_subscription() {}
 
 #[test(tokio::test)]
 #[ignore = "not yet implemented"]
-async fn resuming_all_subscription() {}
+async fn resuming_one_subscription() {}
 
 #[rstest]
 #[test(tokio::test)]
@@ -213,6 +723,8 @@ async fn closing_client_stream() -> Result<(), Box<dyn std::error::Error>> {
             positions: vec![],
         }),
         source_checkpoint: None,
+        max_unacknowledged: 0,
+        target_subnet_filter: vec![],
     }
     .into();
 
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use futures::{stream::BoxStream, StreamExt};
 use hyper::body::Sender;
 use tokio::sync::mpsc;
@@ -90,14 +88,13 @@ impl StreamBuilder {
         let (internal_runtime_command_sender, runtime_receiver) =
             mpsc::channel(self.runtime_channel_size);
 
-        let testable_stream = Stream {
+        let testable_stream = Stream::new(
             stream_id,
-            target_subnet_listeners: HashMap::new(),
-            outbound_stream: sender,
-            inbound_stream: stream,
-            internal_runtime_command_sender,
+            stream,
+            sender,
             command_receiver,
-        };
+            internal_runtime_command_sender,
+        );
 
         (
             tx,
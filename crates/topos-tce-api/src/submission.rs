@@ -0,0 +1,38 @@
+//! Conversion of the outcome of a certificate submission, shared by the GraphQL and REST APIs
+
+use topos_core::api::graphql::certificate::{CertificateSubmission, PendingResult};
+use topos_core::uci::CertificateId;
+use topos_tce_storage::types;
+use tracing::error;
+
+use crate::RuntimeError;
+
+/// Convert the result of the submission of a certificate, a rejection not being an error
+pub(crate) fn certificate_submission(
+    certificate_id: CertificateId,
+    result: Result<types::PendingResult, RuntimeError>,
+) -> Result<CertificateSubmission, RuntimeError> {
+    let (result, pending_id, rejection) = match result {
+        Ok(types::PendingResult::InPending(pending_id)) => {
+            (PendingResult::InPending, Some(pending_id), None)
+        }
+        Ok(types::PendingResult::AwaitPrecedence) => (PendingResult::AwaitPrecedence, None, None),
+        Ok(types::PendingResult::AlreadyPending) => (PendingResult::AlreadyPending, None, None),
+        Ok(types::PendingResult::AlreadyDelivered) => (PendingResult::AlreadyDelivered, None, None),
+        Err(RuntimeError::InvalidCertificate(error)) => {
+            (PendingResult::Rejected, None, Some(error.to_string()))
+        }
+        Err(error) => {
+            error!("Unable to submit the certificate {certificate_id}: {error}");
+
+            return Err(error);
+        }
+    };
+
+    Ok(CertificateSubmission {
+        certificate_id: certificate_id.into(),
+        result,
+        pending_id,
+        rejection,
+    })
+}
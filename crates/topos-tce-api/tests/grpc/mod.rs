@@ -1 +1,2 @@
 mod certificate_precedence;
+mod submit_certificates;
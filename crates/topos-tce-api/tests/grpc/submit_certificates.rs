@@ -0,0 +1,78 @@
+use futures::{Stream, StreamExt};
+use rstest::rstest;
+use std::time::Duration;
+use test_log::test;
+use topos_core::api::grpc::tce::v1::{
+    submit_certificates_response::PendingResult, SubmitCertificatesRequest,
+};
+use topos_core::uci::ValidationError;
+use topos_tce_api::{RuntimeError, RuntimeEvent};
+use topos_tce_storage::types;
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
+    tce::public_api::{create_public_api, PublicApiContext},
+};
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submit_certificates_in_batch(
+    #[future] create_public_api: (PublicApiContext, impl Stream<Item = RuntimeEvent>),
+) {
+    let (api_context, events) = create_public_api.await;
+    let mut client = api_context.api_client;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+
+    let expected: Vec<_> = certificates
+        .iter()
+        .map(|certificate| certificate.certificate.clone())
+        .collect();
+    let runtime = async move {
+        tokio::pin!(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::CertificatesSubmitted {
+                certificates,
+                sender,
+            } = event
+            {
+                assert_eq!(certificates, expected);
+
+                _ = sender.send(vec![
+                    Ok(types::PendingResult::InPending(3)),
+                    Ok(types::PendingResult::AwaitPrecedence),
+                    Err(RuntimeError::InvalidCertificate(
+                        ValidationError::SelfTarget,
+                    )),
+                ]);
+
+                break;
+            }
+        }
+    };
+
+    let (response, _) = tokio::join!(
+        client.submit_certificates(SubmitCertificatesRequest {
+            certificates: certificates
+                .iter()
+                .map(|certificate| certificate.certificate.clone().into())
+                .collect(),
+        }),
+        runtime
+    );
+    let submissions = response.unwrap().into_inner().submissions;
+
+    assert_eq!(submissions.len(), 3);
+    for (submission, certificate) in submissions.iter().zip(&certificates) {
+        assert_eq!(
+            submission.certificate_id,
+            Some(certificate.certificate.id.into())
+        );
+    }
+
+    assert_eq!(submissions[0].result(), PendingResult::InPending);
+    assert_eq!(submissions[0].pending_id, 3);
+    assert_eq!(submissions[1].result(), PendingResult::AwaitPrecedence);
+    assert_eq!(submissions[2].result(), PendingResult::Rejected);
+    assert!(submissions[2].rejection.is_some());
+}
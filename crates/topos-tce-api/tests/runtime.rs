@@ -11,21 +11,25 @@ use tokio_stream::StreamExt;
 use tonic::transport::channel;
 use tonic::transport::Uri;
 use topos_core::api::graphql::certificate::Certificate as GraphQLCertificate;
-use topos_core::api::grpc::shared::v1::checkpoints::TargetCheckpoint;
-use topos_core::api::grpc::shared::v1::positions::TargetStreamPosition;
+use topos_core::api::grpc::shared::v1::checkpoints::{SourceCheckpoint, TargetCheckpoint};
+use topos_core::api::grpc::shared::v1::positions::{SourceStreamPosition, TargetStreamPosition};
 use topos_core::types::stream::Position;
 use topos_core::types::CertificateDelivered;
 use topos_core::uci::CertificateId;
 use topos_core::{
     api::grpc::tce::v1::{
         api_service_client::ApiServiceClient,
-        watch_certificates_request::OpenStream,
+        console_service_client::ConsoleServiceClient,
+        watch_certificates_request::{Ack, Command, OpenStream},
         watch_certificates_response::{CertificatePushed, Event},
+        EvictPendingCertificateRequest, GetPeersRequest, GetSourceHeadsRequest, SetLogLevelRequest,
+        SubmitCertificatesRequest, WatchCertificatesRequest,
     },
     uci::Certificate,
 };
 use topos_metrics::{STORAGE_PENDING_POOL_COUNT, STORAGE_PRECEDENCE_POOL_COUNT};
-use topos_tce_api::{Runtime, RuntimeEvent};
+use topos_p2p::{PeerId, PeerInfo};
+use topos_tce_api::{ClientLimits, Runtime, RuntimeError, RuntimeEvent};
 use topos_tce_storage::types::CertificateDeliveredWithPositions;
 use topos_tce_storage::validator::ValidatorStore;
 use topos_tce_storage::StorageClient;
@@ -36,6 +40,7 @@ use topos_test_sdk::constants::*;
 use topos_test_sdk::networking::get_available_addr;
 use topos_test_sdk::storage::{create_fullnode_store, create_validator_store, storage_client};
 use topos_test_sdk::tce::public_api::{broadcast_stream, create_public_api, PublicApiContext};
+use uuid::Uuid;
 
 mod grpc;
 
@@ -57,7 +62,9 @@ async fn runtime_can_dispatch_a_cert(
                     target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
                     positions: Vec::new()
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                max_unacknowledged: 0,
+                target_subnet_filter: vec![],
             }.into()
         };
 
@@ -139,7 +146,9 @@ async fn can_catchup_with_old_certs(
                     target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
                     positions: Vec::new()
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                max_unacknowledged: 0,
+                target_subnet_filter: vec![],
             }.into()
         };
 
@@ -201,6 +210,197 @@ async fn can_catchup_with_old_certs(
     drop(api_context.api_context.take());
 }
 
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn can_resume_from_acknowledged_position(
+    #[with(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 15)]
+    #[from(create_certificate_chain)]
+    certificates: Vec<CertificateDelivered>,
+) {
+    let storage_client = storage_client::partial_1(&certificates[..]);
+    let (api_context, _) = create_public_api::partial_1(storage_client).await;
+
+    let request_id = Uuid::new_v4();
+    let open_stream = WatchCertificatesRequest {
+        request_id: Some(request_id.into()),
+        command: Some(Command::OpenStream(OpenStream {
+            target_checkpoint: Some(TargetCheckpoint {
+                target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+                positions: Vec::new(),
+            }),
+            source_checkpoint: None,
+            max_unacknowledged: 0,
+            target_subnet_filter: vec![],
+        })),
+    };
+
+    // First connection, consuming and acknowledging the first five certificates
+    let (ack_sender, mut ack_receiver) = mpsc::channel::<WatchCertificatesRequest>(1);
+    let first_open_stream = open_stream.clone();
+    let in_stream = async_stream::stream! {
+        yield first_open_stream;
+        while let Some(ack) = ack_receiver.recv().await {
+            yield ack;
+        }
+    };
+
+    let mut client = api_context.api_client.clone();
+    let mut resp_stream = client
+        .watch_certificates(in_stream)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut received = Vec::new();
+    while received.len() < 5 {
+        if let Some(Event::CertificatePushed(CertificatePushed {
+            certificate: Some(certificate),
+            ..
+        })) = resp_stream.next().await.unwrap().unwrap().event
+        {
+            received.push(Certificate::try_from(certificate).unwrap());
+        }
+    }
+
+    ack_sender
+        .send(WatchCertificatesRequest {
+            request_id: Some(request_id.into()),
+            command: Some(Command::Ack(Ack {
+                position: Some(TargetStreamPosition {
+                    certificate_id: Some(received[4].id.into()),
+                    position: 4,
+                    source_subnet_id: Some(SOURCE_SUBNET_ID_1.into()),
+                    target_subnet_id: Some(TARGET_SUBNET_ID_1.into()),
+                }),
+            })),
+        })
+        .await
+        .unwrap();
+
+    // Let the acknowledgement reach the runtime before disconnecting
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    drop(ack_sender);
+    drop(resp_stream);
+
+    // Second connection, resuming without any position from the client
+    let in_stream = async_stream::stream! {
+        yield open_stream;
+    };
+
+    let mut client = api_context.api_client.clone();
+    let mut resp_stream = client
+        .watch_certificates(in_stream)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut index = 5;
+    while index < certificates.len() {
+        if let Some(Event::CertificatePushed(CertificatePushed {
+            certificate: Some(certificate),
+            ..
+        })) = resp_stream.next().await.unwrap().unwrap().event
+        {
+            assert_eq!(
+                certificates[index].certificate,
+                Certificate::try_from(certificate).unwrap(),
+                "Certificate at index {index} not received"
+            );
+            index += 1;
+        }
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn can_watch_source_subnet_from_position(
+    #[with(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 15)]
+    #[from(create_certificate_chain)]
+    certificates: Vec<CertificateDelivered>,
+) {
+    let storage_client = storage_client::partial_1(&certificates[..]);
+    let (mut api_context, _) = create_public_api::partial_1(storage_client).await;
+
+    let mut client = api_context.api_client.clone();
+
+    let in_stream = async_stream::stream! {
+        yield OpenStream {
+            target_checkpoint: None,
+            source_checkpoint: Some(SourceCheckpoint {
+                source_subnet_ids: vec![SOURCE_SUBNET_ID_1.into()],
+                positions: vec![SourceStreamPosition {
+                    source_subnet_id: Some(SOURCE_SUBNET_ID_1.into()),
+                    position: 10,
+                    certificate_id: None,
+                }],
+            }),
+            max_unacknowledged: 0,
+            target_subnet_filter: vec![],
+        }.into()
+    };
+
+    let mut resp_stream = client
+        .watch_certificates(in_stream)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let last = certificates.last().map(|c| c.certificate.id).unwrap();
+    let live_certificate = create_certificate_at_position(
+        certificates.len().try_into().unwrap(),
+        create_certificate(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], Some(last)),
+    );
+
+    let mut target_positions = std::collections::HashMap::new();
+    target_positions.insert(
+        TARGET_SUBNET_ID_1,
+        topos_core::api::grpc::checkpoints::TargetStreamPosition {
+            position: certificates.len() as u64,
+            source_subnet_id: SOURCE_SUBNET_ID_1,
+            target_subnet_id: TARGET_SUBNET_ID_1,
+            certificate_id: Some(live_certificate.certificate.id),
+        },
+    );
+
+    api_context
+        .client
+        .dispatch_certificate(live_certificate.clone(), target_positions)
+        .await;
+
+    // The replay starts at the requested position and is followed by the live delivery
+    let expected = certificates[10..]
+        .iter()
+        .chain(std::iter::once(&live_certificate));
+
+    for (position, expected) in (10..).zip(expected) {
+        let received = loop {
+            if let Some(Event::CertificatePushed(certificate_pushed)) =
+                resp_stream.next().await.unwrap().unwrap().event
+            {
+                break certificate_pushed;
+            }
+        };
+
+        assert_eq!(
+            expected.certificate,
+            Certificate::try_from(received.certificate.unwrap()).unwrap(),
+            "Certificate at position {position} not received"
+        );
+        assert_eq!(
+            received.source_position,
+            Some(SourceStreamPosition {
+                source_subnet_id: Some(SOURCE_SUBNET_ID_1.into()),
+                position,
+                certificate_id: Some(expected.certificate.id.into()),
+            })
+        );
+    }
+
+    drop(api_context.api_context.take());
+}
+
 #[rstest]
 #[timeout(Duration::from_secs(4))]
 #[test(tokio::test)]
@@ -262,7 +462,9 @@ async fn can_catchup_with_old_certs_with_position(
                         }
                     ]
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                max_unacknowledged: 0,
+                target_subnet_filter: vec![],
             }.into()
         };
 
@@ -498,7 +700,9 @@ async fn can_query_graphql_endpoint_for_certificates(
                         }
                     ]
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                max_unacknowledged: 0,
+                target_subnet_filter: vec![],
             }.into()
         };
 
@@ -628,6 +832,119 @@ async fn can_query_graphql_endpoint_for_certificates(
     );
 }
 
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn can_paginate_graphql_streams(
+    broadcast_stream: broadcast::Receiver<CertificateDeliveredWithPositions>,
+) {
+    let addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 15);
+
+    let fullnode_store = create_fullnode_store::default().await;
+    let store = create_validator_store(
+        &certificates[..],
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let storage_client = StorageClient::new(store.clone());
+
+    let (_runtime_client, _launcher, _ctx) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(storage_client)
+        .store(store)
+        .serve_grpc_addr(addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_metrics_addr(metrics_addr)
+        .build_and_launch()
+        .await;
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let query = format!(
+        r#"
+        query {{
+            sourcePage: sourceStreamCertificates(
+                sourceSubnetId: "{SOURCE_SUBNET_ID_1}",
+                positions: {{ to: 8 }},
+                after: "5",
+                first: 4
+            ) {{
+                edges {{ cursor node {{ id }} }}
+                pageInfo {{ hasPreviousPage hasNextPage }}
+            }}
+            filteredPage: sourceStreamCertificates(
+                sourceSubnetId: "{SOURCE_SUBNET_ID_1}",
+                targetSubnetId: "{TARGET_SUBNET_ID_2}"
+            ) {{
+                edges {{ cursor }}
+                pageInfo {{ hasPreviousPage hasNextPage }}
+            }}
+            targetPage: targetStreamCertificates(
+                targetSubnetId: "{TARGET_SUBNET_ID_1}",
+                sourceSubnetId: "{SOURCE_SUBNET_ID_1}",
+                first: 5
+            ) {{
+                edges {{ cursor }}
+                pageInfo {{ hasPreviousPage hasNextPage endCursor }}
+            }}
+        }}
+        "#
+    );
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}", graphql_addr))
+        .json(&serde_json::json!({
+            "query": query,
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response["data"]["sourcePage"],
+        serde_json::json!({
+            "edges": certificates[6..=8]
+                .iter()
+                .enumerate()
+                .map(|(index, certificate)| serde_json::json!({
+                    "cursor": (index + 6).to_string(),
+                    "node": { "id": certificate.certificate.id.to_string() },
+                }))
+                .collect::<Vec<_>>(),
+            "pageInfo": { "hasPreviousPage": true, "hasNextPage": false },
+        })
+    );
+
+    assert_eq!(
+        response["data"]["filteredPage"],
+        serde_json::json!({
+            "edges": [],
+            "pageInfo": { "hasPreviousPage": false, "hasNextPage": false },
+        })
+    );
+
+    assert_eq!(
+        response["data"]["targetPage"],
+        serde_json::json!({
+            "edges": (0..5)
+                .map(|position| serde_json::json!({ "cursor": position.to_string() }))
+                .collect::<Vec<_>>(),
+            "pageInfo": { "hasPreviousPage": false, "hasNextPage": true, "endCursor": "4" },
+        })
+    );
+}
+
 #[rstest]
 #[timeout(Duration::from_secs(4))]
 #[test(tokio::test)]
@@ -867,3 +1184,409 @@ async fn check_precedence(
         certificates[1].certificate.id
     );
 }
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn console_can_inspect_and_evict(
+    broadcast_stream: broadcast::Receiver<CertificateDeliveredWithPositions>,
+) {
+    let addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+    let console_addr = get_available_addr();
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    let fullnode_store = create_fullnode_store::default().await;
+
+    let store: Arc<ValidatorStore> = create_validator_store(
+        &certificates[..1],
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let storage_client = StorageClient::new(store.clone());
+
+    let (_runtime_client, events, _ctx) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(storage_client)
+        .store(store)
+        .serve_grpc_addr(addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_metrics_addr(metrics_addr)
+        .serve_console_addr(console_addr)
+        .build_and_launch()
+        .await;
+
+    let evicted = certificates[1].certificate.id;
+    spawn(async move {
+        tokio::pin!(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::EvictPendingCertificate {
+                certificate_id,
+                sender,
+            } = event
+            {
+                _ = sender.send(if certificate_id == evicted {
+                    Ok(())
+                } else {
+                    Err(RuntimeError::CertificateNotPending(certificate_id))
+                });
+            }
+        }
+    });
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = ConsoleServiceClient::connect(format!("http://{console_addr}"))
+        .await
+        .unwrap();
+
+    let heads = client
+        .get_source_heads(GetSourceHeadsRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .source_heads;
+
+    assert_eq!(heads.len(), 1);
+    assert_eq!(heads[0].source_subnet_id, Some(SOURCE_SUBNET_ID_1.into()));
+    assert_eq!(
+        heads[0].certificate_id,
+        Some(certificates[0].certificate.id.into())
+    );
+
+    client
+        .evict_pending_certificate(EvictPendingCertificateRequest {
+            certificate_id: Some(evicted.into()),
+        })
+        .await
+        .unwrap();
+
+    let status = client
+        .evict_pending_certificate(EvictPendingCertificateRequest {
+            certificate_id: Some(certificates[0].certificate.id.into()),
+        })
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), tonic::Code::NotFound);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn console_can_list_peers_and_set_the_log_level(
+    broadcast_stream: broadcast::Receiver<CertificateDeliveredWithPositions>,
+) {
+    let addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+    let console_addr = get_available_addr();
+
+    let fullnode_store = create_fullnode_store::default().await;
+    let store: Arc<ValidatorStore> =
+        create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+
+    let (_runtime_client, events, _ctx) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(StorageClient::new(store.clone()))
+        .store(store)
+        .serve_grpc_addr(addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_metrics_addr(metrics_addr)
+        .serve_console_addr(console_addr)
+        .build_and_launch()
+        .await;
+
+    let peer = PeerInfo {
+        peer_id: PeerId::random(),
+        addresses: vec!["/ip4/127.0.0.1/tcp/9090".parse().unwrap()],
+        outbound: true,
+        denied: false,
+    };
+    let expected_peer = peer.clone();
+    spawn(async move {
+        tokio::pin!(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::GetPeers { sender } = event {
+                _ = sender.send(Ok(vec![expected_peer.clone()]));
+            }
+        }
+    });
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = ConsoleServiceClient::connect(format!("http://{console_addr}"))
+        .await
+        .unwrap();
+
+    let peers = client
+        .get_peers(GetPeersRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .peers;
+
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0].peer_id, peer.peer_id.to_string());
+    assert_eq!(
+        peers[0].addresses,
+        vec!["/ip4/127.0.0.1/tcp/9090".to_string()]
+    );
+    assert!(peers[0].outbound);
+    assert!(!peers[0].denied);
+
+    // The filter is checked before being applied
+    let status = client
+        .set_log_level(SetLogLevelRequest {
+            filter: "topos=unknown_level".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // The log layer isn't set up by the tests, leaving no filter to replace
+    let status = client
+        .set_log_level(SetLogLevelRequest {
+            filter: "warn,topos=debug".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn can_query_the_rest_api(
+    broadcast_stream: broadcast::Receiver<CertificateDeliveredWithPositions>,
+) {
+    let addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+    let rest_addr = get_available_addr();
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 6);
+
+    let fullnode_store = create_fullnode_store::default().await;
+    let store = create_validator_store(
+        &certificates[..5],
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let storage_client = StorageClient::new(store.clone());
+
+    let (_runtime_client, _launcher, _ctx) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(storage_client)
+        .store(store)
+        .serve_grpc_addr(addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_metrics_addr(metrics_addr)
+        .serve_rest_addr(rest_addr)
+        .build_and_launch()
+        .await;
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let get = |path: String| {
+        let client = client.clone();
+
+        async move {
+            client
+                .get(format!("http://{rest_addr}{path}"))
+                .send()
+                .await
+                .unwrap()
+        }
+    };
+
+    let certificate = get(format!(
+        "/v1/certificates/{}",
+        certificates[2].certificate.id
+    ))
+    .await
+    .json::<serde_json::Value>()
+    .await
+    .unwrap();
+    assert_eq!(
+        certificate["id"],
+        certificates[2].certificate.id.to_string()
+    );
+
+    let missing = get(format!(
+        "/v1/certificates/{}",
+        certificates[5].certificate.id
+    ))
+    .await;
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let invalid = get("/v1/certificates/invalid".to_string()).await;
+    assert_eq!(invalid.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let head = get(format!("/v1/subnets/{SOURCE_SUBNET_ID_1}/source-head"))
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    assert_eq!(head["position"], 4);
+    assert_eq!(
+        head["certificate"]["id"],
+        certificates[4].certificate.id.to_string()
+    );
+
+    let stream = get(format!(
+        "/v1/subnets/{TARGET_SUBNET_ID_1}/target-streams/{SOURCE_SUBNET_ID_1}?from=1&limit=2"
+    ))
+    .await
+    .json::<serde_json::Value>()
+    .await
+    .unwrap();
+    assert_eq!(
+        stream,
+        serde_json::json!(certificates[1..3]
+            .iter()
+            .enumerate()
+            .map(|(index, certificate)| serde_json::json!({
+                "position": index + 1,
+                "certificate": serde_json::to_value(GraphQLCertificate::from(certificate)).unwrap(),
+            }))
+            .collect::<Vec<_>>())
+    );
+
+    let document = get("/openapi.json".to_string())
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    assert!(document["paths"]["/v1/certificates/{certificate_id}"]["get"].is_object());
+    assert!(document["paths"]["/v1/certificates"]["post"].is_object());
+
+    // The schemas of the bodies are generated from their types
+    assert_eq!(
+        document["paths"]["/v1/certificates"]["post"]["requestBody"]["content"]["application/json"]
+            ["schema"],
+        serde_json::json!({ "$ref": "#/components/schemas/CertificateInput" })
+    );
+    assert_eq!(
+        document["paths"]["/v1/subnets/{target_subnet_id}/target-streams/{source_subnet_id}"]
+            ["get"]["responses"]["200"]["content"]["application/json"]["schema"],
+        serde_json::json!({
+            "type": "array",
+            "items": { "$ref": "#/components/schemas/StreamCertificate" },
+        })
+    );
+
+    let schemas = &document["components"]["schemas"];
+    assert_eq!(
+        schemas["StreamCertificate"]["properties"]["certificate"],
+        serde_json::json!({ "$ref": "#/components/schemas/Certificate" })
+    );
+    assert_eq!(
+        schemas["CertificateSubmission"]["properties"]["pendingId"],
+        serde_json::json!({
+            "type": "integer",
+            "nullable": true,
+            "description": "Index of the certificate in the pending pool, when inserted in it",
+        })
+    );
+    assert_eq!(
+        schemas["PendingResult"]["enum"],
+        serde_json::json!([
+            "InPending",
+            "AwaitPrecedence",
+            "AlreadyPending",
+            "AlreadyDelivered",
+            "Rejected"
+        ])
+    );
+    for schema in ["Certificate", "CertificateInput", "SourceHead", "Error"] {
+        assert!(schemas[schema]["properties"].is_object(), "{schema}");
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn grpc_clients_are_limited(
+    broadcast_stream: broadcast::Receiver<CertificateDeliveredWithPositions>,
+) {
+    let addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    let fullnode_store = create_fullnode_store::default().await;
+    let store = create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+
+    let storage_client = StorageClient::new(store.clone());
+
+    let (_runtime_client, _launcher, _ctx) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(storage_client)
+        .store(store)
+        .serve_grpc_addr(addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_metrics_addr(metrics_addr)
+        .with_client_limits(ClientLimits {
+            submissions_per_second: Some(1),
+            max_streams_per_peer: Some(1),
+            max_pending_certificates: None,
+        })
+        .build_and_launch()
+        .await;
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = ApiServiceClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+
+    let status = client
+        .submit_certificates(SubmitCertificatesRequest {
+            certificates: certificates
+                .iter()
+                .map(|certificate| certificate.certificate.clone().into())
+                .collect(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+    let open_stream = || {
+        async_stream::stream! {
+            yield WatchCertificatesRequest {
+                request_id: Some(Uuid::new_v4().into()),
+                command: Some(Command::OpenStream(OpenStream {
+                    target_checkpoint: Some(TargetCheckpoint {
+                        target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+                        positions: Vec::new(),
+                    }),
+                    source_checkpoint: None,
+                    max_unacknowledged: 0,
+                    target_subnet_filter: vec![],
+                })),
+            };
+        }
+    };
+
+    let stream = client.watch_certificates(open_stream()).await.unwrap();
+
+    let status = client.watch_certificates(open_stream()).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+    // Closing the first stream releases it
+    drop(stream);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert!(client.watch_certificates(open_stream()).await.is_ok());
+}
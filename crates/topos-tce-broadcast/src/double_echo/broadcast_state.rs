@@ -1,17 +1,22 @@
 use crate::event::ProtocolEvents;
 use crate::sampler::SubscriptionsView;
+use crate::BroadcastProgress;
 use std::sync::Arc;
-use std::{collections::HashSet, time};
+use std::{
+    collections::{HashMap, HashSet},
+    time,
+};
 use tokio::sync::mpsc;
 use topos_core::{
     types::{
         stream::{CertificateSourceStreamPosition, Position},
-        CertificateDelivered, ProofOfDelivery, Ready, ValidatorId,
+        CertificateDelivered, ProofOfDelivery, ValidatorId,
     },
     uci::Certificate,
 };
-use topos_crypto::messages::MessageSigner;
+use topos_crypto::messages::{MessageSigner, Signature};
 use topos_metrics::DOUBLE_ECHO_BROADCAST_FINISHED_TOTAL;
+use topos_tce_storage::types::BroadcastState as PersistedBroadcastState;
 use tracing::{debug, error, info, trace};
 mod status;
 
@@ -29,7 +34,11 @@ pub struct BroadcastState {
     message_signer: Arc<MessageSigner>,
     event_sender: mpsc::Sender<ProtocolEvents>,
     delivery_time: time::Instant,
-    readies: HashSet<Ready>,
+    /// Validators from which an Echo has been received
+    echoes: HashSet<ValidatorId>,
+    /// Signed Ready messages received from the validators, used to build the
+    /// [`ProofOfDelivery`] once the certificate is delivered
+    readies: HashMap<ValidatorId, Signature>,
     pub(crate) expected_position: Option<Position>,
 }
 
@@ -57,7 +66,8 @@ impl BroadcastState {
             message_signer,
             event_sender,
             delivery_time: time::Instant::now(),
-            readies: HashSet::new(),
+            echoes: HashSet::new(),
+            readies: HashMap::new(),
             expected_position: None,
         };
 
@@ -95,25 +105,76 @@ impl BroadcastState {
                 readies: self
                     .readies
                     .iter()
-                    .cloned()
-                    .map(|r| (r, "signature".to_string()))
+                    .map(|(validator_id, signature)| (*validator_id, *signature))
                     .collect(),
                 threshold: self.delivery_threshold as u64,
             },
         }
     }
 
+    /// Resume the broadcast from the Echo and Ready messages received before a restart
+    ///
+    /// The status is recomputed from the messages, sending again our own Echo and Ready
+    /// messages as they may not have reached the network before the restart.
+    pub fn resume(&mut self, persisted: PersistedBroadcastState) -> Status {
+        for validator_id in persisted.echoes {
+            if self.subscriptions_view.echo.remove(&validator_id) {
+                self.echoes.insert(validator_id);
+            }
+        }
+
+        for (validator_id, signature) in persisted.readies {
+            if self.subscriptions_view.ready.remove(&validator_id) {
+                self.readies.insert(validator_id, signature);
+            }
+        }
+
+        while self.update_status().is_some() {}
+
+        self.status
+    }
+
+    /// Returns the progress of the broadcast to be checkpointed
+    pub fn checkpoint(&self) -> PersistedBroadcastState {
+        PersistedBroadcastState {
+            echoes: self.echoes.iter().copied().collect(),
+            readies: self
+                .readies
+                .iter()
+                .map(|(validator_id, signature)| (*validator_id, *signature))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of Echo and Ready messages received so far
+    pub fn progress(&self) -> BroadcastProgress {
+        BroadcastProgress {
+            certificate_id: self.certificate.id,
+            echo_count: self.echoes.len(),
+            ready_count: self.readies.len(),
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
     pub fn apply_echo(&mut self, validator_id: ValidatorId) -> Option<Status> {
         if self.subscriptions_view.echo.remove(&validator_id) {
+            self.echoes.insert(validator_id);
             self.update_status()
         } else {
             None
         }
     }
 
-    pub fn apply_ready(&mut self, validator_id: ValidatorId) -> Option<Status> {
+    pub fn apply_ready(
+        &mut self,
+        validator_id: ValidatorId,
+        signature: Signature,
+    ) -> Option<Status> {
         if self.subscriptions_view.ready.remove(&validator_id) {
-            self.readies.insert(validator_id.to_string());
+            self.readies.insert(validator_id, signature);
             self.update_status()
         } else {
             None
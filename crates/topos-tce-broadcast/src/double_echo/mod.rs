@@ -23,7 +23,7 @@ use topos_config::tce::broadcast::ReliableBroadcastParams;
 use topos_core::{types::ValidatorId, uci::CertificateId};
 use topos_crypto::messages::{MessageSigner, Signature};
 use topos_tce_storage::store::ReadStore;
-use topos_tce_storage::types::CertificateDeliveredWithPositions;
+use topos_tce_storage::types::{CertificateDeliveredWithPositions, EpochId};
 use topos_tce_storage::validator::ValidatorStore;
 use tracing::{debug, error, info, warn};
 
@@ -48,6 +48,11 @@ pub struct DoubleEcho {
     pub message_signer: Arc<MessageSigner>,
     /// List of approved validators through smart contract and/or genesis
     pub validators: HashSet<ValidatorId>,
+    /// Current epoch, `validators` being its validator set
+    pub epoch_id: EpochId,
+    /// Validators of the previous epoch, still accepted to complete the broadcasts that
+    /// started during the previous epoch
+    pub previous_validators: HashSet<ValidatorId>,
     pub validator_store: Arc<ValidatorStore>,
     pub broadcast_sender: broadcast::Sender<CertificateDeliveredWithPositions>,
 
@@ -75,6 +80,8 @@ impl DoubleEcho {
             validator_id,
             message_signer,
             validators: validators.clone(),
+            epoch_id: 0,
+            previous_validators: HashSet::new(),
             task_manager_message_sender,
             command_receiver,
             event_sender,
@@ -134,6 +141,15 @@ impl DoubleEcho {
                 },
                 Some(command) = self.command_receiver.recv() => {
                     match command {
+                        DoubleEchoCommand::NewEpoch { epoch_id, validators } => {
+                            self.handle_new_epoch(epoch_id, validators).await
+                        }
+
+                        command @ (DoubleEchoCommand::GetBroadcastProgress { .. }
+                        | DoubleEchoCommand::GetCertificateProgress { .. }
+                        | DoubleEchoCommand::Evict { .. }) => {
+                            _ = self.task_manager_message_sender.send(command).await;
+                        }
 
                         command if self.subscriptions.is_some() => {
                             match command {
@@ -145,7 +161,7 @@ impl DoubleEcho {
                                     }
                                 DoubleEchoCommand::Echo { certificate_id, validator_id, signature } => {
                                     // Check if source is part of known_validators
-                                    if !self.validators.contains(&validator_id) {
+                                    if !self.is_known_validator(&validator_id) {
                                         debug!("ECHO message comes from non-validator: {}", validator_id);
                                         continue;
                                     }
@@ -163,7 +179,7 @@ impl DoubleEcho {
                                 },
                                 DoubleEchoCommand::Ready { certificate_id, validator_id, signature } => {
                                     // Check if source is part of known_validators
-                                    if !self.validators.contains(&validator_id) {
+                                    if !self.is_known_validator(&validator_id) {
                                         debug!("READY message comes from non-validator: {}", validator_id);
                                         continue;
                                     }
@@ -179,6 +195,11 @@ impl DoubleEcho {
 
                                     self.handle_ready(certificate_id, validator_id, signature).await
                                 },
+                                // Handled regardless of the subscriptions
+                                DoubleEchoCommand::NewEpoch { .. }
+                                | DoubleEchoCommand::GetBroadcastProgress { .. }
+                                | DoubleEchoCommand::GetCertificateProgress { .. }
+                                | DoubleEchoCommand::Evict { .. } => {}
                             }
 
                         },
@@ -205,6 +226,58 @@ impl DoubleEcho {
 }
 
 impl DoubleEcho {
+    /// Whether the validator is allowed to sign Echo and Ready messages, either as
+    /// a validator of the current epoch or of the previous one
+    fn is_known_validator(&self, validator_id: &ValidatorId) -> bool {
+        self.validators.contains(validator_id) || self.previous_validators.contains(validator_id)
+    }
+
+    /// Switch to the validator set of a new epoch
+    ///
+    /// The validator set is persisted, then both the [`DoubleEcho`] and the
+    /// [`TaskManager`](crate::task_manager::TaskManager) are updated with the new
+    /// subscriptions and thresholds. Running broadcasts keep their own subscriptions.
+    pub async fn handle_new_epoch(&mut self, epoch_id: EpochId, validators: HashSet<ValidatorId>) {
+        if epoch_id <= self.epoch_id {
+            warn!(
+                "Ignoring epoch {} as the current epoch is already {}",
+                epoch_id, self.epoch_id
+            );
+            return;
+        }
+
+        if let Err(error) = self
+            .validator_store
+            .epoch_validators_store()
+            .insert_validators(epoch_id, validators.clone())
+        {
+            error!(
+                "Unable to persist the validators of epoch {}: {:?}",
+                epoch_id, error
+            );
+            return;
+        }
+
+        info!(
+            "Starting epoch {} with {} validators",
+            epoch_id,
+            validators.len()
+        );
+
+        self.params = ReliableBroadcastParams::new(validators.len());
+        self.subscriptions = SubscriptionsView::from_validators(&validators);
+        self.previous_validators = std::mem::replace(&mut self.validators, validators.clone());
+        self.epoch_id = epoch_id;
+
+        _ = self
+            .task_manager_message_sender
+            .send(DoubleEchoCommand::NewEpoch {
+                epoch_id,
+                validators,
+            })
+            .await;
+    }
+
     pub async fn handle_echo(
         &mut self,
         certificate_id: CertificateId,
@@ -46,7 +46,7 @@ use topos_config::tce::broadcast::ReliableBroadcastParams;
 use topos_core::types::ValidatorId;
 use topos_core::uci::{Certificate, CertificateId};
 use topos_crypto::messages::{MessageSigner, Signature};
-use topos_tce_storage::types::CertificateDeliveredWithPositions;
+use topos_tce_storage::types::{CertificateDeliveredWithPositions, EpochId};
 use topos_tce_storage::validator::ValidatorStore;
 use tracing::{debug, error, Instrument};
 
@@ -72,17 +72,31 @@ pub enum TaskStatus {
     Success,
     /// The task did not finish successfully and stopped.
     Failure,
+    /// The task has been stopped before the end of the broadcast, its certificate being evicted
+    Stopped,
+}
+
+/// Progress of the broadcast of a certificate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastProgress {
+    pub certificate_id: CertificateId,
+    /// Number of distinct validators from which an Echo has been received
+    pub echo_count: usize,
+    /// Number of distinct validators from which a Ready has been received
+    pub ready_count: usize,
 }
 
 /// Configuration of TCE implementation
 pub struct ReliableBroadcastConfig {
     pub tce_params: ReliableBroadcastParams,
+    /// Epoch in which the broadcast starts, `validators` being its validator set
+    pub epoch_id: EpochId,
     pub validator_id: ValidatorId,
     pub validators: HashSet<ValidatorId>,
     pub message_signer: Arc<MessageSigner>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum DoubleEchoCommand {
     /// Entry point for new certificate to submit as initial sender
     Broadcast {
@@ -104,6 +118,26 @@ pub enum DoubleEchoCommand {
         certificate_id: CertificateId,
         signature: Signature,
     },
+
+    /// When a new epoch starts with its own validator set
+    NewEpoch {
+        epoch_id: EpochId,
+        validators: HashSet<ValidatorId>,
+    },
+
+    /// Get the progress of the certificates currently being broadcast
+    GetBroadcastProgress {
+        sender: oneshot::Sender<Vec<BroadcastProgress>>,
+    },
+
+    /// Get the progress of the broadcast of a certificate, if being broadcast
+    GetCertificateProgress {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Option<BroadcastProgress>>,
+    },
+
+    /// Stop the broadcast of a certificate evicted from the pending pool
+    Evict { certificate_id: CertificateId },
 }
 
 /// Thread safe client to the protocol aggregate
@@ -131,7 +165,7 @@ impl ReliableBroadcastClient {
         let (task_manager_message_sender, task_manager_message_receiver) =
             mpsc::channel(*constant::BROADCAST_TASK_MANAGER_CHANNEL_SIZE);
 
-        let double_echo = DoubleEcho::new(
+        let mut double_echo = DoubleEcho::new(
             config.tce_params,
             config.validator_id,
             config.message_signer,
@@ -143,6 +177,7 @@ impl ReliableBroadcastClient {
             validator_store,
             broadcast_sender,
         );
+        double_echo.epoch_id = config.epoch_id;
 
         spawn(
             double_echo
@@ -163,6 +198,61 @@ impl ReliableBroadcastClient {
         self.command_sender.clone()
     }
 
+    /// Switch the reliable broadcast to the validator set of a new epoch.
+    ///
+    /// Broadcasts already in progress keep being resolved against the validator set of
+    /// the epoch in which they started.
+    pub async fn new_epoch(
+        &self,
+        epoch_id: EpochId,
+        validators: HashSet<ValidatorId>,
+    ) -> Result<(), Errors> {
+        self.command_sender
+            .send(DoubleEchoCommand::NewEpoch {
+                epoch_id,
+                validators,
+            })
+            .await
+            .map_err(|error| Errors::DoubleEchoSend(Box::new(error)))
+    }
+
+    /// Returns the progress of the certificates currently being broadcast
+    pub async fn get_broadcast_progress(&self) -> Result<Vec<BroadcastProgress>, Errors> {
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(DoubleEchoCommand::GetBroadcastProgress { sender })
+            .await
+            .map_err(|error| Errors::DoubleEchoSend(Box::new(error)))?;
+
+        Ok(receiver.await?)
+    }
+
+    /// Returns the progress of the broadcast of a certificate, `None` if it isn't being broadcast
+    pub async fn get_certificate_progress(
+        &self,
+        certificate_id: CertificateId,
+    ) -> Result<Option<BroadcastProgress>, Errors> {
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(DoubleEchoCommand::GetCertificateProgress {
+                certificate_id,
+                sender,
+            })
+            .await
+            .map_err(|error| Errors::DoubleEchoSend(Box::new(error)))?;
+
+        Ok(receiver.await?)
+    }
+
+    /// Stop the broadcast of a certificate, which has to be removed from the pending pool
+    /// beforehand to not be picked up again
+    pub async fn evict(&self, certificate_id: CertificateId) -> Result<(), Errors> {
+        self.command_sender
+            .send(DoubleEchoCommand::Evict { certificate_id })
+            .await
+            .map_err(|error| Errors::DoubleEchoSend(Box::new(error)))
+    }
+
     pub async fn shutdown(&self) -> Result<(), Errors> {
         debug!("Shutting down reliable broadcast client");
         let (double_echo_sender, double_echo_receiver) = oneshot::channel();
@@ -30,7 +30,7 @@ use crate::constant::PENDING_LIMIT_PER_REQUEST_TO_STORAGE;
 use crate::double_echo::broadcast_state::BroadcastState;
 use crate::sampler::SubscriptionsView;
 use crate::DoubleEchoCommand;
-use crate::TaskStatus;
+use crate::{BroadcastProgress, TaskStatus};
 use task::{Task, TaskContext};
 use topos_crypto::messages::MessageSigner;
 
@@ -133,18 +133,44 @@ impl TaskManager {
 
                             self.create_task(cert, need_gossip, pending_id)
                         }
+                        DoubleEchoCommand::NewEpoch { epoch_id, validators } => {
+                            debug!("Tasks created from now on are bound to epoch {}", epoch_id);
+
+                            self.thresholds = ReliableBroadcastParams::new(validators.len());
+                            self.subscriptions = SubscriptionsView::from_validators(&validators);
+                        }
+                        DoubleEchoCommand::GetBroadcastProgress { sender } => {
+                            _ = sender.send(self.broadcast_progress());
+                        }
+                        DoubleEchoCommand::GetCertificateProgress { certificate_id, sender } => {
+                            _ = sender.send(
+                                self.tasks
+                                    .get(&certificate_id)
+                                    .map(|task_context| task_context.progress.borrow().clone()),
+                            );
+                        }
+                        DoubleEchoCommand::Evict { certificate_id } => {
+                            self.evict(&certificate_id);
+                        }
                     }
                 }
 
 
                 Some((certificate_id, status)) = self.running_tasks.next() => {
-                    if let TaskStatus::Success = status {
-                        trace!("Task for certificate {} finished successfully", certificate_id);
-                        self.tasks.remove(&certificate_id);
-                        DOUBLE_ECHO_ACTIVE_TASKS_COUNT.dec();
+                    DOUBLE_ECHO_ACTIVE_TASKS_COUNT.dec();
 
-                    } else {
-                        error!("Task for certificate {} finished unsuccessfully", certificate_id);
+                    match status {
+                        TaskStatus::Success => {
+                            trace!("Task for certificate {} finished successfully", certificate_id);
+                            self.tasks.remove(&certificate_id);
+                        }
+                        // The task has already been removed on eviction
+                        TaskStatus::Stopped => {
+                            debug!("Task for certificate {} stopped", certificate_id);
+                        }
+                        TaskStatus::Failure => {
+                            error!("Task for certificate {} finished unsuccessfully", certificate_id);
+                        }
                     }
 
                     self.next_pending_certificate();
@@ -168,6 +194,24 @@ impl TaskManager {
         }
     }
 
+    /// Progress of every task, as reported by the tasks
+    fn broadcast_progress(&self) -> Vec<BroadcastProgress> {
+        self.tasks
+            .values()
+            .map(|task_context| task_context.progress.borrow().clone())
+            .collect()
+    }
+
+    /// Stop the task of the certificate and drop the messages buffered for it
+    fn evict(&mut self, certificate_id: &CertificateId) {
+        self.buffered_messages.remove(certificate_id);
+
+        if let Some(task_context) = self.tasks.remove(certificate_id) {
+            info!("Evicting the broadcast of {}", certificate_id);
+            _ = task_context.shutdown_sender.try_send(());
+        }
+    }
+
     fn start_task(
         running_tasks: &RunningTasks,
         task: Task,
@@ -201,7 +245,7 @@ impl TaskManager {
     fn create_task(&mut self, cert: &Certificate, need_gossip: bool, pending_id: u64) {
         match self.tasks.entry(cert.id) {
             std::collections::hash_map::Entry::Vacant(entry) => {
-                let broadcast_state = BroadcastState::new(
+                let mut broadcast_state = BroadcastState::new(
                     cert.clone(),
                     self.validator_id,
                     self.thresholds.echo_threshold,
@@ -213,6 +257,19 @@ impl TaskManager {
                     self.message_signer.clone(),
                 );
 
+                // The broadcast may have been interrupted by a restart
+                match self.validator_store.get_broadcast_state(&cert.id) {
+                    Ok(Some(persisted)) => {
+                        let status = broadcast_state.resume(persisted);
+                        info!("Resuming the broadcast of {} as {}", cert.id, status);
+                    }
+                    Ok(None) => {}
+                    Err(error) => warn!(
+                        "Unable to fetch the broadcast state of {}: {:?}",
+                        cert.id, error
+                    ),
+                }
+
                 let (task, task_context) = Task::new(
                     cert.id,
                     broadcast_state,
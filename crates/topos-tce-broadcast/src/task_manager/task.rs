@@ -1,7 +1,9 @@
 use std::future::{Future, IntoFuture};
 use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time;
 
 use topos_core::types::stream::Position;
 use topos_core::uci::CertificateId;
@@ -12,12 +14,14 @@ use topos_tce_storage::validator::ValidatorStore;
 use tracing::{debug, error};
 
 use crate::double_echo::broadcast_state::{BroadcastState, Status};
-use crate::{DoubleEchoCommand, TaskStatus};
+use crate::{BroadcastProgress, DoubleEchoCommand, TaskStatus};
 
 #[derive(Debug)]
 pub struct TaskContext {
     pub sink: mpsc::Sender<DoubleEchoCommand>,
     pub shutdown_sender: mpsc::Sender<()>,
+    /// Progress of the broadcast, updated by the task after each Echo and Ready
+    pub progress: watch::Receiver<BroadcastProgress>,
 }
 
 pub struct Task {
@@ -27,6 +31,7 @@ pub struct Task {
     pub broadcast_state: BroadcastState,
     pub shutdown_receiver: mpsc::Receiver<()>,
     broadcast_sender: broadcast::Sender<CertificateDeliveredWithPositions>,
+    progress: watch::Sender<BroadcastProgress>,
 }
 
 impl Task {
@@ -38,10 +43,12 @@ impl Task {
     ) -> (Task, TaskContext) {
         let (message_sender, message_receiver) = mpsc::channel(10_024);
         let (shutdown_sender, shutdown_receiver) = mpsc::channel(1);
+        let (progress, progress_receiver) = watch::channel(broadcast_state.progress());
 
         let task_context = TaskContext {
             sink: message_sender,
             shutdown_sender,
+            progress: progress_receiver,
         };
 
         let task = Task {
@@ -51,11 +58,45 @@ impl Task {
             broadcast_state,
             shutdown_receiver,
             broadcast_sender,
+            progress,
         };
 
         (task, task_context)
     }
 
+    /// Interval at which the progress of the broadcast is checkpointed, the Echo and Ready
+    /// messages received in the meantime being written at once
+    const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Checkpoint the progress of the broadcast to resume it after a restart
+    fn checkpoint(&self) {
+        if let Err(error) = self
+            .validator_store
+            .insert_broadcast_state(&self.certificate_id, &self.broadcast_state.checkpoint())
+        {
+            error!(
+                "Unable to checkpoint the broadcast state of {}: {:?}",
+                self.certificate_id, error
+            );
+        }
+    }
+
+    /// Persist the delivered certificate and notify its delivery
+    async fn deliver(&self) -> TaskStatus {
+        match self.persist().await {
+            Ok(delivered) => {
+                _ = self.broadcast_sender.send(delivered);
+
+                TaskStatus::Success
+            }
+            Err(error) => {
+                error!("Unable to persist one delivered certificate: {:?}", error);
+
+                TaskStatus::Failure
+            }
+        }
+    }
+
     pub async fn persist(&self) -> Result<CertificateDeliveredWithPositions, StorageError> {
         let certificate_delivered = self.broadcast_state.into_delivered();
 
@@ -96,47 +137,42 @@ impl IntoFuture for Task {
             );
             self.broadcast_state.expected_position = Some(expected_position);
 
+            // A resumed broadcast may already have gathered everything needed for the delivery
+            if let Status::DeliveredWithReadySent = self.broadcast_state.status() {
+                return (self.certificate_id, self.deliver().await);
+            }
+
+            let mut checkpoint_interval = time::interval(Self::CHECKPOINT_INTERVAL);
+            // Whether messages were received since the last checkpoint
+            let mut pending_checkpoint = false;
+
             loop {
                 tokio::select! {
+                    _ = checkpoint_interval.tick(), if pending_checkpoint => {
+                        self.checkpoint();
+                        pending_checkpoint = false;
+                    }
                     Some(msg) = self.message_receiver.recv() => {
-                        match msg {
+                        let status = match msg {
                             DoubleEchoCommand::Echo { validator_id, .. } => {
-                                if let Some(Status::DeliveredWithReadySent) = self.broadcast_state.apply_echo(validator_id) {
-                                    match self.persist().await {
-                                        Ok(delivered) => {
-                                            _ = self.broadcast_sender.send(delivered);
-
-                                            return (self.certificate_id, TaskStatus::Success);
-                                        }
-                                        Err(error) => {
-                                            error!("Unable to persist one delivered certificate: {:?}", error);
-                                            return (self.certificate_id, TaskStatus::Failure);
-                                        }
-                                    }
-
-                                }
+                                self.broadcast_state.apply_echo(validator_id)
                             }
-                            DoubleEchoCommand::Ready { validator_id, .. } => {
-                                if let Some(Status::DeliveredWithReadySent) = self.broadcast_state.apply_ready(validator_id) {
-                                    match self.persist().await {
-                                        Ok(delivered) => {
-                                            _ = self.broadcast_sender.send(delivered);
-
-                                            return (self.certificate_id, TaskStatus::Success);
-                                        }
-                                        Err(error) => {
-                                            error!("Unable to persist one delivered certificate: {:?}", error);
-                                            return (self.certificate_id, TaskStatus::Failure);
-                                        }
-                                    }
-                                }
+                            DoubleEchoCommand::Ready { validator_id, signature, .. } => {
+                                self.broadcast_state.apply_ready(validator_id, signature)
                             }
-                            _ => {}
+                            _ => continue,
+                        };
+
+                        if let Some(Status::DeliveredWithReadySent) = status {
+                            return (self.certificate_id, self.deliver().await);
                         }
+
+                        self.progress.send_replace(self.broadcast_state.progress());
+                        pending_checkpoint = true;
                     }
                     _ = self.shutdown_receiver.recv() => {
                         debug!("Received shutdown, shutting down task {:?}", self.certificate_id);
-                        return (self.certificate_id, TaskStatus::Failure)
+                        return (self.certificate_id, TaskStatus::Stopped)
                     }
                 }
             }
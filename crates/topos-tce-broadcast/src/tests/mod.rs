@@ -1,16 +1,18 @@
 use crate::double_echo::*;
 use crate::event::ProtocolEvents;
 use rstest::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use topos_config::tce::broadcast::ReliableBroadcastParams;
+use topos_core::types::{ProofOfDelivery, ProofOfDeliveryError};
 use topos_core::uci::Certificate;
 use topos_crypto::messages::MessageSigner;
 use topos_crypto::validator_id::ValidatorId;
+use topos_tce_storage::store::ReadStore;
 use topos_tce_storage::types::CertificateDeliveredWithPositions;
 use topos_tce_storage::validator::ValidatorStore;
 use topos_test_sdk::constants::*;
@@ -56,6 +58,8 @@ struct Context {
     event_receiver: Receiver<ProtocolEvents>,
     broadcast_receiver: broadcast::Receiver<CertificateDeliveredWithPositions>,
     validator_store: Arc<ValidatorStore>,
+    /// Signer of every validator
+    signers: HashMap<ValidatorId, Arc<MessageSigner>>,
 }
 
 async fn create_context(params: TceParams) -> (DoubleEcho, Context) {
@@ -69,13 +73,16 @@ async fn create_context(params: TceParams) -> (DoubleEcho, Context) {
     let message_signer = Arc::new(MessageSigner::from_str(PRIVATE_KEY).unwrap());
 
     let mut validators = HashSet::new();
+    let mut signers = HashMap::new();
     let validator_id = ValidatorId::from(message_signer.public_address);
     validators.insert(validator_id);
+    signers.insert(validator_id, message_signer.clone());
 
     for i in 1..params.nb_peers {
         let message_signer = Arc::new(MessageSigner::new(&[i as u8; 32]).unwrap());
         let validator_id = ValidatorId::from(message_signer.public_address);
         validators.insert(validator_id);
+        signers.insert(validator_id, message_signer);
     }
 
     let (broadcast_sender, broadcast_receiver) = broadcast::channel(CHANNEL_SIZE);
@@ -100,6 +107,7 @@ async fn create_context(params: TceParams) -> (DoubleEcho, Context) {
             event_receiver,
             broadcast_receiver,
             validator_store,
+            signers,
         },
     )
 }
@@ -151,7 +159,12 @@ async fn reach_ready_threshold(double_echo: &mut DoubleEcho, cert: &Certificate)
     }
 }
 
-async fn reach_delivery_threshold(double_echo: &mut DoubleEcho, cert: &Certificate) {
+/// Every selected validator sends a Ready signed with its own key
+async fn reach_delivery_threshold(
+    double_echo: &mut DoubleEcho,
+    signers: &HashMap<ValidatorId, Arc<MessageSigner>>,
+    cert: &Certificate,
+) {
     let selected = double_echo
         .subscriptions
         .ready
@@ -160,16 +173,10 @@ async fn reach_delivery_threshold(double_echo: &mut DoubleEcho, cert: &Certifica
         .cloned()
         .collect::<Vec<_>>();
 
-    let message_signer = Arc::new(MessageSigner::from_str(PRIVATE_KEY).unwrap());
-    let validator_id = ValidatorId::from(message_signer.public_address);
-
-    let mut payload = Vec::new();
-    payload.extend_from_slice(cert.id.as_array());
-    payload.extend_from_slice(validator_id.as_bytes());
-
-    let signature = message_signer.sign_message(&payload).unwrap();
-
     for val_id in selected {
+        let payload = ProofOfDelivery::ready_payload(&cert.id, &val_id);
+        let signature = signers[&val_id].sign_message(&payload).unwrap();
+
         double_echo.handle_ready(cert.id, val_id, signature).await;
     }
 }
@@ -221,7 +228,7 @@ async fn trigger_success_path_upon_reaching_threshold(#[case] params: TceParams)
     ));
 
     // Trigger Delivery upon reaching the Delivery threshold
-    reach_delivery_threshold(&mut double_echo, &dummy_cert).await;
+    reach_delivery_threshold(&mut double_echo, &ctx.signers, &dummy_cert).await;
     let x = ctx.broadcast_receiver.recv().await;
     assert!(matches!(
             x,
@@ -229,6 +236,58 @@ async fn trigger_success_path_upon_reaching_threshold(#[case] params: TceParams)
     ));
 }
 
+#[rstest]
+#[case::small_config(small_config())]
+#[case(medium_config())]
+#[test_log::test(tokio::test)]
+#[trace]
+#[timeout(Duration::from_secs(10))]
+async fn delivered_certificate_carries_signed_readies(#[case] params: TceParams) {
+    let delivery_threshold = params.broadcast_params.delivery_threshold;
+    let (mut double_echo, mut ctx) = create_context(params).await;
+
+    let dummy_cert =
+        Certificate::new_with_default_fields(PREV_CERTIFICATE_ID, SOURCE_SUBNET_ID_1, &[])
+            .expect("Dummy certificate");
+
+    _ = ctx
+        .validator_store
+        .insert_pending_certificate(&dummy_cert)
+        .await
+        .unwrap();
+
+    reach_echo_threshold(&mut double_echo, &dummy_cert).await;
+    reach_delivery_threshold(&mut double_echo, &ctx.signers, &dummy_cert).await;
+
+    let CertificateDeliveredWithPositions(delivered, _) =
+        ctx.broadcast_receiver.recv().await.unwrap();
+
+    let proof = delivered.proof_of_delivery;
+    assert_eq!(proof.threshold, delivery_threshold as u64);
+    assert_eq!(proof.readies.len(), delivery_threshold);
+    assert_eq!(
+        proof.verify(&double_echo.validators, delivery_threshold as u64),
+        Ok(())
+    );
+
+    // A Ready signature doesn't hold for another validator
+    let mut swapped = proof.clone();
+    let (first, second) = (swapped.readies[0].1, swapped.readies[1].1);
+    swapped.readies[0].1 = second;
+    swapped.readies[1].1 = first;
+    assert_eq!(
+        swapped.verify(&double_echo.validators, delivery_threshold as u64),
+        Err(ProofOfDeliveryError::InvalidSignature(swapped.readies[0].0))
+    );
+
+    let stored = ctx
+        .validator_store
+        .get_certificate(&dummy_cert.id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.proof_of_delivery, proof);
+}
+
 #[rstest]
 #[case::small_config(small_config())]
 #[case(medium_config())]
@@ -271,3 +330,45 @@ async fn trigger_ready_when_reached_enough_ready(#[case] params: TceParams) {
         Some(ProtocolEvents::Ready { .. })
     ));
 }
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(4))]
+async fn new_epoch_rotates_validators(#[from(small_config)] params: TceParams) {
+    let (mut double_echo, ctx) = create_context(params).await;
+    let genesis_validators = double_echo.validators.clone();
+
+    let next_validators: HashSet<ValidatorId> = (100..104u8)
+        .map(|i| ValidatorId::from(MessageSigner::new(&[i; 32]).unwrap().public_address))
+        .collect();
+
+    double_echo
+        .handle_new_epoch(1, next_validators.clone())
+        .await;
+
+    assert_eq!(double_echo.epoch_id, 1);
+    assert_eq!(double_echo.validators, next_validators);
+    assert_eq!(double_echo.previous_validators, genesis_validators);
+    assert_eq!(
+        double_echo.subscriptions.echo, next_validators,
+        "New broadcasts subscribe to the validators of the new epoch"
+    );
+    assert_eq!(double_echo.subscriptions.network_size, 4);
+    assert_eq!(
+        double_echo.params.delivery_threshold,
+        ReliableBroadcastParams::new(4).delivery_threshold
+    );
+    assert_eq!(
+        ctx.validator_store
+            .epoch_validators_store()
+            .get_validators(1)
+            .unwrap(),
+        Some(next_validators.clone())
+    );
+
+    // An epoch can't be started twice
+    double_echo.handle_new_epoch(1, genesis_validators).await;
+
+    assert_eq!(double_echo.epoch_id, 1);
+    assert_eq!(double_echo.validators, next_validators);
+}
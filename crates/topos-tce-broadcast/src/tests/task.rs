@@ -1,13 +1,16 @@
-use std::{future::IntoFuture, sync::Arc, time::Duration};
+use std::{collections::HashSet, future::IntoFuture, sync::Arc, time::Duration};
 
 use rstest::rstest;
 use tokio::{
     spawn,
     sync::{broadcast, mpsc},
 };
+use topos_config::tce::broadcast::ReliableBroadcastParams;
 use topos_core::uci::Certificate;
 use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
-use topos_tce_storage::validator::ValidatorStore;
+use topos_tce_storage::{
+    types::BroadcastState as PersistedBroadcastState, validator::ValidatorStore,
+};
 use topos_test_sdk::{
     certificates::create_certificate_chain,
     constants::{SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
@@ -16,8 +19,11 @@ use topos_test_sdk::{
 };
 
 use crate::{
-    double_echo::broadcast_state::BroadcastState, event::ProtocolEvents,
-    sampler::SubscriptionsView, task_manager::task::Task,
+    double_echo::broadcast_state::{BroadcastState, Status},
+    event::ProtocolEvents,
+    sampler::SubscriptionsView,
+    task_manager::task::Task,
+    DoubleEchoCommand, TaskStatus,
 };
 
 #[rstest]
@@ -82,3 +88,150 @@ async fn start_with_ungossiped_cert(
         }) if id == certificate_id
     ));
 }
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn resume_from_checkpointed_state(
+    #[future(awt)]
+    #[from(create_validator_store)]
+    validatore_store: Arc<ValidatorStore>,
+    message_signer: Arc<MessageSigner>,
+) {
+    let certificate = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+        .pop()
+        .unwrap()
+        .certificate;
+    let certificate_id = certificate.id;
+    let validator_id: ValidatorId = message_signer.public_address.into();
+    let signers: Vec<MessageSigner> = (1..=4u8)
+        .map(|i| MessageSigner::new(&[i; 32]).unwrap())
+        .collect();
+    let validators: HashSet<ValidatorId> = signers
+        .iter()
+        .map(|signer| signer.public_address.into())
+        .collect();
+    let thresholds = ReliableBroadcastParams::new(validators.len());
+
+    let new_state = |certificate: Certificate| {
+        let (event_sender, _) = mpsc::channel(10);
+
+        BroadcastState::new(
+            certificate,
+            validator_id,
+            thresholds.echo_threshold,
+            thresholds.ready_threshold,
+            thresholds.delivery_threshold,
+            event_sender,
+            SubscriptionsView::from_validators(&validators),
+            false,
+            message_signer.clone(),
+        )
+    };
+
+    // Only the broadcast of a pending certificate is checkpointed
+    validatore_store
+        .insert_pending_certificate(&certificate)
+        .await
+        .unwrap();
+
+    let (broadcast_sender, _) = broadcast::channel(1);
+    let (task, ctx) = Task::new(
+        certificate_id,
+        new_state(certificate.clone()),
+        validatore_store.clone(),
+        broadcast_sender,
+    );
+    let _handle = spawn(task.into_future());
+
+    for signer in &signers[..2] {
+        ctx.sink
+            .send(DoubleEchoCommand::Echo {
+                validator_id: signer.public_address.into(),
+                certificate_id,
+                signature: signer.sign_message(&[]).unwrap(),
+            })
+            .await
+            .unwrap();
+    }
+
+    // Echoes are checkpointed while the broadcast goes on
+    let persisted = loop {
+        match validatore_store
+            .get_broadcast_state(&certificate_id)
+            .unwrap()
+        {
+            Some(state) if state.echoes.len() == 2 => break state,
+            _ => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    };
+
+    let mut resumed = new_state(certificate.clone());
+    assert_eq!(resumed.resume(persisted), Status::EchoSent);
+    assert_eq!(resumed.checkpoint().echoes.len(), 2);
+
+    // Enough Ready messages resume the broadcast as delivered
+    let persisted = PersistedBroadcastState {
+        echoes: Vec::new(),
+        readies: signers
+            .iter()
+            .take(thresholds.delivery_threshold)
+            .map(|signer| {
+                let validator_id: ValidatorId = signer.public_address.into();
+                let mut payload = certificate_id.as_array().to_vec();
+                payload.extend_from_slice(validator_id.as_bytes());
+
+                (validator_id, signer.sign_message(&payload).unwrap())
+            })
+            .collect(),
+    };
+
+    let mut resumed = new_state(certificate);
+    assert_eq!(resumed.resume(persisted), Status::DeliveredWithReadySent);
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(1))]
+async fn shutdown_stops_the_task(
+    #[future(awt)]
+    #[from(create_validator_store)]
+    validatore_store: Arc<ValidatorStore>,
+    message_signer: Arc<MessageSigner>,
+) {
+    let certificate = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+        .pop()
+        .unwrap()
+        .certificate;
+    let certificate_id = certificate.id;
+    let thresholds = ReliableBroadcastParams::new(4);
+    let (event_sender, _event_receiver) = mpsc::channel(10);
+    let (broadcast_sender, _) = broadcast::channel(1);
+
+    let broadcast_state = BroadcastState::new(
+        certificate,
+        ValidatorId::default(),
+        thresholds.echo_threshold,
+        thresholds.ready_threshold,
+        thresholds.delivery_threshold,
+        event_sender,
+        SubscriptionsView::default(),
+        false,
+        message_signer,
+    );
+
+    let (task, ctx) = Task::new(
+        certificate_id,
+        broadcast_state,
+        validatore_store,
+        broadcast_sender,
+    );
+    let handle = spawn(task.into_future());
+
+    // An evicted certificate stops its task, which isn't reported as a failure
+    ctx.shutdown_sender.send(()).await.unwrap();
+
+    let (id, status) = handle.await.unwrap();
+    assert_eq!(id, certificate_id);
+    assert!(matches!(status, TaskStatus::Stopped));
+}
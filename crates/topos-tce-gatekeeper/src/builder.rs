@@ -15,11 +15,16 @@ impl IntoFuture for GatekeeperBuilder {
 
     fn into_future(self) -> Self::IntoFuture {
         let (shutdown_channel, shutdown) = mpsc::channel(1);
+        let (commands_channel, commands) = mpsc::channel(Gatekeeper::COMMAND_CHANNEL_SIZE);
 
         futures::future::ok((
-            GatekeeperClient { shutdown_channel },
+            GatekeeperClient {
+                shutdown_channel,
+                commands: commands_channel,
+            },
             Gatekeeper {
                 shutdown,
+                commands,
                 ..Gatekeeper::default()
             },
         ))
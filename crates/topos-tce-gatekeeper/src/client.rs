@@ -1,9 +1,14 @@
-use crate::GatekeeperError;
+use std::collections::HashSet;
+
+use crate::{command::GatekeeperCommand, GatekeeperError, GatekeeperUpdate};
 use tokio::sync::{mpsc, oneshot};
+use topos_core::types::ValidatorId;
+use topos_p2p::PeerId;
 
 #[derive(Clone)]
 pub struct GatekeeperClient {
     pub(crate) shutdown_channel: mpsc::Sender<oneshot::Sender<()>>,
+    pub(crate) commands: mpsc::Sender<GatekeeperCommand>,
 }
 
 impl GatekeeperClient {
@@ -16,4 +21,111 @@ impl GatekeeperClient {
 
         Ok(receiver.await?)
     }
+
+    /// Replace the list of known peers, returning the new list
+    ///
+    /// Fails with [`GatekeeperError::NoUpdate`] if the list is unchanged.
+    pub async fn push_peer_list(
+        &self,
+        peer_list: Vec<PeerId>,
+    ) -> Result<Vec<PeerId>, GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::PushPeerList {
+            peer_list,
+            response,
+        })
+        .await?;
+
+        receiver.await?
+    }
+
+    pub async fn get_all_peers(&self) -> Result<Vec<PeerId>, GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::GetAllPeers { response })
+            .await?;
+
+        Ok(receiver.await?)
+    }
+
+    pub async fn get_random_peers(&self, number: usize) -> Result<Vec<PeerId>, GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::GetRandomPeers { number, response })
+            .await?;
+
+        Ok(receiver.await?)
+    }
+
+    /// Replace the validators with those of a new epoch, returning the changes to apply
+    ///
+    /// Fails with [`GatekeeperError::NoUpdate`] if the validator set is unchanged.
+    pub async fn update_epoch(
+        &self,
+        epoch_id: u64,
+        validators: HashSet<ValidatorId>,
+    ) -> Result<GatekeeperUpdate, GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::UpdateEpoch {
+            epoch_id,
+            validators,
+            response,
+        })
+        .await?;
+
+        receiver.await?
+    }
+
+    /// Record that a peer is publishing messages on behalf of a validator, once the
+    /// [`peer_binding_payload`](crate::peer_binding_payload) of the peer and epoch signed by the
+    /// validator has been verified
+    ///
+    /// Fails with [`GatekeeperError::UnknownValidator`] if the validator isn't part of the
+    /// current or previous epoch, with [`GatekeeperError::AlreadyBound`] if the peer is bound to
+    /// another validator or if the validator is bound to another peer at a later epoch and with
+    /// [`GatekeeperError::NoUpdate`] if the peer is already bound to it. The peer is allowed by
+    /// the [`GatekeeperUpdate`] of the epoch its validator joins the set.
+    pub async fn bind_peer(
+        &self,
+        peer: PeerId,
+        validator_id: ValidatorId,
+        epoch_id: u64,
+    ) -> Result<(), GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::BindPeer {
+            peer,
+            validator_id,
+            epoch_id,
+            response,
+        })
+        .await?;
+
+        receiver.await?
+    }
+
+    /// Returns whether the peer is acting for a validator of the current or previous epoch
+    pub async fn is_peer_allowed(&self, peer: PeerId) -> Result<bool, GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::IsPeerAllowed { peer, response })
+            .await?;
+
+        Ok(receiver.await?)
+    }
+
+    /// Returns whether the validator is part of the current epoch
+    pub async fn is_validator(&self, validator_id: ValidatorId) -> Result<bool, GatekeeperError> {
+        let (response, receiver) = oneshot::channel();
+        self.send(GatekeeperCommand::IsValidator {
+            validator_id,
+            response,
+        })
+        .await?;
+
+        Ok(receiver.await?)
+    }
+
+    async fn send(&self, command: GatekeeperCommand) -> Result<(), GatekeeperError> {
+        self.commands
+            .send(command)
+            .await
+            .map_err(|error| GatekeeperError::InvalidCommand(error.to_string()))
+    }
 }
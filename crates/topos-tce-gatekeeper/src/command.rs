@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use tokio::sync::oneshot;
+use topos_core::types::ValidatorId;
+use topos_p2p::PeerId;
+
+use crate::{GatekeeperError, GatekeeperUpdate};
+
+#[derive(Debug)]
+pub(crate) enum GatekeeperCommand {
+    /// Replace the list of known peers
+    PushPeerList {
+        peer_list: Vec<PeerId>,
+        response: oneshot::Sender<Result<Vec<PeerId>, GatekeeperError>>,
+    },
+
+    /// Ask for the list of known peers
+    GetAllPeers {
+        response: oneshot::Sender<Vec<PeerId>>,
+    },
+
+    /// Ask for a random subset of the known peers
+    GetRandomPeers {
+        number: usize,
+        response: oneshot::Sender<Vec<PeerId>>,
+    },
+
+    /// Replace the validators with those of a new epoch
+    UpdateEpoch {
+        epoch_id: u64,
+        validators: HashSet<ValidatorId>,
+        response: oneshot::Sender<Result<GatekeeperUpdate, GatekeeperError>>,
+    },
+
+    /// Record that a peer is publishing messages on behalf of a validator
+    BindPeer {
+        peer: PeerId,
+        validator_id: ValidatorId,
+        epoch_id: u64,
+        response: oneshot::Sender<Result<(), GatekeeperError>>,
+    },
+
+    /// Ask if a peer is acting for a validator of the current epoch
+    IsPeerAllowed {
+        peer: PeerId,
+        response: oneshot::Sender<bool>,
+    },
+
+    /// Ask if a validator is part of the current epoch
+    IsValidator {
+        validator_id: ValidatorId,
+        response: oneshot::Sender<bool>,
+    },
+}
@@ -1,35 +1,85 @@
-use std::{future::IntoFuture, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    future::IntoFuture,
+    time::Duration,
+};
 
 use builder::GatekeeperBuilder;
+use command::GatekeeperCommand;
 use futures::{future::BoxFuture, FutureExt};
+use rand::{seq::SliceRandom, thread_rng};
 use thiserror::Error;
 use tokio::{
     sync::{mpsc, oneshot},
     time,
 };
-use tracing::error;
+use topos_core::types::ValidatorId;
+use topos_p2p::PeerId;
+use tracing::{debug, error};
 
 mod builder;
 mod client;
+mod command;
 #[cfg(test)]
 mod tests;
 
 pub use client::GatekeeperClient;
 use tracing::{info, warn};
 
+/// Admission authority of the node
+///
+/// Keeps track of the validators of the current epoch and of the peers publishing messages on
+/// their behalf, in order to tell which peers are allowed to take part in the broadcast.
 pub struct Gatekeeper {
     pub(crate) shutdown: mpsc::Receiver<oneshot::Sender<()>>,
+    pub(crate) commands: mpsc::Receiver<GatekeeperCommand>,
     pub(crate) tick_duration: Duration,
+
+    /// Epoch of the current validator set
+    epoch_id: u64,
+    /// Validators of the current epoch
+    validators: HashSet<ValidatorId>,
+    /// Validators of the previous epoch, still admitted to complete the broadcasts that started
+    /// during the previous epoch
+    previous_validators: HashSet<ValidatorId>,
+    /// Known peers of the network
+    peer_list: Vec<PeerId>,
+    /// Validator on behalf of which each peer is publishing messages, along with the epoch at
+    /// which the validator signed the binding
+    peer_validators: HashMap<PeerId, (ValidatorId, u64)>,
+    /// Peers denied for publishing on behalf of a validator outside of the set, bound to it once
+    /// the validator joins the set
+    denied_claims: HashMap<PeerId, (ValidatorId, u64)>,
+}
+
+/// Changes of the admission lists produced by a new validator set
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GatekeeperUpdate {
+    pub epoch_id: u64,
+    pub added_validators: Vec<ValidatorId>,
+    pub removed_validators: Vec<ValidatorId>,
+    /// Peers acting for a validator joining the set
+    pub allowed_peers: Vec<PeerId>,
+    /// Peers acting for a validator leaving the set
+    pub revoked_peers: Vec<PeerId>,
 }
 
 impl Default for Gatekeeper {
     fn default() -> Self {
         let (_shutdown_channel, shutdown) = mpsc::channel(1);
+        let (_commands_channel, commands) = mpsc::channel(1);
         let tick_duration = Duration::from_secs(Self::DEFAULT_TICK_DURATION);
 
         Self {
             shutdown,
+            commands,
             tick_duration,
+            epoch_id: 0,
+            validators: HashSet::new(),
+            previous_validators: HashSet::new(),
+            peer_list: Vec::new(),
+            peer_validators: HashMap::new(),
+            denied_claims: HashMap::new(),
         }
     }
 }
@@ -46,6 +96,7 @@ impl IntoFuture for Gatekeeper {
             let shutdowned: Option<oneshot::Sender<()>> = loop {
                 tokio::select! {
                     _ = interval.tick() => {}
+                    Some(command) = self.commands.recv() => self.handle_command(command),
                     sender = self.shutdown.recv() => {
                         break sender;
                     }
@@ -67,10 +118,219 @@ impl IntoFuture for Gatekeeper {
 
 impl Gatekeeper {
     pub(crate) const DEFAULT_TICK_DURATION: u64 = 10;
+    pub(crate) const COMMAND_CHANNEL_SIZE: usize = 100;
+    /// Maximum number of denied claims remembered until their validator joins the set
+    pub(crate) const MAX_DENIED_CLAIMS: usize = 1024;
 
     pub fn builder() -> GatekeeperBuilder {
         GatekeeperBuilder::default()
     }
+
+    fn handle_command(&mut self, command: GatekeeperCommand) {
+        // A dropped response only means that the initiator isn't interested anymore
+        match command {
+            GatekeeperCommand::PushPeerList {
+                peer_list,
+                response,
+            } => _ = response.send(self.push_peer_list(peer_list)),
+            GatekeeperCommand::GetAllPeers { response } => {
+                _ = response.send(self.peer_list.clone())
+            }
+            GatekeeperCommand::GetRandomPeers { number, response } => {
+                _ = response.send(
+                    self.peer_list
+                        .choose_multiple(&mut thread_rng(), number)
+                        .cloned()
+                        .collect(),
+                )
+            }
+            GatekeeperCommand::UpdateEpoch {
+                epoch_id,
+                validators,
+                response,
+            } => _ = response.send(self.update_epoch(epoch_id, validators)),
+            GatekeeperCommand::BindPeer {
+                peer,
+                validator_id,
+                epoch_id,
+                response,
+            } => _ = response.send(self.bind_peer(peer, validator_id, epoch_id)),
+            GatekeeperCommand::IsPeerAllowed { peer, response } => {
+                _ = response.send(self.is_peer_allowed(&peer))
+            }
+            GatekeeperCommand::IsValidator {
+                validator_id,
+                response,
+            } => _ = response.send(self.validators.contains(&validator_id)),
+        }
+    }
+
+    fn push_peer_list(
+        &mut self,
+        mut peer_list: Vec<PeerId>,
+    ) -> Result<Vec<PeerId>, GatekeeperError> {
+        peer_list.sort();
+        peer_list.dedup();
+
+        if peer_list == self.peer_list {
+            return Err(GatekeeperError::NoUpdate);
+        }
+
+        self.peer_list = peer_list;
+
+        Ok(self.peer_list.clone())
+    }
+
+    fn update_epoch(
+        &mut self,
+        epoch_id: u64,
+        validators: HashSet<ValidatorId>,
+    ) -> Result<GatekeeperUpdate, GatekeeperError> {
+        if epoch_id < self.epoch_id {
+            return Err(GatekeeperError::InvalidCommand(format!(
+                "epoch {epoch_id} is older than the current epoch {}",
+                self.epoch_id
+            )));
+        }
+
+        // The validators of the current epoch are kept admitted during the next epoch
+        let previous_validators = if epoch_id > self.epoch_id {
+            self.validators.clone()
+        } else {
+            self.previous_validators.clone()
+        };
+
+        self.epoch_id = epoch_id;
+
+        if validators == self.validators && previous_validators == self.previous_validators {
+            return Err(GatekeeperError::NoUpdate);
+        }
+
+        let added_validators: Vec<ValidatorId> =
+            validators.difference(&self.validators).copied().collect();
+        let removed_validators: Vec<ValidatorId> =
+            self.validators.difference(&validators).copied().collect();
+
+        let admitted_before = self.admitted_validators();
+        self.validators = validators;
+        self.previous_validators = previous_validators;
+        let admitted = self.admitted_validators();
+
+        // The peers denied for a validator joining the set are bound to it, unless another peer
+        // acts for the validator since a more recent epoch
+        let claims: Vec<(PeerId, (ValidatorId, u64))> = self
+            .denied_claims
+            .iter()
+            .filter(|(_, (validator_id, _))| admitted.contains(validator_id))
+            .map(|(peer, claim)| (*peer, *claim))
+            .collect();
+        for (peer, (validator_id, claim_epoch_id)) in claims {
+            self.denied_claims.remove(&peer);
+
+            _ = self.bind_peer(peer, validator_id, claim_epoch_id);
+        }
+
+        let peers_of = |changed: &HashSet<ValidatorId>| -> Vec<PeerId> {
+            self.peer_validators
+                .iter()
+                .filter(|(_, (validator_id, _))| changed.contains(validator_id))
+                .map(|(peer, _)| *peer)
+                .collect()
+        };
+
+        let update = GatekeeperUpdate {
+            epoch_id,
+            allowed_peers: peers_of(&admitted.difference(&admitted_before).copied().collect()),
+            revoked_peers: peers_of(&admitted_before.difference(&admitted).copied().collect()),
+            added_validators,
+            removed_validators,
+        };
+
+        debug!(
+            "Validator set of epoch {epoch_id} updated: {} added, {} removed",
+            update.added_validators.len(),
+            update.removed_validators.len()
+        );
+
+        Ok(update)
+    }
+
+    /// Bind a peer to the validator on behalf of which it publishes messages, the binding being
+    /// signed by the validator for the peer at the given epoch and verified beforehand.
+    ///
+    /// A peer acts for a single validator and a validator is acted for by a single peer, the
+    /// binding of the validator being replaced by the ones it signed at the same or a later epoch.
+    fn bind_peer(
+        &mut self,
+        peer: PeerId,
+        validator_id: ValidatorId,
+        epoch_id: u64,
+    ) -> Result<(), GatekeeperError> {
+        if let Some((bound, bound_epoch_id)) = self.peer_validators.get_mut(&peer) {
+            if *bound != validator_id {
+                return Err(GatekeeperError::AlreadyBound(validator_id));
+            }
+
+            *bound_epoch_id = epoch_id.max(*bound_epoch_id);
+
+            return Err(GatekeeperError::NoUpdate);
+        }
+
+        if !self.admitted_validators().contains(&validator_id) {
+            // Remembered in order to allow the peer once the validator joins the set
+            if self.denied_claims.len() < Self::MAX_DENIED_CLAIMS
+                || self.denied_claims.contains_key(&peer)
+            {
+                self.denied_claims.insert(peer, (validator_id, epoch_id));
+            }
+
+            return Err(GatekeeperError::UnknownValidator(validator_id));
+        }
+
+        if let Some((bound_peer, bound_epoch_id)) = self.bound_peer(&validator_id) {
+            if bound_epoch_id > epoch_id {
+                return Err(GatekeeperError::AlreadyBound(validator_id));
+            }
+
+            debug!("Peer {bound_peer} replaced by {peer} for {validator_id} at epoch {epoch_id}");
+            self.peer_validators.remove(&bound_peer);
+        }
+
+        self.peer_validators.insert(peer, (validator_id, epoch_id));
+
+        Ok(())
+    }
+
+    fn is_peer_allowed(&self, peer: &PeerId) -> bool {
+        self.peer_validators
+            .get(peer)
+            .is_some_and(|(validator_id, _)| self.admitted_validators().contains(validator_id))
+    }
+
+    /// Returns the validators allowed to publish messages, the ones of the current and of the
+    /// previous epoch
+    fn admitted_validators(&self) -> HashSet<ValidatorId> {
+        self.validators
+            .union(&self.previous_validators)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the peer bound to the validator along with the epoch of its binding
+    fn bound_peer(&self, validator_id: &ValidatorId) -> Option<(PeerId, u64)> {
+        self.peer_validators
+            .iter()
+            .find(|(_, (bound, _))| bound == validator_id)
+            .map(|(peer, (_, epoch_id))| (*peer, *epoch_id))
+    }
+}
+
+/// Returns the payload signed by a validator to bind a peer to it at an epoch
+pub fn peer_binding_payload(peer: &PeerId, epoch_id: u64) -> Vec<u8> {
+    let mut payload = peer.to_bytes();
+    payload.extend_from_slice(&epoch_id.to_be_bytes());
+
+    payload
 }
 
 #[derive(Debug, Error)]
@@ -86,4 +346,10 @@ pub enum GatekeeperError {
 
     #[error("The command produce no update")]
     NoUpdate,
+
+    #[error("The validator {0} isn't part of the current epoch")]
+    UnknownValidator(ValidatorId),
+
+    #[error("The validator {0} is bound to another peer")]
+    AlreadyBound(ValidatorId),
 }
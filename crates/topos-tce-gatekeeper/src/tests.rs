@@ -1,11 +1,12 @@
-use std::future::IntoFuture;
+use std::{collections::HashSet, future::IntoFuture};
 
-use rstest::fixture;
+use rstest::{fixture, rstest};
 use test_log::test;
 use tokio::spawn;
+use topos_core::types::ValidatorId;
 use topos_p2p::PeerId;
 
-use crate::{client::GatekeeperClient, Gatekeeper};
+use crate::{client::GatekeeperClient, Gatekeeper, GatekeeperError, GatekeeperUpdate};
 
 #[test(tokio::test)]
 async fn can_start_and_stop() -> Result<(), Box<dyn std::error::Error>> {
@@ -39,3 +40,181 @@ fn peer_list(#[default(10)] number: usize) -> Vec<PeerId> {
         })
         .collect()
 }
+
+fn validator(i: u8) -> ValidatorId {
+    format!("0x{i:040x}").parse().unwrap()
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn can_push_peer_list(
+    #[future] gatekeeper: GatekeeperClient,
+    peer_list: Vec<PeerId>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let gatekeeper = gatekeeper.await;
+
+    let pushed = gatekeeper.push_peer_list(peer_list.clone()).await?;
+    assert_eq!(pushed.len(), peer_list.len());
+    assert_eq!(gatekeeper.get_all_peers().await?, pushed);
+
+    assert!(matches!(
+        gatekeeper.push_peer_list(peer_list).await,
+        Err(GatekeeperError::NoUpdate)
+    ));
+
+    let random_peers = gatekeeper.get_random_peers(3).await?;
+    assert_eq!(random_peers.len(), 3);
+    assert!(random_peers.iter().all(|peer| pushed.contains(peer)));
+
+    Ok(())
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn can_gate_peers_per_epoch(
+    #[future] gatekeeper: GatekeeperClient,
+    #[with(3)] peer_list: Vec<PeerId>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let gatekeeper = gatekeeper.await;
+
+    let update = gatekeeper
+        .update_epoch(0, HashSet::from([validator(1), validator(2)]))
+        .await?;
+    assert_eq!(update.added_validators.len(), 2);
+    assert!(update.allowed_peers.is_empty());
+
+    gatekeeper.bind_peer(peer_list[0], validator(1), 0).await?;
+    gatekeeper.bind_peer(peer_list[1], validator(2), 0).await?;
+    assert!(matches!(
+        gatekeeper.bind_peer(peer_list[0], validator(1), 0).await,
+        Err(GatekeeperError::NoUpdate)
+    ));
+    assert!(matches!(
+        gatekeeper.bind_peer(peer_list[2], validator(3), 0).await,
+        Err(GatekeeperError::UnknownValidator(_))
+    ));
+
+    assert!(gatekeeper.is_validator(validator(1)).await?);
+    assert!(!gatekeeper.is_validator(validator(3)).await?);
+    assert!(gatekeeper.is_peer_allowed(peer_list[0]).await?);
+    assert!(!gatekeeper.is_peer_allowed(peer_list[2]).await?);
+
+    // The validator 2 leaves the set while the validator 3 joins it, the validator 2 being
+    // still admitted during the epoch 1
+    let update = gatekeeper
+        .update_epoch(1, HashSet::from([validator(1), validator(3)]))
+        .await?;
+    assert_eq!(
+        update,
+        GatekeeperUpdate {
+            epoch_id: 1,
+            added_validators: vec![validator(3)],
+            removed_validators: vec![validator(2)],
+            allowed_peers: vec![peer_list[2]],
+            revoked_peers: Vec::new(),
+        }
+    );
+
+    assert!(gatekeeper.is_peer_allowed(peer_list[1]).await?);
+    assert!(gatekeeper.is_peer_allowed(peer_list[2]).await?);
+    assert!(!gatekeeper.is_validator(validator(2)).await?);
+
+    let update = gatekeeper
+        .update_epoch(2, HashSet::from([validator(1), validator(3)]))
+        .await?;
+    assert_eq!(
+        update,
+        GatekeeperUpdate {
+            epoch_id: 2,
+            revoked_peers: vec![peer_list[1]],
+            ..Default::default()
+        }
+    );
+
+    assert!(!gatekeeper.is_peer_allowed(peer_list[1]).await?);
+    assert!(gatekeeper.is_peer_allowed(peer_list[2]).await?);
+
+    assert!(matches!(
+        gatekeeper
+            .update_epoch(3, HashSet::from([validator(1), validator(3)]))
+            .await,
+        Err(GatekeeperError::NoUpdate)
+    ));
+    assert!(matches!(
+        gatekeeper.update_epoch(1, HashSet::new()).await,
+        Err(GatekeeperError::InvalidCommand(_))
+    ));
+
+    Ok(())
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn binding_is_replaced_by_later_statements(
+    #[future] gatekeeper: GatekeeperClient,
+    #[with(3)] peer_list: Vec<PeerId>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let gatekeeper = gatekeeper.await;
+
+    gatekeeper
+        .update_epoch(0, HashSet::from([validator(1), validator(2)]))
+        .await?;
+    gatekeeper.bind_peer(peer_list[0], validator(1), 1).await?;
+
+    // A binding signed at an earlier epoch doesn't replace the current one
+    assert!(matches!(
+        gatekeeper.bind_peer(peer_list[1], validator(1), 0).await,
+        Err(GatekeeperError::AlreadyBound(_))
+    ));
+    assert!(gatekeeper.is_peer_allowed(peer_list[0]).await?);
+    assert!(!gatekeeper.is_peer_allowed(peer_list[1]).await?);
+
+    // The bound peer can't act for another validator
+    assert!(matches!(
+        gatekeeper.bind_peer(peer_list[0], validator(2), 1).await,
+        Err(GatekeeperError::AlreadyBound(_))
+    ));
+
+    // The validator moving to another peer replaces the binding
+    gatekeeper.bind_peer(peer_list[1], validator(1), 1).await?;
+    assert!(gatekeeper.is_peer_allowed(peer_list[1]).await?);
+    assert!(!gatekeeper.is_peer_allowed(peer_list[0]).await?);
+
+    gatekeeper.bind_peer(peer_list[0], validator(2), 1).await?;
+    assert!(gatekeeper.is_peer_allowed(peer_list[0]).await?);
+
+    // A failed claim of an unknown validator leaves the peer unbound
+    assert!(matches!(
+        gatekeeper.bind_peer(peer_list[2], validator(3), 1).await,
+        Err(GatekeeperError::UnknownValidator(_))
+    ));
+    assert!(!gatekeeper.is_peer_allowed(peer_list[2]).await?);
+
+    Ok(())
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn denied_claims_are_bounded(
+    #[future] gatekeeper: GatekeeperClient,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let gatekeeper = gatekeeper.await;
+
+    let validators: HashSet<ValidatorId> = (0..=Gatekeeper::MAX_DENIED_CLAIMS)
+        .map(|i| format!("0x{i:040x}").parse().unwrap())
+        .collect();
+    for validator_id in &validators {
+        assert!(matches!(
+            gatekeeper
+                .bind_peer(PeerId::random(), *validator_id, 0)
+                .await,
+            Err(GatekeeperError::UnknownValidator(_))
+        ));
+    }
+
+    // Only the remembered claims are allowed once their validator joins the set
+    let update = gatekeeper.update_epoch(1, validators).await?;
+    assert_eq!(update.allowed_peers.len(), Gatekeeper::MAX_DENIED_CLAIMS);
+
+    Ok(())
+}
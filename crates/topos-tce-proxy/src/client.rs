@@ -347,6 +347,13 @@ impl TceClientBuilder {
                                 // All good, after one certificate is submitted carry on
                                 continue;
                             }
+                            Err(Error::CertificateRejected { certificate_id, reason }) => {
+                                if let Some(tce_proxy_event_sender) = tce_proxy_event_sender.clone() {
+                                    if let Err(e) = tce_proxy_event_sender.send(TceProxyEvent::CertificateRejected { certificate_id, reason }).await {
+                                          error!("Unable to send certificate rejection signal: {e}");
+                                    }
+                                }
+                            }
                             Err(e) => {
                                 // Backoff maximum period timeout. We need to restart sequencer.
                                 error!("Failed to submit certificate to the tce network, backoff timeout with error: {e}. Restarting sequencer...");
@@ -399,13 +406,25 @@ impl TceClientBuilder {
                                         .with_context(context_backoff.clone())
                                         .instrument(Span::current())
                                         .await
-                                        .map(|_response| {
-                                            info!("Successfully submitted the Certificate {} (previous: {}) to the TCE at {}",
-                                                &cert_id, &previous_cert_id, &tce_endpoint);
-                                        })
                                         .map_err(|e| {
                                             error!("Failed to submit the Certificate to the TCE at {}, error: {e}", &tce_endpoint);
-                                            new_tce_proxy_backoff_err(e)
+                                            new_tce_proxy_backoff_err(Error::from(e))
+                                        })
+                                        .and_then(|response| match response.into_inner().rejection {
+                                            // Submitting a rejected certificate again won't change the outcome
+                                            Some(rejection) => {
+                                                error!("The Certificate {} (previous: {}) has been rejected by the TCE at {}: {}",
+                                                    &cert_id, &previous_cert_id, &tce_endpoint, rejection.message);
+                                                Err(backoff::Error::Permanent(Error::CertificateRejected {
+                                                    certificate_id: cert_id,
+                                                    reason: rejection.message,
+                                                }))
+                                            }
+                                            None => {
+                                                info!("Successfully submitted the Certificate {} (previous: {}) to the TCE at {}",
+                                                    &cert_id, &previous_cert_id, &tce_endpoint);
+                                                Ok(())
+                                            }
                                         })
                                     };
 
@@ -434,7 +453,9 @@ impl TceClientBuilder {
                                             watch_certificates_request::OpenStream {
                                                 target_checkpoint:
                                                     Some(target_checkpoint.into()),
-                                                source_checkpoint: None
+                                                source_checkpoint: None,
+                                                max_unacknowledged: 0,
+                                                target_subnet_filter: vec![],
                                             }.into(),
                                     )
                                     .await
@@ -10,7 +10,7 @@ use tonic::transport::channel;
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
 use topos_core::{
     api::grpc::tce::v1::api_service_client::ApiServiceClient,
-    uci::{Certificate, SubnetId},
+    uci::{Certificate, CertificateId, SubnetId},
 };
 use tracing::{error, info};
 
@@ -54,6 +54,11 @@ pub enum Error {
         subnet_id: SubnetId,
         details: String,
     },
+    #[error("Certificate {certificate_id} rejected by the TCE: {reason}")]
+    CertificateRejected {
+        certificate_id: CertificateId,
+        reason: String,
+    },
 }
 
 /// Control the TceProxy
@@ -81,6 +86,11 @@ pub enum TceProxyEvent {
     WatchCertificatesChannelFailed,
     /// Failure in communication with the TCE grpc service. Sequencer needs to be restarted
     TceServiceFailure,
+    /// Certificate rejected by the TCE before entering its pending pool, it won't be delivered
+    CertificateRejected {
+        certificate_id: CertificateId,
+        reason: String,
+    },
 }
 
 /// Configuration data for the TCE proxy, used to configure the `TceProxyWorker`.
@@ -87,7 +87,9 @@ async fn test_tce_watch_certificates() -> Result<(), Box<dyn std::error::Error>>
                 target_subnet_ids: vec![ subnet_id_instream ],
                 positions: Vec::new()
             }),
-            source_checkpoint: None
+            source_checkpoint: None,
+            max_unacknowledged: 0,
+            target_subnet_filter: vec![],
         }.into()
     };
 
@@ -417,7 +419,9 @@ async fn test_tce_open_stream_with_checkpoint(
     let in_stream = async_stream::stream! {
         yield watch_certificates_request::OpenStream {
             target_checkpoint: Some(target_checkpoint),
-            source_checkpoint: None
+            source_checkpoint: None,
+            max_unacknowledged: 0,
+            target_subnet_filter: vec![],
         }.into()
     };
 
@@ -438,6 +442,7 @@ async fn test_tce_open_stream_with_checkpoint(
             Some(watch_certificates_response::Event::CertificatePushed(CertificatePushed {
                 certificate: Some(received_certificate),
                 positions,
+                ..
             })) => {
                 if let Some((expected_first_certificate_from_subnet, expected_position)) =
                     expected_certs.get(received_certificate.source_subnet_id.as_ref().unwrap())
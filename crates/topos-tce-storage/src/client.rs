@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use topos_core::types::stream::CertificateTargetStreamPosition;
 use topos_core::types::CertificateDelivered;
-use topos_core::uci::{Certificate, SubnetId};
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
 
 use crate::store::ReadStore;
 use crate::validator::ValidatorStore;
@@ -43,7 +43,20 @@ impl StorageClient {
         filter: FetchCertificatesFilter,
     ) -> Result<Vec<(CertificateDelivered, FetchCertificatesPosition)>, StorageError> {
         match filter {
-            FetchCertificatesFilter::Source { .. } => unimplemented!(),
+            FetchCertificatesFilter::Source {
+                source_stream_position,
+                limit,
+            } => self
+                .store
+                .get_source_stream_certificates_from_position(source_stream_position, limit)
+                .map(|values| {
+                    values
+                        .into_iter()
+                        .map(|(certificate, position)| {
+                            (certificate, FetchCertificatesPosition::Source(position))
+                        })
+                        .collect()
+                }),
             FetchCertificatesFilter::Target {
                 target_stream_position,
                 limit,
@@ -68,6 +81,21 @@ impl StorageClient {
         }
     }
 
+    /// Fetch a delivered certificate by its id
+    ///
+    /// Fails if the proof of the certificate has been pruned.
+    pub async fn get_certificate(
+        &self,
+        certificate_id: CertificateId,
+    ) -> Result<Option<CertificateDelivered>, StorageError> {
+        let certificate = self.store.get_certificate(&certificate_id)?;
+        if let Some(delivered) = &certificate {
+            self.store.fullnode_store().check_proof_kept(delivered)?;
+        }
+
+        Ok(certificate)
+    }
+
     /// Fetch source head certificate for subnet
     ///
     /// Return position of the certificate and certificate itself
@@ -3,10 +3,14 @@ pub(crate) mod cfs {
     pub(crate) const STREAMS: &str = "streams";
     pub(crate) const EPOCH_CHAIN: &str = "epoch_chain";
     pub(crate) const UNVERIFIED: &str = "unverified";
+    pub(crate) const SCHEMA: &str = "schema";
+    pub(crate) const PRUNED_PROOFS: &str = "pruned_proofs";
 
     pub(crate) const PENDING_POOL: &str = "pending_pool";
     pub(crate) const PENDING_POOL_INDEX: &str = "pending_pool_index";
     pub(crate) const PRECEDENCE_POOL: &str = "precedence_pool";
+    pub(crate) const PRECEDENCE_POOL_TIMESTAMPS: &str = "precedence_pool_timestamps";
+    pub(crate) const PRECEDENCE_POOL_INDEX: &str = "precedence_pool_index";
 
     pub(crate) const TARGET_STREAMS: &str = "target_streams";
     pub(crate) const TARGET_SOURCE_LIST: &str = "target_source_list";
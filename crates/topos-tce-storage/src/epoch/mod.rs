@@ -1,49 +1,231 @@
-use std::path::Path;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{collections::HashMap, sync::RwLock};
 
 use arc_swap::ArcSwap;
+use rocksdb::IteratorMode;
 
-use crate::errors::StorageError;
-use crate::types::{EpochId, Validators};
+use crate::errors::{InternalStorageError, StorageError};
+use crate::rocks::map::Map;
+use crate::types::{EpochId, EpochSummary, Validators, VerifiedCheckpointSummary};
 
 pub use self::tables::EpochValidatorsTables;
 pub use self::tables::ValidatorPerEpochTables;
+use self::tables::{EpochSummaryKey, EpochSummaryValue};
 
 mod tables;
 
 /// Epoch contextualized data - can be purged at some point
 pub struct ValidatorPerEpochStore {
-    #[allow(unused)]
     epoch_id: EpochId,
     #[allow(unused)]
     validators: RwLock<Validators>,
-    #[allow(unused)]
     tables: ValidatorPerEpochTables,
+    /// Root path of the storage, under which the tables of every epoch are opened, `None` if
+    /// the tables are kept in memory
+    path: Option<PathBuf>,
 }
 
 impl ValidatorPerEpochStore {
     pub fn new(epoch_id: EpochId, path: &Path) -> Result<ArcSwap<Self>, StorageError> {
-        let tables: ValidatorPerEpochTables = ValidatorPerEpochTables::open(epoch_id, path);
-        let store = ArcSwap::from(Arc::new(Self {
+        let store = ArcSwap::from(Self::open(epoch_id, path)?);
+
+        Ok(store)
+    }
+
+    /// Open the store of the given epoch for reading only, nothing being created under the path
+    pub(crate) fn open_read_only(
+        epoch_id: EpochId,
+        path: &Path,
+    ) -> Result<Arc<Self>, InternalStorageError> {
+        Ok(Arc::new(Self {
             epoch_id,
-            validators: RwLock::new(Vec::new()),
-            tables,
-        }));
+            validators: RwLock::new(Validators::new()),
+            tables: ValidatorPerEpochTables::open_read_only(epoch_id, path)?,
+            path: Some(path.to_path_buf()),
+        }))
+    }
+
+    /// Create a new [`ValidatorPerEpochStore`] in memory, nothing being persisted
+    #[cfg(feature = "inmemory")]
+    pub fn new_in_memory(epoch_id: EpochId) -> Result<ArcSwap<Self>, StorageError> {
+        let store = ArcSwap::from(Self::open_in_memory(epoch_id));
 
         Ok(store)
     }
+
+    fn open(epoch_id: EpochId, path: &Path) -> Result<Arc<Self>, InternalStorageError> {
+        let tables: ValidatorPerEpochTables = ValidatorPerEpochTables::open(epoch_id, path)?;
+
+        Ok(Arc::new(Self {
+            epoch_id,
+            validators: RwLock::new(Validators::new()),
+            tables,
+            path: Some(path.to_path_buf()),
+        }))
+    }
+
+    #[cfg(feature = "inmemory")]
+    fn open_in_memory(epoch_id: EpochId) -> Arc<Self> {
+        Arc::new(Self {
+            epoch_id,
+            validators: RwLock::new(Validators::new()),
+            tables: ValidatorPerEpochTables::open_in_memory(),
+            path: None,
+        })
+    }
+
+    /// Returns the latest epoch having its tables created under the given path, if any
+    pub(crate) fn latest_epoch(path: &Path) -> Option<EpochId> {
+        read_dir(path.join("epochs"))
+            .ok()?
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<EpochId>().ok())
+            .max()
+    }
+
+    /// Opens the store of another epoch under the same root path, or in memory if this one is
+    pub(crate) fn open_epoch(&self, epoch_id: EpochId) -> Result<Arc<Self>, InternalStorageError> {
+        match &self.path {
+            Some(path) => Self::open(epoch_id, path),
+            #[cfg(feature = "inmemory")]
+            None => Ok(Self::open_in_memory(epoch_id)),
+            #[cfg(not(feature = "inmemory"))]
+            None => unreachable!("The tables of the epoch are always opened at a path"),
+        }
+    }
+
+    pub fn epoch_id(&self) -> EpochId {
+        self.epoch_id
+    }
+
+    /// Persist the checkpoint signed by a quorum of validators at the start of the epoch
+    pub fn insert_start_checkpoint(
+        &self,
+        checkpoint: VerifiedCheckpointSummary,
+    ) -> Result<(), StorageError> {
+        self.tables.epoch_summary.multi_insert([
+            (
+                EpochSummaryKey::EpochId,
+                EpochSummaryValue::EpochId(self.epoch_id),
+            ),
+            (
+                EpochSummaryKey::StartCheckpoint,
+                EpochSummaryValue::StartCheckpoint(checkpoint),
+            ),
+        ])?;
+
+        Ok(())
+    }
+
+    /// Persist the checkpoint signed by a quorum of validators at the end of the epoch
+    pub fn insert_end_checkpoint(
+        &self,
+        checkpoint: VerifiedCheckpointSummary,
+    ) -> Result<(), StorageError> {
+        self.tables.epoch_summary.insert(
+            &EpochSummaryKey::EndCheckpoint,
+            &EpochSummaryValue::EndCheckpoint(checkpoint),
+        )?;
+
+        Ok(())
+    }
+
+    /// Persist a checkpoint signed by a quorum of validators during the epoch, after its start
+    /// checkpoint
+    pub fn insert_latest_checkpoint(
+        &self,
+        checkpoint: VerifiedCheckpointSummary,
+    ) -> Result<(), StorageError> {
+        self.tables.epoch_summary.insert(
+            &EpochSummaryKey::LatestCheckpoint,
+            &EpochSummaryValue::LatestCheckpoint(checkpoint),
+        )?;
+
+        Ok(())
+    }
+
+    /// Rewrite the start and end checkpoints of the epoch from its summary
+    pub(crate) fn rewrite_checkpoints(
+        &self,
+        summary: &EpochSummary,
+    ) -> Result<(), InternalStorageError> {
+        let mut checkpoints = vec![(
+            EpochSummaryKey::StartCheckpoint,
+            EpochSummaryValue::StartCheckpoint(summary.start_checkpoint.clone()),
+        )];
+        if let Some(checkpoint) = &summary.end_checkpoint {
+            checkpoints.push((
+                EpochSummaryKey::EndCheckpoint,
+                EpochSummaryValue::EndCheckpoint(checkpoint.clone()),
+            ));
+        }
+
+        self.tables.epoch_summary.multi_insert(checkpoints)
+    }
+
+    /// Returns the signed checkpoint of the start of the epoch, if any
+    pub fn get_start_checkpoint(&self) -> Result<Option<VerifiedCheckpointSummary>, StorageError> {
+        match self
+            .tables
+            .epoch_summary
+            .get(&EpochSummaryKey::StartCheckpoint)?
+        {
+            Some(EpochSummaryValue::StartCheckpoint(checkpoint)) => Ok(Some(checkpoint)),
+            Some(_) => Err(InternalStorageError::UnexpectedDBState(
+                "Start checkpoint stored with an unexpected value",
+            )
+            .into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the signed checkpoint of the end of the epoch, if any
+    pub fn get_end_checkpoint(&self) -> Result<Option<VerifiedCheckpointSummary>, StorageError> {
+        match self
+            .tables
+            .epoch_summary
+            .get(&EpochSummaryKey::EndCheckpoint)?
+        {
+            Some(EpochSummaryValue::EndCheckpoint(checkpoint)) => Ok(Some(checkpoint)),
+            Some(_) => Err(InternalStorageError::UnexpectedDBState(
+                "End checkpoint stored with an unexpected value",
+            )
+            .into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the latest signed checkpoint of the epoch, the start checkpoint if no checkpoint
+    /// has been signed since
+    pub fn get_latest_checkpoint(&self) -> Result<Option<VerifiedCheckpointSummary>, StorageError> {
+        match self
+            .tables
+            .epoch_summary
+            .get(&EpochSummaryKey::LatestCheckpoint)?
+        {
+            Some(EpochSummaryValue::LatestCheckpoint(checkpoint)) => Ok(Some(checkpoint)),
+            Some(_) => Err(InternalStorageError::UnexpectedDBState(
+                "Latest checkpoint stored with an unexpected value",
+            )
+            .into()),
+            None => self.get_start_checkpoint(),
+        }
+    }
 }
+
+/// Store of the validator set of every epoch
+///
+/// The validator set of an epoch is persisted when the epoch starts and is never updated
+/// afterward, certificates broadcast during an epoch are resolved against it.
 pub struct EpochValidatorsStore {
-    #[allow(unused)]
     tables: EpochValidatorsTables,
-    #[allow(unused)]
     caches: RwLock<HashMap<EpochId, Validators>>,
 }
 
 impl EpochValidatorsStore {
     pub fn new(path: &Path) -> Result<Arc<Self>, StorageError> {
-        let tables = EpochValidatorsTables::open(path);
+        let tables = EpochValidatorsTables::open(path)?;
         let store = Arc::new(Self {
             tables,
             caches: RwLock::new(HashMap::new()),
@@ -51,4 +233,70 @@ impl EpochValidatorsStore {
 
         Ok(store)
     }
+
+    /// Create a new [`EpochValidatorsStore`] in memory, nothing being persisted
+    #[cfg(feature = "inmemory")]
+    pub fn new_in_memory() -> Result<Arc<Self>, StorageError> {
+        let store = Arc::new(Self {
+            tables: EpochValidatorsTables::open_in_memory(),
+            caches: RwLock::new(HashMap::new()),
+        });
+
+        Ok(store)
+    }
+
+    /// Persist the validator set of the given epoch
+    pub fn insert_validators(
+        &self,
+        epoch_id: EpochId,
+        validators: Validators,
+    ) -> Result<(), StorageError> {
+        self.tables.validators_map.insert(&epoch_id, &validators)?;
+
+        self.caches
+            .write()
+            .map_err(|_| InternalStorageError::UnexpectedDBState("Validators cache poisoned"))?
+            .insert(epoch_id, validators);
+
+        Ok(())
+    }
+
+    /// Returns the validator set of the given epoch, if any
+    pub fn get_validators(&self, epoch_id: EpochId) -> Result<Option<Validators>, StorageError> {
+        if let Some(validators) = self
+            .caches
+            .read()
+            .map_err(|_| InternalStorageError::UnexpectedDBState("Validators cache poisoned"))?
+            .get(&epoch_id)
+        {
+            return Ok(Some(validators.clone()));
+        }
+
+        let validators = self.tables.validators_map.get(&epoch_id)?;
+        if let Some(ref validators) = validators {
+            self.caches
+                .write()
+                .map_err(|_| InternalStorageError::UnexpectedDBState("Validators cache poisoned"))?
+                .insert(epoch_id, validators.clone());
+        }
+
+        Ok(validators)
+    }
+
+    /// Returns every persisted epoch along with its validator set, from the latest one
+    pub fn get_all_validators(&self) -> Result<Vec<(EpochId, Validators)>, StorageError> {
+        let mut validators: Vec<_> = self.tables.validators_map.iter()?.collect();
+        validators.reverse();
+
+        Ok(validators)
+    }
+
+    /// Returns the latest persisted epoch along with its validator set, if any
+    pub fn get_latest_validators(&self) -> Result<Option<(EpochId, Validators)>, StorageError> {
+        Ok(self
+            .tables
+            .validators_map
+            .iter_with_mode(IteratorMode::End)?
+            .next())
+    }
 }
@@ -1,40 +1,54 @@
 use std::{fs::create_dir_all, path::Path};
 
 use rocksdb::ColumnFamilyDescriptor;
+use serde::{Deserialize, Serialize};
 use topos_core::uci::CertificateId;
 use tracing::warn;
 
+#[cfg(feature = "inmemory")]
+use crate::memory::MemoryDB;
 use crate::{
     constant::cfs,
+    errors::InternalStorageError,
     rocks::{
-        db::{default_options, init_db, init_with_cfs},
+        db::{default_options, init_with_cfs, ReadOnlyDB},
         db_column::DBColumn,
     },
     types::{BroadcastState, EpochId, Validators, VerifiedCheckpointSummary},
 };
 
 pub struct EpochValidatorsTables {
-    #[allow(unused)]
-    validators_map: DBColumn<EpochId, Validators>,
+    pub(crate) validators_map: DBColumn<EpochId, Validators>,
 }
 
 impl EpochValidatorsTables {
-    pub(crate) fn open(path: &Path) -> Self {
+    pub(crate) fn open(path: &Path) -> Result<Self, InternalStorageError> {
         let path = path.join("validators");
-        let mut options = rocksdb::Options::default();
-        options.create_if_missing(true);
-        let db = init_db(&path, options).unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
+        let cfs = vec![ColumnFamilyDescriptor::new(
+            cfs::VALIDATORS,
+            default_options(),
+        )];
 
-        Self {
+        let db = init_with_cfs(&path, default_options(), cfs)?;
+
+        Ok(Self {
             validators_map: DBColumn::reopen(&db, cfs::VALIDATORS),
+        })
+    }
+
+    #[cfg(feature = "inmemory")]
+    pub(crate) fn open_in_memory() -> Self {
+        let db = MemoryDB::new(&[(cfs::VALIDATORS, None)]);
+
+        Self {
+            validators_map: DBColumn::reopen_in_memory(&db, cfs::VALIDATORS),
         }
     }
 }
 
 /// Epoch contextualized data - can be purged at some point
 pub struct ValidatorPerEpochTables {
-    #[allow(unused)]
-    epoch_summary: DBColumn<EpochSummaryKey, EpochSummaryValue>,
+    pub(crate) epoch_summary: DBColumn<EpochSummaryKey, EpochSummaryValue>,
     #[allow(unused)]
     broadcast_states: DBColumn<CertificateId, BroadcastState>,
     #[allow(unused)]
@@ -42,38 +56,72 @@ pub struct ValidatorPerEpochTables {
 }
 
 impl ValidatorPerEpochTables {
-    pub(crate) fn open(epoch_id: EpochId, path: &Path) -> Self {
+    const COLUMNS: &'static [(&'static str, Option<usize>)] =
+        &[(cfs::EPOCH_SUMMARY, None), (cfs::BROADCAST_STATES, None)];
+
+    pub(crate) fn open(epoch_id: EpochId, path: &Path) -> Result<Self, InternalStorageError> {
         let path = path.join("epochs").join(epoch_id.to_string());
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
-            create_dir_all(&path).expect("Cannot create ValidatorPerEpochTables directory");
+            create_dir_all(&path)?;
         }
         let cfs = vec![
             ColumnFamilyDescriptor::new(cfs::EPOCH_SUMMARY, default_options()),
             ColumnFamilyDescriptor::new(cfs::BROADCAST_STATES, default_options()),
         ];
 
-        let db = init_with_cfs(&path, default_options(), cfs)
-            .unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
+        let db = init_with_cfs(&path, default_options(), cfs)?;
 
-        Self {
+        Ok(Self {
             epoch_summary: DBColumn::reopen(&db, cfs::EPOCH_SUMMARY),
             broadcast_states: DBColumn::reopen(&db, cfs::BROADCAST_STATES),
             validators: Vec::new(),
+        })
+    }
+
+    /// Open the tables of the given epoch for reading only, nothing being created. The missing
+    /// tables are read as empty ones.
+    pub(crate) fn open_read_only(
+        epoch_id: EpochId,
+        path: &Path,
+    ) -> Result<Self, InternalStorageError> {
+        let db = ReadOnlyDB::open(
+            &path.join("epochs").join(epoch_id.to_string()),
+            Self::COLUMNS,
+        )?;
+
+        Ok(Self {
+            epoch_summary: db.column(cfs::EPOCH_SUMMARY)?,
+            broadcast_states: db.column(cfs::BROADCAST_STATES)?,
+            validators: Vec::new(),
+        })
+    }
+
+    #[cfg(feature = "inmemory")]
+    pub(crate) fn open_in_memory() -> Self {
+        let db = MemoryDB::new(Self::COLUMNS);
+
+        Self {
+            epoch_summary: DBColumn::reopen_in_memory(&db, cfs::EPOCH_SUMMARY),
+            broadcast_states: DBColumn::reopen_in_memory(&db, cfs::BROADCAST_STATES),
+            validators: Vec::new(),
         }
     }
 }
 
-#[allow(unused)]
-enum EpochSummaryKey {
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum EpochSummaryKey {
     EpochId,
     StartCheckpoint,
     EndCheckpoint,
+    /// Latest checkpoint signed during the epoch, after its start checkpoint
+    LatestCheckpoint,
 }
 
-#[allow(unused)]
-enum EpochSummaryValue {
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum EpochSummaryValue {
     EpochId(EpochId),
     StartCheckpoint(VerifiedCheckpointSummary),
     EndCheckpoint(VerifiedCheckpointSummary),
+    LatestCheckpoint(VerifiedCheckpointSummary),
 }
@@ -1,7 +1,7 @@
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 use topos_core::{
-    types::stream::PositionError,
+    types::{stream::PositionError, ProofOfDeliveryError},
     uci::{CertificateId, SubnetId, SUBNET_ID_LENGTH},
 };
 
@@ -19,6 +19,9 @@ pub enum InternalStorageError {
     #[error("Unable to start storage")]
     UnableToStartStorage,
 
+    #[error("Unable to access the storage directory: {0}")]
+    Io(#[from] std::io::Error),
+
     #[cfg(feature = "rocksdb")]
     #[error("Unable to execute query: {0}")]
     RocksDBError(#[from] rocksdb::Error),
@@ -53,6 +56,21 @@ pub enum InternalStorageError {
 
     #[error("Certificate already exists at position {0} for subnet {1}")]
     CertificateAlreadyExistsAtPosition(u64, SubnetId),
+
+    #[error(
+        "Storage schema version {0} is newer than the supported version {}",
+        crate::migration::SCHEMA_VERSION
+    )]
+    UnsupportedSchemaVersion(u32),
+
+    #[error(
+        "A snapshot import has been interrupted or rejected, the store needs to be removed before \
+         importing a snapshot again"
+    )]
+    InterruptedSnapshotImport,
+
+    #[error("The proof of the certificate {0} has been pruned")]
+    ProofPruned(CertificateId),
 }
 
 #[derive(Debug, Error)]
@@ -69,3 +87,42 @@ pub enum StorageError {
     #[error("Unable to execute shutdown on the storage service: {0}")]
     ShutdownCommunication(mpsc::error::SendError<oneshot::Sender<()>>),
 }
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("Unable to access the snapshot file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Unable to encode or decode the snapshot: {0}")]
+    Bincode(#[from] Box<bincode::ErrorKind>),
+
+    #[error("Unsupported snapshot version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("The snapshot checksum doesn't match its content")]
+    ChecksumMismatch,
+
+    #[error("The snapshot is truncated")]
+    Truncated,
+
+    #[error("Invalid proof of delivery for certificate {0}: {1}")]
+    InvalidProofOfDelivery(CertificateId, #[source] ProofOfDeliveryError),
+
+    #[error("Inconsistent snapshot: {0}")]
+    Inconsistent(String),
+
+    #[error("Unable to import a snapshot in a store already having delivered certificates")]
+    StoreNotEmpty,
+
+    #[error("Unable to export a snapshot of a store whose proofs have been pruned")]
+    StorePruned,
+}
+
+impl From<InternalStorageError> for SnapshotError {
+    fn from(error: InternalStorageError) -> Self {
+        Self::Storage(error.into())
+    }
+}
@@ -1,10 +1,16 @@
-use std::{collections::HashMap, path::Path, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs::{create_dir_all, remove_dir_all},
+    path::Path,
+    sync::Arc,
+};
 
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 
-use rocksdb::properties::ESTIMATE_NUM_KEYS;
-use tokio::sync::OwnedMutexGuard;
+use rocksdb::{properties::ESTIMATE_NUM_KEYS, IteratorMode};
+use tokio::sync::{OwnedMutexGuard, RwLock};
 use topos_core::{
     types::{
         stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position},
@@ -16,18 +22,25 @@ use tracing::{error, info};
 
 use crate::{
     epoch::{EpochValidatorsStore, ValidatorPerEpochStore},
-    errors::{InternalStorageError, StorageError},
+    errors::{InternalStorageError, SnapshotError, StorageError},
     index::IndexTables,
+    integrity::{self, IntegrityReport},
+    migration,
     rocks::{map::Map, TargetSourceListKey},
+    snapshot::{self, SnapshotImport, SnapshotReader, SnapshotSummary},
     store::{ReadStore, WriteStore},
+    types::{CheckpointSummary, EpochId, EpochSummary, Validators, VerifiedCheckpointSummary},
     validator::ValidatorPerpetualTables,
-    CertificatePositions, SourceHead,
+    CertificatePositions, SourceHead, TargetHead,
 };
 
 use self::locking::LockGuards;
 
 pub mod locking;
 
+/// Number of certificates rewritten at once when pruning their proofs
+const PRUNING_BATCH_SIZE: usize = 1_000;
+
 /// Store to manage FullNode data
 ///
 /// The [`FullNodeStore`] is responsible for storing and exposing the data that is
@@ -43,23 +56,26 @@ pub mod locking;
 pub struct FullNodeStore {
     certificate_lock_guards: LockGuards<CertificateId>,
     subnet_lock_guards: LockGuards<SubnetId>,
-    #[allow(unused)]
-    epoch_store: ArcSwap<ValidatorPerEpochStore>,
-    #[allow(unused)]
+    pub(crate) epoch_store: ArcSwap<ValidatorPerEpochStore>,
     validators_store: Arc<EpochValidatorsStore>,
     pub(crate) perpetual_tables: Arc<ValidatorPerpetualTables>,
     pub(crate) index_tables: Arc<IndexTables>,
+    /// Held for writing while a snapshot is taken or imported, blocking the deliveries
+    snapshot_lock: RwLock<()>,
 }
 
 impl FullNodeStore {
     /// Try to create a new instance of [`FullNodeStore`] based on the given path
     pub fn new(path: &Path) -> Result<Arc<Self>, StorageError> {
-        let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(path));
-        let index_tables = Arc::new(IndexTables::open(path));
+        let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(path)?);
+        let index_tables = Arc::new(IndexTables::open(path)?);
 
         let validators_store = EpochValidatorsStore::new(path)?;
 
-        let epoch_store = ValidatorPerEpochStore::new(0, path)?;
+        let epoch_store = ValidatorPerEpochStore::new(
+            ValidatorPerEpochStore::latest_epoch(path).unwrap_or(0),
+            path,
+        )?;
 
         FullNodeStore::open(
             epoch_store,
@@ -68,12 +84,27 @@ impl FullNodeStore {
             index_tables,
         )
     }
+
+    /// Try to create a new instance of [`FullNodeStore`] in memory, nothing being persisted
+    #[cfg(feature = "inmemory")]
+    pub fn new_in_memory() -> Result<Arc<Self>, StorageError> {
+        FullNodeStore::open(
+            ValidatorPerEpochStore::new_in_memory(0)?,
+            EpochValidatorsStore::new_in_memory()?,
+            Arc::new(ValidatorPerpetualTables::open_in_memory()),
+            Arc::new(IndexTables::open_in_memory()),
+        )
+    }
+
     pub fn open(
         epoch_store: ArcSwap<ValidatorPerEpochStore>,
         validators_store: Arc<EpochValidatorsStore>,
         perpetual_tables: Arc<ValidatorPerpetualTables>,
         index_tables: Arc<IndexTables>,
     ) -> Result<Arc<Self>, StorageError> {
+        migration::check_schema_version(&perpetual_tables)?;
+        snapshot::check_no_pending_import(&perpetual_tables)?;
+
         Ok(Arc::new(Self {
             certificate_lock_guards: LockGuards::new(),
             subnet_lock_guards: LockGuards::new(),
@@ -81,9 +112,511 @@ impl FullNodeStore {
             validators_store,
             perpetual_tables,
             index_tables,
+            snapshot_lock: RwLock::new(()),
         }))
     }
 
+    /// Returns the [`EpochValidatorsStore`] holding the validator set of every epoch
+    pub fn epoch_validators_store(&self) -> Arc<EpochValidatorsStore> {
+        self.validators_store.clone()
+    }
+
+    /// Returns the summary of the given epoch, if any
+    pub fn get_epoch_summary(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<Option<EpochSummary>, StorageError> {
+        Ok(self.perpetual_tables.epoch_chain.get(&epoch_id)?)
+    }
+
+    /// Returns the summary of the latest known epoch, if any
+    pub fn get_latest_epoch_summary(&self) -> Result<Option<EpochSummary>, StorageError> {
+        Ok(self
+            .perpetual_tables
+            .epoch_chain
+            .iter_with_mode(IteratorMode::End)?
+            .next()
+            .map(|(_, summary)| summary))
+    }
+
+    /// Start a new epoch from the given checkpoint
+    ///
+    /// The checkpoint also ends the epoch that was running before, if any.
+    pub fn start_epoch(
+        &self,
+        epoch_id: EpochId,
+        checkpoint: VerifiedCheckpointSummary,
+    ) -> Result<EpochSummary, StorageError> {
+        let mut summaries = Vec::with_capacity(2);
+
+        if let Some(mut previous) = self.get_latest_epoch_summary()? {
+            if previous.epoch_id >= epoch_id {
+                return Err(InternalStorageError::InvalidQueryArgument(
+                    "Epoch is older than the latest known epoch",
+                )
+                .into());
+            }
+
+            previous.end_checkpoint = Some(checkpoint.clone());
+            summaries.push((previous.epoch_id, previous));
+        }
+
+        let summary = EpochSummary {
+            epoch_id,
+            start_checkpoint: checkpoint,
+            end_checkpoint: None,
+        };
+        summaries.push((epoch_id, summary.clone()));
+
+        self.perpetual_tables.epoch_chain.multi_insert(summaries)?;
+
+        Ok(summary)
+    }
+
+    /// Persist a checkpoint signed by a quorum of validators
+    ///
+    /// The first checkpoint signed in an epoch starts it and ends the previous one: the per
+    /// epoch store is switched to the epoch of the checkpoint and the epoch summaries are
+    /// updated accordingly. The following checkpoints of the epoch are only kept as its latest
+    /// one.
+    pub fn insert_signed_checkpoint(
+        &self,
+        checkpoint: VerifiedCheckpointSummary,
+    ) -> Result<(), StorageError> {
+        let epoch_id = checkpoint.0.epoch;
+        let epoch_store = self.epoch_store.load_full();
+
+        match epoch_store.epoch_id().cmp(&epoch_id) {
+            Ordering::Greater => {
+                return Err(InternalStorageError::InvalidQueryArgument(
+                    "Checkpoint is older than the current epoch",
+                )
+                .into())
+            }
+            Ordering::Equal => match epoch_store.get_latest_checkpoint()? {
+                Some(VerifiedCheckpointSummary(latest, _))
+                    if latest.sequence_number >= checkpoint.0.sequence_number =>
+                {
+                    return Err(InternalStorageError::InvalidQueryArgument(
+                        "Checkpoint is older than the latest signed checkpoint",
+                    )
+                    .into())
+                }
+                Some(_) => return epoch_store.insert_latest_checkpoint(checkpoint),
+                None => epoch_store.insert_start_checkpoint(checkpoint.clone())?,
+            },
+            Ordering::Less => {
+                epoch_store.insert_end_checkpoint(checkpoint.clone())?;
+
+                let next_epoch_store = epoch_store.open_epoch(epoch_id)?;
+                next_epoch_store.insert_start_checkpoint(checkpoint.clone())?;
+                self.epoch_store.store(next_epoch_store);
+            }
+        }
+
+        let mut summaries = Vec::with_capacity(2);
+
+        if let Some((previous_epoch_id, mut previous)) = self
+            .perpetual_tables
+            .epoch_chain
+            .iter()?
+            .take_while(|(id, _)| *id < epoch_id)
+            .last()
+        {
+            previous.end_checkpoint = Some(checkpoint.clone());
+            summaries.push((previous_epoch_id, previous));
+        }
+
+        let summary = match self.get_epoch_summary(epoch_id)? {
+            Some(mut summary) => {
+                summary.start_checkpoint = checkpoint;
+                summary
+            }
+            None => EpochSummary {
+                epoch_id,
+                start_checkpoint: checkpoint,
+                end_checkpoint: None,
+            },
+        };
+        summaries.push((epoch_id, summary));
+
+        self.perpetual_tables.epoch_chain.multi_insert(summaries)?;
+
+        Ok(())
+    }
+
+    /// Returns the latest checkpoint signed by a quorum of validators, if any
+    pub fn get_latest_signed_checkpoint(
+        &self,
+    ) -> Result<Option<VerifiedCheckpointSummary>, StorageError> {
+        self.epoch_store.load().get_latest_checkpoint()
+    }
+
+    /// Returns whether every source and target stream head of the checkpoint is delivered
+    pub fn has_delivered_heads(
+        &self,
+        checkpoint: &CheckpointSummary,
+    ) -> Result<bool, StorageError> {
+        for head in &checkpoint.checkpoint_data {
+            let position = CertificateSourceStreamPosition::new(head.subnet_id, head.position);
+            if self.perpetual_tables.streams.get(&position)? != Some(head.certificate_id) {
+                return Ok(false);
+            }
+        }
+
+        for head in &checkpoint.target_checkpoint_data {
+            let position = CertificateTargetStreamPosition::new(
+                head.target_subnet_id,
+                head.source_subnet_id,
+                head.position,
+            );
+            if self.index_tables.target_streams.get(&position)? != Some(head.certificate_id) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the positions of a delivered certificate in the streams of its target subnets
+    ///
+    /// The certificates of a target stream are ordered by their position in the source stream,
+    /// the certificate being looked up by a binary search on each of its target streams.
+    pub fn get_target_positions(
+        &self,
+        certificate: &CertificateDelivered,
+    ) -> Result<HashMap<SubnetId, CertificateTargetStreamPosition>, StorageError> {
+        let source_subnet_id = certificate.certificate.source_subnet_id;
+        let mut positions = HashMap::new();
+
+        for target_subnet_id in &certificate.certificate.target_subnets {
+            if let Some(position) =
+                self.find_target_position(*target_subnet_id, source_subnet_id, certificate)?
+            {
+                positions.insert(*target_subnet_id, position);
+            }
+        }
+
+        Ok(positions)
+    }
+
+    /// Binary search of a certificate in the target stream of its source subnet, the stream
+    /// possibly having gaps when bootstrapped from a checkpoint
+    fn find_target_position(
+        &self,
+        target_subnet_id: SubnetId,
+        source_subnet_id: SubnetId,
+        certificate: &CertificateDelivered,
+    ) -> Result<Option<CertificateTargetStreamPosition>, StorageError> {
+        let prefix = TargetSourceListKey(target_subnet_id, source_subnet_id);
+        let Some(head) = self.index_tables.target_source_list.get(&prefix)? else {
+            return Ok(None);
+        };
+
+        let source_position = *certificate.proof_of_delivery.delivery_position.position;
+        let (mut low, mut high) = (0u64, *head);
+
+        while low <= high {
+            let middle = low + (high - low) / 2;
+
+            // First certificate of the stream at or after the middle position
+            let Some((position, certificate_id)) = self
+                .index_tables
+                .target_streams
+                .prefix_iter_at(
+                    &prefix,
+                    &CertificateTargetStreamPosition::new(
+                        target_subnet_id,
+                        source_subnet_id,
+                        middle,
+                    ),
+                )?
+                .next()
+                .filter(|(position, _)| {
+                    position.target_subnet_id == target_subnet_id
+                        && position.source_subnet_id == source_subnet_id
+                        && *position.position <= high
+                })
+            else {
+                // Nothing left in [middle, high]
+                if middle == 0 {
+                    break;
+                }
+                high = middle - 1;
+                continue;
+            };
+
+            if certificate_id == certificate.certificate.id {
+                return Ok(Some(position));
+            }
+
+            // A certificate missing from the store can't be ordered, the search goes past it
+            let ordering = self
+                .perpetual_tables
+                .certificates
+                .get(&certificate_id)?
+                .map_or(Ordering::Less, |found| {
+                    (*found.proof_of_delivery.delivery_position.position).cmp(&source_position)
+                });
+
+            match ordering {
+                Ordering::Less => low = *position.position + 1,
+                Ordering::Greater | Ordering::Equal if middle == 0 => break,
+                Ordering::Greater | Ordering::Equal => high = middle - 1,
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Export a snapshot of the delivered certificates and their streams to the given file
+    ///
+    /// The snapshot is taken from RocksDB checkpoints of the tables, created next to the file and
+    /// removed once the snapshot is written. Deliveries are only blocked while the checkpoints
+    /// are created.
+    ///
+    /// The snapshot of a pruned store is refused, its certificates no longer matching their id.
+    pub async fn export_snapshot(&self, path: &Path) -> Result<SnapshotSummary, SnapshotError> {
+        if self.is_pruned()? {
+            return Err(SnapshotError::StorePruned);
+        }
+
+        let checkpoint_path = path.with_extension("checkpoint");
+        create_dir_all(&checkpoint_path)?;
+
+        let summary = self.write_snapshot(&checkpoint_path, path).await;
+        remove_dir_all(&checkpoint_path)?;
+
+        summary
+    }
+
+    async fn write_snapshot(
+        &self,
+        checkpoint_path: &Path,
+        path: &Path,
+    ) -> Result<SnapshotSummary, SnapshotError> {
+        {
+            let _snapshot_guard = self.snapshot_lock.write().await;
+
+            self.perpetual_tables.create_checkpoint(checkpoint_path)?;
+            self.index_tables.create_checkpoint(checkpoint_path)?;
+        }
+
+        snapshot::export(
+            &ValidatorPerpetualTables::open(checkpoint_path)?,
+            &IndexTables::open(checkpoint_path)?,
+            path,
+        )
+    }
+
+    /// Import the snapshot of the given file in an empty store, verifying it along the way
+    ///
+    /// The proofs of delivery are verified against the `validators` and threshold of every
+    /// epoch, the genesis ones first, see the [`snapshot`](module@crate::snapshot) module for
+    /// the verification of the snapshot. A store whose import fails can't be opened anymore and
+    /// needs to be removed.
+    pub async fn import_snapshot(
+        &self,
+        path: &Path,
+        validators: &[(Validators, u64)],
+    ) -> Result<SnapshotSummary, SnapshotError> {
+        let mut reader = SnapshotReader::open(path)?;
+
+        let _snapshot_guard = self.snapshot_lock.write().await;
+        let mut import =
+            SnapshotImport::start(&self.perpetual_tables, &self.index_tables, validators)?;
+
+        while let Some(chunk) = reader.next_chunk()? {
+            import.import_chunk(chunk)?;
+        }
+        let summary = import.finish()?;
+
+        info!(
+            "Snapshot of {} certificates imported from {} source subnets",
+            summary.certificates,
+            summary.source_heads.len()
+        );
+
+        Ok(summary)
+    }
+
+    /// Check the integrity of the delivered certificates, their streams and their indexes
+    ///
+    /// See the [`integrity`](module@crate::integrity) module for the checks performed.
+    pub fn check_integrity(&self) -> Result<IntegrityReport, StorageError> {
+        Ok(integrity::check(
+            &self.perpetual_tables,
+            &self.index_tables,
+        )?)
+    }
+
+    /// Rebuild the source heads, the target streams and the target source lists from the
+    /// delivered certificates and their source streams
+    ///
+    /// Deliveries are blocked during the repair. Returns the integrity report of the repaired
+    /// store, holding the issues which can't be repaired.
+    pub async fn repair_indexes(&self) -> Result<IntegrityReport, StorageError> {
+        {
+            let _snapshot_guard = self.snapshot_lock.write().await;
+
+            integrity::repair(&self.perpetual_tables, &self.index_tables)?;
+        }
+
+        self.check_integrity()
+    }
+
+    /// Remove the unverified proofs of delivery of the certificates already delivered
+    ///
+    /// Returns the number of unverified proofs removed.
+    pub fn prune_unverified_proofs(&self) -> Result<usize, StorageError> {
+        let delivered = self.perpetual_tables.delivered_unverified_proofs()?;
+
+        for certificate_id in &delivered {
+            self.perpetual_tables.unverified.delete(certificate_id)?;
+        }
+
+        Ok(delivered.len())
+    }
+
+    /// Drop the proof of the delivered certificates, except for the latest
+    /// `proofs_per_source_stream` certificates of every source stream
+    ///
+    /// The ids and the positions of the certificates are kept, see the
+    /// [`retention`](module@crate::retention) module for the consequences of pruning the proofs.
+    /// The position up to which the proofs are pruned is written along with the certificates,
+    /// tagging the store as pruned. Returns the number of certificates whose proof has been
+    /// dropped.
+    pub fn prune_proofs(&self, proofs_per_source_stream: u64) -> Result<usize, StorageError> {
+        let mut pruned = 0;
+
+        for (subnet_id, (_, head)) in self.index_tables.source_list.iter()? {
+            let Some(until) = (*head + 1).checked_sub(proofs_per_source_stream) else {
+                continue;
+            };
+
+            let from = self.proofs_pruned_until(&subnet_id)?;
+            if *from >= until {
+                continue;
+            }
+
+            let certificate_ids: Vec<(Position, CertificateId)> = self
+                .perpetual_tables
+                .streams
+                .prefix_iter_at(
+                    &subnet_id,
+                    &CertificateSourceStreamPosition::new(subnet_id, from),
+                )?
+                .take_while(|(position, _)| *position.position < until)
+                .map(|(position, certificate_id)| (position.position, certificate_id))
+                .collect();
+
+            for chunk in certificate_ids.chunks(PRUNING_BATCH_SIZE) {
+                let mut certificates = Vec::with_capacity(chunk.len());
+                for (_, certificate_id) in chunk {
+                    if let Some(mut delivered) =
+                        self.perpetual_tables.certificates.get(certificate_id)?
+                    {
+                        if !delivered.certificate.proof.is_empty() {
+                            delivered.certificate.proof = Vec::new();
+                            certificates.push(delivered);
+                        }
+                    }
+                }
+
+                let Some((last, _)) = chunk.last() else {
+                    continue;
+                };
+                let pruned_until = last.increment().map_err(|error| {
+                    InternalStorageError::PositionError(error, subnet_id.into())
+                })?;
+
+                pruned += certificates.len();
+                self.perpetual_tables
+                    .certificates
+                    .batch()
+                    .insert_batch(
+                        &self.perpetual_tables.certificates,
+                        certificates
+                            .iter()
+                            .map(|delivered| (delivered.certificate.id, delivered)),
+                    )?
+                    .insert_batch(
+                        &self.perpetual_tables.pruned_proofs,
+                        [(subnet_id, pruned_until)],
+                    )?
+                    .write()?;
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    /// Returns the first position of the source stream whose certificates still have their proof
+    pub fn proofs_pruned_until(&self, subnet_id: &SubnetId) -> Result<Position, StorageError> {
+        Ok(self
+            .perpetual_tables
+            .pruned_proofs
+            .get(subnet_id)?
+            .unwrap_or(Position::ZERO))
+    }
+
+    /// Returns whether the proofs of some certificates have been pruned
+    ///
+    /// A pruned store can't serve the synchronization nor export snapshots, its old certificates
+    /// no longer matching their id.
+    pub fn is_pruned(&self) -> Result<bool, StorageError> {
+        Ok(self.perpetual_tables.pruned_proofs.iter()?.next().is_some())
+    }
+
+    /// Returns whether the proof of the given certificate is kept
+    pub fn is_proof_kept(&self, delivered: &CertificateDelivered) -> Result<bool, StorageError> {
+        let position = &delivered.proof_of_delivery.delivery_position;
+
+        Ok(position.position >= self.proofs_pruned_until(&position.subnet_id)?)
+    }
+
+    /// Fail if the proof of the given certificate has been pruned, the certificate no longer
+    /// matching its id
+    pub fn check_proof_kept(&self, delivered: &CertificateDelivered) -> Result<(), StorageError> {
+        if !self.is_proof_kept(delivered)? {
+            return Err(InternalStorageError::ProofPruned(delivered.certificate.id).into());
+        }
+
+        Ok(())
+    }
+
+    /// Collect up to `limit` certificates of a stream whose proof is kept, skipping the pruned ones
+    fn collect_stream_page<P>(
+        &self,
+        mut entries: impl Iterator<Item = (CertificateId, P)>,
+        limit: usize,
+    ) -> Result<Vec<(CertificateDelivered, P)>, StorageError> {
+        let mut page = Vec::new();
+
+        loop {
+            let batch: Vec<_> = entries.by_ref().take(limit - page.len()).collect();
+            if batch.is_empty() {
+                return Ok(page);
+            }
+
+            let certificate_ids: Vec<_> = batch.iter().map(|(k, _)| k).cloned().collect();
+            let certificates = self
+                .perpetual_tables
+                .certificates
+                .multi_get(&certificate_ids[..])?;
+
+            for ((certificate_id, position), certificate) in batch.into_iter().zip(certificates) {
+                if let Some(certificate) =
+                    certificate.filter(|c| c.certificate.id == certificate_id)
+                {
+                    if self.is_proof_kept(&certificate)? {
+                        page.push((certificate, position));
+                    }
+                }
+            }
+        }
+    }
+
     /// Await for a [`LockGuards`] for the given certificate id
     pub(crate) async fn certificate_lock_guard(
         &self,
@@ -104,15 +637,63 @@ impl FullNodeStore {
             .lock_owned()
             .await
     }
-}
 
-#[async_trait]
-impl WriteStore for FullNodeStore {
-    async fn insert_certificate_delivered(
+    /// Returns the heads of every target stream
+    pub fn get_target_checkpoint(&self) -> Result<Vec<TargetHead>, StorageError> {
+        self.index_tables
+            .target_source_list
+            .iter()?
+            .map(
+                |(TargetSourceListKey(target_subnet_id, source_subnet_id), position)| {
+                    let certificate_id = self
+                        .index_tables
+                        .target_streams
+                        .get(&CertificateTargetStreamPosition::new(
+                            target_subnet_id,
+                            source_subnet_id,
+                            position,
+                        ))?
+                        .ok_or(InternalStorageError::UnexpectedDBState(
+                            "Missing certificate at the head of a target stream",
+                        ))?;
+
+                    Ok(TargetHead {
+                        certificate_id,
+                        target_subnet_id,
+                        source_subnet_id,
+                        position,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Insert a certificate found at the heads of a signed checkpoint, using the positions of
+    /// its target streams given by the checkpoint.
+    ///
+    /// The certificate is only added to the target streams for which a position is given, as
+    /// the certificate may not be the head of every stream it targets. This is used to
+    /// bootstrap a store from a signed checkpoint, the certificates preceding the heads being
+    /// unknown.
+    pub async fn insert_checkpoint_head(
         &self,
         certificate: &CertificateDelivered,
+        target_positions: &HashMap<SubnetId, Position>,
+    ) -> Result<CertificatePositions, StorageError> {
+        self.insert_delivered(certificate, Some(target_positions))
+            .await
+    }
+
+    /// Insert a delivered certificate, its target stream positions being the next ones of each
+    /// target stream unless they are given
+    async fn insert_delivered(
+        &self,
+        certificate: &CertificateDelivered,
+        target_positions: Option<&HashMap<SubnetId, Position>>,
     ) -> Result<CertificatePositions, StorageError> {
         // Lock resources for concurrency issues
+        let _snapshot_guard = self.snapshot_lock.read().await;
+
         let _cert_guard = self
             .certificate_lock_guard(certificate.certificate.id)
             .await;
@@ -201,26 +782,36 @@ impl WriteStore for FullNodeStore {
             .collect();
 
         for target_subnet_id in &certificate.certificate.target_subnets {
-            let target = match self
-                .index_tables
-                .target_streams
-                .prefix_iter(&TargetSourceListKey(*target_subnet_id, subnet_id))?
-                .last()
-            {
-                None => CertificateTargetStreamPosition::new(
-                    *target_subnet_id,
-                    subnet_id,
-                    Position::ZERO,
-                ),
-                Some((mut target_stream_position, _)) => {
-                    target_stream_position.position = target_stream_position
-                        .position
-                        .increment()
-                        .map_err(|error| {
-                        InternalStorageError::PositionError(error, subnet_id.into())
-                    })?;
-                    target_stream_position
-                }
+            let target = match target_positions {
+                Some(positions) => match positions.get(target_subnet_id) {
+                    Some(position) => CertificateTargetStreamPosition::new(
+                        *target_subnet_id,
+                        subnet_id,
+                        *position,
+                    ),
+                    None => continue,
+                },
+                None => match self
+                    .index_tables
+                    .target_streams
+                    .prefix_iter(&TargetSourceListKey(*target_subnet_id, subnet_id))?
+                    .last()
+                {
+                    None => CertificateTargetStreamPosition::new(
+                        *target_subnet_id,
+                        subnet_id,
+                        Position::ZERO,
+                    ),
+                    Some((mut target_stream_position, _)) => {
+                        target_stream_position.position = target_stream_position
+                            .position
+                            .increment()
+                            .map_err(|error| {
+                                InternalStorageError::PositionError(error, subnet_id.into())
+                            })?;
+                        target_stream_position
+                    }
+                },
             };
 
             target_subnet_stream_positions.insert(*target_subnet_id, target);
@@ -245,6 +836,9 @@ impl WriteStore for FullNodeStore {
         batch.write()?;
         index_batch.write()?;
 
+        // The unverified proof collected by the synchronization isn't needed anymore
+        self.perpetual_tables.unverified.delete(&certificate_id)?;
+
         info!(
             "Certificate {} inserted at position {}",
             certificate.certificate.id, expected_position
@@ -255,6 +849,16 @@ impl WriteStore for FullNodeStore {
             source: expected_position,
         })
     }
+}
+
+#[async_trait]
+impl WriteStore for FullNodeStore {
+    async fn insert_certificate_delivered(
+        &self,
+        certificate: &CertificateDelivered,
+    ) -> Result<CertificatePositions, StorageError> {
+        self.insert_delivered(certificate, None).await
+    }
 
     async fn insert_certificates_delivered(
         &self,
@@ -340,7 +944,7 @@ impl ReadStore for FullNodeStore {
         limit: usize,
     ) -> Result<Vec<(CertificateDelivered, CertificateSourceStreamPosition)>, StorageError> {
         let starting_position = from.position;
-        let x: Vec<(CertificateId, CertificateSourceStreamPosition)> = self
+        let entries = self
             .perpetual_tables
             .streams
             .prefix_iter(&from.subnet_id)?
@@ -349,25 +953,9 @@ impl ReadStore for FullNodeStore {
                     "Unable to parse Position",
                 ))
             })?)
-            .take(limit)
-            .map(|(k, v)| (v, k))
-            .collect();
+            .map(|(k, v)| (v, k));
 
-        let certificate_ids: Vec<_> = x.iter().map(|(k, _)| k).cloned().collect();
-
-        let certificates = self
-            .perpetual_tables
-            .certificates
-            .multi_get(&certificate_ids[..])?;
-
-        Ok(x.into_iter()
-            .zip(certificates)
-            .filter_map(|((certificate_id, position), certificate)| {
-                certificate
-                    .filter(|c| c.certificate.id == certificate_id)
-                    .map(|cert| (cert, position))
-            })
-            .collect())
+        self.collect_stream_page(entries, limit)
     }
 
     fn get_target_stream_certificates_from_position(
@@ -378,7 +966,7 @@ impl ReadStore for FullNodeStore {
         let starting_position = position.position;
         let prefix = TargetSourceListKey(position.target_subnet_id, position.source_subnet_id);
 
-        let certs_with_positions: Vec<(CertificateId, CertificateTargetStreamPosition)> = self
+        let entries = self
             .index_tables
             .target_streams
             .prefix_iter(&prefix)?
@@ -387,30 +975,9 @@ impl ReadStore for FullNodeStore {
                     "Unable to parse Position",
                 ))
             })?)
-            .take(limit)
-            .map(|(k, v)| (v, k))
-            .collect();
+            .map(|(k, v)| (v, k));
 
-        let certificate_ids: Vec<_> = certs_with_positions
-            .iter()
-            .map(|(k, _)| k)
-            .cloned()
-            .collect();
-
-        let certificates = self
-            .perpetual_tables
-            .certificates
-            .multi_get(&certificate_ids[..])?;
-
-        Ok(certs_with_positions
-            .into_iter()
-            .zip(certificates)
-            .filter_map(|((certificate_id, position), certificate)| {
-                certificate
-                    .filter(|c| c.certificate.id == certificate_id)
-                    .map(|cert| (cert, position))
-            })
-            .collect())
+        self.collect_stream_page(entries, limit)
     }
 
     fn get_target_source_subnet_list(
@@ -7,8 +7,11 @@ use topos_core::{
 };
 use tracing::warn;
 
+#[cfg(feature = "inmemory")]
+use crate::memory::MemoryDB;
 use crate::{
     constant::cfs,
+    errors::InternalStorageError,
     rocks::{
         constants,
         db::{default_options, init_with_cfs},
@@ -27,11 +30,14 @@ pub struct IndexTables {
 }
 
 impl IndexTables {
-    pub fn open(path: &Path) -> Self {
+    /// Open the [`IndexTables`] at the given path.
+    ///
+    /// Fails if the database can't be opened, e.g. when it is already opened by a running node.
+    pub fn open(path: &Path) -> Result<Self, InternalStorageError> {
         let path = path.join("index");
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
-            create_dir_all(&path).expect("Cannot create IndexTables directory");
+            create_dir_all(&path)?;
         }
         let mut options_stream = default_options();
         options_stream.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
@@ -48,10 +54,9 @@ impl IndexTables {
             ),
         ];
 
-        let db = init_with_cfs(&path, default_options(), cfs)
-            .unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
+        let db = init_with_cfs(&path, default_options(), cfs)?;
 
-        Self {
+        Ok(Self {
             target_streams: DBColumn::reopen(&db, cfs::TARGET_STREAMS),
             target_source_list: DBColumn::reopen(&db, cfs::TARGET_SOURCE_LIST),
             source_list: DBColumn::reopen(&db, cfs::SOURCE_LIST),
@@ -59,6 +64,35 @@ impl IndexTables {
                 &db,
                 cfs::DELIVERED_CERTIFICATES_PER_SOURCE_FOR_TARGET,
             ),
+        })
+    }
+
+    /// Create a RocksDB checkpoint of the tables, which can be opened at the given path
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.target_streams.create_checkpoint(&path.join("index"))
+    }
+
+    /// Open the [`IndexTables`] in memory, nothing being persisted.
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Self {
+        let db = MemoryDB::new(&[
+            (
+                cfs::TARGET_STREAMS,
+                Some(constants::TARGET_STREAMS_PREFIX_SIZE),
+            ),
+            (cfs::TARGET_SOURCE_LIST, None),
+            (cfs::SOURCE_LIST, None),
+            (cfs::DELIVERED_CERTIFICATES_PER_SOURCE_FOR_TARGET, None),
+        ]);
+
+        Self {
+            target_streams: DBColumn::reopen_in_memory(&db, cfs::TARGET_STREAMS),
+            target_source_list: DBColumn::reopen_in_memory(&db, cfs::TARGET_SOURCE_LIST),
+            source_list: DBColumn::reopen_in_memory(&db, cfs::SOURCE_LIST),
+            source_list_per_target: DBColumn::reopen_in_memory(
+                &db,
+                cfs::DELIVERED_CERTIFICATES_PER_SOURCE_FOR_TARGET,
+            ),
         }
     }
 }
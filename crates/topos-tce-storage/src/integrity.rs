@@ -0,0 +1,445 @@
+//! Integrity checks of the [`FullNodeStore`](struct@crate::fullnode::FullNodeStore)
+//!
+//! The delivered certificates and their source streams are the primary data of the store, the
+//! source heads, the target streams and the target source lists being indexes derived from
+//! them. As the indexes are written in a different database than the primary data, a crash can
+//! leave them disagreeing.
+//!
+//! [`check`] walks the certificates and the streams, reporting every [`IntegrityIssue`]:
+//!
+//! - the source streams need to be contiguous from the position `0`, every entry pointing to an
+//!   existing certificate delivered at this position and chained to the previous one,
+//! - every certificate needs to be in its source stream,
+//! - the indexes need to match the ones derived from the source streams.
+//!
+//! The indexes can be rebuilt from the primary data by [`repair`], while the issues of the
+//! primary data can only be reported.
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+use topos_core::{
+    types::{
+        stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position},
+        CertificateDelivered,
+    },
+    uci::{CertificateId, SubnetId},
+};
+
+use crate::{
+    errors::InternalStorageError,
+    index::IndexTables,
+    rocks::{map::Map, TargetSourceListKey},
+    validator::ValidatorPerpetualTables,
+};
+
+/// Inconsistency found by an integrity check
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum IntegrityIssue {
+    #[error("Source stream entry {0} points to the missing certificate {1}")]
+    MissingCertificate(CertificateSourceStreamPosition, CertificateId),
+
+    #[error("Certificate {certificate_id} is at {position} instead of {delivery_position}")]
+    MisplacedCertificate {
+        certificate_id: CertificateId,
+        position: CertificateSourceStreamPosition,
+        delivery_position: CertificateSourceStreamPosition,
+    },
+
+    #[error("Source stream {subnet_id} expected position {expected} instead of {found}")]
+    SourceStreamGap {
+        subnet_id: SubnetId,
+        expected: Position,
+        found: Position,
+    },
+
+    #[error("Certificate {certificate_id} follows {prev_id} instead of {expected_prev_id}")]
+    BrokenChain {
+        certificate_id: CertificateId,
+        prev_id: CertificateId,
+        expected_prev_id: CertificateId,
+    },
+
+    #[error("Certificate {0} isn't part of its source stream")]
+    OrphanCertificate(CertificateId),
+
+    #[error("Source head of {0} doesn't match its source stream")]
+    SourceHeadMismatch(SubnetId),
+
+    #[error(
+        "Target stream of {source_subnet_id} to {target_subnet_id} doesn't match its source \
+         stream ({found} entries, {expected} expected)"
+    )]
+    TargetStreamMismatch {
+        target_subnet_id: SubnetId,
+        source_subnet_id: SubnetId,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error(
+        "Target source list of {source_subnet_id} to {target_subnet_id} doesn't match its target \
+         stream"
+    )]
+    TargetSourceListMismatch {
+        target_subnet_id: SubnetId,
+        source_subnet_id: SubnetId,
+    },
+}
+
+impl IntegrityIssue {
+    /// Returns true if the issue is about an index, which can be rebuilt by [`repair`]
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            Self::SourceHeadMismatch(_)
+                | Self::TargetStreamMismatch { .. }
+                | Self::TargetSourceListMismatch { .. }
+        )
+    }
+}
+
+/// Result of an integrity check
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// Number of delivered certificates
+    pub certificates: usize,
+    /// Number of source stream entries
+    pub source_stream_entries: usize,
+    /// Number of target stream entries
+    pub target_stream_entries: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Returns true if no issue has been found
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Indexes derived from the source streams
+#[derive(Default)]
+struct DerivedIndexes {
+    source_heads: Vec<(SubnetId, (CertificateId, Position))>,
+    /// Certificates of every (target subnet, source subnet) stream, in delivery order
+    target_streams: Vec<((SubnetId, SubnetId), Vec<CertificateId>)>,
+}
+
+impl DerivedIndexes {
+    fn target_stream_entries(
+        &self,
+    ) -> impl Iterator<Item = (CertificateTargetStreamPosition, CertificateId)> + '_ {
+        self.target_streams
+            .iter()
+            .flat_map(|((target, source), certificates)| {
+                certificates
+                    .iter()
+                    .enumerate()
+                    .map(|(position, certificate_id)| {
+                        (
+                            CertificateTargetStreamPosition::new(*target, *source, position as u64),
+                            *certificate_id,
+                        )
+                    })
+            })
+    }
+}
+
+/// Walk the primary data of the tables, reporting its issues and deriving the indexes
+fn walk(
+    perpetual_tables: &ValidatorPerpetualTables,
+    report: &mut IntegrityReport,
+) -> Result<DerivedIndexes, InternalStorageError> {
+    let mut indexes = DerivedIndexes::default();
+    let mut target_streams: HashMap<(SubnetId, SubnetId), usize> = HashMap::new();
+    let mut streamed = HashSet::new();
+
+    let mut previous: Option<(CertificateSourceStreamPosition, CertificateId)> = None;
+    for (position, certificate_id) in perpetual_tables.streams.iter()? {
+        report.source_stream_entries += 1;
+        streamed.insert(certificate_id);
+
+        let (expected, expected_prev_id) = match &previous {
+            Some((previous_position, previous_id))
+                if previous_position.subnet_id == position.subnet_id =>
+            {
+                (*previous_position.position + 1, Some(*previous_id))
+            }
+            _ => (0, None),
+        };
+
+        if *position.position != expected {
+            report.issues.push(IntegrityIssue::SourceStreamGap {
+                subnet_id: position.subnet_id,
+                expected: Position::from(expected),
+                found: position.position,
+            });
+        }
+
+        match perpetual_tables.certificates.get(&certificate_id)? {
+            None => report.issues.push(IntegrityIssue::MissingCertificate(
+                position.clone(),
+                certificate_id,
+            )),
+            Some(delivered) => {
+                check_certificate(&delivered, &position, expected_prev_id, report);
+                derive_target_streams(&delivered, &mut indexes.target_streams, &mut target_streams);
+            }
+        }
+
+        match indexes.source_heads.last_mut() {
+            Some((subnet_id, head)) if *subnet_id == position.subnet_id => {
+                *head = (certificate_id, position.position)
+            }
+            _ => indexes
+                .source_heads
+                .push((position.subnet_id, (certificate_id, position.position))),
+        }
+
+        previous = Some((position, certificate_id));
+    }
+
+    for (certificate_id, _) in perpetual_tables.certificates.iter()? {
+        report.certificates += 1;
+
+        if !streamed.contains(&certificate_id) {
+            report
+                .issues
+                .push(IntegrityIssue::OrphanCertificate(certificate_id));
+        }
+    }
+
+    Ok(indexes)
+}
+
+fn check_certificate(
+    delivered: &CertificateDelivered,
+    position: &CertificateSourceStreamPosition,
+    expected_prev_id: Option<CertificateId>,
+    report: &mut IntegrityReport,
+) {
+    let certificate_id = delivered.certificate.id;
+
+    if delivered.proof_of_delivery.delivery_position != *position {
+        report.issues.push(IntegrityIssue::MisplacedCertificate {
+            certificate_id,
+            position: position.clone(),
+            delivery_position: delivered.proof_of_delivery.delivery_position.clone(),
+        });
+    }
+
+    if let Some(expected_prev_id) = expected_prev_id {
+        if delivered.certificate.prev_id != expected_prev_id {
+            report.issues.push(IntegrityIssue::BrokenChain {
+                certificate_id,
+                prev_id: delivered.certificate.prev_id,
+                expected_prev_id,
+            });
+        }
+    }
+}
+
+fn derive_target_streams(
+    delivered: &CertificateDelivered,
+    target_streams: &mut Vec<((SubnetId, SubnetId), Vec<CertificateId>)>,
+    indexes: &mut HashMap<(SubnetId, SubnetId), usize>,
+) {
+    let source_subnet_id = delivered.certificate.source_subnet_id;
+
+    for target_subnet_id in &delivered.certificate.target_subnets {
+        let key = (*target_subnet_id, source_subnet_id);
+        let index = *indexes.entry(key).or_insert_with(|| {
+            target_streams.push((key, Vec::new()));
+
+            target_streams.len() - 1
+        });
+
+        target_streams[index].1.push(delivered.certificate.id);
+    }
+}
+
+/// Check the integrity of the tables
+pub(crate) fn check(
+    perpetual_tables: &ValidatorPerpetualTables,
+    index_tables: &IndexTables,
+) -> Result<IntegrityReport, InternalStorageError> {
+    let mut report = IntegrityReport::default();
+    let derived = walk(perpetual_tables, &mut report)?;
+
+    let expected_heads: HashMap<_, _> = derived.source_heads.iter().copied().collect();
+    let mut subnets: Vec<SubnetId> = derived
+        .source_heads
+        .iter()
+        .map(|(subnet_id, _)| *subnet_id)
+        .collect();
+    for (subnet_id, _) in index_tables.source_list.iter()? {
+        if !expected_heads.contains_key(&subnet_id) {
+            subnets.push(subnet_id);
+        }
+    }
+
+    for subnet_id in subnets {
+        if index_tables.source_list.get(&subnet_id)? != expected_heads.get(&subnet_id).copied() {
+            report
+                .issues
+                .push(IntegrityIssue::SourceHeadMismatch(subnet_id));
+        }
+    }
+
+    let mut found: HashMap<(SubnetId, SubnetId), Vec<(Position, CertificateId)>> = HashMap::new();
+    for (position, certificate_id) in index_tables.target_streams.iter()? {
+        report.target_stream_entries += 1;
+        found
+            .entry((position.target_subnet_id, position.source_subnet_id))
+            .or_default()
+            .push((position.position, certificate_id));
+    }
+
+    let mut keys: Vec<(SubnetId, SubnetId)> =
+        derived.target_streams.iter().map(|(key, _)| *key).collect();
+    keys.extend(found.keys().filter(|key| {
+        !derived
+            .target_streams
+            .iter()
+            .any(|(derived, _)| derived == *key)
+    }));
+
+    let expected_streams: HashMap<_, _> = derived.target_streams.iter().cloned().collect();
+    for (target_subnet_id, source_subnet_id) in keys {
+        let key = (target_subnet_id, source_subnet_id);
+        let expected = expected_streams.get(&key).cloned().unwrap_or_default();
+        let entries = found.remove(&key).unwrap_or_default();
+
+        let matching = entries
+            .iter()
+            .enumerate()
+            .filter(|(index, (position, certificate_id))| {
+                **position == *index as u64 && expected.get(*index) == Some(certificate_id)
+            })
+            .count();
+
+        if matching != expected.len() || entries.len() != expected.len() {
+            report.issues.push(IntegrityIssue::TargetStreamMismatch {
+                target_subnet_id,
+                source_subnet_id,
+                expected: expected.len(),
+                found: entries.len(),
+            });
+        }
+
+        let expected_head = expected
+            .len()
+            .checked_sub(1)
+            .map(|last| Position::from(last as u64));
+        let head = index_tables
+            .target_source_list
+            .get(&TargetSourceListKey(target_subnet_id, source_subnet_id))?;
+        let listed = index_tables
+            .source_list_per_target
+            .get(&(target_subnet_id, source_subnet_id))?
+            .is_some();
+
+        if head != expected_head || listed != expected_head.is_some() {
+            report
+                .issues
+                .push(IntegrityIssue::TargetSourceListMismatch {
+                    target_subnet_id,
+                    source_subnet_id,
+                });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Rebuild the indexes of the tables from their primary data, the entries of the primary data
+/// pointing to missing certificates being skipped
+pub(crate) fn repair(
+    perpetual_tables: &ValidatorPerpetualTables,
+    index_tables: &IndexTables,
+) -> Result<(), InternalStorageError> {
+    let mut report = IntegrityReport::default();
+    let derived = walk(perpetual_tables, &mut report)?;
+
+    let source_heads: HashSet<SubnetId> = derived
+        .source_heads
+        .iter()
+        .map(|(subnet_id, _)| *subnet_id)
+        .collect();
+    for (subnet_id, _) in index_tables.source_list.iter()? {
+        if !source_heads.contains(&subnet_id) {
+            index_tables.source_list.delete(&subnet_id)?;
+        }
+    }
+
+    let target_streams: HashSet<(SubnetId, SubnetId)> =
+        derived.target_streams.iter().map(|(key, _)| *key).collect();
+    let derived_entries: HashSet<(SubnetId, SubnetId, u64)> = derived
+        .target_stream_entries()
+        .map(|(position, _)| {
+            (
+                position.target_subnet_id,
+                position.source_subnet_id,
+                *position.position,
+            )
+        })
+        .collect();
+
+    for (position, _) in index_tables.target_streams.iter()? {
+        if !derived_entries.contains(&(
+            position.target_subnet_id,
+            position.source_subnet_id,
+            *position.position,
+        )) {
+            index_tables.target_streams.delete(&position)?;
+        }
+    }
+
+    for (TargetSourceListKey(target, source), _) in index_tables.target_source_list.iter()? {
+        if !target_streams.contains(&(target, source)) {
+            index_tables
+                .target_source_list
+                .delete(&TargetSourceListKey(target, source))?;
+        }
+    }
+
+    for (key, _) in index_tables.source_list_per_target.iter()? {
+        if !target_streams.contains(&key) {
+            index_tables.source_list_per_target.delete(&key)?;
+        }
+    }
+
+    index_tables
+        .target_streams
+        .batch()
+        .insert_batch(
+            &index_tables.source_list,
+            derived
+                .source_heads
+                .iter()
+                .map(|(subnet_id, head)| (subnet_id, head)),
+        )?
+        .insert_batch(
+            &index_tables.target_streams,
+            derived.target_stream_entries(),
+        )?
+        .insert_batch(
+            &index_tables.target_source_list,
+            derived
+                .target_streams
+                .iter()
+                .map(|((target, source), certificates)| {
+                    (
+                        TargetSourceListKey(*target, *source),
+                        Position::from(certificates.len() as u64 - 1),
+                    )
+                }),
+        )?
+        .insert_batch(
+            &index_tables.source_list_per_target,
+            target_streams.iter().map(|key| (key, true)),
+        )?
+        .write()?;
+
+    Ok(())
+}
@@ -49,8 +49,11 @@
 //!
 //! When using the storage layer, be aware of the following:
 //! - The storage layer uses [rocksdb](https://rocksdb.org/) as the backend, which means don't need an external service, as `rocksdb` is an embedded key-value store.
+//! - The stores can also be opened in memory (behind the `inmemory` feature), using the same tables on top of sorted maps instead of `rocksdb`. Nothing is persisted, which is meant for tests and ephemeral networks.
 //! - The storage layer uses [`Arc`](struct@std::sync::Arc) to share the stores between threads. It also means that a `store` is only instantiated once.
 //! - Some storage methods are batching multiple writes into a single transaction.
+//! - The version of the storage schema is recorded in the database, the pending migrations being applied when opening a [`ValidatorStore`](struct@validator::ValidatorStore). See the [`migration`](module@migration) module.
+//! - The consistency of the streams, the certificates and the indexes derived from them can be checked, the indexes being rebuilt if needed. See the [`integrity`](module@integrity) module.
 //!
 //! ## Design Philosophy
 //!
@@ -100,6 +103,14 @@ pub mod errors;
 #[cfg(feature = "rocksdb")]
 pub(crate) mod rocks;
 
+#[cfg(feature = "inmemory")]
+pub(crate) mod memory;
+
+pub mod integrity;
+pub mod migration;
+pub mod retention;
+pub mod snapshot;
+
 #[cfg(test)]
 mod tests;
 
@@ -137,7 +148,7 @@ pub struct CertificatePositions {
 /// Uniquely identify the source certificate stream head of one subnet.
 /// The head represent the internal state of the TCE regarding a source subnet stream for
 /// certificates that it receives from local sequencer
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SourceHead {
     /// Certificate id of the head
     pub certificate_id: CertificateId,
@@ -146,3 +157,17 @@ pub struct SourceHead {
     /// Position of the Certificate
     pub position: Position,
 }
+
+/// Uniquely identify the head of the stream of the certificates delivered from one source subnet
+/// to one target subnet
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TargetHead {
+    /// Certificate id of the head
+    pub certificate_id: CertificateId,
+    /// Target subnet id of the stream
+    pub target_subnet_id: SubnetId,
+    /// Source subnet id of the stream
+    pub source_subnet_id: SubnetId,
+    /// Position of the Certificate in the target stream
+    pub position: Position,
+}
@@ -0,0 +1,212 @@
+//! In-memory backend of the storage
+//!
+//! The [`MemoryDB`] keeps every column family in a sorted map, using the same serialized keys as
+//! RocksDB so that the [`DBColumn`](crate::rocks::db_column::DBColumn) and the stores built on
+//! top of it behave the same way whatever the backend is. Nothing is persisted, the data being
+//! dropped along with the last handle on the database.
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::errors::InternalStorageError;
+
+type Columns = HashMap<&'static str, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+/// Key and value to insert in a column family, applied atomically by [`MemoryDB::write`]
+pub(crate) type MemoryWrite = (&'static str, Vec<u8>, Vec<u8>);
+
+/// Position from which a [`MemoryIterator`] starts
+pub(crate) enum Seek {
+    First,
+    Last,
+    Key(Vec<u8>),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct MemoryDB {
+    columns: Arc<RwLock<Columns>>,
+    /// Fixed prefix size of the column families, mirroring the RocksDB prefix extractors
+    prefix_sizes: Arc<HashMap<&'static str, usize>>,
+}
+
+impl MemoryDB {
+    /// Create a database with the given column families and their optional fixed prefix size
+    pub(crate) fn new(cfs: &[(&'static str, Option<usize>)]) -> Self {
+        Self {
+            columns: Arc::new(RwLock::new(
+                cfs.iter().map(|(cf, _)| (*cf, BTreeMap::new())).collect(),
+            )),
+            prefix_sizes: Arc::new(
+                cfs.iter()
+                    .filter_map(|(cf, size)| size.map(|size| (*cf, size)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns true if both handles are pointing to the same database
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.columns, &other.columns)
+    }
+
+    pub(crate) fn get(
+        &self,
+        cf: &'static str,
+        key: &[u8],
+    ) -> Result<Option<Vec<u8>>, InternalStorageError> {
+        Ok(Self::column(&*self.read()?, cf)?.get(key).cloned())
+    }
+
+    pub(crate) fn put(
+        &self,
+        cf: &'static str,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InternalStorageError> {
+        self.write(vec![(cf, key, value)])
+    }
+
+    pub(crate) fn delete(&self, cf: &'static str, key: &[u8]) -> Result<(), InternalStorageError> {
+        let mut columns = self.write_lock()?;
+        columns
+            .get_mut(cf)
+            .ok_or(InternalStorageError::InvalidColumnFamily(cf))?
+            .remove(key);
+
+        Ok(())
+    }
+
+    /// Apply every write or none of them if one is targeting an unknown column family
+    pub(crate) fn write(&self, writes: Vec<MemoryWrite>) -> Result<(), InternalStorageError> {
+        let mut columns = self.write_lock()?;
+
+        for (cf, ..) in &writes {
+            if !columns.contains_key(cf) {
+                return Err(InternalStorageError::InvalidColumnFamily(cf));
+            }
+        }
+
+        for (cf, key, value) in writes {
+            if let Some(column) = columns.get_mut(cf) {
+                column.insert(key, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of keys of the column family
+    pub(crate) fn len(&self, cf: &'static str) -> Result<u64, InternalStorageError> {
+        Ok(Self::column(&*self.read()?, cf)?.len() as u64)
+    }
+
+    /// Returns an iterator over the column family starting at the given position
+    ///
+    /// If `prefixed` is set, the iteration stops at the first key not sharing the fixed prefix of
+    /// the column family with the seeked key, like a RocksDB prefix iterator does. Column
+    /// families without a prefix size are iterated until their end.
+    pub(crate) fn iterator(
+        &self,
+        cf: &'static str,
+        seek: Seek,
+        prefixed: bool,
+    ) -> Result<MemoryIterator, InternalStorageError> {
+        let columns = self.read()?;
+        let column = Self::column(&columns, cf)?;
+
+        let (next, prefix) = match seek {
+            Seek::First => (Bound::Unbounded, None),
+            Seek::Last => match column.keys().next_back() {
+                Some(key) => (Bound::Included(key.clone()), None),
+                None => return Ok(MemoryIterator::empty(self.clone(), cf)),
+            },
+            Seek::Key(key) => {
+                let prefix = self
+                    .prefix_sizes
+                    .get(cf)
+                    .filter(|_| prefixed)
+                    .map(|size| key[..key.len().min(*size)].to_vec());
+
+                (Bound::Included(key), prefix)
+            }
+        };
+
+        Ok(MemoryIterator {
+            db: self.clone(),
+            cf,
+            next: Some(next),
+            prefix,
+        })
+    }
+
+    fn column<'a>(
+        columns: &'a Columns,
+        cf: &'static str,
+    ) -> Result<&'a BTreeMap<Vec<u8>, Vec<u8>>, InternalStorageError> {
+        columns
+            .get(cf)
+            .ok_or(InternalStorageError::InvalidColumnFamily(cf))
+    }
+
+    fn read(&self) -> Result<RwLockReadGuard<'_, Columns>, InternalStorageError> {
+        self.columns
+            .read()
+            .map_err(|_| InternalStorageError::UnexpectedDBState("In-memory database poisoned"))
+    }
+
+    fn write_lock(&self) -> Result<RwLockWriteGuard<'_, Columns>, InternalStorageError> {
+        self.columns
+            .write()
+            .map_err(|_| InternalStorageError::UnexpectedDBState("In-memory database poisoned"))
+    }
+}
+
+/// Iterator over the raw entries of a column family of a [`MemoryDB`]
+///
+/// The entries are read one at a time so that the database isn't locked while iterating. Unlike a
+/// RocksDB iterator, the writes made after its creation can be seen.
+pub(crate) struct MemoryIterator {
+    db: MemoryDB,
+    cf: &'static str,
+    /// Lower bound of the next entry, `None` once the iterator is exhausted
+    next: Option<Bound<Vec<u8>>>,
+    prefix: Option<Vec<u8>>,
+}
+
+impl MemoryIterator {
+    fn empty(db: MemoryDB, cf: &'static str) -> Self {
+        Self {
+            db,
+            cf,
+            next: None,
+            prefix: None,
+        }
+    }
+}
+
+impl Iterator for MemoryIterator {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lower = self.next.take()?;
+        let columns = self.db.read().ok()?;
+
+        let (key, value) = MemoryDB::column(&columns, self.cf)
+            .ok()?
+            .range((lower, Bound::Unbounded))
+            .next()
+            .map(|(key, value)| (key.clone(), value.clone()))?;
+
+        if let Some(prefix) = &self.prefix {
+            if !key.starts_with(prefix) {
+                return None;
+            }
+        }
+
+        self.next = Some(Bound::Excluded(key.clone()));
+
+        Some((key, value))
+    }
+}
@@ -0,0 +1,363 @@
+//! Versioning and migrations of the storage schema
+//!
+//! The values of the tables are bincode encoded without any version marker, so a database needs
+//! to know which schema it has been written with. The version of the schema is recorded in the
+//! [`ValidatorPerpetualTables`] when the stores are opened, a database without record being
+//! considered at version `0`.
+//!
+//! A [`ValidatorStore`](struct@crate::validator::ValidatorStore) applies the pending migrations
+//! when it is opened, while a database written by a newer schema is refused. Migrations can also
+//! be applied, or only reported with `dry_run`, using [`migrate`].
+//!
+//! Every migration brings the schema to its version from the previous one. Migrations are
+//! ordered by version and idempotent: they only change the entries which aren't migrated yet, so
+//! a migration interrupted by a crash can safely be applied again.
+use std::path::Path;
+
+use tracing::info;
+
+use crate::{
+    epoch::ValidatorPerEpochStore,
+    errors::{InternalStorageError, StorageError},
+    rocks::map::Map,
+    types::{EpochId, EpochSummary},
+    validator::{ValidatorPendingTables, ValidatorPerpetualTables},
+};
+
+/// Version of the schema written by this version of the storage
+pub const SCHEMA_VERSION: u32 = 4;
+
+/// Key of the schema version record
+pub(crate) const SCHEMA_VERSION_KEY: &str = "version";
+
+/// Migration of the schema from the previous version to `version`
+struct Migration {
+    version: u32,
+    description: &'static str,
+    /// Applies the migration, or only counts the entries to change if `dry_run` is set.
+    /// Returns the number of entries changed
+    apply: fn(&MigrationTables, bool) -> Result<usize, InternalStorageError>,
+}
+
+/// Tables accessed by the migrations
+struct MigrationTables<'a> {
+    perpetual_tables: &'a ValidatorPerpetualTables,
+    pending_tables: &'a ValidatorPendingTables,
+    /// Store of the latest epoch, the only one read by the node
+    epoch_store: &'a ValidatorPerEpochStore,
+}
+
+/// Every migration, ordered by version
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Remove the unverified proofs of delivery of the delivered certificates",
+        apply: remove_delivered_unverified_proofs,
+    },
+    Migration {
+        version: 2,
+        description: "Record the time at which the certificates entered the precedence pool",
+        apply: timestamp_precedence_pool,
+    },
+    Migration {
+        version: 3,
+        description: "Add the target stream heads to the signed checkpoints",
+        apply: add_checkpoint_target_heads,
+    },
+    Migration {
+        version: 4,
+        description: "Index the certificates of the precedence pool by their id",
+        apply: index_precedence_pool,
+    },
+];
+
+/// Migration applied, or to apply in case of a dry run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStep {
+    pub version: u32,
+    pub description: &'static str,
+    /// Number of entries changed by the migration
+    pub changes: usize,
+}
+
+/// Report of the migrations of a database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Version of the schema before the migrations
+    pub from: u32,
+    /// Version of the schema after the migrations
+    pub to: u32,
+    pub steps: Vec<MigrationStep>,
+    /// Whether the migrations have only been reported, without changing the database
+    pub dry_run: bool,
+}
+
+/// Migrate the database at the given path to the current [`SCHEMA_VERSION`]
+///
+/// The database must not be opened by a running node, the migration failing otherwise. If
+/// `dry_run` is set, the migrations to apply are reported without changing the database, which
+/// is opened for reading only.
+pub fn migrate(path: &Path, dry_run: bool) -> Result<MigrationReport, StorageError> {
+    let epoch_id = ValidatorPerEpochStore::latest_epoch(path).unwrap_or(0);
+
+    let (perpetual_tables, pending_tables, epoch_store) = if dry_run {
+        (
+            ValidatorPerpetualTables::open_read_only(path)?,
+            ValidatorPendingTables::open_read_only(path)?,
+            ValidatorPerEpochStore::open_read_only(epoch_id, path)?,
+        )
+    } else {
+        (
+            ValidatorPerpetualTables::open(path)?,
+            ValidatorPendingTables::open(path)?,
+            ValidatorPerEpochStore::new(epoch_id, path)?.load_full(),
+        )
+    };
+
+    Ok(run(
+        &perpetual_tables,
+        &pending_tables,
+        &epoch_store,
+        dry_run,
+    )?)
+}
+
+/// Returns the version of the schema recorded in the tables
+pub(crate) fn schema_version(
+    perpetual_tables: &ValidatorPerpetualTables,
+) -> Result<u32, InternalStorageError> {
+    Ok(perpetual_tables
+        .schema
+        .get(&SCHEMA_VERSION_KEY.to_string())?
+        .unwrap_or(0))
+}
+
+/// Check that the tables aren't written by a newer schema than the supported one
+pub(crate) fn check_schema_version(
+    perpetual_tables: &ValidatorPerpetualTables,
+) -> Result<u32, InternalStorageError> {
+    let version = schema_version(perpetual_tables)?;
+
+    if version > SCHEMA_VERSION {
+        return Err(InternalStorageError::UnsupportedSchemaVersion(version));
+    }
+
+    Ok(version)
+}
+
+/// Apply the pending migrations to the tables, recording the version after every migration
+pub(crate) fn run(
+    perpetual_tables: &ValidatorPerpetualTables,
+    pending_tables: &ValidatorPendingTables,
+    epoch_store: &ValidatorPerEpochStore,
+    dry_run: bool,
+) -> Result<MigrationReport, InternalStorageError> {
+    let from = check_schema_version(perpetual_tables)?;
+    let tables = MigrationTables {
+        perpetual_tables,
+        pending_tables,
+        epoch_store,
+    };
+
+    let mut steps = Vec::new();
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > from)
+    {
+        let changes = (migration.apply)(&tables, dry_run)?;
+
+        if !dry_run {
+            perpetual_tables
+                .schema
+                .insert(&SCHEMA_VERSION_KEY.to_string(), &migration.version)?;
+
+            info!(
+                "Storage schema migrated to version {}: {} ({} changes)",
+                migration.version, migration.description, changes
+            );
+        }
+
+        steps.push(MigrationStep {
+            version: migration.version,
+            description: migration.description,
+            changes,
+        });
+    }
+
+    Ok(MigrationReport {
+        from,
+        to: steps.last().map_or(from, |step| step.version),
+        steps,
+        dry_run,
+    })
+}
+
+fn remove_delivered_unverified_proofs(
+    tables: &MigrationTables,
+    dry_run: bool,
+) -> Result<usize, InternalStorageError> {
+    let delivered = tables.perpetual_tables.delivered_unverified_proofs()?;
+
+    if !dry_run {
+        for certificate_id in &delivered {
+            tables.perpetual_tables.unverified.delete(certificate_id)?;
+        }
+    }
+
+    Ok(delivered.len())
+}
+
+fn timestamp_precedence_pool(
+    tables: &MigrationTables,
+    dry_run: bool,
+) -> Result<usize, InternalStorageError> {
+    let pending_tables = tables.pending_tables;
+    let now = crate::validator::unix_timestamp();
+
+    let mut missing = Vec::new();
+    for (prev_id, _) in pending_tables.precedence_pool.iter()? {
+        if pending_tables
+            .precedence_pool_timestamps
+            .get(&prev_id)?
+            .is_none()
+        {
+            missing.push((prev_id, now));
+        }
+    }
+
+    let changes = missing.len();
+    if !dry_run {
+        pending_tables
+            .precedence_pool_timestamps
+            .multi_insert(missing)?;
+    }
+
+    Ok(changes)
+}
+
+/// Rewrite the epoch summaries with checkpoints without target stream heads, which keeps the
+/// signatures valid as the heads are only appended to the signed payload when there are some.
+///
+/// The epoch chain is rewritten in a single batch along with the version of the schema, the
+/// checkpoints of the latest epoch store being rewritten beforehand from the epoch chain.
+fn add_checkpoint_target_heads(
+    tables: &MigrationTables,
+    dry_run: bool,
+) -> Result<usize, InternalStorageError> {
+    let perpetual_tables = tables.perpetual_tables;
+    let summaries: Vec<(EpochId, EpochSummary)> = perpetual_tables
+        .epoch_chain
+        .retype::<EpochId, legacy::EpochSummary>()
+        .iter()?
+        .map(|(epoch_id, summary)| (epoch_id, summary.into()))
+        .collect();
+
+    let epoch_store = tables.epoch_store;
+    let current = summaries
+        .iter()
+        .find(|(epoch_id, _)| *epoch_id == epoch_store.epoch_id())
+        .map(|(_, summary)| summary);
+
+    let mut changes = summaries.len();
+    if let Some(summary) = current {
+        changes += 1 + usize::from(summary.end_checkpoint.is_some());
+    }
+
+    if dry_run {
+        return Ok(changes);
+    }
+
+    if let Some(summary) = current {
+        epoch_store.rewrite_checkpoints(summary)?;
+    }
+
+    perpetual_tables
+        .epoch_chain
+        .batch()
+        .insert_batch(&perpetual_tables.epoch_chain, summaries)?
+        .insert_batch(
+            &perpetual_tables.schema,
+            [(SCHEMA_VERSION_KEY.to_string(), 3)],
+        )?
+        .write()?;
+
+    Ok(changes)
+}
+
+fn index_precedence_pool(
+    tables: &MigrationTables,
+    dry_run: bool,
+) -> Result<usize, InternalStorageError> {
+    let pending_tables = tables.pending_tables;
+
+    let mut missing = Vec::new();
+    for (prev_id, certificate) in pending_tables.precedence_pool.iter()? {
+        if pending_tables
+            .precedence_pool_index
+            .get(&certificate.id)?
+            .is_none()
+        {
+            missing.push((certificate.id, prev_id));
+        }
+    }
+
+    let changes = missing.len();
+    if !dry_run {
+        pending_tables.precedence_pool_index.multi_insert(missing)?;
+    }
+
+    Ok(changes)
+}
+
+/// Layouts of the values written by previous versions of the schema
+mod legacy {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        types::{self, EpochId, ValidatorQuorumSignatureInfo},
+        SourceHead,
+    };
+
+    #[derive(Debug, Deserialize, Serialize)]
+    pub(super) struct CheckpointSummary {
+        epoch: EpochId,
+        sequence_number: usize,
+        checkpoint_data: Vec<SourceHead>,
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    pub(super) struct VerifiedCheckpointSummary(CheckpointSummary, ValidatorQuorumSignatureInfo);
+
+    #[derive(Debug, Deserialize, Serialize)]
+    pub(super) struct EpochSummary {
+        epoch_id: EpochId,
+        start_checkpoint: VerifiedCheckpointSummary,
+        end_checkpoint: Option<VerifiedCheckpointSummary>,
+    }
+
+    impl From<VerifiedCheckpointSummary> for types::VerifiedCheckpointSummary {
+        fn from(value: VerifiedCheckpointSummary) -> Self {
+            let VerifiedCheckpointSummary(checkpoint, quorum) = value;
+
+            Self(
+                types::CheckpointSummary {
+                    epoch: checkpoint.epoch,
+                    sequence_number: checkpoint.sequence_number,
+                    checkpoint_data: checkpoint.checkpoint_data,
+                    target_checkpoint_data: Vec::new(),
+                },
+                quorum,
+            )
+        }
+    }
+
+    impl From<EpochSummary> for types::EpochSummary {
+        fn from(value: EpochSummary) -> Self {
+            Self {
+                epoch_id: value.epoch_id,
+                start_checkpoint: value.start_checkpoint.into(),
+                end_checkpoint: value.end_checkpoint.map(Into::into),
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Retention policies of the stores
+//!
+//! By default nothing is ever removed from the storage. A [`RetentionPolicy`] defines what can
+//! be pruned by [`ValidatorStore::prune`](crate::validator::ValidatorStore::prune):
+//!
+//! - the certificates waiting in the precedence pool for longer than a TTL, their previous
+//!   certificate being unlikely to ever be delivered,
+//! - the proofs of the delivered certificates older than the latest N positions of their source
+//!   stream (pruned mode), their ids and positions being kept.
+//!
+//! The unverified proofs of delivery collected by the synchronization are always removed once
+//! their certificate is delivered.
+//!
+//! A certificate whose proof has been pruned no longer matches its id, so its proof of delivery
+//! can't be verified anymore. The position up to which the proofs are pruned is persisted, tagging
+//! the store as pruned: the certificates whose proof is pruned aren't served by the APIs anymore,
+//! and a pruned store neither serves the synchronization nor exports snapshots. Nodes doing so
+//! need to keep every proof (archive mode).
+use std::time::Duration;
+
+/// Policy defining the data pruned from the stores, a policy which isn't set keeping the data
+/// forever
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Duration after which a certificate waiting in the precedence pool is evicted
+    pub precedence_pool_ttl: Option<Duration>,
+    /// Number of latest certificates per source stream keeping their proof
+    pub proofs_per_source_stream: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn with_precedence_pool_ttl(mut self, ttl: Duration) -> Self {
+        self.precedence_pool_ttl = Some(ttl);
+
+        self
+    }
+
+    pub fn with_proofs_per_source_stream(mut self, proofs_per_source_stream: u64) -> Self {
+        self.proofs_per_source_stream = Some(proofs_per_source_stream);
+
+        self
+    }
+}
+
+/// Data removed by a pruning of the stores
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruningSummary {
+    /// Number of certificates evicted from the precedence pool
+    pub precedence_pool_evicted: usize,
+    /// Number of unverified proofs of delivery removed
+    pub unverified_proofs_removed: usize,
+    /// Number of delivered certificates whose proof has been dropped
+    pub proofs_pruned: usize,
+}
@@ -1,14 +1,26 @@
 use rocksdb::MultiThreaded;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use rocksdb::{ColumnFamilyDescriptor, Options};
 
+use super::db_column::DBColumn;
 use crate::errors::InternalStorageError;
-
-use super::constants;
+#[cfg(feature = "inmemory")]
+use crate::memory::MemoryDB;
 
 pub(crate) type RocksDB = Arc<rocksdb::DBWithThreadMode<MultiThreaded>>;
 
+/// Backend holding the column families of a [`DBColumn`](super::db_column::DBColumn)
+#[derive(Clone, Debug)]
+pub(crate) enum Database {
+    RocksDB(RocksDB),
+    #[cfg(feature = "inmemory")]
+    Memory(MemoryDB),
+}
+
 pub(crate) fn init_with_cfs(
     path: &PathBuf,
     mut options: rocksdb::Options,
@@ -27,27 +39,68 @@ pub(crate) fn default_options() -> rocksdb::Options {
     options
 }
 
-pub(crate) fn init_db(
-    path: &PathBuf,
-    options: rocksdb::Options,
-) -> Result<RocksDB, InternalStorageError> {
-    let mut options_source = default_options();
-    options_source.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
-        constants::SOURCE_STREAMS_PREFIX_SIZE,
-    ));
-
-    let mut options_target = Options::default();
-    options_target.create_if_missing(true);
-    options_target.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
-        constants::TARGET_STREAMS_PREFIX_SIZE,
-    ));
-    let cfs = vec![
-        ColumnFamilyDescriptor::new(constants::PENDING_CERTIFICATES, default_options()),
-        ColumnFamilyDescriptor::new(constants::CERTIFICATES, rocksdb::Options::default()),
-        ColumnFamilyDescriptor::new(constants::SOURCE_STREAMS, options_source),
-        ColumnFamilyDescriptor::new(constants::TARGET_STREAMS, options_target),
-        ColumnFamilyDescriptor::new(constants::TARGET_SOURCES, default_options()),
-    ];
-
-    init_with_cfs(path, options, cfs)
+/// Database opened for reading only, nothing being created at its path
+///
+/// The column families missing from the database, or all of them if there is no database at
+/// the path, are read as empty columns.
+pub(crate) struct ReadOnlyDB {
+    db: Option<RocksDB>,
+    #[cfg(feature = "inmemory")]
+    missing: MemoryDB,
+}
+
+impl ReadOnlyDB {
+    /// Open the given column families, with their prefix size if any, of the database at the
+    /// given path
+    pub(crate) fn open(
+        path: &Path,
+        cfs: &[(&'static str, Option<usize>)],
+    ) -> Result<Self, InternalStorageError> {
+        let db = if path.exists() {
+            let existing = rocksdb::DB::list_cf(&Options::default(), path)?;
+            let cfs = cfs
+                .iter()
+                .filter(|(cf, _)| existing.iter().any(|existing| existing == cf))
+                .map(|(cf, prefix_size)| {
+                    let mut options = Options::default();
+                    if let Some(prefix_size) = prefix_size {
+                        options.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
+                            *prefix_size,
+                        ));
+                    }
+                    ColumnFamilyDescriptor::new(*cf, options)
+                });
+
+            Some(Arc::new(
+                rocksdb::DBWithThreadMode::<MultiThreaded>::open_cf_descriptors_read_only(
+                    &Options::default(),
+                    path,
+                    cfs,
+                    false,
+                )?,
+            ))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            db,
+            #[cfg(feature = "inmemory")]
+            missing: MemoryDB::new(cfs),
+        })
+    }
+
+    /// Returns the given column, empty if it is missing from the database
+    pub(crate) fn column<K, V>(
+        &self,
+        cf: &'static str,
+    ) -> Result<DBColumn<K, V>, InternalStorageError> {
+        match &self.db {
+            Some(db) if db.cf_handle(cf).is_some() => Ok(DBColumn::reopen(db, cf)),
+            #[cfg(feature = "inmemory")]
+            _ => Ok(DBColumn::reopen_in_memory(&self.missing, cf)),
+            #[cfg(not(feature = "inmemory"))]
+            _ => Err(InternalStorageError::InvalidColumnFamily(cf)),
+        }
+    }
 }
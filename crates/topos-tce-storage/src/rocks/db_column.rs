@@ -1,26 +1,27 @@
 use std::{borrow::Borrow, marker::PhantomData, sync::Arc};
 
-#[cfg(test)]
 use std::path::Path;
 
 #[cfg(test)]
 use rocksdb::ColumnFamilyDescriptor;
 use rocksdb::{
-    BoundColumnFamily, CStrLike, DBRawIteratorWithThreadMode, DBWithThreadMode, Direction,
-    IteratorMode, MultiThreaded, ReadOptions, WriteBatch,
+    checkpoint::Checkpoint, BoundColumnFamily, CStrLike, DBRawIteratorWithThreadMode, Direction,
+    IteratorMode, ReadOptions, WriteBatch,
 };
 
 use bincode::Options;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::errors::InternalStorageError;
+#[cfg(feature = "inmemory")]
+use crate::memory::{MemoryDB, MemoryWrite, Seek};
 
-use super::{iterator::ColumnIterator, map::Map, RocksDB};
+use super::{db::Database, iterator::ColumnIterator, map::Map, RocksDB};
 
 /// A DBColumn represents a CF structure
 #[derive(Clone, Debug)]
 pub struct DBColumn<K, V> {
-    pub(crate) rocksdb: RocksDB,
+    pub(crate) db: Database,
     _phantom: PhantomData<fn(K) -> V>,
     cf: &'static str,
 }
@@ -51,7 +52,7 @@ impl<K, V> DBColumn<K, V> {
         };
 
         Ok(Self {
-            rocksdb,
+            db: Database::RocksDB(rocksdb),
             _phantom: PhantomData,
             cf: column,
         })
@@ -59,18 +60,72 @@ impl<K, V> DBColumn<K, V> {
 
     pub fn reopen(db: &RocksDB, column: &'static str) -> Self {
         Self {
-            rocksdb: db.clone(),
+            db: Database::RocksDB(db.clone()),
+            _phantom: PhantomData,
+            cf: column,
+        }
+    }
+
+    /// Open the column of an in-memory database
+    #[cfg(feature = "inmemory")]
+    pub(crate) fn reopen_in_memory(db: &MemoryDB, column: &'static str) -> Self {
+        Self {
+            db: Database::Memory(db.clone()),
             _phantom: PhantomData,
             cf: column,
         }
     }
 
+    /// Returns the same column with other key and value types, used to read the entries
+    /// written with a previous layout
+    pub(crate) fn retype<K2, V2>(&self) -> DBColumn<K2, V2> {
+        DBColumn {
+            db: self.db.clone(),
+            _phantom: PhantomData,
+            cf: self.cf,
+        }
+    }
+
     /// Returns the CF of the DBColumn, used to build queries.
-    pub(crate) fn cf(&self) -> Result<Arc<BoundColumnFamily<'_>>, InternalStorageError> {
-        self.rocksdb
+    pub(crate) fn cf<'a>(
+        &self,
+        rocksdb: &'a RocksDB,
+    ) -> Result<Arc<BoundColumnFamily<'a>>, InternalStorageError> {
+        rocksdb
             .cf_handle(self.cf)
             .ok_or(InternalStorageError::InvalidColumnFamily(self.cf))
     }
+
+    /// Create a RocksDB checkpoint of the database holding the column at the given path
+    ///
+    /// The checkpoint is a consistent copy of every column of the database, hard linking the
+    /// immutable files when possible. In-memory databases can't be checkpointed.
+    pub(crate) fn create_checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                Checkpoint::new(rocksdb)?.create_checkpoint(path)?;
+
+                Ok(())
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(_) => Err(InternalStorageError::UnexpectedDBState(
+                "Checkpoints are not supported by the in-memory database",
+            )),
+        }
+    }
+
+    /// Compact the whole column, which is a no-op for an in-memory database
+    pub(crate) fn compact(&self) -> Result<(), InternalStorageError> {
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                rocksdb.compact_range_cf(&self.cf(rocksdb)?, None::<&[u8]>, None::<&[u8]>)
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(_) => {}
+        }
+
+        Ok(())
+    }
 }
 
 impl<K, V> DBColumn<K, V>
@@ -78,28 +133,38 @@ where
     K: DeserializeOwned + Serialize + std::fmt::Debug,
     V: DeserializeOwned + Serialize + std::fmt::Debug,
 {
+    /// Returns the value of a RocksDB property of the column
+    ///
+    /// An in-memory database only knows its exact number of keys, which is returned whatever the
+    /// requested property is.
     pub(crate) fn property_int_value(
         &self,
         property: impl CStrLike,
     ) -> Result<u64, InternalStorageError> {
-        self.rocksdb
-            .property_int_value_cf(&self.cf()?, property)?
-            .ok_or(InternalStorageError::UnexpectedDBState(
-                "Property not found",
-            ))
+        match &self.db {
+            Database::RocksDB(rocksdb) => rocksdb
+                .property_int_value_cf(&self.cf(rocksdb)?, property)?
+                .ok_or(InternalStorageError::UnexpectedDBState(
+                    "Property not found",
+                )),
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => db.len(self.cf),
+        }
     }
 
     /// Insert a record into the storage by passing a Key and a Value.
     ///
     /// Key are fixed length bincode serialized.
     pub(crate) fn insert(&self, key: &K, value: &V) -> Result<(), InternalStorageError> {
-        let cf = self.cf()?;
-
         let key_buf = be_fix_int_ser(key)?;
 
         let value_buf = bincode::serialize(value)?;
 
-        self.rocksdb.put_cf(&cf, key_buf, value_buf)?;
+        match &self.db {
+            Database::RocksDB(rocksdb) => rocksdb.put_cf(&self.cf(rocksdb)?, key_buf, value_buf)?,
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => db.put(self.cf, key_buf, value_buf)?,
+        }
 
         Ok(())
     }
@@ -110,7 +175,11 @@ where
     pub(crate) fn delete(&self, key: &K) -> Result<(), InternalStorageError> {
         let key_buf = be_fix_int_ser(key)?;
 
-        self.rocksdb.delete_cf(&self.cf()?, key_buf)?;
+        match &self.db {
+            Database::RocksDB(rocksdb) => rocksdb.delete_cf(&self.cf(rocksdb)?, key_buf)?,
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => db.delete(self.cf, &key_buf)?,
+        }
 
         Ok(())
     }
@@ -121,13 +190,21 @@ where
     pub(crate) fn get(&self, key: &K) -> Result<Option<V>, InternalStorageError> {
         let key_buf = be_fix_int_ser(key)?;
 
-        self.rocksdb
-            .get_pinned_cf(&self.cf()?, key_buf)?
-            .map_or(Ok(None), |v| {
-                bincode::deserialize::<V>(&v)
-                    .map(|r| Some(r))
-                    .map_err(|_| InternalStorageError::UnableToDeserializeValue)
-            })
+        let deserialize = |v: &[u8]| {
+            bincode::deserialize::<V>(v)
+                .map(|r| Some(r))
+                .map_err(|_| InternalStorageError::UnableToDeserializeValue)
+        };
+
+        match &self.db {
+            Database::RocksDB(rocksdb) => rocksdb
+                .get_pinned_cf(&self.cf(rocksdb)?, key_buf)?
+                .map_or(Ok(None), |v| deserialize(&v)),
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => db
+                .get(self.cf, &key_buf)?
+                .map_or(Ok(None), |v| deserialize(&v)),
+        }
     }
 
     pub(crate) fn multi_insert(
@@ -143,14 +220,32 @@ where
         let keys: Result<Vec<_>, InternalStorageError> =
             keys.iter().map(|k| be_fix_int_ser(k)).collect();
 
-        let results: Result<Vec<_>, InternalStorageError> = self
-            .rocksdb
-            .batched_multi_get_cf_opt(&self.cf()?, keys?, false, &ReadOptions::default())
-            .into_iter()
-            .map(|r| r.map_err(InternalStorageError::RocksDBError))
-            .collect();
+        let values: Vec<Option<Vec<u8>>> = match &self.db {
+            Database::RocksDB(rocksdb) => {
+                let results: Result<Vec<_>, InternalStorageError> = rocksdb
+                    .batched_multi_get_cf_opt(
+                        &self.cf(rocksdb)?,
+                        keys?,
+                        false,
+                        &ReadOptions::default(),
+                    )
+                    .into_iter()
+                    .map(|r| {
+                        r.map(|v| v.map(|v| v.to_vec()))
+                            .map_err(InternalStorageError::RocksDBError)
+                    })
+                    .collect();
+
+                results?
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => keys?
+                .iter()
+                .map(|key| db.get(self.cf, key))
+                .collect::<Result<_, _>>()?,
+        };
 
-        results?
+        values
             .into_iter()
             .map(|e| match e {
                 Some(v) => bincode::deserialize(&v)
@@ -166,24 +261,46 @@ where
         let key_buf = be_fix_int_ser(key)?;
         let value_buf = bincode::serialize(&value)?;
 
-        Ok(self.rocksdb.merge_cf(&self.cf()?, key_buf, value_buf)?)
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                Ok(rocksdb.merge_cf(&self.cf(rocksdb)?, key_buf, value_buf)?)
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(_) => Err(InternalStorageError::UnexpectedDBState(
+                "Merge is not supported by the in-memory database",
+            )),
+        }
     }
 
     pub(crate) fn batch(&self) -> DBBatch {
-        DBBatch::new(&self.rocksdb)
+        DBBatch::new(&self.db)
     }
 }
 
-pub(crate) struct DBBatch {
-    rocksdb: Arc<DBWithThreadMode<MultiThreaded>>,
-    batch: WriteBatch,
+pub(crate) enum DBBatch {
+    RocksDB {
+        rocksdb: RocksDB,
+        batch: WriteBatch,
+    },
+    #[cfg(feature = "inmemory")]
+    Memory {
+        db: MemoryDB,
+        writes: Vec<MemoryWrite>,
+    },
 }
 
 impl DBBatch {
-    fn new(rocksdb: &Arc<DBWithThreadMode<MultiThreaded>>) -> Self {
-        Self {
-            rocksdb: rocksdb.clone(),
-            batch: WriteBatch::default(),
+    fn new(db: &Database) -> Self {
+        match db {
+            Database::RocksDB(rocksdb) => Self::RocksDB {
+                rocksdb: rocksdb.clone(),
+                batch: WriteBatch::default(),
+            },
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => Self::Memory {
+                db: db.clone(),
+                writes: Vec::new(),
+            },
         }
     }
 
@@ -198,14 +315,22 @@ impl DBBatch {
         Key: Borrow<K>,
         Value: Borrow<V>,
     {
-        check_cross_batch(&self.rocksdb, &db.rocksdb)?;
+        check_cross_batch(&self, &db.db)?;
 
         values
             .into_iter()
             .try_for_each::<_, Result<(), InternalStorageError>>(|(k, v)| {
                 let key_buffer = be_fix_int_ser(k.borrow())?;
                 let value_buffer = bincode::serialize(v.borrow())?;
-                self.batch.put_cf(&db.cf()?, key_buffer, value_buffer);
+                match &mut self {
+                    DBBatch::RocksDB { rocksdb, batch } => {
+                        batch.put_cf(&db.cf(rocksdb)?, key_buffer, value_buffer)
+                    }
+                    #[cfg(feature = "inmemory")]
+                    DBBatch::Memory { writes, .. } => {
+                        writes.push((db.cf, key_buffer, value_buffer))
+                    }
+                }
                 Ok(())
             })?;
 
@@ -213,7 +338,11 @@ impl DBBatch {
     }
 
     pub(crate) fn write(self) -> Result<(), InternalStorageError> {
-        self.rocksdb.write(self.batch)?;
+        match self {
+            DBBatch::RocksDB { rocksdb, batch } => rocksdb.write(batch)?,
+            #[cfg(feature = "inmemory")]
+            DBBatch::Memory { db, writes } => db.write(writes)?,
+        }
 
         Ok(())
     }
@@ -227,50 +356,103 @@ where
     type Iterator = ColumnIterator<'a, K, V>;
 
     fn iter(&'a self) -> Result<Self::Iterator, InternalStorageError> {
-        let mut raw_iterator = self.rocksdb.raw_iterator_cf(&self.cf()?);
-        raw_iterator.seek_to_first();
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                let mut raw_iterator = rocksdb.raw_iterator_cf(&self.cf(rocksdb)?);
+                raw_iterator.seek_to_first();
 
-        Ok(ColumnIterator::new(raw_iterator))
+                Ok(ColumnIterator::new(raw_iterator))
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => Ok(ColumnIterator::new_in_memory(db.iterator(
+                self.cf,
+                Seek::First,
+                false,
+            )?)),
+        }
     }
 
     fn iter_at<I: Serialize>(&'a self, index: &I) -> Result<Self::Iterator, InternalStorageError> {
-        let mut raw_iterator = self.rocksdb.raw_iterator_cf(&self.cf()?);
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                let mut raw_iterator = rocksdb.raw_iterator_cf(&self.cf(rocksdb)?);
 
-        raw_iterator.seek(be_fix_int_ser(index)?);
-        Ok(ColumnIterator::new(raw_iterator))
+                raw_iterator.seek(be_fix_int_ser(index)?);
+                Ok(ColumnIterator::new(raw_iterator))
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => Ok(ColumnIterator::new_in_memory(db.iterator(
+                self.cf,
+                Seek::Key(be_fix_int_ser(index)?),
+                false,
+            )?)),
+        }
     }
 
     fn iter_with_mode(
         &'a self,
         mode: IteratorMode<'_>,
     ) -> Result<Self::Iterator, InternalStorageError> {
-        let mut raw_iterator = self.rocksdb.raw_iterator_cf(&self.cf()?);
-
-        let direction = match mode {
-            IteratorMode::Start => {
-                raw_iterator.seek_to_first();
-                Direction::Forward
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                let mut raw_iterator = rocksdb.raw_iterator_cf(&self.cf(rocksdb)?);
+
+                let direction = match mode {
+                    IteratorMode::Start => {
+                        raw_iterator.seek_to_first();
+                        Direction::Forward
+                    }
+                    IteratorMode::End => {
+                        raw_iterator.seek_to_last();
+                        Direction::Forward
+                    }
+                    IteratorMode::From(..) => {
+                        return Err(InternalStorageError::InvalidQueryArgument(
+                            "Iterating from a key requires iter_at",
+                        ))
+                    }
+                };
+
+                Ok(ColumnIterator::new_with_direction(raw_iterator, direction))
             }
-            IteratorMode::End => {
-                raw_iterator.seek_to_last();
-                Direction::Forward
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => {
+                let seek = match mode {
+                    IteratorMode::Start => Seek::First,
+                    IteratorMode::End => Seek::Last,
+                    IteratorMode::From(..) => {
+                        return Err(InternalStorageError::InvalidQueryArgument(
+                            "Iterating from a key requires iter_at",
+                        ))
+                    }
+                };
+
+                Ok(ColumnIterator::new_in_memory(
+                    db.iterator(self.cf, seek, false)?,
+                ))
             }
-            _ => unimplemented!(),
-        };
-
-        Ok(ColumnIterator::new_with_direction(raw_iterator, direction))
+        }
     }
 
     fn prefix_iter<P: Serialize>(
         &'a self,
         prefix: &P,
     ) -> Result<Self::Iterator, InternalStorageError> {
-        let iterator = self
-            .rocksdb
-            .prefix_iterator_cf(&self.cf()?, be_fix_int_ser(prefix)?)
-            .into();
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                let iterator = rocksdb
+                    .prefix_iterator_cf(&self.cf(rocksdb)?, be_fix_int_ser(prefix)?)
+                    .into();
 
-        Ok(ColumnIterator::new(iterator))
+                Ok(ColumnIterator::new(iterator))
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => Ok(ColumnIterator::new_in_memory(db.iterator(
+                self.cf,
+                Seek::Key(be_fix_int_ser(prefix)?),
+                true,
+            )?)),
+        }
     }
 
     fn prefix_iter_at<P: Serialize, I: Serialize>(
@@ -278,13 +460,22 @@ where
         prefix: &P,
         index: &I,
     ) -> Result<Self::Iterator, InternalStorageError> {
-        let mut iterator: DBRawIteratorWithThreadMode<_> = self
-            .rocksdb
-            .prefix_iterator_cf(&self.cf()?, be_fix_int_ser(prefix)?)
-            .into();
-
-        iterator.seek(be_fix_int_ser(index)?);
-        Ok(ColumnIterator::new(iterator))
+        match &self.db {
+            Database::RocksDB(rocksdb) => {
+                let mut iterator: DBRawIteratorWithThreadMode<_> = rocksdb
+                    .prefix_iterator_cf(&self.cf(rocksdb)?, be_fix_int_ser(prefix)?)
+                    .into();
+
+                iterator.seek(be_fix_int_ser(index)?);
+                Ok(ColumnIterator::new(iterator))
+            }
+            #[cfg(feature = "inmemory")]
+            Database::Memory(db) => Ok(ColumnIterator::new_in_memory(db.iterator(
+                self.cf,
+                Seek::Key(be_fix_int_ser(index)?),
+                true,
+            )?)),
+        }
     }
 }
 
@@ -299,8 +490,18 @@ where
         .serialize(t)?)
 }
 
-fn check_cross_batch(base: &RocksDB, current: &RocksDB) -> Result<(), InternalStorageError> {
-    if !Arc::ptr_eq(base, current) {
+fn check_cross_batch(base: &DBBatch, current: &Database) -> Result<(), InternalStorageError> {
+    let same_db = match (base, current) {
+        (DBBatch::RocksDB { rocksdb, .. }, Database::RocksDB(current)) => {
+            Arc::ptr_eq(rocksdb, current)
+        }
+        #[cfg(feature = "inmemory")]
+        (DBBatch::Memory { db, .. }, Database::Memory(current)) => db.ptr_eq(current),
+        #[cfg(feature = "inmemory")]
+        _ => false,
+    };
+
+    if !same_db {
         return Err(InternalStorageError::ConcurrentDBBatchDetected);
     }
 
@@ -4,12 +4,23 @@ use bincode::Options;
 use rocksdb::{DBRawIteratorWithThreadMode, DBWithThreadMode, Direction, MultiThreaded};
 use serde::de::DeserializeOwned;
 
+#[cfg(feature = "inmemory")]
+use crate::memory::MemoryIterator;
+
 pub struct ColumnIterator<'a, K, V> {
-    iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>,
-    direction: Direction,
+    iterator: RawIterator<'a>,
     _phantom: PhantomData<(K, V)>,
 }
 
+enum RawIterator<'a> {
+    RocksDB {
+        iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>,
+        direction: Direction,
+    },
+    #[cfg(feature = "inmemory")]
+    Memory(MemoryIterator),
+}
+
 impl<'a, K, V> ColumnIterator<'a, K, V> {
     /// Creates a new ColumnIterator base on a DBRawIteratorWithThreadMode
     pub fn new(iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>) -> Self {
@@ -21,11 +32,37 @@ impl<'a, K, V> ColumnIterator<'a, K, V> {
         direction: Direction,
     ) -> Self {
         Self {
-            iterator,
-            direction,
+            iterator: RawIterator::RocksDB {
+                iterator,
+                direction,
+            },
             _phantom: PhantomData,
         }
     }
+
+    /// Creates a new ColumnIterator base on a MemoryIterator
+    #[cfg(feature = "inmemory")]
+    pub(crate) fn new_in_memory(iterator: MemoryIterator) -> Self {
+        Self {
+            iterator: RawIterator::Memory(iterator),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn deserialize(key: Option<&[u8]>, value: Option<&[u8]>) -> Option<(K, V)>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+
+        let key = key.and_then(|k| config.deserialize(k).ok());
+        let value = value.and_then(|v| bincode::deserialize(v).ok());
+
+        key.and_then(|k| value.map(|v| (k, v)))
+    }
 }
 
 impl<'a, K, V> Iterator for ColumnIterator<'a, K, V>
@@ -36,25 +73,30 @@ where
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.iterator.valid() {
-            let config = bincode::DefaultOptions::new()
-                .with_big_endian()
-                .with_fixint_encoding();
-
-            let key = self.iterator.key().and_then(|k| config.deserialize(k).ok());
-            let value = self
-                .iterator
-                .value()
-                .and_then(|v| bincode::deserialize(v).ok());
-
-            match self.direction {
-                Direction::Forward => self.iterator.next(),
-                Direction::Reverse => self.iterator.prev(),
+        match &mut self.iterator {
+            RawIterator::RocksDB {
+                iterator,
+                direction,
+            } => {
+                if iterator.valid() {
+                    let item = Self::deserialize(iterator.key(), iterator.value());
+
+                    match direction {
+                        Direction::Forward => iterator.next(),
+                        Direction::Reverse => iterator.prev(),
+                    }
+
+                    item
+                } else {
+                    None
+                }
             }
+            #[cfg(feature = "inmemory")]
+            RawIterator::Memory(iterator) => {
+                let (key, value) = iterator.next()?;
 
-            key.and_then(|k| value.map(|v| (k, v)))
-        } else {
-            None
+                Self::deserialize(Some(&key), Some(&value))
+            }
         }
     }
 }
@@ -0,0 +1,585 @@
+//! Portable snapshots of the [`FullNodeStore`](struct@crate::fullnode::FullNodeStore)
+//!
+//! A snapshot holds the delivered certificates along with their target streams and target
+//! source lists, the source streams being the delivery positions of the certificates. It is
+//! exported from a consistent point of the store, using RocksDB checkpoints.
+//!
+//! The snapshot file is a [`SnapshotHeader`] followed by [`SnapshotRecord`]s, each one holding a
+//! [`SnapshotChunk`] of bounded size, so that neither the export nor the import needs to hold the
+//! whole snapshot in memory. Every record is checksummed along with the previous one, and the
+//! last one marks the end of the snapshot, so that a corrupted or truncated snapshot is rejected.
+//!
+//! A snapshot is verified while it is imported: the proof of delivery of every certificate is
+//! checked against the validators, the source streams need to be contiguous and to chain the
+//! certificates, the target streams and the source heads are recomputed from the certificates
+//! instead of being trusted. The import being written chunk by chunk, a marker is recorded in the
+//! store for the duration of the import, and a store whose import has been interrupted or
+//! rejected is refused when opened.
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Write},
+    path::Path,
+};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use topos_core::{
+    types::{
+        stream::{CertificateTargetStreamPosition, Position},
+        CertificateDelivered,
+    },
+    uci::{CertificateId, SubnetId},
+};
+use topos_crypto::hash::calculate_hash;
+
+use crate::{
+    errors::{InternalStorageError, SnapshotError},
+    index::IndexTables,
+    rocks::{map::Map, TargetSourceListKey},
+    types::Validators,
+    validator::ValidatorPerpetualTables,
+    SourceHead,
+};
+
+/// Version of the snapshot format, bumped on every incompatible change
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// Maximum size of a record of a snapshot file, bounding what is read at once
+pub const MAX_SNAPSHOT_RECORD_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Number of certificates per chunk of a snapshot
+const CERTIFICATES_PER_CHUNK: usize = 256;
+
+/// Number of stream entries per chunk of a snapshot
+const ENTRIES_PER_CHUNK: usize = 4_096;
+
+/// Key of the marker recorded in the schema table while a snapshot is imported
+pub(crate) const SNAPSHOT_IMPORT_KEY: &str = "snapshot_import";
+
+/// Encoding of the snapshot files, limiting the size of what is decoded at once
+fn options() -> impl Options {
+    bincode::options().with_limit(MAX_SNAPSHOT_RECORD_SIZE)
+}
+
+/// First record of a snapshot file
+#[derive(Serialize, Deserialize)]
+struct SnapshotHeader {
+    version: u32,
+}
+
+/// Record of a snapshot file, following the [`SnapshotHeader`]
+#[derive(Serialize, Deserialize)]
+struct SnapshotRecord {
+    /// Keccak256 hash of the checksum of the previous record followed by `data`
+    checksum: [u8; 32],
+    /// Bincode serialized [`SnapshotChunk`]
+    data: Vec<u8>,
+}
+
+/// Part of a snapshot
+///
+/// The certificates come first, ordered by source stream, followed by the target streams and
+/// the target source lists. The snapshot ends with [`SnapshotChunk::End`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum SnapshotChunk {
+    /// Delivered certificates, ordered by source stream
+    Certificates(Vec<CertificateDelivered>),
+    /// Target stream entries
+    TargetStreams(Vec<(CertificateTargetStreamPosition, CertificateId)>),
+    /// Last position of (target subnet, source subnet) streams
+    TargetSourceList(Vec<(SubnetId, SubnetId, Position)>),
+    End,
+}
+
+/// Summary of a snapshot once exported or imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotSummary {
+    /// Number of certificates in the snapshot
+    pub certificates: usize,
+    /// Head of every source stream in the snapshot
+    pub source_heads: Vec<SourceHead>,
+}
+
+/// Writer of a snapshot file, one chunk at a time
+pub(crate) struct SnapshotWriter {
+    writer: BufWriter<File>,
+    checksum: [u8; 32],
+}
+
+impl SnapshotWriter {
+    pub(crate) fn create(path: &Path) -> Result<Self, SnapshotError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        options().serialize_into(
+            &mut writer,
+            &SnapshotHeader {
+                version: SNAPSHOT_VERSION,
+            },
+        )?;
+
+        Ok(Self {
+            writer,
+            checksum: [0; 32],
+        })
+    }
+
+    pub(crate) fn write_chunk(&mut self, chunk: &SnapshotChunk) -> Result<(), SnapshotError> {
+        let data = options().serialize(chunk)?;
+        self.checksum = calculate_hash(&[self.checksum.as_slice(), &data].concat());
+
+        options().serialize_into(
+            &mut self.writer,
+            &SnapshotRecord {
+                checksum: self.checksum,
+                data,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Write the end of the snapshot and flush it to the file
+    pub(crate) fn finish(mut self) -> Result<(), SnapshotError> {
+        self.write_chunk(&SnapshotChunk::End)?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Reader of a snapshot file, one chunk at a time
+pub(crate) struct SnapshotReader {
+    reader: BufReader<File>,
+    checksum: [u8; 32],
+    ended: bool,
+}
+
+impl SnapshotReader {
+    /// Open a snapshot file, checking its version
+    pub(crate) fn open(path: &Path) -> Result<Self, SnapshotError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let header: SnapshotHeader = options().deserialize_from(&mut reader).map_err(truncated)?;
+
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+
+        Ok(Self {
+            reader,
+            checksum: [0; 32],
+            ended: false,
+        })
+    }
+
+    /// Returns the next chunk of the snapshot once its checksum is checked, `None` once the end
+    /// of the snapshot is reached
+    pub(crate) fn next_chunk(&mut self) -> Result<Option<SnapshotChunk>, SnapshotError> {
+        if self.ended {
+            return Ok(None);
+        }
+
+        let record: SnapshotRecord = options()
+            .deserialize_from(&mut self.reader)
+            .map_err(truncated)?;
+
+        let checksum = calculate_hash(&[self.checksum.as_slice(), &record.data].concat());
+        if checksum != record.checksum {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+        self.checksum = checksum;
+
+        match options().deserialize(&record.data)? {
+            SnapshotChunk::End => {
+                self.ended = true;
+
+                Ok(None)
+            }
+            chunk => Ok(Some(chunk)),
+        }
+    }
+}
+
+/// Reaching the end of the file before the end of the snapshot means that it is truncated
+fn truncated(error: Box<bincode::ErrorKind>) -> SnapshotError {
+    match *error {
+        bincode::ErrorKind::Io(error) if error.kind() == ErrorKind::UnexpectedEof => {
+            SnapshotError::Truncated
+        }
+        error => SnapshotError::Bincode(Box::new(error)),
+    }
+}
+
+/// Write the snapshot of the given tables to a file
+pub(crate) fn export(
+    perpetual_tables: &ValidatorPerpetualTables,
+    index_tables: &IndexTables,
+    path: &Path,
+) -> Result<SnapshotSummary, SnapshotError> {
+    let mut writer = SnapshotWriter::create(path)?;
+    let mut summary = SnapshotSummary {
+        certificates: 0,
+        source_heads: Vec::new(),
+    };
+
+    let mut certificates = Vec::with_capacity(CERTIFICATES_PER_CHUNK);
+    for (position, certificate_id) in perpetual_tables.streams.iter()? {
+        certificates.push(
+            perpetual_tables
+                .certificates
+                .get(&certificate_id)?
+                .ok_or(InternalStorageError::CertificateNotFound(certificate_id))?,
+        );
+
+        let head = SourceHead {
+            certificate_id,
+            subnet_id: position.subnet_id,
+            position: position.position,
+        };
+        match summary.source_heads.last_mut() {
+            Some(last) if last.subnet_id == position.subnet_id => *last = head,
+            _ => summary.source_heads.push(head),
+        }
+        summary.certificates += 1;
+
+        if certificates.len() == CERTIFICATES_PER_CHUNK {
+            writer.write_chunk(&SnapshotChunk::Certificates(std::mem::take(
+                &mut certificates,
+            )))?;
+        }
+    }
+    if !certificates.is_empty() {
+        writer.write_chunk(&SnapshotChunk::Certificates(certificates))?;
+    }
+
+    let mut target_streams = Vec::with_capacity(ENTRIES_PER_CHUNK);
+    for entry in index_tables.target_streams.iter()? {
+        target_streams.push(entry);
+
+        if target_streams.len() == ENTRIES_PER_CHUNK {
+            writer.write_chunk(&SnapshotChunk::TargetStreams(std::mem::take(
+                &mut target_streams,
+            )))?;
+        }
+    }
+    if !target_streams.is_empty() {
+        writer.write_chunk(&SnapshotChunk::TargetStreams(target_streams))?;
+    }
+
+    let mut target_source_list = Vec::with_capacity(ENTRIES_PER_CHUNK);
+    for (TargetSourceListKey(target, source), position) in index_tables.target_source_list.iter()? {
+        target_source_list.push((target, source, position));
+
+        if target_source_list.len() == ENTRIES_PER_CHUNK {
+            writer.write_chunk(&SnapshotChunk::TargetSourceList(std::mem::take(
+                &mut target_source_list,
+            )))?;
+        }
+    }
+    if !target_source_list.is_empty() {
+        writer.write_chunk(&SnapshotChunk::TargetSourceList(target_source_list))?;
+    }
+
+    writer.finish()?;
+
+    Ok(summary)
+}
+
+/// Fail if a snapshot import has been interrupted or rejected in the given tables
+pub(crate) fn check_no_pending_import(
+    perpetual_tables: &ValidatorPerpetualTables,
+) -> Result<(), InternalStorageError> {
+    if perpetual_tables
+        .schema
+        .get(&SNAPSHOT_IMPORT_KEY.to_string())?
+        .is_some()
+    {
+        return Err(InternalStorageError::InterruptedSnapshotImport);
+    }
+
+    Ok(())
+}
+
+/// Import of a snapshot in empty tables, verifying and writing one chunk at a time
+pub(crate) struct SnapshotImport<'a> {
+    perpetual_tables: &'a ValidatorPerpetualTables,
+    index_tables: &'a IndexTables,
+    /// Validators and threshold of every epoch, the genesis ones first
+    validators: &'a [(Validators, u64)],
+    certificates: usize,
+    /// Heads of the source streams, in the order of the snapshot
+    source_heads: Vec<SourceHead>,
+    /// Head of every target stream, recomputed from the certificates
+    target_heads: HashMap<(SubnetId, SubnetId), Position>,
+    /// Head of every target stream of the snapshot
+    imported_target_heads: HashMap<(SubnetId, SubnetId), Position>,
+    /// Target source lists of the snapshot
+    target_source_list: HashMap<(SubnetId, SubnetId), Position>,
+}
+
+impl<'a> SnapshotImport<'a> {
+    /// Start the import, recording the marker of an import in progress
+    pub(crate) fn start(
+        perpetual_tables: &'a ValidatorPerpetualTables,
+        index_tables: &'a IndexTables,
+        validators: &'a [(Validators, u64)],
+    ) -> Result<Self, SnapshotError> {
+        if perpetual_tables.certificates.iter()?.next().is_some() {
+            return Err(SnapshotError::StoreNotEmpty);
+        }
+
+        perpetual_tables
+            .schema
+            .insert(&SNAPSHOT_IMPORT_KEY.to_string(), &SNAPSHOT_VERSION)?;
+
+        Ok(Self {
+            perpetual_tables,
+            index_tables,
+            validators,
+            certificates: 0,
+            source_heads: Vec::new(),
+            target_heads: HashMap::new(),
+            imported_target_heads: HashMap::new(),
+            target_source_list: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn import_chunk(&mut self, chunk: SnapshotChunk) -> Result<(), SnapshotError> {
+        match chunk {
+            SnapshotChunk::Certificates(certificates) => self.import_certificates(certificates),
+            SnapshotChunk::TargetStreams(entries) => self.verify_target_streams(&entries),
+            SnapshotChunk::TargetSourceList(heads) => {
+                for (target, source, position) in heads {
+                    if self
+                        .target_source_list
+                        .insert((target, source), position)
+                        .is_some()
+                    {
+                        return Err(SnapshotError::Inconsistent(format!(
+                            "target source list of {target} for {source} is present more than once"
+                        )));
+                    }
+                }
+
+                Ok(())
+            }
+            SnapshotChunk::End => Ok(()),
+        }
+    }
+
+    /// Verify the proof of delivery of a certificate against the validators of every epoch, as
+    /// the epoch in which a certificate has been delivered isn't part of its proof
+    ///
+    /// The error raised against the genesis validators is returned if no validator set proves
+    /// the delivery.
+    fn verify_delivery(&self, delivered: &CertificateDelivered) -> Result<(), SnapshotError> {
+        let certificate_id = delivered.certificate.id;
+        let mut error = None;
+
+        for (validators, threshold) in self.validators {
+            match delivered.verify(validators, *threshold) {
+                Ok(()) => return Ok(()),
+                Err(e) => _ = error.get_or_insert(e),
+            }
+        }
+
+        Err(match error {
+            Some(error) => SnapshotError::InvalidProofOfDelivery(certificate_id, error),
+            None => SnapshotError::Inconsistent(format!(
+                "no validators to verify the proof of delivery of {certificate_id}"
+            )),
+        })
+    }
+
+    /// Verify the certificates and their chaining in their source stream, writing them along with
+    /// their source and target streams
+    fn import_certificates(
+        &mut self,
+        certificates: Vec<CertificateDelivered>,
+    ) -> Result<(), SnapshotError> {
+        let mut source_streams = Vec::with_capacity(certificates.len());
+        let mut target_streams = Vec::new();
+
+        for delivered in &certificates {
+            self.verify_delivery(delivered)?;
+
+            let certificate_id = delivered.certificate.id;
+            let position = delivered.proof_of_delivery.delivery_position.clone();
+            let head = SourceHead {
+                certificate_id,
+                subnet_id: position.subnet_id,
+                position: position.position,
+            };
+
+            match self
+                .source_heads
+                .iter_mut()
+                .find(|head| head.subnet_id == position.subnet_id)
+            {
+                Some(previous) => {
+                    if *position.position != *previous.position + 1 {
+                        return Err(SnapshotError::Inconsistent(format!(
+                            "source stream {} jumps from {} to {}",
+                            position.subnet_id, previous.position, position.position
+                        )));
+                    }
+
+                    if delivered.certificate.prev_id != previous.certificate_id {
+                        return Err(SnapshotError::Inconsistent(format!(
+                            "certificate {certificate_id} doesn't follow {} in the source stream \
+                             {}",
+                            previous.certificate_id, position.subnet_id
+                        )));
+                    }
+
+                    *previous = head;
+                }
+                None if position.position != Position::ZERO => {
+                    return Err(SnapshotError::Inconsistent(format!(
+                        "source stream {} starts at {}",
+                        position.subnet_id, position.position
+                    )));
+                }
+                None => self.source_heads.push(head),
+            }
+
+            for target_subnet_id in &delivered.certificate.target_subnets {
+                let key = (*target_subnet_id, position.subnet_id);
+                let target_position = match self.target_heads.get(&key) {
+                    Some(head) => head.increment().map_err(|_| {
+                        SnapshotError::Inconsistent(format!(
+                            "target stream {target_subnet_id} of {} overflows",
+                            position.subnet_id
+                        ))
+                    })?,
+                    None => Position::ZERO,
+                };
+                self.target_heads.insert(key, target_position);
+
+                target_streams.push((
+                    CertificateTargetStreamPosition::new(
+                        *target_subnet_id,
+                        position.subnet_id,
+                        target_position,
+                    ),
+                    certificate_id,
+                ));
+            }
+
+            source_streams.push((position, certificate_id));
+        }
+
+        self.certificates += certificates.len();
+
+        self.perpetual_tables
+            .certificates
+            .batch()
+            .insert_batch(
+                &self.perpetual_tables.certificates,
+                certificates
+                    .iter()
+                    .map(|delivered| (delivered.certificate.id, delivered)),
+            )?
+            .insert_batch(
+                &self.perpetual_tables.streams,
+                source_streams.iter().map(|(position, id)| (position, id)),
+            )?
+            .write()?;
+
+        self.index_tables
+            .target_streams
+            .batch()
+            .insert_batch(
+                &self.index_tables.target_streams,
+                target_streams.iter().map(|(position, id)| (position, id)),
+            )?
+            .write()?;
+
+        Ok(())
+    }
+
+    /// Check the target streams of the snapshot against the ones recomputed from the certificates
+    fn verify_target_streams(
+        &mut self,
+        entries: &[(CertificateTargetStreamPosition, CertificateId)],
+    ) -> Result<(), SnapshotError> {
+        for (position, certificate_id) in entries {
+            let key = (position.target_subnet_id, position.source_subnet_id);
+            let expected = match self.imported_target_heads.get(&key) {
+                Some(head) => *head.increment().map_err(|_| {
+                    SnapshotError::Inconsistent(format!(
+                        "target stream {} of {} overflows",
+                        position.target_subnet_id, position.source_subnet_id
+                    ))
+                })?,
+                None => 0,
+            };
+
+            if *position.position != expected {
+                return Err(SnapshotError::Inconsistent(format!(
+                    "target stream {} of {} expected position {expected} instead of {}",
+                    position.target_subnet_id, position.source_subnet_id, position.position
+                )));
+            }
+
+            if self.index_tables.target_streams.get(position)? != Some(*certificate_id) {
+                return Err(SnapshotError::Inconsistent(format!(
+                    "certificate {certificate_id} isn't delivered at {} of the target stream {} \
+                     of {}",
+                    position.position, position.target_subnet_id, position.source_subnet_id
+                )));
+            }
+
+            self.imported_target_heads.insert(key, position.position);
+        }
+
+        Ok(())
+    }
+
+    /// Check the streams of the snapshot as a whole and write the heads of the streams, removing
+    /// the marker of the import
+    pub(crate) fn finish(self) -> Result<SnapshotSummary, SnapshotError> {
+        if self.imported_target_heads != self.target_heads {
+            return Err(SnapshotError::Inconsistent(
+                "target streams don't match the certificates".to_string(),
+            ));
+        }
+
+        if self.target_source_list != self.target_heads {
+            return Err(SnapshotError::Inconsistent(
+                "target source lists don't match the target streams".to_string(),
+            ));
+        }
+
+        self.index_tables
+            .target_source_list
+            .batch()
+            .insert_batch(
+                &self.index_tables.target_source_list,
+                self.target_heads
+                    .iter()
+                    .map(|((target, source), position)| {
+                        (TargetSourceListKey(*target, *source), position)
+                    }),
+            )?
+            .insert_batch(
+                &self.index_tables.source_list_per_target,
+                self.target_heads.keys().map(|key| (key, true)),
+            )?
+            .insert_batch(
+                &self.index_tables.source_list,
+                self.source_heads
+                    .iter()
+                    .map(|head| (head.subnet_id, (head.certificate_id, head.position))),
+            )?
+            .write()?;
+
+        self.perpetual_tables
+            .schema
+            .delete(&SNAPSHOT_IMPORT_KEY.to_string())?;
+
+        Ok(SnapshotSummary {
+            certificates: self.certificates,
+            source_heads: self.source_heads,
+        })
+    }
+}
@@ -81,6 +81,9 @@ pub trait ReadStore: Send {
     fn get_checkpoint(&self) -> Result<HashMap<SubnetId, SourceHead>, StorageError>;
 
     /// Returns the certificates delivered by a source subnet from a position.
+    ///
+    /// The certificates whose proof has been pruned are skipped, leaving a gap in the returned
+    /// positions. The next page starts after the position of the last returned certificate.
     fn get_source_stream_certificates_from_position(
         &self,
         from: CertificateSourceStreamPosition,
@@ -88,6 +91,9 @@ pub trait ReadStore: Send {
     ) -> Result<Vec<(CertificateDelivered, CertificateSourceStreamPosition)>, StorageError>;
 
     /// Returns the certificates delivered to a target subnet from a position.
+    ///
+    /// The certificates whose proof has been pruned are skipped, leaving a gap in the returned
+    /// positions. The next page starts after the position of the last returned certificate.
     fn get_target_stream_certificates_from_position(
         &self,
         position: CertificateTargetStreamPosition,
@@ -1,3 +1,4 @@
+use rocksdb::{Direction, IteratorMode};
 use rstest::rstest;
 use test_log::test;
 use topos_core::types::stream::CertificateSourceStreamPosition;
@@ -5,6 +6,7 @@ use topos_core::uci::Certificate;
 use topos_test_sdk::certificates::create_certificate_at_position;
 use topos_test_sdk::constants::SOURCE_SUBNET_ID_1;
 
+use crate::errors::InternalStorageError;
 use crate::rocks::map::Map;
 use crate::tests::{PREV_CERTIFICATE_ID, SOURCE_STORAGE_SUBNET_ID};
 use crate::types::{CertificatesColumn, PendingCertificatesColumn, StreamsColumn};
@@ -123,3 +125,12 @@ async fn position_can_be_fetch_for_multiple_subnets() {}
 #[test(tokio::test)]
 #[ignore = "not yet implemented"]
 async fn position_can_be_fetch_for_all_subnets() {}
+
+#[rstest]
+#[test(tokio::test)]
+async fn iterating_from_a_raw_key_is_rejected(pending_column: PendingCertificatesColumn) {
+    assert!(matches!(
+        pending_column.iter_with_mode(IteratorMode::From(&[0], Direction::Forward)),
+        Err(InternalStorageError::InvalidQueryArgument(_))
+    ));
+}
@@ -0,0 +1,299 @@
+use std::{collections::HashSet, str::FromStr, sync::Arc};
+
+use rstest::rstest;
+use topos_core::types::{stream::Position, ValidatorId};
+use topos_crypto::messages::MessageSigner;
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
+};
+
+use super::support::store;
+use crate::{
+    store::{ReadStore, WriteStore},
+    types::{
+        CheckpointSignatureError, CheckpointSummary, ValidatorQuorumSignatureInfo,
+        VerifiedCheckpointSummary,
+    },
+    validator::ValidatorStore,
+};
+
+fn verified_checkpoint(checkpoint: CheckpointSummary) -> VerifiedCheckpointSummary {
+    let epoch = checkpoint.epoch;
+
+    VerifiedCheckpointSummary(
+        checkpoint,
+        ValidatorQuorumSignatureInfo {
+            epoch,
+            signatures: Vec::new(),
+        },
+    )
+}
+
+#[rstest]
+#[tokio::test]
+async fn persist_validators_per_epoch(store: Arc<ValidatorStore>) {
+    let epoch_validators = store.epoch_validators_store();
+
+    assert!(epoch_validators.get_latest_validators().unwrap().is_none());
+
+    let first = ValidatorId::from_str("0x100d617e4392c02b31bdce650b26b6c0c3e04f95").unwrap();
+    let second = ValidatorId::from_str("0x659ca3b40f4fa1d7f4a2cd8e5d1a1b8e4b8a3c9f").unwrap();
+
+    let genesis_set: HashSet<ValidatorId> = [first].into_iter().collect();
+    let next_set: HashSet<ValidatorId> = [first, second].into_iter().collect();
+
+    epoch_validators
+        .insert_validators(0, genesis_set.clone())
+        .unwrap();
+    epoch_validators
+        .insert_validators(1, next_set.clone())
+        .unwrap();
+
+    assert_eq!(
+        epoch_validators.get_validators(0).unwrap(),
+        Some(genesis_set.clone())
+    );
+    assert_eq!(
+        epoch_validators.get_validators(1).unwrap(),
+        Some(next_set.clone())
+    );
+    assert!(epoch_validators.get_validators(2).unwrap().is_none());
+
+    assert_eq!(
+        epoch_validators.get_latest_validators().unwrap(),
+        Some((1, next_set.clone()))
+    );
+    assert_eq!(
+        epoch_validators.get_all_validators().unwrap(),
+        vec![(1, next_set), (0, genesis_set)]
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn start_epochs_from_checkpoints(store: Arc<ValidatorStore>) {
+    let fullnode_store = store.fullnode_store();
+
+    assert!(fullnode_store.get_latest_epoch_summary().unwrap().is_none());
+
+    let genesis_checkpoint =
+        verified_checkpoint(CheckpointSummary::new(0, 0, Vec::new(), Vec::new()));
+    fullnode_store
+        .start_epoch(0, genesis_checkpoint.clone())
+        .unwrap();
+
+    for certificate in create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 4) {
+        _ = store.insert_certificate_delivered(&certificate).await;
+    }
+
+    let checkpoint = verified_checkpoint(CheckpointSummary::new(
+        1,
+        0,
+        store.get_checkpoint().unwrap().into_values(),
+        fullnode_store.get_target_checkpoint().unwrap(),
+    ));
+    let summary = fullnode_store.start_epoch(1, checkpoint.clone()).unwrap();
+
+    assert_eq!(summary.epoch_id, 1);
+    assert_eq!(summary.start_checkpoint.0.checkpoint_data.len(), 1);
+    assert_eq!(*summary.start_checkpoint.0.checkpoint_data[0].position, 3);
+    assert_eq!(summary.start_checkpoint.0.target_checkpoint_data.len(), 1);
+    assert_eq!(
+        summary.start_checkpoint.0.target_checkpoint_data[0].certificate_id,
+        summary.start_checkpoint.0.checkpoint_data[0].certificate_id
+    );
+    assert_eq!(
+        *summary.start_checkpoint.0.target_checkpoint_data[0].position,
+        3
+    );
+    assert!(summary.end_checkpoint.is_none());
+
+    let genesis = fullnode_store.get_epoch_summary(0).unwrap().unwrap();
+    assert_eq!(genesis.start_checkpoint, genesis_checkpoint);
+    assert_eq!(genesis.end_checkpoint, Some(checkpoint.clone()));
+
+    assert_eq!(
+        fullnode_store.get_latest_epoch_summary().unwrap(),
+        Some(summary)
+    );
+
+    // An epoch can't be started twice
+    assert!(fullnode_store.start_epoch(1, checkpoint).is_err());
+}
+
+fn signed_checkpoint(
+    checkpoint: CheckpointSummary,
+    signers: &[MessageSigner],
+) -> VerifiedCheckpointSummary {
+    let epoch = checkpoint.epoch;
+    let payload = checkpoint.payload();
+
+    VerifiedCheckpointSummary(
+        checkpoint,
+        ValidatorQuorumSignatureInfo {
+            epoch,
+            signatures: signers
+                .iter()
+                .map(|signer| {
+                    (
+                        signer.public_address.into(),
+                        signer.sign_message(&payload).unwrap(),
+                    )
+                })
+                .collect(),
+        },
+    )
+}
+
+#[test]
+fn signed_checkpoint_verification() {
+    let signers: Vec<MessageSigner> = (1..=4u8)
+        .map(|i| MessageSigner::new(&[i; 32]).unwrap())
+        .collect();
+    let validators: HashSet<ValidatorId> = signers
+        .iter()
+        .map(|signer| signer.public_address.into())
+        .collect();
+
+    let checkpoint = CheckpointSummary::new(3, 0, Vec::new(), Vec::new());
+
+    assert!(signed_checkpoint(checkpoint.clone(), &signers[..3])
+        .verify(&validators, 3)
+        .is_ok());
+
+    assert_eq!(
+        signed_checkpoint(checkpoint.clone(), &signers[..2]).verify(&validators, 3),
+        Err(CheckpointSignatureError::NotEnoughSignatures {
+            received: 2,
+            threshold: 3
+        })
+    );
+
+    let outsider = [MessageSigner::new(&[42u8; 32]).unwrap()];
+    assert_eq!(
+        signed_checkpoint(checkpoint.clone(), &outsider).verify(&validators, 1),
+        Err(CheckpointSignatureError::UnknownValidator(
+            outsider[0].public_address.into()
+        ))
+    );
+
+    // Signatures of a checkpoint don't attest any other checkpoint
+    let mut tampered = signed_checkpoint(checkpoint, &signers[..3]);
+    tampered.0.sequence_number = 1;
+    assert_eq!(
+        tampered.verify(&validators, 3),
+        Err(CheckpointSignatureError::InvalidSignature(
+            signers[0].public_address.into()
+        ))
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn persist_signed_checkpoints(store: Arc<ValidatorStore>) {
+    let fullnode_store = store.fullnode_store();
+    let signers = [MessageSigner::new(&[1u8; 32]).unwrap()];
+
+    assert!(fullnode_store
+        .get_latest_signed_checkpoint()
+        .unwrap()
+        .is_none());
+
+    let first = signed_checkpoint(
+        CheckpointSummary::new(0, 0, Vec::new(), Vec::new()),
+        &signers,
+    );
+    fullnode_store
+        .insert_signed_checkpoint(first.clone())
+        .unwrap();
+
+    assert_eq!(
+        fullnode_store.get_latest_signed_checkpoint().unwrap(),
+        Some(first.clone())
+    );
+
+    let second = signed_checkpoint(
+        CheckpointSummary::new(2, 0, Vec::new(), Vec::new()),
+        &signers,
+    );
+    fullnode_store
+        .insert_signed_checkpoint(second.clone())
+        .unwrap();
+
+    assert_eq!(
+        fullnode_store.get_latest_signed_checkpoint().unwrap(),
+        Some(second.clone())
+    );
+
+    // Epoch summaries are chained by the signed checkpoints
+    let summary = fullnode_store.get_epoch_summary(0).unwrap().unwrap();
+    assert_eq!(summary.start_checkpoint, first);
+    assert_eq!(summary.end_checkpoint, Some(second.clone()));
+
+    let summary = fullnode_store.get_latest_epoch_summary().unwrap().unwrap();
+    assert_eq!(summary.epoch_id, 2);
+    assert_eq!(summary.start_checkpoint, second);
+
+    // A checkpoint of a past epoch is rejected
+    assert!(fullnode_store.insert_signed_checkpoint(first).is_err());
+
+    // The following checkpoints of the epoch are kept as its latest one
+    let third = signed_checkpoint(
+        CheckpointSummary::new(2, 3, Vec::new(), Vec::new()),
+        &signers,
+    );
+    fullnode_store
+        .insert_signed_checkpoint(third.clone())
+        .unwrap();
+
+    assert_eq!(
+        fullnode_store.get_latest_signed_checkpoint().unwrap(),
+        Some(third.clone())
+    );
+
+    let summary = fullnode_store.get_latest_epoch_summary().unwrap().unwrap();
+    assert_eq!(summary.start_checkpoint, second);
+
+    // A checkpoint older than the latest one of the epoch is rejected
+    let older = signed_checkpoint(
+        CheckpointSummary::new(2, 1, Vec::new(), Vec::new()),
+        &signers,
+    );
+    assert!(fullnode_store.insert_signed_checkpoint(older).is_err());
+    assert!(fullnode_store.insert_signed_checkpoint(third).is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn checkpoint_heads_delivered(store: Arc<ValidatorStore>) {
+    let fullnode_store = store.fullnode_store();
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 4);
+
+    for certificate in &certificates[..3] {
+        _ = store.insert_certificate_delivered(certificate).await;
+    }
+
+    let checkpoint = CheckpointSummary::new(
+        0,
+        0,
+        store.get_checkpoint().unwrap().into_values(),
+        fullnode_store.get_target_checkpoint().unwrap(),
+    );
+    assert!(fullnode_store.has_delivered_heads(&checkpoint).unwrap());
+
+    // Heads ahead of the local streams aren't delivered yet
+    let mut ahead = checkpoint.clone();
+    ahead.checkpoint_data[0].certificate_id = certificates[3].certificate.id;
+    ahead.checkpoint_data[0].position = Position::from(3u64);
+    assert!(!fullnode_store.has_delivered_heads(&ahead).unwrap());
+
+    let mut ahead = checkpoint;
+    ahead.target_checkpoint_data[0].certificate_id = certificates[3].certificate.id;
+    ahead.target_checkpoint_data[0].position = Position::from(3u64);
+    assert!(!fullnode_store.has_delivered_heads(&ahead).unwrap());
+
+    _ = store.insert_certificate_delivered(&certificates[3]).await;
+
+    assert!(fullnode_store.has_delivered_heads(&ahead).unwrap());
+}
@@ -0,0 +1,156 @@
+use rstest::rstest;
+use std::sync::Arc;
+use test_log::test;
+use topos_core::types::stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition};
+use topos_test_sdk::certificates::create_certificate_chain;
+use topos_test_sdk::constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1};
+
+use crate::{
+    integrity::IntegrityIssue,
+    rocks::TargetSourceListKey,
+    store::{ReadStore, WriteStore},
+    validator::ValidatorStore,
+};
+
+use super::support::store;
+
+#[rstest]
+#[test(tokio::test)]
+async fn consistent_store_passes_the_check(store: Arc<ValidatorStore>) {
+    for subnet_id in [SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2] {
+        store
+            .insert_certificates_delivered(&create_certificate_chain(
+                subnet_id,
+                &[TARGET_SUBNET_ID_1],
+                3,
+            ))
+            .await
+            .unwrap();
+    }
+
+    let report = store.fullnode_store().check_integrity().unwrap();
+
+    assert!(report.is_consistent(), "{:?}", report.issues);
+    assert_eq!(report.certificates, 6);
+    assert_eq!(report.source_stream_entries, 6);
+    assert_eq!(report.target_stream_entries, 6);
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn broken_indexes_are_repaired(store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    let fullnode_store = store.fullnode_store();
+    let index_tables = &fullnode_store.index_tables;
+
+    // Indexes left behind by a crash during the delivery of the last certificate
+    index_tables
+        .source_list
+        .insert(
+            &SOURCE_SUBNET_ID_1,
+            &(
+                certificates[1].certificate.id,
+                certificates[1].proof_of_delivery.delivery_position.position,
+            ),
+        )
+        .unwrap();
+    index_tables
+        .target_streams
+        .delete(&CertificateTargetStreamPosition::new(
+            TARGET_SUBNET_ID_1,
+            SOURCE_SUBNET_ID_1,
+            2,
+        ))
+        .unwrap();
+    index_tables
+        .target_source_list
+        .insert(
+            &TargetSourceListKey(TARGET_SUBNET_ID_1, SOURCE_SUBNET_ID_1),
+            &1.into(),
+        )
+        .unwrap();
+
+    let report = fullnode_store.check_integrity().unwrap();
+
+    assert_eq!(
+        report.issues,
+        vec![
+            IntegrityIssue::SourceHeadMismatch(SOURCE_SUBNET_ID_1),
+            IntegrityIssue::TargetStreamMismatch {
+                target_subnet_id: TARGET_SUBNET_ID_1,
+                source_subnet_id: SOURCE_SUBNET_ID_1,
+                expected: 3,
+                found: 2,
+            },
+            IntegrityIssue::TargetSourceListMismatch {
+                target_subnet_id: TARGET_SUBNET_ID_1,
+                source_subnet_id: SOURCE_SUBNET_ID_1,
+            },
+        ]
+    );
+    assert!(report.issues.iter().all(IntegrityIssue::is_repairable));
+
+    let report = fullnode_store.repair_indexes().await.unwrap();
+
+    assert!(report.is_consistent(), "{:?}", report.issues);
+    assert_eq!(
+        fullnode_store
+            .get_source_head(&SOURCE_SUBNET_ID_1)
+            .unwrap()
+            .unwrap()
+            .certificate_id,
+        certificates[2].certificate.id
+    );
+    assert_eq!(report.target_stream_entries, 3);
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn broken_source_stream_is_reported(store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 4);
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    let fullnode_store = store.fullnode_store();
+    let perpetual_tables = &fullnode_store.perpetual_tables;
+
+    perpetual_tables
+        .streams
+        .delete(&CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, 1))
+        .unwrap();
+    perpetual_tables
+        .certificates
+        .delete(&certificates[3].certificate.id)
+        .unwrap();
+
+    let issues = fullnode_store.repair_indexes().await.unwrap().issues;
+
+    assert_eq!(
+        issues,
+        vec![
+            IntegrityIssue::SourceStreamGap {
+                subnet_id: SOURCE_SUBNET_ID_1,
+                expected: 1.into(),
+                found: 2.into(),
+            },
+            IntegrityIssue::BrokenChain {
+                certificate_id: certificates[2].certificate.id,
+                prev_id: certificates[1].certificate.id,
+                expected_prev_id: certificates[0].certificate.id,
+            },
+            IntegrityIssue::MissingCertificate(
+                CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, 3),
+                certificates[3].certificate.id,
+            ),
+            IntegrityIssue::OrphanCertificate(certificates[1].certificate.id),
+        ]
+    );
+    assert!(!issues.iter().any(IntegrityIssue::is_repairable));
+}
@@ -0,0 +1,169 @@
+use rocksdb::IteratorMode;
+use rstest::rstest;
+use std::sync::Arc;
+use test_log::test;
+use topos_core::types::stream::{
+    CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position,
+};
+use topos_test_sdk::certificates::create_certificate_chain;
+use topos_test_sdk::constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1};
+
+use crate::constant::cfs;
+use crate::memory::MemoryDB;
+use crate::rocks::{constants, db_column::DBColumn, map::Map};
+use crate::store::{ReadStore, WriteStore};
+use crate::types::StreamsColumn;
+use crate::validator::ValidatorStore;
+
+use super::support::columns::source_streams_column;
+use super::support::memory_store;
+
+#[rstest]
+#[test(tokio::test)]
+async fn prefix_iteration_matches_rocksdb(source_streams_column: StreamsColumn) {
+    let db = MemoryDB::new(&[(cfs::STREAMS, Some(constants::SOURCE_STREAMS_PREFIX_SIZE))]);
+    let memory_column: StreamsColumn = DBColumn::reopen_in_memory(&db, cfs::STREAMS);
+
+    for subnet_id in [SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2] {
+        for certificate in create_certificate_chain(subnet_id, &[TARGET_SUBNET_ID_1], 3) {
+            let position = certificate.proof_of_delivery.delivery_position;
+            let certificate_id = certificate.certificate.id;
+
+            source_streams_column
+                .insert(&position, &certificate_id)
+                .unwrap();
+            memory_column.insert(&position, &certificate_id).unwrap();
+        }
+    }
+
+    let from = CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_2, 1);
+
+    for (rocks, memory) in [
+        (
+            source_streams_column.iter().unwrap().collect::<Vec<_>>(),
+            memory_column.iter().unwrap().collect::<Vec<_>>(),
+        ),
+        (
+            source_streams_column
+                .prefix_iter(&SOURCE_SUBNET_ID_2)
+                .unwrap()
+                .collect(),
+            memory_column
+                .prefix_iter(&SOURCE_SUBNET_ID_2)
+                .unwrap()
+                .collect(),
+        ),
+        (
+            source_streams_column
+                .prefix_iter_at(&SOURCE_SUBNET_ID_2, &from)
+                .unwrap()
+                .collect(),
+            memory_column
+                .prefix_iter_at(&SOURCE_SUBNET_ID_2, &from)
+                .unwrap()
+                .collect(),
+        ),
+        (
+            source_streams_column
+                .iter_with_mode(IteratorMode::End)
+                .unwrap()
+                .collect(),
+            memory_column
+                .iter_with_mode(IteratorMode::End)
+                .unwrap()
+                .collect(),
+        ),
+    ] {
+        assert!(!memory.is_empty());
+        assert_eq!(rocks, memory);
+    }
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn delivered_certificates_are_streamed_in_memory(memory_store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+
+    memory_store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    assert_eq!(memory_store.count_certificates_delivered().unwrap(), 3);
+    assert_eq!(
+        memory_store
+            .get_source_head(&SOURCE_SUBNET_ID_1)
+            .unwrap()
+            .unwrap()
+            .certificate_id,
+        certificates[2].certificate.id
+    );
+
+    let source_stream = memory_store
+        .get_source_stream_certificates_from_position(
+            CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, Position::ZERO),
+            10,
+        )
+        .unwrap();
+    let target_stream = memory_store
+        .get_target_stream_certificates_from_position(
+            CertificateTargetStreamPosition::new(
+                TARGET_SUBNET_ID_1,
+                SOURCE_SUBNET_ID_1,
+                Position::ZERO,
+            ),
+            10,
+        )
+        .unwrap();
+
+    assert_eq!(
+        source_stream
+            .into_iter()
+            .map(|(certificate, _)| certificate)
+            .collect::<Vec<_>>(),
+        certificates
+    );
+    assert_eq!(target_stream.len(), 3);
+    assert_eq!(
+        memory_store
+            .get_target_source_subnet_list(&TARGET_SUBNET_ID_1)
+            .unwrap(),
+        vec![SOURCE_SUBNET_ID_1]
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn pending_certificates_are_promoted_in_memory(memory_store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    assert!(memory_store
+        .insert_pending_certificate(&certificates[1].certificate)
+        .await
+        .unwrap()
+        .is_none());
+    let pending_id = memory_store
+        .insert_pending_certificate(&certificates[0].certificate)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(memory_store.pending_pool_size().unwrap(), 1);
+    assert_eq!(memory_store.precedence_pool_size().unwrap(), 1);
+
+    memory_store
+        .insert_certificate_delivered(&certificates[0])
+        .await
+        .unwrap();
+
+    assert_eq!(memory_store.precedence_pool_size().unwrap(), 0);
+    assert_eq!(
+        memory_store
+            .get_next_pending_certificates(&pending_id, 10)
+            .unwrap()
+            .into_iter()
+            .map(|(_, certificate)| certificate)
+            .collect::<Vec<_>>(),
+        vec![certificates[1].certificate.clone()]
+    );
+}
@@ -0,0 +1,257 @@
+use rocksdb::ColumnFamilyDescriptor;
+use test_log::test;
+use topos_crypto::messages::MessageSigner;
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{CERTIFICATE_ID_1, SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
+    storage::create_folder,
+};
+
+use crate::{
+    constant::cfs,
+    errors::{InternalStorageError, StorageError},
+    migration::{self, migrate, SCHEMA_VERSION, SCHEMA_VERSION_KEY},
+    rocks::db::{default_options, init_with_cfs},
+    store::WriteStore,
+    types::{CheckpointSummary, EpochId, ValidatorQuorumSignatureInfo, VerifiedCheckpointSummary},
+    validator::ValidatorStore,
+    SourceHead,
+};
+
+#[test(tokio::test)]
+async fn legacy_database_is_migrated() {
+    let path = create_folder::default();
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+
+    {
+        let store = ValidatorStore::new(&path).unwrap();
+        let perpetual_tables = &store.fullnode_store.perpetual_tables;
+
+        assert_eq!(
+            migration::schema_version(perpetual_tables).unwrap(),
+            SCHEMA_VERSION
+        );
+
+        store
+            .insert_certificate_delivered(&certificates[0])
+            .await
+            .unwrap();
+
+        // Written before the schema was versioned: an unverified proof left for a delivered
+        // certificate and a certificate in the precedence pool without timestamp nor index
+        perpetual_tables
+            .unverified
+            .insert(
+                &certificates[0].certificate.id,
+                &certificates[0].proof_of_delivery,
+            )
+            .unwrap();
+        store
+            .pending_tables
+            .precedence_pool
+            .insert(
+                &certificates[1].certificate.id,
+                &certificates[2].certificate,
+            )
+            .unwrap();
+        perpetual_tables
+            .schema
+            .delete(&SCHEMA_VERSION_KEY.to_string())
+            .unwrap();
+    }
+
+    let report = migrate(&path, true).unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!((report.from, report.to), (0, SCHEMA_VERSION));
+    assert_eq!(
+        report
+            .steps
+            .iter()
+            .map(|step| (step.version, step.changes))
+            .collect::<Vec<_>>(),
+        vec![(1, 1), (2, 1), (3, 0), (4, 1)]
+    );
+
+    // A dry run doesn't change the database
+    assert_eq!(migrate(&path, true).unwrap(), report);
+
+    let report = migrate(&path, false).unwrap();
+
+    assert!(!report.dry_run);
+    assert_eq!((report.from, report.to), (0, SCHEMA_VERSION));
+    assert_eq!(
+        report
+            .steps
+            .iter()
+            .map(|step| step.changes)
+            .collect::<Vec<_>>(),
+        vec![1, 1, 0, 1]
+    );
+
+    let report = migrate(&path, false).unwrap();
+
+    assert!(report.steps.is_empty());
+    assert_eq!((report.from, report.to), (SCHEMA_VERSION, SCHEMA_VERSION));
+
+    let store = ValidatorStore::new(&path).unwrap();
+    assert!(store
+        .get_unverified_proof(&certificates[0].certificate.id)
+        .unwrap()
+        .is_none());
+    assert!(store
+        .pending_tables
+        .precedence_pool_timestamps
+        .get(&certificates[1].certificate.id)
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        store
+            .get_awaiting_precedence(&certificates[2].certificate.id)
+            .unwrap(),
+        Some(certificates[2].certificate.clone())
+    );
+}
+
+#[test(tokio::test)]
+async fn newer_schema_is_refused() {
+    let path = create_folder::default();
+
+    {
+        let store = ValidatorStore::new(&path).unwrap();
+        store
+            .fullnode_store
+            .perpetual_tables
+            .schema
+            .insert(&SCHEMA_VERSION_KEY.to_string(), &(SCHEMA_VERSION + 1))
+            .unwrap();
+    }
+
+    assert!(matches!(
+        ValidatorStore::new(&path),
+        Err(StorageError::InternalStorage(
+            InternalStorageError::UnsupportedSchemaVersion(version)
+        )) if version == SCHEMA_VERSION + 1
+    ));
+}
+
+#[test(tokio::test)]
+async fn checkpoints_without_target_heads_are_migrated() {
+    let path = create_folder::default();
+    let signer = MessageSigner::new(&[1u8; 32]).unwrap();
+
+    let checkpoint = CheckpointSummary::new(
+        0,
+        0,
+        vec![SourceHead {
+            certificate_id: CERTIFICATE_ID_1,
+            subnet_id: SOURCE_SUBNET_ID_1,
+            position: 0.into(),
+        }],
+        Vec::new(),
+    );
+    let quorum = ValidatorQuorumSignatureInfo {
+        epoch: 0,
+        signatures: vec![(
+            signer.public_address.into(),
+            signer.sign_message(&checkpoint.payload()).unwrap(),
+        )],
+    };
+
+    {
+        let store = ValidatorStore::new(&path).unwrap();
+        let perpetual_tables = &store.fullnode_store.perpetual_tables;
+
+        // Layout of an epoch summary written before the target stream heads were checkpointed
+        let legacy_summary = (
+            0u64,
+            (
+                (0u64, 0usize, checkpoint.checkpoint_data.clone()),
+                quorum.clone(),
+            ),
+            None::<()>,
+        );
+        perpetual_tables
+            .epoch_chain
+            .retype::<EpochId, _>()
+            .insert(&0, &legacy_summary)
+            .unwrap();
+        perpetual_tables
+            .schema
+            .insert(&SCHEMA_VERSION_KEY.to_string(), &2)
+            .unwrap();
+    }
+
+    let report = migrate(&path, false).unwrap();
+
+    assert_eq!(
+        report
+            .steps
+            .iter()
+            .map(|step| (step.version, step.changes))
+            .collect::<Vec<_>>(),
+        vec![(3, 2), (4, 0)]
+    );
+
+    let store = ValidatorStore::new(&path).unwrap();
+    let fullnode_store = store.fullnode_store();
+    let expected = VerifiedCheckpointSummary(checkpoint, quorum);
+
+    let summary = fullnode_store.get_epoch_summary(0).unwrap().unwrap();
+    assert_eq!(summary.start_checkpoint, expected);
+    assert_eq!(
+        fullnode_store.get_latest_signed_checkpoint().unwrap(),
+        Some(expected.clone())
+    );
+
+    // The signatures still cover the migrated checkpoint
+    assert!(expected
+        .verify(&[signer.public_address.into()].into_iter().collect(), 1)
+        .is_ok());
+}
+
+#[test(tokio::test)]
+async fn dry_run_does_not_create_anything() {
+    let path = create_folder::default();
+    let perpetual_path = path.join("perpetual");
+
+    // Written before the schema was versioned, with only some of the current tables
+    init_with_cfs(
+        &perpetual_path,
+        default_options(),
+        vec![ColumnFamilyDescriptor::new(
+            cfs::CERTIFICATES,
+            default_options(),
+        )],
+    )
+    .unwrap();
+
+    let report = migrate(&path, true).unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!((report.from, report.to), (0, SCHEMA_VERSION));
+    assert_eq!(
+        rocksdb::DB::list_cf(&rocksdb::Options::default(), &perpetual_path).unwrap(),
+        vec!["default".to_string(), cfs::CERTIFICATES.to_string()]
+    );
+    assert!(!path.join("pending").exists());
+    assert!(!path.join("epochs").exists());
+}
+
+#[test(tokio::test)]
+async fn migration_of_an_opened_database_fails() {
+    let path = create_folder::default();
+    let _store = ValidatorStore::new(&path).unwrap();
+
+    assert!(matches!(
+        migrate(&path, false),
+        Err(StorageError::InternalStorage(
+            InternalStorageError::RocksDBError(_)
+        ))
+    ));
+
+    // Opened for reading only, a dry run doesn't need the database to be closed
+    let report = migrate(&path, true).unwrap();
+    assert_eq!(report.from, SCHEMA_VERSION);
+    assert!(report.steps.is_empty());
+}
@@ -23,9 +23,15 @@ use topos_test_sdk::constants::*;
 
 mod checkpoints;
 mod db_columns;
+mod epoch;
+mod integrity;
+mod memory;
+mod migration;
 mod pending_certificates;
 mod position;
+mod retention;
 mod rocks;
+mod snapshot;
 pub(crate) mod support;
 
 const SOURCE_STORAGE_SUBNET_ID: SubnetId = SOURCE_SUBNET_ID_1;
@@ -268,6 +274,39 @@ async fn delivered_certificate_are_added_to_target_stream(store: Arc<ValidatorSt
         .unwrap();
 
     assert_eq!(stream_element.0.position, Position::ZERO);
+
+    let positions = store
+        .fullnode_store
+        .get_target_positions(&certificate)
+        .unwrap();
+
+    assert_eq!(*positions[&TARGET_STORAGE_SUBNET_ID_1].position, 1);
+    assert_eq!(
+        positions[&TARGET_STORAGE_SUBNET_ID_2].position,
+        Position::ZERO
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn target_positions_are_found_in_long_streams(store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 17);
+
+    for certificate in &certificates {
+        store
+            .insert_certificate_delivered(certificate)
+            .await
+            .unwrap();
+    }
+
+    for (position, certificate) in certificates.iter().enumerate() {
+        let positions = store
+            .fullnode_store
+            .get_target_positions(certificate)
+            .unwrap();
+
+        assert_eq!(*positions[&TARGET_SUBNET_ID_1].position, position as u64);
+    }
 }
 
 #[rstest]
@@ -1,14 +1,23 @@
 use std::{sync::Arc, time::Duration};
 
 use rstest::rstest;
-use topos_core::uci::{Certificate, INITIAL_CERTIFICATE_ID};
+use topos_core::{
+    types::ValidatorId,
+    uci::{Certificate, INITIAL_CERTIFICATE_ID},
+};
 use topos_test_sdk::{
     certificates::{create_certificate_at_position, create_certificate_chain},
     constants::{SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
 };
 
 use super::support::store;
-use crate::{store::WriteStore, validator::ValidatorStore};
+use crate::{
+    store::WriteStore,
+    types::{
+        BroadcastState, CheckpointSummary, ValidatorQuorumSignatureInfo, VerifiedCheckpointSummary,
+    },
+    validator::ValidatorStore,
+};
 
 #[rstest]
 #[tokio::test]
@@ -212,3 +221,143 @@ mod concurrency {
         ));
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn evicting_pending_certificates(store: Arc<ValidatorStore>) {
+    let pending = &Certificate::new_with_default_fields(
+        INITIAL_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let awaiting_precedence = &Certificate::new_with_default_fields(
+        pending.id,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let awaiting_descendant = &Certificate::new_with_default_fields(
+        awaiting_precedence.id,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+
+    let pending_id = store
+        .insert_pending_certificate(pending)
+        .await
+        .unwrap()
+        .unwrap();
+    for certificate in [awaiting_precedence, awaiting_descendant] {
+        assert!(store
+            .insert_pending_certificate(certificate)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    assert_eq!(
+        store
+            .get_awaiting_precedence(&awaiting_precedence.id)
+            .unwrap(),
+        Some(awaiting_precedence.clone())
+    );
+
+    // The descendants waiting for the evicted certificate are evicted along
+    assert_eq!(
+        store
+            .evict_pending_certificate(&awaiting_precedence.id)
+            .await
+            .unwrap(),
+        vec![awaiting_precedence.clone(), awaiting_descendant.clone()]
+    );
+    assert!(store
+        .check_precedence(&awaiting_precedence.prev_id)
+        .unwrap()
+        .is_none());
+    assert!(store
+        .get_awaiting_precedence(&awaiting_descendant.id)
+        .unwrap()
+        .is_none());
+    assert!(store.get_pending_id(&pending.id).unwrap().is_some());
+
+    assert!(store
+        .insert_pending_certificate(awaiting_precedence)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        store.evict_pending_certificate(&pending.id).await.unwrap(),
+        vec![pending.clone(), awaiting_precedence.clone()]
+    );
+    assert!(store.get_pending_id(&pending.id).unwrap().is_none());
+    assert!(store
+        .get_pending_certificate(&pending_id)
+        .unwrap()
+        .is_none());
+    assert!(store
+        .get_awaiting_precedence(&awaiting_precedence.id)
+        .unwrap()
+        .is_none());
+
+    assert!(store
+        .evict_pending_certificate(&pending.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[rstest]
+#[tokio::test]
+async fn broadcast_states_live_as_long_as_the_pending_certificate(store: Arc<ValidatorStore>) {
+    let certificate = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+        .pop()
+        .unwrap();
+    let certificate_id = certificate.certificate.id;
+    let state = BroadcastState {
+        echoes: vec![ValidatorId::default()],
+        readies: Vec::new(),
+    };
+
+    // Nothing is checkpointed for a certificate which isn't pending
+    store
+        .insert_broadcast_state(&certificate_id, &state)
+        .unwrap();
+    assert!(store
+        .get_broadcast_state(&certificate_id)
+        .unwrap()
+        .is_none());
+
+    store
+        .insert_pending_certificate(&certificate.certificate)
+        .await
+        .unwrap();
+    store
+        .insert_broadcast_state(&certificate_id, &state)
+        .unwrap();
+
+    // The state survives the switch to the next epoch
+    store
+        .fullnode_store()
+        .insert_signed_checkpoint(VerifiedCheckpointSummary(
+            CheckpointSummary::new(1, 0, Vec::new(), Vec::new()),
+            ValidatorQuorumSignatureInfo {
+                epoch: 1,
+                signatures: Vec::new(),
+            },
+        ))
+        .unwrap();
+    assert_eq!(
+        store.get_broadcast_state(&certificate_id).unwrap(),
+        Some(state)
+    );
+
+    store
+        .insert_certificate_delivered(&certificate)
+        .await
+        .unwrap();
+    assert!(store
+        .get_broadcast_state(&certificate_id)
+        .unwrap()
+        .is_none());
+}
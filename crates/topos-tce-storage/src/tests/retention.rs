@@ -0,0 +1,236 @@
+use std::{fs, sync::Arc, time::Duration};
+
+use rstest::rstest;
+use test_log::test;
+use topos_core::types::stream::{
+    CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position,
+};
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1},
+    storage::create_folder,
+};
+
+use super::support::store;
+use crate::{
+    epoch::{EpochValidatorsStore, ValidatorPerEpochStore},
+    errors::SnapshotError,
+    fullnode::FullNodeStore,
+    retention::{PruningSummary, RetentionPolicy},
+    store::{ReadStore, WriteStore},
+    validator::ValidatorStore,
+};
+
+#[rstest]
+#[test(tokio::test)]
+async fn expired_precedence_certificates_are_evicted(store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    assert!(store
+        .insert_pending_certificate(&certificates[1].certificate)
+        .await
+        .unwrap()
+        .is_none());
+
+    let summary = store
+        .prune(&RetentionPolicy::default().with_precedence_pool_ttl(Duration::from_secs(3600)))
+        .await
+        .unwrap();
+
+    assert_eq!(summary, PruningSummary::default());
+    assert_eq!(store.precedence_pool_size().unwrap(), 1);
+
+    let summary = store
+        .prune(&RetentionPolicy::default().with_precedence_pool_ttl(Duration::ZERO))
+        .await
+        .unwrap();
+
+    assert_eq!(summary.precedence_pool_evicted, 1);
+    assert_eq!(store.precedence_pool_size().unwrap(), 0);
+    assert!(store
+        .check_precedence(&certificates[0].certificate.id)
+        .unwrap()
+        .is_none());
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn unverified_proofs_are_removed_once_delivered(store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    store
+        .insert_unverified_proofs(
+            certificates
+                .iter()
+                .map(|delivered| delivered.proof_of_delivery.clone())
+                .collect(),
+        )
+        .unwrap();
+
+    store
+        .insert_certificate_delivered(&certificates[0])
+        .await
+        .unwrap();
+
+    assert!(store
+        .get_unverified_proof(&certificates[0].certificate.id)
+        .unwrap()
+        .is_none());
+    assert!(store
+        .get_unverified_proof(&certificates[1].certificate.id)
+        .unwrap()
+        .is_some());
+
+    // An unverified proof received after the delivery is removed by the pruning
+    store
+        .insert_unverified_proofs(vec![certificates[0].proof_of_delivery.clone()])
+        .unwrap();
+
+    let summary = store.prune(&RetentionPolicy::default()).await.unwrap();
+
+    assert_eq!(summary.unverified_proofs_removed, 1);
+    assert!(store
+        .get_unverified_proof(&certificates[0].certificate.id)
+        .unwrap()
+        .is_none());
+    assert!(store
+        .get_unverified_proof(&certificates[1].certificate.id)
+        .unwrap()
+        .is_some());
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn old_proofs_are_pruned(store: Arc<ValidatorStore>) {
+    for subnet_id in [SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2] {
+        let mut certificates = create_certificate_chain(subnet_id, &[TARGET_SUBNET_ID_1], 5);
+        for delivered in &mut certificates {
+            delivered.certificate.proof = vec![1; 32];
+        }
+
+        store
+            .insert_certificates_delivered(&certificates)
+            .await
+            .unwrap();
+    }
+
+    let policy = RetentionPolicy::default().with_proofs_per_source_stream(2);
+
+    assert_eq!(store.prune(&policy).await.unwrap().proofs_pruned, 6);
+    assert_eq!(store.prune(&policy).await.unwrap().proofs_pruned, 0);
+
+    for subnet_id in [SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2] {
+        // The certificates whose proof is pruned are skipped
+        let source_stream = store
+            .get_source_stream_certificates_from_position(
+                CertificateSourceStreamPosition::new(subnet_id, 0),
+                10,
+            )
+            .unwrap();
+
+        assert_eq!(source_stream.len(), 2);
+        for (delivered, _) in source_stream {
+            assert!(!delivered.certificate.proof.is_empty());
+        }
+    }
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn streams_are_paged_across_pruned_proofs(store: Arc<ValidatorStore>) {
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 5);
+    for delivered in &mut certificates {
+        delivered.certificate.proof = vec![1; 32];
+    }
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    store
+        .prune(&RetentionPolicy::default().with_proofs_per_source_stream(3))
+        .await
+        .unwrap();
+
+    // Each page starts after the last returned position, the pruned ones leaving a gap
+    let mut source_positions = Vec::new();
+    let mut position = 0;
+    loop {
+        let page = store
+            .get_source_stream_certificates_from_position(
+                CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, position),
+                2,
+            )
+            .unwrap();
+        let Some((_, last)) = page.last() else {
+            break;
+        };
+        position = *last.position + 1;
+        source_positions.extend(page.iter().map(|(_, position)| *position.position));
+    }
+    assert_eq!(source_positions, vec![2, 3, 4]);
+
+    let target_stream = store
+        .get_target_stream_certificates_from_position(
+            CertificateTargetStreamPosition::new(TARGET_SUBNET_ID_1, SOURCE_SUBNET_ID_1, 0),
+            2,
+        )
+        .unwrap();
+    let target_positions: Vec<_> = target_stream
+        .iter()
+        .map(|(_, position)| *position.position)
+        .collect();
+    assert_eq!(target_positions, vec![2, 3]);
+    assert_eq!(
+        target_stream[0].0.certificate.id,
+        certificates[2].certificate.id
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn pruned_store_is_tagged(store: Arc<ValidatorStore>) {
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+    for delivered in &mut certificates {
+        delivered.certificate.proof = vec![1; 32];
+    }
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    let fullnode_store = store.fullnode_store();
+    assert!(!fullnode_store.is_pruned().unwrap());
+
+    store
+        .prune(&RetentionPolicy::default().with_proofs_per_source_stream(1))
+        .await
+        .unwrap();
+
+    // The pruning state is persisted along with the certificates
+    let reopened = FullNodeStore::open(
+        ValidatorPerEpochStore::new_in_memory(0).unwrap(),
+        EpochValidatorsStore::new_in_memory().unwrap(),
+        fullnode_store.perpetual_tables.clone(),
+        fullnode_store.index_tables.clone(),
+    )
+    .unwrap();
+
+    assert!(reopened.is_pruned().unwrap());
+    assert_eq!(
+        reopened.proofs_pruned_until(&SOURCE_SUBNET_ID_1).unwrap(),
+        Position::from(2)
+    );
+    assert!(reopened.check_proof_kept(&certificates[1]).is_err());
+    assert!(reopened.check_proof_kept(&certificates[2]).is_ok());
+    assert_eq!(reopened.prune_proofs(1).unwrap(), 0);
+
+    let folder = create_folder::default();
+    fs::create_dir_all(&folder).unwrap();
+    assert!(matches!(
+        reopened
+            .export_snapshot(&folder.join("certificates.snapshot"))
+            .await,
+        Err(SnapshotError::StorePruned)
+    ));
+}
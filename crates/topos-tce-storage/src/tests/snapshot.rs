@@ -0,0 +1,281 @@
+use rstest::rstest;
+use std::{collections::HashSet, fs, io::Write, sync::Arc};
+use test_log::test;
+use topos_core::types::{
+    stream::{CertificateTargetStreamPosition, Position},
+    ValidatorId,
+};
+use topos_test_sdk::certificates::create_certificate_chain;
+use topos_test_sdk::constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1};
+use topos_test_sdk::storage::create_folder;
+
+use crate::{
+    epoch::{EpochValidatorsStore, ValidatorPerEpochStore},
+    errors::{InternalStorageError, SnapshotError, StorageError},
+    fullnode::FullNodeStore,
+    snapshot::{SnapshotChunk, SnapshotWriter, MAX_SNAPSHOT_RECORD_SIZE, SNAPSHOT_VERSION},
+    store::{ReadStore, WriteStore},
+    validator::ValidatorStore,
+};
+
+use super::support::store;
+
+#[rstest]
+#[test(tokio::test)]
+async fn snapshot_can_be_exported_and_imported(store: Arc<ValidatorStore>) {
+    let folder = create_folder::default();
+    fs::create_dir_all(&folder).unwrap();
+    let path = folder.join("certificates.snapshot");
+
+    for subnet_id in [SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2] {
+        store
+            .insert_certificates_delivered(&create_certificate_chain(
+                subnet_id,
+                &[TARGET_SUBNET_ID_1],
+                3,
+            ))
+            .await
+            .unwrap();
+    }
+
+    let exported = store.fullnode_store().export_snapshot(&path).await.unwrap();
+
+    assert_eq!(exported.certificates, 6);
+    assert_eq!(exported.source_heads.len(), 2);
+    assert!(!path.with_extension("checkpoint").exists());
+
+    let imported_store = FullNodeStore::new_in_memory().unwrap();
+    let imported = imported_store
+        .import_snapshot(&path, &[(HashSet::new(), 0)])
+        .await
+        .unwrap();
+
+    assert_eq!(imported, exported);
+    assert_eq!(imported_store.count_certificates_delivered().unwrap(), 6);
+    for head in &exported.source_heads {
+        assert_eq!(
+            imported_store
+                .get_source_head(&head.subnet_id)
+                .unwrap()
+                .as_ref(),
+            Some(head)
+        );
+    }
+
+    let target_stream = |store: &dyn ReadStore| {
+        store
+            .get_target_stream_certificates_from_position(
+                CertificateTargetStreamPosition::new(
+                    TARGET_SUBNET_ID_1,
+                    SOURCE_SUBNET_ID_2,
+                    Position::ZERO,
+                ),
+                10,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|(certificate, _)| certificate)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(target_stream(&*imported_store), target_stream(&*store));
+
+    assert!(matches!(
+        imported_store
+            .import_snapshot(&path, &[(HashSet::new(), 0)])
+            .await,
+        Err(SnapshotError::StoreNotEmpty)
+    ));
+}
+
+/// Export the snapshot of a chain of `certificates` certificates delivered from the source subnet
+/// 1 to the target subnet 1
+async fn export_chain(certificates: usize) -> std::path::PathBuf {
+    let folder = create_folder::default();
+    fs::create_dir_all(&folder).unwrap();
+    let path = folder.join("certificates.snapshot");
+
+    let store = store::default();
+    store
+        .insert_certificates_delivered(&create_certificate_chain(
+            SOURCE_SUBNET_ID_1,
+            &[TARGET_SUBNET_ID_1],
+            certificates,
+        ))
+        .await
+        .unwrap();
+    store.fullnode_store().export_snapshot(&path).await.unwrap();
+
+    path
+}
+
+#[test(tokio::test)]
+async fn tampered_snapshot_is_rejected() {
+    let path = export_chain(2).await;
+
+    let mut content = fs::read(&path).unwrap();
+    let last = content.len() - 1;
+    content[last] ^= 0xff;
+    fs::write(&path, &content).unwrap();
+
+    assert!(matches!(
+        FullNodeStore::new_in_memory()
+            .unwrap()
+            .import_snapshot(&path, &[(HashSet::new(), 0)])
+            .await,
+        Err(SnapshotError::ChecksumMismatch)
+    ));
+
+    fs::write(&path, &content[..content.len() / 2]).unwrap();
+
+    assert!(matches!(
+        FullNodeStore::new_in_memory()
+            .unwrap()
+            .import_snapshot(&path, &[(HashSet::new(), 0)])
+            .await,
+        Err(SnapshotError::Truncated)
+    ));
+}
+
+#[test(tokio::test)]
+async fn oversized_snapshot_record_is_rejected() {
+    let path = export_chain(1).await;
+
+    // Header followed by a record whose data is larger than the limit
+    let mut content = vec![SNAPSHOT_VERSION as u8];
+    content.extend([0; 32]);
+    content.push(0xfd);
+    content.extend((MAX_SNAPSHOT_RECORD_SIZE + 1).to_le_bytes());
+    let file = fs::File::create(&path).unwrap();
+    (&file).write_all(&content).unwrap();
+    file.set_len(content.len() as u64 + MAX_SNAPSHOT_RECORD_SIZE + 1)
+        .unwrap();
+
+    assert!(matches!(
+        FullNodeStore::new_in_memory()
+            .unwrap()
+            .import_snapshot(&path, &[(HashSet::new(), 0)])
+            .await,
+        Err(SnapshotError::Bincode(error)) if matches!(*error, bincode::ErrorKind::SizeLimit)
+    ));
+}
+
+#[test(tokio::test)]
+async fn proofs_are_verified_against_every_epoch() {
+    let path = export_chain(2).await;
+    let genesis = (HashSet::from([ValidatorId::default()]), 1);
+
+    assert!(matches!(
+        FullNodeStore::new_in_memory()
+            .unwrap()
+            .import_snapshot(&path, &[genesis.clone()])
+            .await,
+        Err(SnapshotError::InvalidProofOfDelivery(..))
+    ));
+
+    let imported = FullNodeStore::new_in_memory()
+        .unwrap()
+        .import_snapshot(&path, &[genesis, (HashSet::new(), 0)])
+        .await
+        .unwrap();
+
+    assert_eq!(imported.certificates, 2);
+}
+
+#[test(tokio::test)]
+async fn inconsistent_snapshot_is_rejected() {
+    let folder = create_folder::default();
+    fs::create_dir_all(&folder).unwrap();
+    let path = folder.join("certificates.snapshot");
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+    let import = |chunks: Vec<SnapshotChunk>| {
+        let path = path.clone();
+        async move {
+            let mut writer = SnapshotWriter::create(&path).unwrap();
+            for chunk in &chunks {
+                writer.write_chunk(chunk).unwrap();
+            }
+            writer.finish().unwrap();
+
+            FullNodeStore::new_in_memory()
+                .unwrap()
+                .import_snapshot(&path, &[(HashSet::new(), 0)])
+                .await
+        }
+    };
+
+    let mut missing_certificate = certificates.clone();
+    missing_certificate.remove(1);
+    assert!(matches!(
+        import(vec![SnapshotChunk::Certificates(missing_certificate)]).await,
+        Err(SnapshotError::Inconsistent(_))
+    ));
+
+    // The target stream and the target source list are missing
+    assert!(matches!(
+        import(vec![SnapshotChunk::Certificates(certificates.clone())]).await,
+        Err(SnapshotError::Inconsistent(_))
+    ));
+
+    let target_stream = certificates
+        .iter()
+        .enumerate()
+        .map(|(position, delivered)| {
+            (
+                CertificateTargetStreamPosition::new(
+                    TARGET_SUBNET_ID_1,
+                    SOURCE_SUBNET_ID_1,
+                    position as u64,
+                ),
+                delivered.certificate.id,
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut swapped_target_stream = target_stream.clone();
+    swapped_target_stream[0].1 = target_stream[1].1;
+    swapped_target_stream[1].1 = target_stream[0].1;
+
+    assert!(matches!(
+        import(vec![
+            SnapshotChunk::Certificates(certificates.clone()),
+            SnapshotChunk::TargetStreams(swapped_target_stream),
+        ])
+        .await,
+        Err(SnapshotError::Inconsistent(_))
+    ));
+
+    assert!(import(vec![
+        SnapshotChunk::Certificates(certificates),
+        SnapshotChunk::TargetStreams(target_stream),
+        SnapshotChunk::TargetSourceList(vec![(
+            TARGET_SUBNET_ID_1,
+            SOURCE_SUBNET_ID_1,
+            Position::from(2)
+        )]),
+    ])
+    .await
+    .is_ok());
+}
+
+#[test(tokio::test)]
+async fn store_with_a_failed_import_is_refused() {
+    let path = export_chain(2).await;
+    let store = FullNodeStore::new_in_memory().unwrap();
+
+    assert!(store
+        .import_snapshot(&path, &[(HashSet::from([ValidatorId::default()]), 1)])
+        .await
+        .is_err());
+
+    assert!(matches!(
+        FullNodeStore::open(
+            ValidatorPerEpochStore::new_in_memory(0).unwrap(),
+            EpochValidatorsStore::new_in_memory().unwrap(),
+            store.perpetual_tables.clone(),
+            store.index_tables.clone(),
+        ),
+        Err(StorageError::InternalStorage(
+            InternalStorageError::InterruptedSnapshotImport
+        ))
+    ));
+}
@@ -1,6 +1,7 @@
+use rocksdb::{ColumnFamilyDescriptor, Options};
 use rstest::fixture;
 
-use crate::rocks::{constants, db_column::DBColumn};
+use crate::rocks::{constants, db::default_options, db_column::DBColumn};
 use crate::types::{
     CertificatesColumn, PendingCertificatesColumn, StreamsColumn, TargetSourceListColumn,
     TargetStreamsColumn,
@@ -9,27 +10,54 @@ use crate::types::{
 use super::database_name;
 use super::rocks_db;
 
+const PENDING_CERTIFICATES: &str = "PENDING_CERTIFICATES";
+const CERTIFICATES: &str = "CERTIFICATES";
+const SOURCE_STREAMS: &str = "SOURCE_STREAMS";
+const TARGET_STREAMS: &str = "TARGET_STREAMS";
+const TARGET_SOURCES: &str = "TARGET_SOURCES";
+
+/// Column families of the database used by the column fixtures
+pub(crate) fn column_families() -> Vec<ColumnFamilyDescriptor> {
+    let mut options_source = default_options();
+    options_source.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
+        constants::SOURCE_STREAMS_PREFIX_SIZE,
+    ));
+
+    let mut options_target = default_options();
+    options_target.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
+        constants::TARGET_STREAMS_PREFIX_SIZE,
+    ));
+
+    vec![
+        ColumnFamilyDescriptor::new(PENDING_CERTIFICATES, default_options()),
+        ColumnFamilyDescriptor::new(CERTIFICATES, Options::default()),
+        ColumnFamilyDescriptor::new(SOURCE_STREAMS, options_source),
+        ColumnFamilyDescriptor::new(TARGET_STREAMS, options_target),
+        ColumnFamilyDescriptor::new(TARGET_SOURCES, default_options()),
+    ]
+}
+
 #[fixture]
 pub(crate) fn pending_column(database_name: &'static str) -> PendingCertificatesColumn {
-    DBColumn::reopen(&rocks_db(database_name), constants::PENDING_CERTIFICATES)
+    DBColumn::reopen(&rocks_db(database_name), PENDING_CERTIFICATES)
 }
 
 #[fixture]
 pub(crate) fn certificates_column(database_name: &'static str) -> CertificatesColumn {
-    DBColumn::reopen(&rocks_db(database_name), constants::CERTIFICATES)
+    DBColumn::reopen(&rocks_db(database_name), CERTIFICATES)
 }
 
 #[fixture]
 pub(crate) fn source_streams_column(database_name: &'static str) -> StreamsColumn {
-    DBColumn::reopen(&rocks_db(database_name), constants::SOURCE_STREAMS)
+    DBColumn::reopen(&rocks_db(database_name), SOURCE_STREAMS)
 }
 
 #[fixture]
 pub(crate) fn target_streams_column(database_name: &'static str) -> TargetStreamsColumn {
-    DBColumn::reopen(&rocks_db(database_name), constants::TARGET_STREAMS)
+    DBColumn::reopen(&rocks_db(database_name), TARGET_STREAMS)
 }
 
 #[fixture]
 pub(crate) fn target_source_list_column(database_name: &'static str) -> TargetSourceListColumn {
-    DBColumn::reopen(&rocks_db(database_name), constants::TARGET_SOURCES)
+    DBColumn::reopen(&rocks_db(database_name), TARGET_SOURCES)
 }
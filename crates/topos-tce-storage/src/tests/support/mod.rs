@@ -15,7 +15,7 @@ use crate::{
     epoch::{EpochValidatorsStore, ValidatorPerEpochStore},
     fullnode::FullNodeStore,
     index::IndexTables,
-    rocks::{db::init_db, db::RocksDB},
+    rocks::{db::init_with_cfs, db::RocksDB},
     validator::{ValidatorPerpetualTables, ValidatorStore},
 };
 
@@ -40,8 +40,8 @@ pub(crate) fn database_name() -> &'static str {
 #[fixture]
 pub(crate) fn store() -> Arc<ValidatorStore> {
     let temp_dir = create_folder::default();
-    let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(&temp_dir));
-    let index_tables = Arc::new(IndexTables::open(&temp_dir));
+    let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(&temp_dir).unwrap());
+    let index_tables = Arc::new(IndexTables::open(&temp_dir).unwrap());
 
     let participants_store =
         EpochValidatorsStore::new(&temp_dir).expect("Unable to create Participant store");
@@ -60,6 +60,11 @@ pub(crate) fn store() -> Arc<ValidatorStore> {
     ValidatorStore::open(&temp_dir, store).unwrap()
 }
 
+#[fixture]
+pub(crate) fn memory_store() -> Arc<ValidatorStore> {
+    ValidatorStore::new_in_memory().expect("Unable to create in-memory validator store")
+}
+
 #[fixture]
 pub(crate) fn rocks_db(database_name: &'static str) -> Arc<RocksDB> {
     let mut dbs = DB.lock().unwrap();
@@ -72,7 +77,7 @@ pub(crate) fn rocks_db(database_name: &'static str) -> Arc<RocksDB> {
             options.create_if_missing(true);
             options.create_missing_column_families(true);
 
-            Arc::new(init_db(&path, options).unwrap())
+            Arc::new(init_with_cfs(&path, options, columns::column_families()).unwrap())
         })
         .clone()
 }
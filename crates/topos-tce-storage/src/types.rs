@@ -1,22 +1,29 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
 use topos_core::{
-    api::grpc::checkpoints::SourceStreamPosition,
+    api::grpc::{
+        checkpoints::{SourceStreamPosition, TargetStreamPosition},
+        tce::v1::{
+            CheckpointSummary as GrpcCheckpointSummary, SignedCheckpoint, ValidatorSignature,
+        },
+    },
+    errors::GrpcParsingError,
     types::{
         stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position},
-        CertificateDelivered, Ready, Signature,
+        CertificateDelivered, Signature, ValidatorId,
     },
     uci::{Certificate, CertificateId},
 };
 
 use crate::{
     rocks::{db_column::DBColumn, TargetSourceListKey},
-    CertificatePositions, PendingCertificateId,
+    CertificatePositions, PendingCertificateId, SourceHead, TargetHead,
 };
 
-pub type Echo = String;
-
 pub type CertificateSequenceNumber = u64;
 pub type EpochId = u64;
-pub type Validators = Vec<String>;
+pub type Validators = HashSet<ValidatorId>;
 
 /// Column that keeps certificates that are not yet delivered
 pub(crate) type PendingCertificatesColumn = DBColumn<u64, Certificate>;
@@ -43,31 +50,288 @@ pub enum PendingResult {
 #[derive(Debug, Clone)]
 pub struct CertificateDeliveredWithPositions(pub CertificateDelivered, pub CertificatePositions);
 
-#[allow(unused)]
+/// Summary of an epoch, bounded by the checkpoints taken when it started and when it ended
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct EpochSummary {
-    epoch_id: EpochId,
-    start_checkpoint: VerifiedCheckpointSummary,
-    end_checkpoint: Option<VerifiedCheckpointSummary>,
+    pub epoch_id: EpochId,
+    pub start_checkpoint: VerifiedCheckpointSummary,
+    /// Not defined as long as the epoch is the current one
+    pub end_checkpoint: Option<VerifiedCheckpointSummary>,
 }
 
-#[allow(unused)]
+/// Heads of every source and target stream known at some point of an epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CheckpointSummary {
-    epoch: EpochId,
-    sequence_number: usize,
-    checkpoint_data: Vec<SourceStreamPosition>,
+    pub epoch: EpochId,
+    pub sequence_number: usize,
+    /// Source stream heads, ordered by subnet id
+    pub checkpoint_data: Vec<SourceHead>,
+    /// Target stream heads, ordered by target and source subnet id
+    pub target_checkpoint_data: Vec<TargetHead>,
 }
-#[allow(unused)]
-pub struct VerifiedCheckpointSummary(CheckpointSummary, ValidatorQuorumSignatureInfo);
 
-#[allow(unused)]
+impl CheckpointSummary {
+    /// Creates a summary of the given source and target stream heads
+    pub fn new(
+        epoch: EpochId,
+        sequence_number: usize,
+        checkpoint: impl IntoIterator<Item = SourceHead>,
+        target_checkpoint: impl IntoIterator<Item = TargetHead>,
+    ) -> Self {
+        let mut checkpoint_data: Vec<SourceHead> = checkpoint.into_iter().collect();
+        checkpoint_data.sort_by(|a, b| a.subnet_id.as_array().cmp(b.subnet_id.as_array()));
+
+        let mut target_checkpoint_data: Vec<TargetHead> = target_checkpoint.into_iter().collect();
+        target_checkpoint_data.sort_by(|a, b| {
+            (a.target_subnet_id.as_array(), a.source_subnet_id.as_array())
+                .cmp(&(b.target_subnet_id.as_array(), b.source_subnet_id.as_array()))
+        });
+
+        Self {
+            epoch,
+            sequence_number,
+            checkpoint_data,
+            target_checkpoint_data,
+        }
+    }
+
+    /// Returns the payload signed by the validators to attest the summary
+    pub fn payload(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&self.epoch.to_be_bytes());
+        payload.extend_from_slice(&(self.sequence_number as u64).to_be_bytes());
+        for head in &self.checkpoint_data {
+            payload.extend_from_slice(head.subnet_id.as_array());
+            payload.extend_from_slice(head.certificate_id.as_array());
+            payload.extend_from_slice(&head.position.to_be_bytes());
+        }
+        for head in &self.target_checkpoint_data {
+            payload.extend_from_slice(head.target_subnet_id.as_array());
+            payload.extend_from_slice(head.source_subnet_id.as_array());
+            payload.extend_from_slice(head.certificate_id.as_array());
+            payload.extend_from_slice(&head.position.to_be_bytes());
+        }
+
+        payload
+    }
+}
+
+/// A [`CheckpointSummary`] along with the signatures attesting it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VerifiedCheckpointSummary(pub CheckpointSummary, pub ValidatorQuorumSignatureInfo);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ValidatorQuorumSignatureInfo {
-    epoch: EpochId,
-    signature: [u8; 32],
+    pub epoch: EpochId,
+    pub signatures: Vec<(ValidatorId, Signature)>,
+}
+
+/// Errors raised when a [`VerifiedCheckpointSummary`] isn't attested by a quorum of validators
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CheckpointSignatureError {
+    #[error("Signatures are given for epoch {signatures} instead of epoch {checkpoint}")]
+    EpochMismatch {
+        checkpoint: EpochId,
+        signatures: EpochId,
+    },
+
+    #[error("Checkpoint signed by an unknown validator: {0}")]
+    UnknownValidator(ValidatorId),
+
+    #[error("Signature from validator {0} appears more than once")]
+    DuplicatedSignature(ValidatorId),
+
+    #[error("Invalid checkpoint signature from validator {0}")]
+    InvalidSignature(ValidatorId),
+
+    #[error("Not enough signatures to reach the threshold: {received}/{threshold}")]
+    NotEnoughSignatures { received: usize, threshold: u64 },
+}
+
+impl VerifiedCheckpointSummary {
+    /// Verify that the checkpoint is signed by at least `threshold` of the given validators.
+    pub fn verify(
+        &self,
+        validators: &HashSet<ValidatorId>,
+        threshold: u64,
+    ) -> Result<(), CheckpointSignatureError> {
+        let Self(checkpoint, quorum) = self;
+
+        if checkpoint.epoch != quorum.epoch {
+            return Err(CheckpointSignatureError::EpochMismatch {
+                checkpoint: checkpoint.epoch,
+                signatures: quorum.epoch,
+            });
+        }
+
+        let payload = checkpoint.payload();
+        let mut signers = HashSet::with_capacity(quorum.signatures.len());
+        for (validator_id, signature) in &quorum.signatures {
+            if !validators.contains(validator_id) {
+                return Err(CheckpointSignatureError::UnknownValidator(*validator_id));
+            }
+
+            if !signers.insert(validator_id) {
+                return Err(CheckpointSignatureError::DuplicatedSignature(*validator_id));
+            }
+
+            signature
+                .verify(payload.clone(), validator_id.address())
+                .map_err(|_| CheckpointSignatureError::InvalidSignature(*validator_id))?;
+        }
+
+        if (signers.len() as u64) < threshold {
+            return Err(CheckpointSignatureError::NotEnoughSignatures {
+                received: signers.len(),
+                threshold,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl From<CheckpointSummary> for GrpcCheckpointSummary {
+    fn from(value: CheckpointSummary) -> Self {
+        Self {
+            epoch: value.epoch,
+            sequence_number: value.sequence_number as u64,
+            heads: value
+                .checkpoint_data
+                .into_iter()
+                .map(|head| {
+                    SourceStreamPosition {
+                        source_subnet_id: head.subnet_id,
+                        position: *head.position,
+                        certificate_id: Some(head.certificate_id),
+                    }
+                    .into()
+                })
+                .collect(),
+            target_heads: value
+                .target_checkpoint_data
+                .into_iter()
+                .map(|head| {
+                    TargetStreamPosition {
+                        target_subnet_id: head.target_subnet_id,
+                        source_subnet_id: head.source_subnet_id,
+                        position: *head.position,
+                        certificate_id: Some(head.certificate_id),
+                    }
+                    .into()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<GrpcCheckpointSummary> for CheckpointSummary {
+    type Error = GrpcParsingError;
+
+    fn try_from(value: GrpcCheckpointSummary) -> Result<Self, Self::Error> {
+        let heads = value
+            .heads
+            .into_iter()
+            .map(|head| {
+                let position: SourceStreamPosition = head.try_into()?;
+
+                Ok(SourceHead {
+                    certificate_id: position
+                        .certificate_id
+                        .ok_or(GrpcParsingError::GrpcMalformedType("heads.certificate_id"))?,
+                    subnet_id: position.source_subnet_id,
+                    position: position.position.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, GrpcParsingError>>()?;
+
+        let target_heads = value
+            .target_heads
+            .into_iter()
+            .map(|head| {
+                let position: TargetStreamPosition = head.try_into()?;
+
+                Ok(TargetHead {
+                    certificate_id: position.certificate_id.ok_or(
+                        GrpcParsingError::GrpcMalformedType("target_heads.certificate_id"),
+                    )?,
+                    target_subnet_id: position.target_subnet_id,
+                    source_subnet_id: position.source_subnet_id,
+                    position: position.position.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, GrpcParsingError>>()?;
+
+        Ok(Self::new(
+            value.epoch,
+            value.sequence_number as usize,
+            heads,
+            target_heads,
+        ))
+    }
+}
+
+impl From<VerifiedCheckpointSummary> for SignedCheckpoint {
+    fn from(value: VerifiedCheckpointSummary) -> Self {
+        let VerifiedCheckpointSummary(checkpoint, quorum) = value;
+
+        Self {
+            checkpoint: Some(checkpoint.into()),
+            signatures: quorum
+                .signatures
+                .into_iter()
+                .map(|(validator_id, signature)| ValidatorSignature {
+                    validator_id: Some(validator_id.into()),
+                    signature: Some(signature.into()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<SignedCheckpoint> for VerifiedCheckpointSummary {
+    type Error = GrpcParsingError;
+
+    fn try_from(value: SignedCheckpoint) -> Result<Self, Self::Error> {
+        let checkpoint: CheckpointSummary = value
+            .checkpoint
+            .ok_or(GrpcParsingError::GrpcMalformedType("checkpoint"))?
+            .try_into()?;
+
+        let signatures = value
+            .signatures
+            .into_iter()
+            .map(|v| {
+                let validator_id = v
+                    .validator_id
+                    .ok_or(GrpcParsingError::GrpcMalformedType(
+                        "signatures.validator_id",
+                    ))?
+                    .try_into()?;
+                let signature = v
+                    .signature
+                    .ok_or(GrpcParsingError::GrpcMalformedType("signatures.signature"))?
+                    .into();
+
+                Ok((validator_id, signature))
+            })
+            .collect::<Result<_, GrpcParsingError>>()?;
+
+        let epoch = checkpoint.epoch;
+
+        Ok(Self(
+            checkpoint,
+            ValidatorQuorumSignatureInfo { epoch, signatures },
+        ))
+    }
 }
 
-#[allow(unused)]
+/// Progress of the broadcast of a certificate, checkpointed to resume the broadcast after a
+/// restart
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BroadcastState {
-    echoes: Vec<(Echo, Signature)>,
-    readies: Vec<(Ready, Signature)>,
-    delivered: bool,
+    /// Validators from which an Echo has been received
+    pub echoes: Vec<ValidatorId>,
+    /// Signed Ready messages received from the validators
+    pub readies: Vec<(ValidatorId, Signature)>,
 }
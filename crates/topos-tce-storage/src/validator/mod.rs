@@ -18,6 +18,7 @@ use std::{
     collections::HashMap,
     path::Path,
     sync::{atomic::Ordering, Arc},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -34,10 +35,14 @@ use topos_metrics::{STORAGE_PENDING_POOL_COUNT, STORAGE_PRECEDENCE_POOL_COUNT};
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::{
+    epoch::EpochValidatorsStore,
     errors::{InternalStorageError, StorageError},
     fullnode::FullNodeStore,
+    migration,
+    retention::{PruningSummary, RetentionPolicy},
     rocks::map::Map,
     store::{ReadStore, WriteStore},
+    types::BroadcastState,
     CertificatePositions, CertificateTargetStreamPosition, PendingCertificateId, SourceHead,
 };
 
@@ -71,32 +76,51 @@ impl ValidatorStore {
         Self::open(path, fullnode_store)
     }
 
+    /// Try to create a new instance of [`ValidatorStore`] in memory, nothing being persisted
+    #[cfg(feature = "inmemory")]
+    pub fn new_in_memory() -> Result<Arc<Self>, StorageError> {
+        let fullnode_store = FullNodeStore::new_in_memory()?;
+
+        Self::open_in_memory(fullnode_store)
+    }
+
     /// Open a [`ValidatorStore`] at the given `path` and using the given [`FullNodeStore`]
     pub fn open(
         path: &Path,
         fullnode_store: Arc<FullNodeStore>,
     ) -> Result<Arc<Self>, StorageError> {
-        let pending_tables: ValidatorPendingTables = ValidatorPendingTables::open(path);
+        Self::with_pending_tables(ValidatorPendingTables::open(path)?, fullnode_store)
+    }
+
+    /// Open a [`ValidatorStore`] keeping its pending pools in memory and using the given
+    /// [`FullNodeStore`]
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory(fullnode_store: Arc<FullNodeStore>) -> Result<Arc<Self>, StorageError> {
+        Self::with_pending_tables(ValidatorPendingTables::open_in_memory(), fullnode_store)
+    }
+
+    fn with_pending_tables(
+        pending_tables: ValidatorPendingTables,
+        fullnode_store: Arc<FullNodeStore>,
+    ) -> Result<Arc<Self>, StorageError> {
+        let report = migration::run(
+            &fullnode_store.perpetual_tables,
+            &pending_tables,
+            &fullnode_store.epoch_store.load(),
+            false,
+        )?;
+        debug!(
+            "Storage schema at version {} (from version {})",
+            report.to, report.from
+        );
 
         let store = Arc::new(Self {
             pending_tables,
             fullnode_store,
         });
 
-        store.pending_tables.pending_pool.rocksdb.compact_range_cf(
-            &store.pending_tables.pending_pool.cf()?,
-            None::<&[u8]>,
-            None::<&[u8]>,
-        );
-        store
-            .pending_tables
-            .precedence_pool
-            .rocksdb
-            .compact_range_cf(
-                &store.pending_tables.precedence_pool.cf()?,
-                None::<&[u8]>,
-                None::<&[u8]>,
-            );
+        store.pending_tables.pending_pool.compact()?;
+        store.pending_tables.precedence_pool.compact()?;
 
         let pending_count: i64 = store.pending_pool_size()?.try_into().map_err(|error| {
             error!("Failed to convert estimate-num-keys to i64: {}", error);
@@ -123,6 +147,11 @@ impl ValidatorStore {
         self.fullnode_store.clone()
     }
 
+    /// Returns the [`EpochValidatorsStore`] holding the validator set of every epoch
+    pub fn epoch_validators_store(&self) -> Arc<EpochValidatorsStore> {
+        self.fullnode_store.epoch_validators_store()
+    }
+
     /// Returns the number of certificates in the pending pool
     pub fn pending_pool_size(&self) -> Result<u64, StorageError> {
         Ok(self
@@ -139,6 +168,37 @@ impl ValidatorStore {
             .property_int_value(ESTIMATE_NUM_KEYS)?)
     }
 
+    /// Checkpoint the progress of the broadcast of a pending certificate
+    ///
+    /// Nothing is written once the certificate left the pending pool, the state having been
+    /// removed alongside.
+    pub fn insert_broadcast_state(
+        &self,
+        certificate_id: &CertificateId,
+        state: &BroadcastState,
+    ) -> Result<(), StorageError> {
+        if self
+            .pending_tables
+            .pending_pool_index
+            .get(certificate_id)?
+            .is_some()
+        {
+            self.pending_tables
+                .broadcast_states
+                .insert(certificate_id, state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the checkpointed progress of the broadcast of a pending certificate, if any
+    pub fn get_broadcast_state(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<BroadcastState>, StorageError> {
+        Ok(self.pending_tables.broadcast_states.get(certificate_id)?)
+    }
+
     /// Try to return the [`PendingCertificateId`] for a [`CertificateId`]
     ///
     /// Return `Ok(None)` if the `certificate_id` is not found.
@@ -215,6 +275,44 @@ impl ValidatorStore {
         Ok(self.pending_tables.precedence_pool.get(certificate_id)?)
     }
 
+    /// Returns the [Certificate] with the given [CertificateId] if it is waiting in the
+    /// precedence pool for its previous certificate to be delivered
+    pub fn get_awaiting_precedence(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<Certificate>, StorageError> {
+        let Some(prev_id) = self
+            .pending_tables
+            .precedence_pool_index
+            .get(certificate_id)?
+        else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .pending_tables
+            .precedence_pool
+            .get(&prev_id)?
+            .filter(|certificate| certificate.id == *certificate_id))
+    }
+
+    /// Remove a certificate from the precedence pool, along with its timestamp and its entry
+    /// in the index of the precedence pool
+    fn delete_from_precedence_pool(
+        &self,
+        certificate: &Certificate,
+    ) -> Result<(), InternalStorageError> {
+        self.pending_tables
+            .precedence_pool
+            .delete(&certificate.prev_id)?;
+        self.pending_tables
+            .precedence_pool_timestamps
+            .delete(&certificate.prev_id)?;
+        self.pending_tables
+            .precedence_pool_index
+            .delete(&certificate.id)
+    }
+
     // TODO: Performance issue on this one as we iter over all the pending certificates
     // We need to improve how we request the pending certificates.
     pub fn get_pending_certificates_for_subnets(
@@ -351,7 +449,20 @@ impl ValidatorStore {
         } else {
             self.pending_tables
                 .precedence_pool
-                .insert(&certificate.prev_id, certificate)?;
+                .batch()
+                .insert_batch(
+                    &self.pending_tables.precedence_pool,
+                    [(&certificate.prev_id, certificate)],
+                )?
+                .insert_batch(
+                    &self.pending_tables.precedence_pool_timestamps,
+                    [(&certificate.prev_id, unix_timestamp())],
+                )?
+                .insert_batch(
+                    &self.pending_tables.precedence_pool_index,
+                    [(&certificate.id, &certificate.prev_id)],
+                )?
+                .write()?;
 
             STORAGE_PRECEDENCE_POOL_COUNT.inc();
             debug!(
@@ -364,6 +475,155 @@ impl ValidatorStore {
         }
     }
 
+    /// Remove a certificate from the pending or the precedence pool, along with the progress
+    /// of its broadcast
+    ///
+    /// The certificates of the precedence pool depending on the evicted certificate can't be
+    /// delivered anymore and are evicted as well.
+    ///
+    /// Returns the evicted certificate followed by its evicted descendants, nothing being
+    /// evicted if the certificate isn't pending.
+    pub async fn evict_pending_certificate(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Vec<Certificate>, StorageError> {
+        let _certificate_guard = self
+            .fullnode_store
+            .certificate_lock_guard(*certificate_id)
+            .await;
+
+        let evicted =
+            if let Some(pending_id) = self.pending_tables.pending_pool_index.get(certificate_id)? {
+                let certificate = self.pending_tables.pending_pool.get(&pending_id)?;
+                self.pending_tables.pending_pool.delete(&pending_id)?;
+                self.pending_tables
+                    .pending_pool_index
+                    .delete(certificate_id)?;
+
+                STORAGE_PENDING_POOL_COUNT.dec();
+                certificate
+            } else if let Some(certificate) = self.get_awaiting_precedence(certificate_id)? {
+                self.delete_from_precedence_pool(&certificate)?;
+
+                STORAGE_PRECEDENCE_POOL_COUNT.dec();
+                Some(certificate)
+            } else {
+                None
+            };
+
+        let Some(evicted) = evicted else {
+            return Ok(Vec::new());
+        };
+
+        self.pending_tables
+            .broadcast_states
+            .delete(certificate_id)?;
+
+        // The precedence pool is keyed by the previous certificate, giving the next descendant
+        let mut evicted = vec![evicted];
+        while let Some(next) = self
+            .pending_tables
+            .precedence_pool
+            .get(&evicted[evicted.len() - 1].id)?
+        {
+            self.delete_from_precedence_pool(&next)?;
+            self.pending_tables.broadcast_states.delete(&next.id)?;
+
+            STORAGE_PRECEDENCE_POOL_COUNT.dec();
+            evicted.push(next);
+        }
+
+        Ok(evicted)
+    }
+
+    /// Prune the stores according to the given [`RetentionPolicy`]
+    pub async fn prune(&self, policy: &RetentionPolicy) -> Result<PruningSummary, StorageError> {
+        let precedence_pool_evicted = match policy.precedence_pool_ttl {
+            Some(ttl) => self.evict_expired_precedence_certificates(ttl).await?,
+            None => 0,
+        };
+
+        let unverified_proofs_removed = self.fullnode_store.prune_unverified_proofs()?;
+
+        let proofs_pruned = match policy.proofs_per_source_stream {
+            Some(proofs_per_source_stream) => {
+                self.fullnode_store.prune_proofs(proofs_per_source_stream)?
+            }
+            None => 0,
+        };
+
+        Ok(PruningSummary {
+            precedence_pool_evicted,
+            unverified_proofs_removed,
+            proofs_pruned,
+        })
+    }
+
+    /// Evict the certificates waiting in the precedence pool for longer than the given `ttl`
+    ///
+    /// The certificates without timestamp, which entered the precedence pool before their
+    /// timestamp was recorded, are considered as entering it now.
+    async fn evict_expired_precedence_certificates(
+        &self,
+        ttl: Duration,
+    ) -> Result<usize, StorageError> {
+        let now = unix_timestamp();
+
+        let mut expired = Vec::new();
+        for (prev_id, certificate) in self.pending_tables.precedence_pool.iter()? {
+            match self
+                .pending_tables
+                .precedence_pool_timestamps
+                .get(&prev_id)?
+            {
+                Some(timestamp) if now.saturating_sub(timestamp) >= ttl.as_secs() => {
+                    expired.push(certificate)
+                }
+                Some(_) => {}
+                None => self
+                    .pending_tables
+                    .precedence_pool_timestamps
+                    .insert(&prev_id, &now)?,
+            }
+        }
+
+        let mut evicted = 0;
+        for certificate in expired {
+            let _certificate_guard = self
+                .fullnode_store
+                .certificate_lock_guard(certificate.id)
+                .await;
+
+            // The previous certificate may have been delivered in the meantime
+            match self
+                .pending_tables
+                .precedence_pool
+                .get(&certificate.prev_id)?
+            {
+                Some(awaiting) if awaiting.id == certificate.id => {}
+                _ => continue,
+            }
+
+            self.delete_from_precedence_pool(&certificate)?;
+            self.pending_tables
+                .broadcast_states
+                .delete(&certificate.id)?;
+
+            STORAGE_PRECEDENCE_POOL_COUNT.dec();
+            info!(
+                "Certificate {} evicted from the precedence pool, its previous certificate {} \
+                 isn't delivered after {}s",
+                certificate.id,
+                certificate.prev_id,
+                ttl.as_secs()
+            );
+
+            evicted += 1;
+        }
+
+        Ok(evicted)
+    }
+
     #[instrument(skip(self, proofs))]
     pub fn insert_unverified_proofs(
         &self,
@@ -411,10 +671,6 @@ impl ValidatorStore {
                 "Certificate Sync: unverified proof has been removed for {}",
                 certificate_id
             );
-            self.fullnode_store
-                .perpetual_tables
-                .unverified
-                .delete(&certificate_id)?;
 
             Ok(())
         } else {
@@ -631,6 +887,11 @@ impl WriteStore for ValidatorStore {
             STORAGE_PENDING_POOL_COUNT.dec();
         }
 
+        _ = self
+            .pending_tables
+            .broadcast_states
+            .delete(&certificate.certificate.id);
+
         if let Ok(Some(next_certificate)) = self
             .pending_tables
             .precedence_pool
@@ -641,9 +902,7 @@ impl WriteStore for ValidatorStore {
                 certificate.certificate.id, next_certificate.id
             );
             self.insert_pending_certificate(&next_certificate).await?;
-            self.pending_tables
-                .precedence_pool
-                .delete(&certificate.certificate.id)?;
+            self.delete_from_precedence_pool(&next_certificate)?;
 
             STORAGE_PRECEDENCE_POOL_COUNT.dec();
             STORAGE_PENDING_POOL_COUNT.inc();
@@ -661,3 +920,11 @@ impl WriteStore for ValidatorStore {
             .await
     }
 }
+
+/// Returns the current unix timestamp in seconds
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
@@ -4,22 +4,28 @@ use std::{
     sync::atomic::{AtomicU64, Ordering},
 };
 
-use bincode::Options;
-use rocksdb::ColumnFamilyDescriptor;
+use rocksdb::{ColumnFamilyDescriptor, IteratorMode};
 use topos_core::{
-    types::ProofOfDelivery,
-    uci::{Certificate, CertificateId},
+    types::{stream::Position, ProofOfDelivery},
+    uci::{Certificate, CertificateId, SubnetId},
 };
 use tracing::warn;
 
+#[cfg(feature = "inmemory")]
+use crate::memory::MemoryDB;
 use crate::{
     constant::cfs,
+    errors::InternalStorageError,
     rocks::{
         constants,
-        db::{default_options, init_with_cfs},
+        db::{default_options, init_with_cfs, ReadOnlyDB},
         db_column::DBColumn,
+        map::Map,
+    },
+    types::{
+        BroadcastState, CertificatesColumn, EpochId, EpochSummary, PendingCertificatesColumn,
+        StreamsColumn,
     },
-    types::{CertificatesColumn, EpochId, EpochSummary, PendingCertificatesColumn, StreamsColumn},
     PendingCertificateId,
 };
 
@@ -53,83 +59,164 @@ use crate::{
 /// check for any child [`Certificate`] in the precedence pool waiting to be promoted to the
 /// pending pool in order to be broadcast.
 ///
+/// The time at which a [`Certificate`] entered the precedence pool is kept alongside, allowing
+/// the [`ValidatorStore`](struct@super::ValidatorStore) to evict the certificates whose previous
+/// [`Certificate`] is never delivered.
+///
+/// ## Broadcast states
+///
+/// The progress of the broadcast of the pending certificates is checkpointed, allowing to resume
+/// the broadcasts after a restart. A [`BroadcastState`] lives as long as its [`Certificate`] is
+/// pending, whatever the epoch.
+///
 pub struct ValidatorPendingTables {
     pub(crate) next_pending_id: AtomicU64,
     pub(crate) pending_pool: PendingCertificatesColumn,
     pub(crate) pending_pool_index: DBColumn<CertificateId, PendingCertificateId>,
     pub(crate) precedence_pool: DBColumn<CertificateId, Certificate>,
+    /// Unix timestamp in seconds at which a certificate entered the precedence pool, keyed by
+    /// its previous certificate like the precedence pool
+    pub(crate) precedence_pool_timestamps: DBColumn<CertificateId, u64>,
+    /// Previous certificate of the certificates of the precedence pool, allowing to look up a
+    /// certificate of the precedence pool by its id
+    pub(crate) precedence_pool_index: DBColumn<CertificateId, CertificateId>,
+    pub(crate) broadcast_states: DBColumn<CertificateId, BroadcastState>,
 }
 
 impl ValidatorPendingTables {
+    const COLUMNS: &'static [(&'static str, Option<usize>)] = &[
+        (cfs::PENDING_POOL, None),
+        (cfs::PENDING_POOL_INDEX, None),
+        (cfs::PRECEDENCE_POOL, None),
+        (cfs::PRECEDENCE_POOL_TIMESTAMPS, None),
+        (cfs::PRECEDENCE_POOL_INDEX, None),
+        (cfs::BROADCAST_STATES, None),
+    ];
+
     /// Open the [`ValidatorPendingTables`] at the given path.
-    pub fn open(path: &Path) -> Self {
+    ///
+    /// Fails if the database can't be opened, e.g. when it is already opened by a running node.
+    pub fn open(path: &Path) -> Result<Self, InternalStorageError> {
         let path = path.join("pending");
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
-            create_dir_all(&path).expect("Cannot create ValidatorPendingTables directory");
+            create_dir_all(&path)?;
         }
         let cfs = vec![
             ColumnFamilyDescriptor::new(cfs::PENDING_POOL, default_options()),
             ColumnFamilyDescriptor::new(cfs::PENDING_POOL_INDEX, default_options()),
             ColumnFamilyDescriptor::new(cfs::PRECEDENCE_POOL, default_options()),
+            ColumnFamilyDescriptor::new(cfs::PRECEDENCE_POOL_TIMESTAMPS, default_options()),
+            ColumnFamilyDescriptor::new(cfs::PRECEDENCE_POOL_INDEX, default_options()),
+            ColumnFamilyDescriptor::new(cfs::BROADCAST_STATES, default_options()),
         ];
 
-        let db = init_with_cfs(&path, default_options(), cfs)
-            .unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
-        let pending_pool = DBColumn::reopen(&db, cfs::PENDING_POOL);
-        let next_pending_id = {
-            let cf = pending_pool
-                .rocksdb
-                .cf_handle(cfs::PENDING_POOL)
-                .expect("Cannot get cf handle for pending pool");
-            let mut pending_iterator = pending_pool.rocksdb.raw_iterator_cf(&cf);
-
-            pending_iterator.seek_to_last();
-            if pending_iterator.valid() {
-                AtomicU64::new(
-                    pending_iterator
-                        .key()
-                        .map(|key| {
-                            bincode::DefaultOptions::new()
-                                .with_big_endian()
-                                .with_fixint_encoding()
-                                .deserialize(key)
-                                .unwrap_or(0)
-                        })
-                        .unwrap_or(0),
-                )
-            } else {
-                AtomicU64::new(0)
-            }
-        };
+        let db = init_with_cfs(&path, default_options(), cfs)?;
+
+        Ok(Self::from_columns(
+            DBColumn::reopen(&db, cfs::PENDING_POOL),
+            DBColumn::reopen(&db, cfs::PENDING_POOL_INDEX),
+            DBColumn::reopen(&db, cfs::PRECEDENCE_POOL),
+            DBColumn::reopen(&db, cfs::PRECEDENCE_POOL_TIMESTAMPS),
+            DBColumn::reopen(&db, cfs::PRECEDENCE_POOL_INDEX),
+            DBColumn::reopen(&db, cfs::BROADCAST_STATES),
+        ))
+    }
+
+    /// Open the [`ValidatorPendingTables`] at the given path for reading only, nothing being
+    /// created. The missing tables are read as empty ones.
+    pub fn open_read_only(path: &Path) -> Result<Self, InternalStorageError> {
+        let db = ReadOnlyDB::open(&path.join("pending"), Self::COLUMNS)?;
+
+        Ok(Self::from_columns(
+            db.column(cfs::PENDING_POOL)?,
+            db.column(cfs::PENDING_POOL_INDEX)?,
+            db.column(cfs::PRECEDENCE_POOL)?,
+            db.column(cfs::PRECEDENCE_POOL_TIMESTAMPS)?,
+            db.column(cfs::PRECEDENCE_POOL_INDEX)?,
+            db.column(cfs::BROADCAST_STATES)?,
+        ))
+    }
+
+    /// Open the [`ValidatorPendingTables`] in memory, nothing being persisted.
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Self {
+        let db = MemoryDB::new(Self::COLUMNS);
+
+        Self::from_columns(
+            DBColumn::reopen_in_memory(&db, cfs::PENDING_POOL),
+            DBColumn::reopen_in_memory(&db, cfs::PENDING_POOL_INDEX),
+            DBColumn::reopen_in_memory(&db, cfs::PRECEDENCE_POOL),
+            DBColumn::reopen_in_memory(&db, cfs::PRECEDENCE_POOL_TIMESTAMPS),
+            DBColumn::reopen_in_memory(&db, cfs::PRECEDENCE_POOL_INDEX),
+            DBColumn::reopen_in_memory(&db, cfs::BROADCAST_STATES),
+        )
+    }
+
+    fn from_columns(
+        pending_pool: PendingCertificatesColumn,
+        pending_pool_index: DBColumn<CertificateId, PendingCertificateId>,
+        precedence_pool: DBColumn<CertificateId, Certificate>,
+        precedence_pool_timestamps: DBColumn<CertificateId, u64>,
+        precedence_pool_index: DBColumn<CertificateId, CertificateId>,
+        broadcast_states: DBColumn<CertificateId, BroadcastState>,
+    ) -> Self {
+        let next_pending_id = AtomicU64::new(
+            pending_pool
+                .iter_with_mode(IteratorMode::End)
+                .ok()
+                .and_then(|mut iterator| iterator.next())
+                .map(|(pending_id, _)| pending_id)
+                .unwrap_or(0),
+        );
 
         next_pending_id.fetch_add(1, Ordering::Relaxed);
 
         Self {
             next_pending_id,
             pending_pool,
-            pending_pool_index: DBColumn::reopen(&db, cfs::PENDING_POOL_INDEX),
-            precedence_pool: DBColumn::reopen(&db, cfs::PRECEDENCE_POOL),
+            pending_pool_index,
+            precedence_pool,
+            precedence_pool_timestamps,
+            precedence_pool_index,
+            broadcast_states,
         }
     }
 }
 
-/// Data that shouldn't be purged at all.
+/// Data that shouldn't be purged, apart from the proofs of the old certificates when a
+/// [`RetentionPolicy`](struct@crate::retention::RetentionPolicy) prunes them.
 // TODO: TP-774: Rename and move to FullNode domain
 pub struct ValidatorPerpetualTables {
     pub(crate) certificates: CertificatesColumn,
     pub(crate) streams: StreamsColumn,
-    #[allow(unused)]
-    epoch_chain: DBColumn<EpochId, EpochSummary>,
+    pub(crate) epoch_chain: DBColumn<EpochId, EpochSummary>,
     pub(crate) unverified: DBColumn<CertificateId, ProofOfDelivery>,
+    /// Version of the storage schema, see the [`migration`](module@crate::migration) module
+    pub(crate) schema: DBColumn<String, u32>,
+    /// First position of every source stream whose certificates still have their proof, the
+    /// proofs before it having been pruned
+    pub(crate) pruned_proofs: DBColumn<SubnetId, Position>,
 }
 
 impl ValidatorPerpetualTables {
-    pub fn open(path: &Path) -> Self {
+    const COLUMNS: &'static [(&'static str, Option<usize>)] = &[
+        (cfs::CERTIFICATES, None),
+        (cfs::STREAMS, Some(constants::SOURCE_STREAMS_PREFIX_SIZE)),
+        (cfs::EPOCH_CHAIN, None),
+        (cfs::UNVERIFIED, None),
+        (cfs::SCHEMA, None),
+        (cfs::PRUNED_PROOFS, None),
+    ];
+
+    /// Open the [`ValidatorPerpetualTables`] at the given path.
+    ///
+    /// Fails if the database can't be opened, e.g. when it is already opened by a running node.
+    pub fn open(path: &Path) -> Result<Self, InternalStorageError> {
         let path = path.join("perpetual");
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
-            create_dir_all(&path).expect("Cannot create ValidatorPerpetualTables directory");
+            create_dir_all(&path)?;
         }
         let mut options_stream = default_options();
         options_stream.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(
@@ -141,17 +228,69 @@ impl ValidatorPerpetualTables {
             ColumnFamilyDescriptor::new(cfs::STREAMS, options_stream),
             ColumnFamilyDescriptor::new(cfs::EPOCH_CHAIN, default_options()),
             ColumnFamilyDescriptor::new(cfs::UNVERIFIED, default_options()),
+            ColumnFamilyDescriptor::new(cfs::SCHEMA, default_options()),
+            ColumnFamilyDescriptor::new(cfs::PRUNED_PROOFS, default_options()),
         ];
 
-        let db = init_with_cfs(&path, default_options(), cfs).unwrap_or_else(|e| {
-            panic!("Cannot open DB at {:?} => error {:?}", path, e);
-        });
+        let db = init_with_cfs(&path, default_options(), cfs)?;
 
-        Self {
+        Ok(Self {
             certificates: DBColumn::reopen(&db, cfs::CERTIFICATES),
             streams: DBColumn::reopen(&db, cfs::STREAMS),
             epoch_chain: DBColumn::reopen(&db, cfs::EPOCH_CHAIN),
             unverified: DBColumn::reopen(&db, cfs::UNVERIFIED),
+            schema: DBColumn::reopen(&db, cfs::SCHEMA),
+            pruned_proofs: DBColumn::reopen(&db, cfs::PRUNED_PROOFS),
+        })
+    }
+
+    /// Open the [`ValidatorPerpetualTables`] at the given path for reading only, nothing being
+    /// created. The missing tables are read as empty ones.
+    pub fn open_read_only(path: &Path) -> Result<Self, InternalStorageError> {
+        let db = ReadOnlyDB::open(&path.join("perpetual"), Self::COLUMNS)?;
+
+        Ok(Self {
+            certificates: db.column(cfs::CERTIFICATES)?,
+            streams: db.column(cfs::STREAMS)?,
+            epoch_chain: db.column(cfs::EPOCH_CHAIN)?,
+            unverified: db.column(cfs::UNVERIFIED)?,
+            schema: db.column(cfs::SCHEMA)?,
+            pruned_proofs: db.column(cfs::PRUNED_PROOFS)?,
+        })
+    }
+
+    /// Create a RocksDB checkpoint of the tables, which can be opened at the given path
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.certificates.create_checkpoint(&path.join("perpetual"))
+    }
+
+    /// Open the [`ValidatorPerpetualTables`] in memory, nothing being persisted.
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Self {
+        let db = MemoryDB::new(Self::COLUMNS);
+
+        Self {
+            certificates: DBColumn::reopen_in_memory(&db, cfs::CERTIFICATES),
+            streams: DBColumn::reopen_in_memory(&db, cfs::STREAMS),
+            epoch_chain: DBColumn::reopen_in_memory(&db, cfs::EPOCH_CHAIN),
+            unverified: DBColumn::reopen_in_memory(&db, cfs::UNVERIFIED),
+            schema: DBColumn::reopen_in_memory(&db, cfs::SCHEMA),
+            pruned_proofs: DBColumn::reopen_in_memory(&db, cfs::PRUNED_PROOFS),
         }
     }
+
+    /// Returns the certificates already delivered which still have an unverified proof of
+    /// delivery
+    pub(crate) fn delivered_unverified_proofs(
+        &self,
+    ) -> Result<Vec<CertificateId>, InternalStorageError> {
+        let mut delivered = Vec::new();
+        for (certificate_id, _) in self.unverified.iter()? {
+            if self.certificates.get(&certificate_id)?.is_some() {
+                delivered.push(certificate_id);
+            }
+        }
+
+        Ok(delivered)
+    }
 }
@@ -1,8 +1,13 @@
-use std::{future::IntoFuture, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    future::IntoFuture,
+    sync::Arc,
+};
 
 use tokio::{spawn, sync::mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
+use topos_core::types::ValidatorId;
 use topos_p2p::NetworkClient;
 use topos_tce_storage::validator::ValidatorStore;
 use tracing::Instrument;
@@ -11,12 +16,20 @@ use crate::{
     checkpoints_collector::{CheckpointSynchronizer, CheckpointsCollectorError},
     Synchronizer, SynchronizerError, SynchronizerEvent,
 };
-use topos_config::tce::synchronization::SynchronizationConfig;
+use topos_config::tce::{
+    broadcast::ReliableBroadcastParams, synchronization::SynchronizationConfig,
+};
 
 pub struct SynchronizerBuilder {
     network_client: Option<NetworkClient>,
     store: Option<Arc<ValidatorStore>>,
     config: SynchronizationConfig,
+    /// Validators of the genesis epoch, the validators of the following epochs being read from
+    /// the store
+    validators: HashSet<ValidatorId>,
+    /// Minimum number of Ready messages expected in a proof of delivery of the genesis epoch,
+    /// the quorum of the genesis `validators` if unset
+    delivery_threshold: Option<u64>,
     /// Size of the channel producing events (default: 100)
     event_channel_size: usize,
     /// CancellationToken used to trigger shutdown of the Synchronizer
@@ -29,6 +42,8 @@ impl Default for SynchronizerBuilder {
             network_client: None,
             store: None,
             config: SynchronizationConfig::default(),
+            validators: HashSet::new(),
+            delivery_threshold: None,
             event_channel_size: 100,
             shutdown: None,
         }
@@ -69,6 +84,11 @@ impl SynchronizerBuilder {
                         CheckpointsCollectorError::NoStore,
                     ))?;
                 },
+                delivery_threshold: self.delivery_threshold.unwrap_or(
+                    ReliableBroadcastParams::new(self.validators.len()).delivery_threshold as u64,
+                ),
+                validators: self.validators,
+                penalized_peers: HashMap::new(),
                 current_request_id: None,
                 shutdown: shutdown.child_token(),
                 events: sync_events,
@@ -107,6 +127,18 @@ impl SynchronizerBuilder {
         self
     }
 
+    pub fn with_validators(mut self, validators: HashSet<ValidatorId>) -> Self {
+        self.validators = validators;
+
+        self
+    }
+
+    pub fn with_delivery_threshold(mut self, delivery_threshold: u64) -> Self {
+        self.delivery_threshold = Some(delivery_threshold);
+
+        self
+    }
+
     pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
         self.shutdown = Some(shutdown);
 
@@ -3,6 +3,7 @@ use std::{
     future::IntoFuture,
     str::FromStr,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use futures::{future::BoxFuture, FutureExt};
@@ -16,17 +17,24 @@ use topos_core::{
         tce::v1::{
             synchronizer_service_client::SynchronizerServiceClient,
             synchronizer_service_server::SynchronizerServiceServer, CheckpointRequest,
-            CheckpointResponse, FetchCertificatesRequest,
+            CheckpointResponse, FetchCertificatesRequest, SignedCheckpointRequest,
         },
     },
     errors::GrpcParsingError,
-    types::ProofOfDelivery,
+    types::{CertificateDelivered, ProofOfDelivery, ProofOfDeliveryError, ValidatorId},
     uci::{Certificate, CertificateId, SubnetId},
 };
 
-use topos_config::tce::synchronization::SynchronizationConfig;
+use topos_config::tce::{
+    broadcast::ReliableBroadcastParams, synchronization::SynchronizationConfig,
+};
 use topos_p2p::{error::P2PError, NetworkClient, PeerId};
-use topos_tce_storage::{errors::StorageError, store::ReadStore, validator::ValidatorStore};
+use topos_tce_storage::{
+    errors::StorageError,
+    store::ReadStore,
+    types::{CheckpointSignatureError, EpochId, Validators, VerifiedCheckpointSummary},
+    validator::ValidatorStore,
+};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -42,9 +50,17 @@ pub struct CheckpointSynchronizer {
     pub(crate) config: SynchronizationConfig,
 
     pub(crate) network: NetworkClient,
-    #[allow(unused)]
     pub(crate) store: Arc<ValidatorStore>,
 
+    /// Validators of the genesis epoch, allowed to sign the Ready messages of a
+    /// [`ProofOfDelivery`] along with the validators of the following epochs
+    pub(crate) validators: HashSet<ValidatorId>,
+    /// Minimum number of Ready messages expected in a [`ProofOfDelivery`] of the genesis epoch
+    pub(crate) delivery_threshold: u64,
+    /// Peers that served invalid proofs or certificates, ignored until the associated
+    /// [`Instant`]
+    pub(crate) penalized_peers: HashMap<PeerId, Instant>,
+
     pub(crate) current_request_id: Option<APIUuid>,
 
     pub(crate) shutdown: CancellationToken,
@@ -120,6 +136,58 @@ enum SyncError {
 
     #[error(transparent)]
     Grpc(#[from] Status),
+
+    #[error(
+        "Invalid proof of delivery for certificate {certificate_id} served by {peer}: {error}"
+    )]
+    InvalidProofOfDelivery {
+        peer: PeerId,
+        certificate_id: CertificateId,
+        #[source]
+        error: ProofOfDeliveryError,
+    },
+
+    #[error("Invalid signed checkpoint of epoch {epoch} served by {peer}: {error}")]
+    InvalidSignedCheckpoint {
+        peer: PeerId,
+        epoch: EpochId,
+        #[source]
+        error: CheckpointSignatureError,
+    },
+
+    #[error("Missing proof of delivery for the checkpoint head {certificate_id} served by {peer}")]
+    MissingCheckpointHead {
+        peer: PeerId,
+        certificate_id: CertificateId,
+    },
+
+    #[error("Invalid certificate {certificate_id} served by {peer}: {error}")]
+    InvalidCertificate {
+        peer: PeerId,
+        certificate_id: CertificateId,
+        #[source]
+        error: ProofOfDeliveryError,
+    },
+
+    #[error("Unexpected certificate {certificate_id} served by {peer}")]
+    UnexpectedCertificate {
+        peer: PeerId,
+        certificate_id: CertificateId,
+    },
+}
+
+impl SyncError {
+    /// Returns true if the error is caused by invalid data served by a peer
+    fn is_invalid_data(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidProofOfDelivery { .. }
+                | Self::InvalidSignedCheckpoint { .. }
+                | Self::MissingCheckpointHead { .. }
+                | Self::InvalidCertificate { .. }
+                | Self::UnexpectedCertificate { .. }
+        )
+    }
 }
 
 impl CheckpointSynchronizer {
@@ -188,6 +256,94 @@ impl CheckpointSynchronizer {
         Ok(diff)
     }
 
+    /// Returns the validator sets of the epochs following the genesis one, along with their
+    /// delivery threshold, from the latest epoch
+    fn epoch_validators(&self) -> Result<Vec<(Validators, u64)>, SyncError> {
+        Ok(self
+            .store
+            .epoch_validators_store()
+            .get_all_validators()?
+            .into_iter()
+            .filter(|(epoch_id, _)| *epoch_id > 0)
+            .map(|(_, validators)| {
+                let threshold = ReliableBroadcastParams::new(validators.len()).delivery_threshold;
+
+                (validators, threshold as u64)
+            })
+            .collect())
+    }
+
+    /// Verify a delivery against the validator set of every known epoch, as the epoch in which
+    /// a certificate has been delivered isn't part of its [`ProofOfDelivery`].
+    ///
+    /// The error raised against the genesis validators is returned if no validator set proves
+    /// the delivery.
+    fn verify_delivery<F>(
+        &self,
+        epochs: &[(Validators, u64)],
+        verify: F,
+    ) -> Result<(), ProofOfDeliveryError>
+    where
+        F: Fn(&Validators, u64) -> Result<(), ProofOfDeliveryError>,
+    {
+        if epochs
+            .iter()
+            .any(|(validators, threshold)| verify(validators, *threshold).is_ok())
+        {
+            return Ok(());
+        }
+
+        verify(&self.validators, self.delivery_threshold)
+    }
+
+    /// Verify every [`ProofOfDelivery`] of a checkpoint diff against the known validators.
+    ///
+    /// The whole diff is rejected as soon as one proof is invalid.
+    fn verify_checkpoint_diff(
+        &self,
+        peer: PeerId,
+        diff: &HashMap<SubnetId, Vec<ProofOfDelivery>>,
+    ) -> Result<(), SyncError> {
+        let epochs = self.epoch_validators()?;
+
+        for proof in diff.values().flatten() {
+            self.verify_delivery(&epochs, |validators, threshold| {
+                proof.verify(validators, threshold)
+            })
+            .map_err(|error| SyncError::InvalidProofOfDelivery {
+                peer,
+                certificate_id: proof.certificate_id,
+                error,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Exclude a peer from the synchronization for the configured penalty duration
+    fn penalize(&mut self, peer: PeerId) {
+        let until = Instant::now() + Duration::from_secs(self.config.penalty_duration_seconds);
+        warn!("Peer {} penalized until {:?}", peer, until);
+
+        self.penalized_peers.insert(peer, until);
+    }
+
+    /// Penalize the peer if the error is caused by invalid data it served
+    fn penalize_on_invalid_data(&mut self, peer: PeerId, error: SyncError) -> SyncError {
+        if error.is_invalid_data() {
+            self.penalize(peer);
+        }
+
+        error
+    }
+
+    fn is_penalized(&mut self, peer: &PeerId) -> bool {
+        let now = Instant::now();
+        self.penalized_peers.retain(|_, until| *until > now);
+
+        self.penalized_peers.contains_key(peer)
+    }
+
     fn insert_unverified_proofs(
         &self,
         diff: HashMap<SubnetId, Vec<ProofOfDelivery>>,
@@ -216,16 +372,15 @@ impl CheckpointSynchronizer {
         Ok(chunked_certs)
     }
 
+    /// Fetch the certificates of the given unverified proofs of delivery from a peer.
+    ///
+    /// Every certificate served needs to be one of the requested ones and to be proven
+    /// delivered by its unverified proof, see [`CertificateDelivered::verify`].
     async fn fetch_certificates(
         &self,
+        target_peer: PeerId,
         certificate_ids: Vec<CertificateId>,
     ) -> Result<Vec<Certificate>, SyncError> {
-        let target_peer = self
-            .network
-            .random_known_peer()
-            .await
-            .map_err(|_| SyncError::UnableToFetchTargetPeer)?;
-
         let request_id: Option<APIUuid> = Some(Uuid::new_v4().into());
         let req = FetchCertificatesRequest {
             request_id,
@@ -246,13 +401,287 @@ impl CheckpointSynchronizer {
 
         let response = client.fetch_certificates(req).await?.into_inner();
 
-        let certificates: Result<Vec<Certificate>, _> = response
+        let certificates = response
             .certificates
             .into_iter()
             .map(TryInto::try_into)
+            .collect::<Result<Vec<Certificate>, _>>()?;
+
+        self.verify_certificates(target_peer, &certificate_ids, &certificates)?;
+
+        Ok(certificates)
+    }
+
+    fn verify_certificates(
+        &self,
+        peer: PeerId,
+        certificate_ids: &[CertificateId],
+        certificates: &[Certificate],
+    ) -> Result<(), SyncError> {
+        let epochs = self.epoch_validators()?;
+
+        for certificate in certificates {
+            let unexpected = SyncError::UnexpectedCertificate {
+                peer,
+                certificate_id: certificate.id,
+            };
+
+            if !certificate_ids.contains(&certificate.id) {
+                return Err(unexpected);
+            }
+
+            let proof_of_delivery = self
+                .store
+                .get_unverified_proof(&certificate.id)?
+                .ok_or(unexpected)?;
+
+            let delivered = CertificateDelivered {
+                certificate: certificate.clone(),
+                proof_of_delivery,
+            };
+
+            self.verify_delivery(&epochs, |validators, threshold| {
+                delivered.verify(validators, threshold)
+            })
+            .map_err(|error| SyncError::InvalidCertificate {
+                peer,
+                certificate_id: certificate.id,
+                error,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the validators expected to sign the checkpoint of the given epoch, along with
+    /// the number of signatures needed.
+    ///
+    /// The validators of the latest known epoch are expected if the epoch isn't known locally,
+    /// the genesis validators being expected if no epoch is known.
+    fn checkpoint_validators(&self, epoch: EpochId) -> Result<(Validators, u64), SyncError> {
+        let epoch_validators_store = self.store.epoch_validators_store();
+
+        let validators = match epoch_validators_store.get_validators(epoch)? {
+            Some(validators) => validators,
+            None => epoch_validators_store
+                .get_latest_validators()?
+                .map(|(_, validators)| validators)
+                .unwrap_or_else(|| self.validators.clone()),
+        };
+        let threshold = ReliableBroadcastParams::new(validators.len()).delivery_threshold;
+
+        Ok((validators, threshold as u64))
+    }
+
+    /// Verify a signed checkpoint served by a peer, along with the proofs of delivery of the
+    /// certificates at its source and target heads.
+    ///
+    /// Returns the proofs of delivery of the certificates at the heads of the checkpoint.
+    fn verify_signed_checkpoint(
+        &self,
+        peer: PeerId,
+        checkpoint: &VerifiedCheckpointSummary,
+        heads: Vec<ProofOfDelivery>,
+    ) -> Result<Vec<ProofOfDelivery>, SyncError> {
+        let VerifiedCheckpointSummary(summary, _) = checkpoint;
+
+        let (validators, threshold) = self.checkpoint_validators(summary.epoch)?;
+        checkpoint.verify(&validators, threshold).map_err(|error| {
+            SyncError::InvalidSignedCheckpoint {
+                peer,
+                epoch: summary.epoch,
+                error,
+            }
+        })?;
+
+        let mut proofs: HashMap<CertificateId, ProofOfDelivery> = heads
+            .into_iter()
+            .map(|proof| (proof.certificate_id, proof))
             .collect();
 
-        Ok(certificates?)
+        let epochs = self.epoch_validators()?;
+        let mut verify = |certificate_id: CertificateId,
+                          is_expected: &dyn Fn(&ProofOfDelivery) -> bool|
+         -> Result<Option<ProofOfDelivery>, SyncError> {
+            // The certificate can be at the head of multiple streams
+            let Some(proof) = proofs.remove(&certificate_id) else {
+                return Ok(None);
+            };
+
+            if !is_expected(&proof) {
+                return Err(SyncError::MissingCheckpointHead {
+                    peer,
+                    certificate_id,
+                });
+            }
+
+            self.verify_delivery(&epochs, |validators, threshold| {
+                proof.verify(validators, threshold)
+            })
+            .map_err(|error| SyncError::InvalidProofOfDelivery {
+                peer,
+                certificate_id,
+                error,
+            })?;
+
+            Ok(Some(proof))
+        };
+
+        let mut verified = Vec::new();
+        for head in &summary.checkpoint_data {
+            let proof = verify(head.certificate_id, &|proof| {
+                proof.delivery_position.subnet_id == head.subnet_id
+                    && proof.delivery_position.position == head.position
+            })?
+            .ok_or(SyncError::MissingCheckpointHead {
+                peer,
+                certificate_id: head.certificate_id,
+            })?;
+
+            verified.push(proof);
+        }
+
+        for head in &summary.target_checkpoint_data {
+            let source_head = summary
+                .checkpoint_data
+                .iter()
+                .find(|source_head| source_head.subnet_id == head.source_subnet_id);
+
+            // The certificate is either at the head of its source stream or precedes it
+            let proof = verify(head.certificate_id, &|proof| {
+                source_head.is_some_and(|source_head| {
+                    proof.delivery_position.subnet_id == source_head.subnet_id
+                        && *proof.delivery_position.position <= *source_head.position
+                })
+            })?;
+
+            match proof {
+                Some(proof) => verified.push(proof),
+                None if verified
+                    .iter()
+                    .any(|proof| proof.certificate_id == head.certificate_id) => {}
+                None => {
+                    return Err(SyncError::MissingCheckpointHead {
+                        peer,
+                        certificate_id: head.certificate_id,
+                    })
+                }
+            }
+        }
+
+        Ok(verified)
+    }
+
+    /// Insert the certificates at the heads of a signed checkpoint, at the positions of the
+    /// source and target streams given by the checkpoint, then the checkpoint itself.
+    ///
+    /// The proofs of delivery of the certificates are expected to be persisted as unverified.
+    async fn insert_checkpoint_heads(
+        &self,
+        peer: PeerId,
+        checkpoint: VerifiedCheckpointSummary,
+        certificates: Vec<Certificate>,
+    ) -> Result<(), SyncError> {
+        let fullnode_store = self.store.fullnode_store();
+        let VerifiedCheckpointSummary(summary, _) = &checkpoint;
+
+        for certificate in certificates {
+            let certificate_id = certificate.id;
+            let proof_of_delivery = self.store.get_unverified_proof(&certificate_id)?.ok_or(
+                SyncError::UnexpectedCertificate {
+                    peer,
+                    certificate_id,
+                },
+            )?;
+            let target_positions = summary
+                .target_checkpoint_data
+                .iter()
+                .filter(|head| head.certificate_id == certificate_id)
+                .map(|head| (head.target_subnet_id, head.position))
+                .collect();
+
+            match fullnode_store
+                .insert_checkpoint_head(
+                    &CertificateDelivered {
+                        certificate,
+                        proof_of_delivery,
+                    },
+                    &target_positions,
+                )
+                .await
+            {
+                Ok(_)
+                | Err(StorageError::InternalStorage(
+                    topos_tce_storage::errors::InternalStorageError::CertificateAlreadyExists,
+                )) => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        fullnode_store.insert_signed_checkpoint(checkpoint)?;
+
+        Ok(())
+    }
+
+    /// Bootstrap an empty store from the latest checkpoint signed by a quorum of validators,
+    /// instead of replaying the whole history of the source streams.
+    ///
+    /// The certificates at the heads of the checkpoint are synchronized along with their proof
+    /// of delivery, the following synchronizations starting from them.
+    /// Returns `false` if the peer doesn't know any signed checkpoint.
+    async fn bootstrap_from_signed_checkpoint(&self, peer: PeerId) -> Result<bool, SyncError> {
+        let request_id = Uuid::new_v4();
+
+        debug!(
+            "Asking {} for the latest signed checkpoint (request_id: {})",
+            peer, request_id
+        );
+
+        let mut client: SynchronizerServiceClient<_> = self
+            .network
+            .new_grpc_client::<SynchronizerServiceClient<_>, SynchronizerServiceServer<SynchronizerService>>(peer)
+            .await?;
+
+        let response = client
+            .fetch_signed_checkpoint(SignedCheckpointRequest {
+                request_id: Some(request_id.into()),
+            })
+            .await?
+            .into_inner();
+
+        let Some(checkpoint) = response.checkpoint else {
+            return Ok(false);
+        };
+
+        let checkpoint: VerifiedCheckpointSummary = checkpoint.try_into()?;
+        let heads = response
+            .heads
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<ProofOfDelivery>, _>>()?;
+        let heads = self.verify_signed_checkpoint(peer, &checkpoint, heads)?;
+
+        let certificate_ids = self.store.insert_unverified_proofs(heads)?;
+        let certificates = self
+            .fetch_certificates(peer, certificate_ids.clone())
+            .await?;
+        if let Some(certificate_id) = certificate_ids
+            .into_iter()
+            .find(|id| !certificates.iter().any(|certificate| certificate.id == *id))
+        {
+            return Err(SyncError::MissingCheckpointHead {
+                peer,
+                certificate_id,
+            });
+        }
+
+        let epoch = checkpoint.0.epoch;
+        self.insert_checkpoint_heads(peer, checkpoint, certificates)
+            .await?;
+
+        info!("Bootstrapped from the signed checkpoint of epoch {}", epoch);
+
+        Ok(true)
     }
 
     async fn initiate_request(&mut self) -> Result<(), SyncError> {
@@ -263,15 +692,44 @@ impl CheckpointSynchronizer {
             .await
             .map_err(|_| SyncError::UnableToFetchTargetPeer)?;
 
+        if self.is_penalized(&target_peer) {
+            debug!(
+                "Skipping synchronization with penalized peer {}",
+                target_peer
+            );
+            return Ok(());
+        }
+
+        // An empty store starts from the latest signed checkpoint, if any
+        if self.store.get_checkpoint()?.is_empty() {
+            match self.bootstrap_from_signed_checkpoint(target_peer).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(error) if error.is_invalid_data() => {
+                    return Err(self.penalize_on_invalid_data(target_peer, error));
+                }
+                Err(error) => warn!("Unable to bootstrap from a signed checkpoint: {}", error),
+            }
+        }
+
         let diff = self.ask_for_checkpoint(target_peer).await?;
 
+        //  2. Validate the PoD diff, the peer is penalized if it serves invalid proofs
+        if let Err(error) = self.verify_checkpoint_diff(target_peer, &diff) {
+            return Err(self.penalize_on_invalid_data(target_peer, error));
+        }
+
         let certificates_to_catchup = self.insert_unverified_proofs(diff)?;
         info!("Certificates to catchup: {}", certificates_to_catchup.len());
 
+        //  3. Fetch the missing certificates, the peer is penalized if it serves certificates
+        //     which aren't proven delivered
         for certificates in certificates_to_catchup {
-            let certificates = self.fetch_certificates(certificates).await?;
+            let certificates = match self.fetch_certificates(target_peer, certificates).await {
+                Ok(certificates) => certificates,
+                Err(error) => return Err(self.penalize_on_invalid_data(target_peer, error)),
+            };
 
-            // TODO: verify every certificates
             for certificate in certificates {
                 let store = self.store.clone();
                 tokio::spawn(async move {
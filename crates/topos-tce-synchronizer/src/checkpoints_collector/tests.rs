@@ -1,28 +1,135 @@
-use std::time::Duration;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use rstest::rstest;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use topos_config::tce::synchronization::SynchronizationConfig;
 use topos_core::{
     api::grpc::tce::v1::{
         synchronizer_service_client::SynchronizerServiceClient,
         synchronizer_service_server::SynchronizerServiceServer, CheckpointMapFieldEntry,
         CheckpointRequest, CheckpointResponse, FetchCertificatesRequest,
     },
-    types::CertificateDelivered,
+    types::{
+        stream::CertificateSourceStreamPosition, CertificateDelivered, ProofOfDelivery, ValidatorId,
+    },
+    uci::{Certificate, SubnetId},
 };
+use topos_crypto::messages::MessageSigner;
 
-use topos_p2p::GrpcRouter;
+use topos_p2p::{GrpcRouter, NetworkClient};
+use topos_tce_storage::{
+    retention::RetentionPolicy,
+    store::{ReadStore, WriteStore},
+    types::{
+        CheckpointSignatureError, CheckpointSummary, ValidatorQuorumSignatureInfo,
+        VerifiedCheckpointSummary,
+    },
+    validator::ValidatorStore,
+};
 use topos_test_sdk::{
     certificates::create_certificate_chain,
+    constants::{PREV_CERTIFICATE_ID, SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1, TARGET_SUBNET_ID_2},
     storage::{create_fullnode_store, create_validator_store},
     tce::{create_network, NodeConfig},
 };
 
 use uuid::Uuid;
 
-use crate::SynchronizerService;
+use super::{CheckpointSynchronizer, SyncError};
+use crate::{GrpcSynchronizerService, SynchronizerService};
 
 mod integration;
 
+fn create_synchronizer(
+    network: NetworkClient,
+    store: Arc<ValidatorStore>,
+    validators: HashSet<ValidatorId>,
+    delivery_threshold: u64,
+) -> CheckpointSynchronizer {
+    CheckpointSynchronizer {
+        config: SynchronizationConfig::default(),
+        network,
+        store,
+        validators,
+        delivery_threshold,
+        penalized_peers: HashMap::new(),
+        current_request_id: None,
+        shutdown: CancellationToken::new(),
+        events: mpsc::channel(1).0,
+    }
+}
+
+fn sign_proof(proof: &mut ProofOfDelivery, signers: &[&MessageSigner]) {
+    proof.readies = signers
+        .iter()
+        .map(|signer| {
+            let validator_id = ValidatorId::from(signer.public_address);
+            let payload = ProofOfDelivery::ready_payload(&proof.certificate_id, &validator_id);
+
+            (validator_id, signer.sign_message(&payload).unwrap())
+        })
+        .collect();
+    proof.threshold = signers.len() as u64;
+}
+
+fn sign_checkpoint(
+    checkpoint: &CheckpointSummary,
+    signers: &[&MessageSigner],
+) -> VerifiedCheckpointSummary {
+    VerifiedCheckpointSummary(
+        checkpoint.clone(),
+        ValidatorQuorumSignatureInfo {
+            epoch: checkpoint.epoch,
+            signatures: signers
+                .iter()
+                .map(|signer| {
+                    (
+                        signer.public_address.into(),
+                        signer.sign_message(&checkpoint.payload()).unwrap(),
+                    )
+                })
+                .collect(),
+        },
+    )
+}
+
+/// Creates a chain of certificates of the given source subnet, each one with its own targets
+fn create_certificates_with_targets(
+    source_subnet: SubnetId,
+    targets: &[&[SubnetId]],
+) -> Vec<CertificateDelivered> {
+    let mut prev_id = PREV_CERTIFICATE_ID;
+
+    targets
+        .iter()
+        .enumerate()
+        .map(|(position, target_subnets)| {
+            let certificate =
+                Certificate::new_with_default_fields(prev_id, source_subnet, target_subnets)
+                    .unwrap();
+            prev_id = certificate.id;
+
+            CertificateDelivered {
+                proof_of_delivery: ProofOfDelivery {
+                    certificate_id: certificate.id,
+                    delivery_position: CertificateSourceStreamPosition::new(
+                        source_subnet,
+                        position as u64,
+                    ),
+                    readies: Vec::new(),
+                    threshold: 0,
+                },
+                certificate,
+            }
+        })
+        .collect()
+}
+
 #[test]
 fn encode() {
     use topos_core::api::grpc::shared::v1::Uuid as APIUuid;
@@ -122,6 +229,307 @@ async fn check_fetch_certificates() {
     assert_eq!(res.certificates, expected);
 }
 
+#[test_log::test(tokio::test)]
+async fn pruned_store_refuses_to_serve_the_synchronization() {
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+    for delivered in &mut certificates {
+        delivered.certificate.proof = vec![1; 32];
+    }
+
+    let fullnode_store = create_fullnode_store(&certificates).await;
+    let validator_store =
+        create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+    let service = SynchronizerService {
+        validator_store: validator_store.clone(),
+    };
+    let request = || FetchCertificatesRequest {
+        request_id: Some(Uuid::new_v4().into()),
+        certificates: vec![certificates[0].certificate.id.into()],
+    };
+
+    assert!(service
+        .fetch_certificates(tonic::Request::new(request()))
+        .await
+        .is_ok());
+
+    validator_store
+        .prune(&RetentionPolicy::default().with_proofs_per_source_stream(1))
+        .await
+        .unwrap();
+
+    let status = service
+        .fetch_certificates(tonic::Request::new(request()))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    let status = service
+        .fetch_checkpoint(tonic::Request::new(CheckpointRequest {
+            request_id: Some(Uuid::new_v4().into()),
+            checkpoint: Vec::new(),
+            limit_per_subnet: 10,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(10))]
+async fn peer_serving_invalid_certificate_is_penalized() {
+    let subnet = topos_test_sdk::constants::SOURCE_SUBNET_ID_1;
+    let mut certificates: Vec<CertificateDelivered> =
+        create_certificate_chain(subnet, &[topos_test_sdk::constants::TARGET_SUBNET_ID_1], 1);
+    // The content of the certificate doesn't match its id anymore
+    certificates[0].certificate.state_root = [9u8; 32];
+    let certificate_id = certificates[0].certificate.id;
+
+    let boot_node = NodeConfig::from_seed(1);
+    let cluster = create_network(5, &certificates[..]).await;
+    let boot_node = cluster
+        .get(&boot_node.keypair.public().to_peer_id())
+        .unwrap()
+        .node_config
+        .clone();
+
+    let cfg = NodeConfig {
+        seed: 6,
+        minimum_cluster_size: 3,
+        ..Default::default()
+    };
+
+    let fullnode_store = create_fullnode_store(&[]).await;
+    let validator_store =
+        create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+
+    let (network, _, _) = cfg
+        .bootstrap(&[cfg.clone(), boot_node.clone()], None)
+        .await
+        .unwrap();
+
+    let mut synchronizer = create_synchronizer(network, validator_store.clone(), HashSet::new(), 0);
+
+    assert!(matches!(
+        synchronizer.initiate_request().await,
+        Err(SyncError::InvalidCertificate { certificate_id: id, .. }) if id == certificate_id
+    ));
+    assert_eq!(synchronizer.penalized_peers.len(), 1);
+    assert!(validator_store
+        .get_certificate(&certificate_id)
+        .unwrap()
+        .is_none());
+}
+
+#[test_log::test(tokio::test)]
+async fn proofs_are_verified_against_the_validators_of_every_epoch() {
+    let genesis_validator = MessageSigner::new(&[1u8; 32]).unwrap();
+    let next_validator = MessageSigner::new(&[2u8; 32]).unwrap();
+    let outsider = MessageSigner::new(&[3u8; 32]).unwrap();
+
+    let fullnode_store = create_fullnode_store(&[]).await;
+    let validator_store =
+        create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+    let (network, _, _) = NodeConfig::default().create(&[], None).await.unwrap();
+    let peer = NodeConfig::from_seed(1).peer_id();
+
+    let synchronizer = create_synchronizer(
+        network,
+        validator_store.clone(),
+        [genesis_validator.public_address.into()]
+            .into_iter()
+            .collect(),
+        1,
+    );
+
+    let subnet = topos_test_sdk::constants::SOURCE_SUBNET_ID_1;
+    let certificates =
+        create_certificate_chain(subnet, &[topos_test_sdk::constants::TARGET_SUBNET_ID_1], 3);
+    let mut proofs: Vec<ProofOfDelivery> = certificates
+        .into_iter()
+        .map(|delivered| delivered.proof_of_delivery)
+        .collect();
+    sign_proof(&mut proofs[0], &[&genesis_validator]);
+    sign_proof(&mut proofs[1], &[&next_validator]);
+    sign_proof(&mut proofs[2], &[&outsider]);
+
+    let diff = |proofs: &[ProofOfDelivery]| HashMap::from([(subnet, proofs.to_vec())]);
+
+    assert!(synchronizer
+        .verify_checkpoint_diff(peer, &diff(&proofs[..1]))
+        .is_ok());
+    assert!(matches!(
+        synchronizer.verify_checkpoint_diff(peer, &diff(&proofs[..2])),
+        Err(SyncError::InvalidProofOfDelivery { .. })
+    ));
+
+    // The validators of the next epoch are known once the epoch has started
+    validator_store
+        .epoch_validators_store()
+        .insert_validators(
+            1,
+            [next_validator.public_address.into()].into_iter().collect(),
+        )
+        .unwrap();
+
+    assert!(synchronizer
+        .verify_checkpoint_diff(peer, &diff(&proofs[..2]))
+        .is_ok());
+    assert!(matches!(
+        synchronizer.verify_checkpoint_diff(peer, &diff(&proofs)),
+        Err(SyncError::InvalidProofOfDelivery { certificate_id, .. })
+            if certificate_id == proofs[2].certificate_id
+    ));
+}
+
+#[test_log::test(tokio::test)]
+async fn signed_checkpoint_is_verified_against_the_validators_of_its_epoch() {
+    let genesis_validator = MessageSigner::new(&[1u8; 32]).unwrap();
+    let validators: Vec<_> = (2..6u8)
+        .map(|seed| MessageSigner::new(&[seed; 32]).unwrap())
+        .collect();
+
+    let fullnode_store = create_fullnode_store(&[]).await;
+    let validator_store =
+        create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+    validator_store
+        .epoch_validators_store()
+        .insert_validators(
+            1,
+            validators
+                .iter()
+                .map(|signer| signer.public_address.into())
+                .collect(),
+        )
+        .unwrap();
+    let (network, _, _) = NodeConfig::default().create(&[], None).await.unwrap();
+    let peer = NodeConfig::from_seed(1).peer_id();
+
+    let synchronizer = create_synchronizer(
+        network,
+        validator_store,
+        [genesis_validator.public_address.into()]
+            .into_iter()
+            .collect(),
+        1,
+    );
+
+    let checkpoint = CheckpointSummary::new(1, 0, Vec::new(), Vec::new());
+    let verify = |signers: &[&MessageSigner]| {
+        synchronizer.verify_signed_checkpoint(
+            peer,
+            &sign_checkpoint(&checkpoint, signers),
+            Vec::new(),
+        )
+    };
+
+    // The genesis validators don't sign the checkpoints of the epoch 1
+    assert!(matches!(
+        verify(&[&genesis_validator]),
+        Err(SyncError::InvalidSignedCheckpoint {
+            error: CheckpointSignatureError::UnknownValidator(_),
+            ..
+        })
+    ));
+    assert!(matches!(
+        verify(&[&validators[0], &validators[1]]),
+        Err(SyncError::InvalidSignedCheckpoint {
+            error: CheckpointSignatureError::NotEnoughSignatures {
+                received: 2,
+                threshold: 3
+            },
+            ..
+        })
+    ));
+    assert!(verify(&[&validators[0], &validators[1], &validators[2]]).is_ok());
+}
+
+#[test_log::test(tokio::test)]
+async fn bootstrap_inserts_the_heads_at_the_positions_of_the_checkpoint() {
+    let validator = MessageSigner::new(&[1u8; 32]).unwrap();
+    let certificates = create_certificates_with_targets(
+        SOURCE_SUBNET_ID_1,
+        &[
+            &[TARGET_SUBNET_ID_1],
+            &[TARGET_SUBNET_ID_2],
+            &[TARGET_SUBNET_ID_2],
+            &[TARGET_SUBNET_ID_1, TARGET_SUBNET_ID_2],
+        ],
+    );
+    let (history, next) = certificates.split_at(3);
+
+    let serving_store = create_fullnode_store(history).await;
+    let checkpoint = sign_checkpoint(
+        &CheckpointSummary::new(
+            0,
+            0,
+            serving_store.get_checkpoint().unwrap().into_values(),
+            serving_store.get_target_checkpoint().unwrap(),
+        ),
+        &[&validator],
+    );
+    // The source stream head along with the head of the stream to the first target
+    let heads = vec![
+        history[2].proof_of_delivery.clone(),
+        history[0].proof_of_delivery.clone(),
+    ];
+
+    let fullnode_store = create_fullnode_store(&[]).await;
+    let validator_store =
+        create_validator_store(&[], futures::future::ready(fullnode_store.clone())).await;
+    let (network, _, _) = NodeConfig::default().create(&[], None).await.unwrap();
+    let peer = NodeConfig::from_seed(1).peer_id();
+
+    let synchronizer = create_synchronizer(
+        network,
+        validator_store.clone(),
+        [validator.public_address.into()].into_iter().collect(),
+        0,
+    );
+
+    assert!(matches!(
+        synchronizer.verify_signed_checkpoint(peer, &checkpoint, heads[..1].to_vec()),
+        Err(SyncError::MissingCheckpointHead { certificate_id, .. })
+            if certificate_id == history[0].certificate.id
+    ));
+
+    let heads = synchronizer
+        .verify_signed_checkpoint(peer, &checkpoint, heads)
+        .unwrap();
+    let certificate_ids = validator_store.insert_unverified_proofs(heads).unwrap();
+    let served = history
+        .iter()
+        .filter(|delivered| certificate_ids.contains(&delivered.certificate.id))
+        .map(|delivered| delivered.certificate.clone())
+        .collect();
+    synchronizer
+        .insert_checkpoint_heads(peer, checkpoint.clone(), served)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        fullnode_store.get_checkpoint().unwrap(),
+        serving_store.get_checkpoint().unwrap()
+    );
+    assert_eq!(
+        fullnode_store.get_target_checkpoint().unwrap(),
+        serving_store.get_target_checkpoint().unwrap()
+    );
+    assert_eq!(
+        fullnode_store.get_latest_signed_checkpoint().unwrap(),
+        Some(checkpoint)
+    );
+
+    // The following certificates are delivered after the heads of the streams
+    let positions = validator_store
+        .insert_certificate_delivered(&next[0])
+        .await
+        .unwrap();
+    assert_eq!(*positions.source.position, 3);
+    assert_eq!(*positions.targets[&TARGET_SUBNET_ID_1].position, 1);
+    assert_eq!(*positions.targets[&TARGET_SUBNET_ID_2].position, 2);
+}
+
 #[test]
 fn sync_unordered_certificates() {}
 
@@ -18,13 +18,11 @@ use tokio_util::sync::CancellationToken;
 use tonic::{Request, Response, Status};
 use topos_config::tce::synchronization::SynchronizationConfig;
 use topos_core::{
-    api::grpc::{
-        shared::v1::positions::SourceStreamPosition,
-        tce::v1::{
-            synchronizer_service_server::SynchronizerService as GrpcSynchronizerService,
-            CheckpointMapFieldEntry, CheckpointRequest, CheckpointResponse,
-            FetchCertificatesRequest, FetchCertificatesResponse, ProofOfDelivery, SignedReady,
-        },
+    api::grpc::tce::v1::{
+        synchronizer_service_server::SynchronizerService as GrpcSynchronizerService,
+        CheckpointMapFieldEntry, CheckpointRequest, CheckpointResponse, FetchCertificatesRequest,
+        FetchCertificatesResponse, ProofOfDelivery, SignedCheckpointRequest,
+        SignedCheckpointResponse,
     },
     uci::CertificateId,
 };
@@ -106,12 +104,34 @@ pub struct SynchronizerService {
     pub validator_store: Arc<ValidatorStore>,
 }
 
+impl SynchronizerService {
+    /// Refuse to serve the synchronization from a pruned store, whose old certificates no longer
+    /// match their id
+    fn check_not_pruned(&self) -> Result<(), Status> {
+        match self.validator_store.fullnode_store().is_pruned() {
+            Ok(false) => Ok(()),
+            Ok(true) => Err(Status::failed_precondition(
+                "The proofs of this node are pruned, it can't serve the synchronization",
+            )),
+            Err(error) => {
+                error!("Unable to check whether the store is pruned: {error}");
+
+                Err(Status::internal(
+                    "Unable to check whether the store is pruned",
+                ))
+            }
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl GrpcSynchronizerService for SynchronizerService {
     async fn fetch_certificates(
         &self,
         request: Request<FetchCertificatesRequest>,
     ) -> Result<Response<FetchCertificatesResponse>, Status> {
+        self.check_not_pruned()?;
+
         let request = request.into_inner();
         let certificate_ids: Vec<CertificateId> = request
             .certificates
@@ -144,6 +164,8 @@ impl GrpcSynchronizerService for SynchronizerService {
         &self,
         request: Request<CheckpointRequest>,
     ) -> Result<Response<CheckpointResponse>, Status> {
+        self.check_not_pruned()?;
+
         let request = request.into_inner();
         let id = request
             .request_id
@@ -186,22 +208,7 @@ impl GrpcSynchronizerService for SynchronizerService {
                 );
                 diff.into_iter()
                     .map(|(key, value)| {
-                        let v: Vec<_> = value
-                            .into_iter()
-                            .map(|v| ProofOfDelivery {
-                                delivery_position: Some(SourceStreamPosition {
-                                    source_subnet_id: Some(v.delivery_position.subnet_id.into()),
-                                    position: *v.delivery_position.position,
-                                    certificate_id: Some(v.certificate_id.into()),
-                                }),
-                                readies: v
-                                    .readies
-                                    .into_iter()
-                                    .map(|(ready, signature)| SignedReady { ready, signature })
-                                    .collect(),
-                                threshold: v.threshold,
-                            })
-                            .collect();
+                        let v: Vec<ProofOfDelivery> = value.into_iter().map(Into::into).collect();
                         CheckpointMapFieldEntry {
                             key: key.to_string(),
                             value: v,
@@ -233,4 +240,68 @@ impl GrpcSynchronizerService for SynchronizerService {
 
         Ok(Response::new(response))
     }
+
+    async fn fetch_signed_checkpoint(
+        &self,
+        request: Request<SignedCheckpointRequest>,
+    ) -> Result<Response<SignedCheckpointResponse>, Status> {
+        let request = request.into_inner();
+
+        let checkpoint = self
+            .validator_store
+            .fullnode_store()
+            .get_latest_signed_checkpoint()
+            .map_err(|error| {
+                error!("Unable to get the latest signed checkpoint: {error}");
+
+                Status::internal("Unable to get the latest signed checkpoint")
+            })?;
+
+        let Some(checkpoint) = checkpoint else {
+            return Ok(Response::new(SignedCheckpointResponse {
+                request_id: request.request_id,
+                checkpoint: None,
+                heads: Vec::new(),
+            }));
+        };
+
+        let mut certificate_ids: Vec<CertificateId> = checkpoint
+            .0
+            .checkpoint_data
+            .iter()
+            .map(|head| head.certificate_id)
+            .collect();
+        for head in &checkpoint.0.target_checkpoint_data {
+            if !certificate_ids.contains(&head.certificate_id) {
+                certificate_ids.push(head.certificate_id);
+            }
+        }
+
+        let heads = self
+            .validator_store
+            .get_certificates(&certificate_ids[..])
+            .map_err(|error| {
+                error!("Unable to get the heads of the signed checkpoint: {error}");
+
+                Status::internal("Unable to get the heads of the signed checkpoint")
+            })?
+            .into_iter()
+            .map(|delivered| {
+                delivered
+                    .map(|delivered| delivered.proof_of_delivery.into())
+                    .ok_or_else(|| Status::internal("Missing head of the signed checkpoint"))
+            })
+            .collect::<Result<Vec<ProofOfDelivery>, _>>()?;
+
+        debug!(
+            "Responding to request {:?} with the signed checkpoint of epoch {}",
+            request.request_id, checkpoint.0.epoch
+        );
+
+        Ok(Response::new(SignedCheckpointResponse {
+            request_id: request.request_id,
+            checkpoint: Some(checkpoint.into()),
+            heads,
+        }))
+    }
 }
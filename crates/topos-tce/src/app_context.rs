@@ -2,13 +2,17 @@
 //! Application logic glue
 //!
 use crate::events::Events;
+use epoch::{CheckpointRound, PendingCheckpoint};
 use futures::{Stream, StreamExt};
 use prometheus::HistogramTimer;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
-use topos_core::uci::CertificateId;
+use topos_clock::Event as ClockEvent;
+use topos_core::api::grpc::tce::v1::PeerBinding;
+use topos_core::uci::{CertificateId, CertificateValidator};
+use topos_crypto::messages::MessageSigner;
 use topos_metrics::CERTIFICATE_DELIVERED_TOTAL;
 use topos_p2p::{Event as NetEvent, NetworkClient};
 use topos_tce_api::RuntimeClient as ApiClient;
@@ -25,6 +29,7 @@ use topos_tce_synchronizer::SynchronizerEvent;
 use tracing::{error, info, warn};
 
 mod api;
+pub(crate) mod epoch;
 mod network;
 pub(crate) mod protocol;
 
@@ -49,6 +54,16 @@ pub struct AppContext {
 
     pub validator_store: Arc<ValidatorStore>,
     pub api_context: RuntimeContext,
+    pub message_signer: Arc<MessageSigner>,
+    /// Checkpoints signed by validators, indexed by their round, until reaching the quorum
+    pub pending_checkpoints: BTreeMap<CheckpointRound, PendingCheckpoint>,
+    /// Latest round of the checkpoint agreement started by the clock
+    pub checkpoint_round: Option<CheckpointRound>,
+    /// Binding of the local peer to the validator, signed for the current epoch and attached to
+    /// the Echo and Ready messages
+    pub peer_binding: Option<PeerBinding>,
+    /// Validation pipeline run on the certificates submitted or gossiped
+    pub certificate_validator: CertificateValidator,
 }
 
 impl AppContext {
@@ -68,6 +83,8 @@ impl AppContext {
         gatekeeper: GatekeeperClient,
         validator_store: Arc<ValidatorStore>,
         api_context: RuntimeContext,
+        message_signer: Arc<MessageSigner>,
+        certificate_validator: CertificateValidator,
     ) -> (Self, mpsc::Receiver<Events>) {
         let (events, receiver) = mpsc::channel(100);
         (
@@ -82,6 +99,11 @@ impl AppContext {
                 delivery_latency: Default::default(),
                 validator_store,
                 api_context,
+                message_signer,
+                pending_checkpoints: Default::default(),
+                checkpoint_round: None,
+                peer_binding: None,
+                certificate_validator,
             },
             receiver,
         )
@@ -96,6 +118,7 @@ impl AppContext {
         mut api_stream: impl Stream<Item = ApiEvent> + Unpin,
         mut synchronizer_stream: impl Stream<Item = SynchronizerEvent> + Unpin,
         mut broadcast_stream: impl Stream<Item = CertificateDeliveredWithPositions> + Unpin,
+        mut clock_stream: impl Stream<Item = ClockEvent> + Unpin,
         shutdown: (CancellationToken, mpsc::Sender<()>),
     ) {
         loop {
@@ -130,6 +153,11 @@ impl AppContext {
                 Some(_event) = synchronizer_stream.next() => {
                 }
 
+                // Clock events
+                Some(event) = clock_stream.next() => {
+                    self.on_clock_event(event).await;
+                }
+
                 // Shutdown signal
                 _ = shutdown.0.cancelled() => {
                     info!("Shutting down TCE app context...");
@@ -1,12 +1,14 @@
 use crate::AppContext;
 use std::collections::HashMap;
-use topos_core::uci::{Certificate, SubnetId};
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
 use topos_metrics::CERTIFICATE_DELIVERY_LATENCY;
-use topos_tce_api::RuntimeError;
 use topos_tce_api::RuntimeEvent as ApiEvent;
+use topos_tce_api::{CertificateStatus, RuntimeError};
 use topos_tce_broadcast::DoubleEchoCommand;
 use topos_tce_storage::errors::{InternalStorageError, StorageError};
+use topos_tce_storage::store::ReadStore;
 use topos_tce_storage::types::PendingResult;
+use topos_tce_storage::CertificatePositions;
 use tracing::debug;
 use tracing::{error, warn};
 
@@ -17,82 +19,21 @@ impl AppContext {
                 certificate,
                 sender,
             } => {
-                self.delivery_latency
-                    .insert(certificate.id, CERTIFICATE_DELIVERY_LATENCY.start_timer());
-
-                _ = match self
-                    .validator_store
-                    .insert_pending_certificate(&certificate)
-                    .await
-                {
-                    Ok(Some(pending_id)) => {
-                        let certificate_id = certificate.id;
-                        debug!(
-                            "Certificate {} from subnet {} has been inserted into pending pool",
-                            certificate_id, certificate.source_subnet_id
-                        );
+                _ = sender.send(self.submit_certificate(*certificate).await);
+            }
 
-                        if self
-                            .tce_cli
-                            .get_double_echo_channel()
-                            .send(DoubleEchoCommand::Broadcast {
-                                need_gossip: true,
-                                cert: *certificate,
-                                pending_id,
-                            })
-                            .await
-                            .is_err()
-                        {
-                            error!(
-                                "Unable to send DoubleEchoCommand::Broadcast command to double \
-                                 echo for {}",
-                                certificate_id
-                            );
-
-                            sender.send(Err(RuntimeError::CommunicationError(
-                                "Unable to send DoubleEchoCommand::Broadcast command to double \
-                                 echo"
-                                    .to_string(),
-                            )))
-                        } else {
-                            sender.send(Ok(PendingResult::InPending(pending_id)))
-                        }
-                    }
-                    Ok(None) => {
-                        debug!(
-                            "Certificate {} from subnet {} has been inserted into precedence pool \
-                             waiting for {}",
-                            certificate.id, certificate.source_subnet_id, certificate.prev_id
-                        );
-                        sender.send(Ok(PendingResult::AwaitPrecedence))
-                    }
-                    Err(StorageError::InternalStorage(
-                        InternalStorageError::CertificateAlreadyPending,
-                    )) => {
-                        debug!(
-                            "Certificate {} has already been added to the pending pool, skipping",
-                            certificate.id
-                        );
-                        sender.send(Ok(PendingResult::AlreadyPending))
-                    }
-                    Err(StorageError::InternalStorage(
-                        InternalStorageError::CertificateAlreadyExists,
-                    )) => {
-                        debug!(
-                            "Certificate {} has already been delivered, skipping",
-                            certificate.id
-                        );
-                        sender.send(Ok(PendingResult::AlreadyDelivered))
-                    }
-                    Err(error) => {
-                        error!(
-                            "Unable to insert pending certificate {}: {}",
-                            certificate.id, error
-                        );
+            ApiEvent::CertificatesSubmitted {
+                certificates,
+                sender,
+            } => {
+                let mut results = Vec::with_capacity(certificates.len());
+                // Submitted one after the other, so that each certificate finds its previous
+                // one in the pending pool and waits in the precedence pool
+                for certificate in certificates {
+                    results.push(self.submit_certificate(certificate).await);
+                }
 
-                        sender.send(Err(error.into()))
-                    }
-                };
+                _ = sender.send(results);
             }
 
             ApiEvent::GetSourceHead { subnet_id, sender } => {
@@ -144,6 +85,68 @@ impl AppContext {
                 _ = sender.send(result);
             }
 
+            ApiEvent::GetCertificateStatus {
+                certificate_id,
+                sender,
+            } => {
+                _ = sender.send(self.certificate_status(&certificate_id).await);
+            }
+
+            ApiEvent::GetPeers { sender } => {
+                _ = sender.send(
+                    self.network_client
+                        .peers_info()
+                        .await
+                        .map_err(|error| RuntimeError::CommunicationError(error.to_string())),
+                );
+            }
+
+            ApiEvent::GetBroadcastProgress { sender } => {
+                _ = sender.send(
+                    self.tce_cli
+                        .get_broadcast_progress()
+                        .await
+                        .map_err(|error| RuntimeError::CommunicationError(error.to_string())),
+                );
+            }
+
+            ApiEvent::EvictPendingCertificate {
+                certificate_id,
+                sender,
+            } => {
+                let result = match self
+                    .validator_store
+                    .evict_pending_certificate(&certificate_id)
+                    .await
+                {
+                    Ok(evicted) if evicted.is_empty() => {
+                        Err(RuntimeError::CertificateNotPending(certificate_id))
+                    }
+                    Ok(evicted) => {
+                        warn!(
+                            "Certificate {} has been evicted from the pending pool along with {} \
+                             certificates depending on it",
+                            certificate_id,
+                            evicted.len() - 1
+                        );
+
+                        let mut result = Ok(());
+                        for certificate in evicted {
+                            self.delivery_latency.remove(&certificate.id);
+
+                            if let Err(error) = self.tce_cli.evict(certificate.id).await {
+                                result = Err(RuntimeError::CommunicationError(error.to_string()));
+                            }
+                        }
+
+                        result
+                    }
+                    Err(error) => Err(error.into()),
+                };
+
+                _ = sender.send(result);
+            }
+
             ApiEvent::GetLastPendingCertificates {
                 mut subnet_ids,
                 sender,
@@ -185,4 +188,133 @@ impl AppContext {
             }
         }
     }
+
+    /// Validate the certificate and insert it in the pending pool, or in the precedence pool
+    /// when its previous certificate is not delivered yet
+    async fn submit_certificate(
+        &mut self,
+        certificate: Certificate,
+    ) -> Result<PendingResult, RuntimeError> {
+        if let Err(error) = self.certificate_validator.validate(&certificate) {
+            warn!(
+                "Rejecting the certificate {} from subnet {}: {}",
+                certificate.id, certificate.source_subnet_id, error
+            );
+
+            return Err(RuntimeError::InvalidCertificate(error));
+        }
+
+        self.delivery_latency
+            .insert(certificate.id, CERTIFICATE_DELIVERY_LATENCY.start_timer());
+
+        match self
+            .validator_store
+            .insert_pending_certificate(&certificate)
+            .await
+        {
+            Ok(Some(pending_id)) => {
+                let certificate_id = certificate.id;
+                debug!(
+                    "Certificate {} from subnet {} has been inserted into pending pool",
+                    certificate_id, certificate.source_subnet_id
+                );
+
+                if self
+                    .tce_cli
+                    .get_double_echo_channel()
+                    .send(DoubleEchoCommand::Broadcast {
+                        need_gossip: true,
+                        cert: certificate,
+                        pending_id,
+                    })
+                    .await
+                    .is_err()
+                {
+                    error!(
+                        "Unable to send DoubleEchoCommand::Broadcast command to double echo for {}",
+                        certificate_id
+                    );
+
+                    Err(RuntimeError::CommunicationError(
+                        "Unable to send DoubleEchoCommand::Broadcast command to double echo"
+                            .to_string(),
+                    ))
+                } else {
+                    Ok(PendingResult::InPending(pending_id))
+                }
+            }
+            Ok(None) => {
+                debug!(
+                    "Certificate {} from subnet {} has been inserted into precedence pool waiting \
+                     for {}",
+                    certificate.id, certificate.source_subnet_id, certificate.prev_id
+                );
+                Ok(PendingResult::AwaitPrecedence)
+            }
+            Err(StorageError::InternalStorage(InternalStorageError::CertificateAlreadyPending)) => {
+                debug!(
+                    "Certificate {} has already been added to the pending pool, skipping",
+                    certificate.id
+                );
+                Ok(PendingResult::AlreadyPending)
+            }
+            Err(StorageError::InternalStorage(InternalStorageError::CertificateAlreadyExists)) => {
+                debug!(
+                    "Certificate {} has already been delivered, skipping",
+                    certificate.id
+                );
+                Ok(PendingResult::AlreadyDelivered)
+            }
+            Err(error) => {
+                error!(
+                    "Unable to insert pending certificate {}: {}",
+                    certificate.id, error
+                );
+
+                Err(error.into())
+            }
+        }
+    }
+
+    /// Look for the certificate from the most advanced stage to the least advanced one:
+    /// delivered, being broadcast, pending and finally awaiting its previous certificate
+    async fn certificate_status(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificateStatus>, RuntimeError> {
+        if let Some(delivered) = self.validator_store.get_certificate(certificate_id)? {
+            let targets = self
+                .validator_store
+                .fullnode_store()
+                .get_target_positions(&delivered)?;
+
+            return Ok(Some(CertificateStatus::Delivered(CertificatePositions {
+                targets,
+                source: delivered.proof_of_delivery.delivery_position,
+            })));
+        }
+
+        if let Some(progress) = self
+            .tce_cli
+            .get_certificate_progress(*certificate_id)
+            .await
+            .map_err(|error| RuntimeError::CommunicationError(error.to_string()))?
+        {
+            return Ok(Some(CertificateStatus::InBroadcast {
+                echo_count: progress.echo_count,
+                ready_count: progress.ready_count,
+            }));
+        }
+
+        if let Some(pending_id) = self.validator_store.get_pending_id(certificate_id)? {
+            return Ok(Some(CertificateStatus::Pending { pending_id }));
+        }
+
+        Ok(self
+            .validator_store
+            .get_awaiting_precedence(certificate_id)?
+            .map(|certificate| CertificateStatus::AwaitingPrecedence {
+                prev_id: certificate.prev_id,
+            }))
+    }
 }
@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+
+use crate::AppContext;
+use topos_clock::Event as ClockEvent;
+use topos_config::tce::broadcast::ReliableBroadcastParams;
+use topos_core::api::grpc::tce::v1::{
+    double_echo_request, DoubleEchoRequest, PeerBinding, SignedCheckpoint,
+};
+use topos_core::types::Signature;
+use topos_crypto::validator_id::ValidatorId;
+use topos_tce_gatekeeper::{peer_binding_payload, GatekeeperError};
+use topos_tce_storage::store::ReadStore;
+use topos_tce_storage::types::{
+    CheckpointSummary, EpochId, ValidatorQuorumSignatureInfo, Validators, VerifiedCheckpointSummary,
+};
+use tracing::{debug, error, info, warn};
+
+/// Round of the checkpoint agreement, identified by the epoch and the sequence number of the
+/// checkpoint within the epoch
+pub type CheckpointRound = (EpochId, usize);
+
+/// Checkpoints of a round waiting for enough validator signatures to reach the quorum
+#[derive(Default)]
+pub struct PendingCheckpoint {
+    /// Checkpoint proposed for the round, waiting for its heads to be delivered to be signed
+    pub proposal: Option<CheckpointSummary>,
+    /// Checkpoints signed during the round along with their signatures, indexed by payload
+    pub signatures: HashMap<Vec<u8>, (CheckpointSummary, HashMap<ValidatorId, Signature>)>,
+}
+
+impl PendingCheckpoint {
+    fn is_signed_by(&self, validator_id: &ValidatorId) -> bool {
+        self.signatures
+            .values()
+            .any(|(_, signatures)| signatures.contains_key(validator_id))
+    }
+}
+
+/// Returns the validator proposing the checkpoint of the round, the validators taking turns
+/// from one round to the next
+pub(crate) fn checkpoint_proposer(
+    validators: &Validators,
+    (epoch_id, sequence_number): CheckpointRound,
+) -> Option<ValidatorId> {
+    let mut validators: Vec<_> = validators.iter().copied().collect();
+    validators.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+    (!validators.is_empty())
+        .then(|| validators[(epoch_id as usize + sequence_number) % validators.len()])
+}
+
+impl AppContext {
+    /// Maximum number of rounds gathering signatures at the same time
+    const MAX_PENDING_CHECKPOINT_ROUNDS: usize = 4;
+
+    pub async fn on_clock_event(&mut self, event: ClockEvent) {
+        match event {
+            ClockEvent::EpochChange(epoch_id) => {
+                if let Err(error) = self.start_epoch(epoch_id).await {
+                    error!("Unable to start the epoch {epoch_id}: {error}");
+                }
+            }
+            ClockEvent::Checkpoint {
+                epoch,
+                sequence_number,
+            } => {
+                if let Err(error) = self
+                    .start_checkpoint_round((epoch, sequence_number as usize))
+                    .await
+                {
+                    error!(
+                        "Unable to start the checkpoint {sequence_number} of epoch {epoch}: \
+                         {error}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Start a new epoch, closing the current one with the first checkpoint of the epoch.
+    async fn start_epoch(&mut self, epoch_id: EpochId) -> Result<(), Box<dyn std::error::Error>> {
+        let fullnode_store = self.validator_store.fullnode_store();
+
+        if let Some(latest) = fullnode_store.get_latest_epoch_summary()? {
+            if latest.epoch_id > epoch_id {
+                warn!(
+                    "Ignoring the epoch {epoch_id} as the epoch {} is already started",
+                    latest.epoch_id
+                );
+
+                return Ok(());
+            }
+        }
+
+        // The validator set is kept as is until validator changes are driven on-chain
+        let validators = self
+            .validator_store
+            .epoch_validators_store()
+            .get_latest_validators()?
+            .map(|(_, validators)| validators)
+            .unwrap_or_default();
+
+        self.sign_peer_binding(epoch_id);
+        self.update_admission(epoch_id, validators.clone()).await;
+        self.tce_cli.new_epoch(epoch_id, validators).await?;
+        self.api_client.set_epoch(epoch_id).await;
+
+        info!("Epoch {epoch_id} started");
+
+        self.start_checkpoint_round((epoch_id, 0)).await
+    }
+
+    /// Start a round of the checkpoint agreement.
+    ///
+    /// The proposer of the round signs a checkpoint of its source and target stream heads, the
+    /// other validators signing it once they delivered the same heads. The checkpoint is
+    /// persisted once signed by a quorum of validators, a round missing the quorum being retried
+    /// by the following ones.
+    async fn start_checkpoint_round(
+        &mut self,
+        round: CheckpointRound,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(latest) = self.latest_checkpoint_round()? {
+            if latest >= round {
+                debug!(
+                    "Checkpoint {} of epoch {} is already signed by a quorum of validators",
+                    latest.1, latest.0
+                );
+
+                return Ok(());
+            }
+        }
+
+        self.checkpoint_round = Some(round);
+
+        // The previous round is kept to gather the signatures still in flight
+        let previous = self
+            .pending_checkpoints
+            .range(..round)
+            .next_back()
+            .map(|(previous, _)| *previous);
+        self.pending_checkpoints
+            .retain(|pending_round, _| Some(*pending_round) >= previous);
+
+        if !self.is_validator {
+            return Ok(());
+        }
+
+        // Proposals whose heads weren't delivered on reception are checked again
+        let proposed_rounds: Vec<_> = self
+            .pending_checkpoints
+            .iter()
+            .filter(|(_, pending)| pending.proposal.is_some())
+            .map(|(proposed_round, _)| *proposed_round)
+            .collect();
+        for proposed_round in proposed_rounds {
+            self.sign_proposal(proposed_round).await?;
+        }
+
+        let validators = self.checkpoint_validators(round.0)?;
+        let validator_id: ValidatorId = self.message_signer.public_address.into();
+
+        if checkpoint_proposer(&validators, round) == Some(validator_id) {
+            let fullnode_store = self.validator_store.fullnode_store();
+            let checkpoint = CheckpointSummary::new(
+                round.0,
+                round.1,
+                fullnode_store.get_checkpoint()?.into_values(),
+                fullnode_store.get_target_checkpoint()?,
+            );
+
+            let signature = self.message_signer.sign_message(&checkpoint.payload())?;
+
+            self.share_checkpoint_signatures(&checkpoint, vec![(validator_id, signature)])
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// Sign the checkpoint proposed for the round if its heads are delivered locally, sharing
+    /// the signature along with those already gathered for it.
+    async fn sign_proposal(
+        &mut self,
+        round: CheckpointRound,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let fullnode_store = self.validator_store.fullnode_store();
+        let validator_id: ValidatorId = self.message_signer.public_address.into();
+
+        let Some(pending) = self.pending_checkpoints.get_mut(&round) else {
+            return Ok(());
+        };
+
+        let Some(checkpoint) = pending.proposal.take() else {
+            return Ok(());
+        };
+
+        if pending.is_signed_by(&validator_id) {
+            return Ok(());
+        }
+
+        if !fullnode_store.has_delivered_heads(&checkpoint)? {
+            debug!(
+                "Heads of the checkpoint {} of epoch {} aren't delivered yet",
+                round.1, round.0
+            );
+            pending.proposal = Some(checkpoint);
+
+            return Ok(());
+        }
+
+        let payload = checkpoint.payload();
+        let mut signatures: Vec<_> = pending
+            .signatures
+            .get(&payload)
+            .map(|(_, signatures)| signatures.clone().into_iter().collect())
+            .unwrap_or_default();
+        signatures.push((validator_id, self.message_signer.sign_message(&payload)?));
+
+        self.share_checkpoint_signatures(&checkpoint, signatures)
+            .await;
+
+        Ok(())
+    }
+
+    /// Sign the binding of the local peer to the validator for the epoch, allowing the other
+    /// nodes to admit the peer as the publisher of the Echo and Ready of the validator
+    pub fn sign_peer_binding(&mut self, epoch_id: EpochId) {
+        if !self.is_validator {
+            return;
+        }
+
+        let payload = peer_binding_payload(&self.network_client.local_peer_id, epoch_id);
+        match self.message_signer.sign_message(&payload) {
+            Ok(signature) => {
+                self.peer_binding = Some(PeerBinding {
+                    epoch_id,
+                    signature: Some(signature.into()),
+                })
+            }
+            Err(error) => error!("Unable to sign the peer binding of epoch {epoch_id}: {error}"),
+        }
+    }
+
+    /// Update the gatekeeper with the validators of the epoch, denying the peers of the
+    /// validators leaving the set from the echo and ready topics.
+    async fn update_admission(&mut self, epoch_id: EpochId, validators: Validators) {
+        match self.gatekeeper.update_epoch(epoch_id, validators).await {
+            Ok(update) => {
+                info!(
+                    "Validator set of epoch {epoch_id} changed: {} added, {} removed",
+                    update.added_validators.len(),
+                    update.removed_validators.len()
+                );
+
+                if update.revoked_peers.is_empty() && update.allowed_peers.is_empty() {
+                    return;
+                }
+
+                if let Err(error) = self
+                    .network_client
+                    .update_denied_peers(update.revoked_peers, update.allowed_peers)
+                    .await
+                {
+                    error!("Unable to update the denied peers of epoch {epoch_id}: {error}");
+                }
+            }
+            Err(GatekeeperError::NoUpdate) => {}
+            Err(error) => error!("Unable to update the gatekeeper for epoch {epoch_id}: {error}"),
+        }
+    }
+
+    /// Publish the signatures of a checkpoint and account for them locally
+    async fn share_checkpoint_signatures(
+        &mut self,
+        checkpoint: &CheckpointSummary,
+        signatures: Vec<(ValidatorId, Signature)>,
+    ) {
+        let request = DoubleEchoRequest {
+            request: Some(double_echo_request::Request::Checkpoint(
+                VerifiedCheckpointSummary(
+                    checkpoint.clone(),
+                    ValidatorQuorumSignatureInfo {
+                        epoch: checkpoint.epoch,
+                        signatures: signatures.clone(),
+                    },
+                )
+                .into(),
+            )),
+        };
+
+        if let Err(error) = self
+            .network_client
+            .publish(topos_p2p::TOPOS_CHECKPOINT, request)
+            .await
+        {
+            error!("Unable to send checkpoint signature: {error}");
+        }
+
+        for (validator_id, signature) in signatures {
+            if let Err(error) = self.add_checkpoint_signature(checkpoint, validator_id, signature) {
+                error!(
+                    "Unable to add the signature of the checkpoint {} of epoch {}: {error}",
+                    checkpoint.sequence_number, checkpoint.epoch
+                );
+            }
+        }
+    }
+
+    pub async fn on_checkpoint_signature(&mut self, checkpoint: SignedCheckpoint) {
+        let VerifiedCheckpointSummary(checkpoint, quorum) = match checkpoint.try_into() {
+            Ok(checkpoint) => checkpoint,
+            Err(error) => {
+                error!("Failed to parse the received checkpoint: {error}");
+                return;
+            }
+        };
+
+        let round = (checkpoint.epoch, checkpoint.sequence_number);
+        let proposer = match self.checkpoint_validators(checkpoint.epoch) {
+            Ok(validators) => checkpoint_proposer(&validators, round),
+            Err(error) => {
+                error!(
+                    "Unable to get the validators of epoch {}: {error}",
+                    checkpoint.epoch
+                );
+                return;
+            }
+        };
+
+        let mut proposed = false;
+        for (validator_id, signature) in quorum.signatures {
+            match self.add_checkpoint_signature(&checkpoint, validator_id, signature) {
+                Ok(()) => proposed |= Some(validator_id) == proposer,
+                Err(error) => error!(
+                    "Unable to add the signature of {validator_id} for the checkpoint {} of epoch \
+                     {}: {error}",
+                    checkpoint.sequence_number, checkpoint.epoch
+                ),
+            }
+        }
+
+        if !proposed || !self.is_validator {
+            return;
+        }
+
+        // The round is gone if the checkpoint already reached the quorum
+        if let Some(pending) = self.pending_checkpoints.get_mut(&round) {
+            pending.proposal.get_or_insert(checkpoint);
+        }
+
+        if let Err(error) = self.sign_proposal(round).await {
+            error!(
+                "Unable to sign the checkpoint {} of epoch {}: {error}",
+                round.1, round.0
+            );
+        }
+    }
+
+    /// Account for the signature of a checkpoint, persisting the checkpoint once signed by a
+    /// quorum of the validators of its epoch.
+    fn add_checkpoint_signature(
+        &mut self,
+        checkpoint: &CheckpointSummary,
+        validator_id: ValidatorId,
+        signature: Signature,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let round = (checkpoint.epoch, checkpoint.sequence_number);
+
+        if let Some(latest) = self.latest_checkpoint_round()? {
+            if latest >= round {
+                debug!(
+                    "Ignoring signature for the checkpoint {} of epoch {} as the checkpoint {} of \
+                     epoch {} is already signed",
+                    round.1, round.0, latest.1, latest.0
+                );
+
+                return Ok(());
+            }
+        }
+
+        if let Some((epoch_id, _)) = self.checkpoint_round {
+            if round.0 > epoch_id + 1 {
+                return Err(format!("Epoch {} is too far ahead", round.0).into());
+            }
+        }
+
+        let validators = self.checkpoint_validators(checkpoint.epoch)?;
+        let payload = checkpoint.payload();
+
+        if !validators.contains(&validator_id) {
+            return Err(format!("{validator_id} isn't a validator").into());
+        }
+
+        signature
+            .verify(payload.clone(), validator_id.address())
+            .map_err(|_| format!("Invalid signature from {validator_id}"))?;
+
+        if !self.pending_checkpoints.contains_key(&round)
+            && self.pending_checkpoints.len() >= Self::MAX_PENDING_CHECKPOINT_ROUNDS
+        {
+            return Err(format!(
+                "Too many pending checkpoints to start the checkpoint {} of epoch {}",
+                round.1, round.0
+            )
+            .into());
+        }
+
+        let pending = self.pending_checkpoints.entry(round).or_default();
+
+        // A validator signs a single checkpoint per round, which bounds the number of pending
+        // checkpoints of a round by the number of validators
+        if pending
+            .signatures
+            .iter()
+            .any(|(pending_payload, (_, signatures))| {
+                *pending_payload != payload && signatures.contains_key(&validator_id)
+            })
+        {
+            return Err(format!(
+                "{validator_id} already signed another checkpoint {} of epoch {}",
+                round.1, round.0
+            )
+            .into());
+        }
+
+        let (_, signatures) = pending
+            .signatures
+            .entry(payload)
+            .or_insert_with(|| (checkpoint.clone(), HashMap::new()));
+        signatures.insert(validator_id, signature);
+
+        let threshold = ReliableBroadcastParams::new(validators.len()).delivery_threshold;
+        if signatures.len() < threshold {
+            return Ok(());
+        }
+
+        let signed = VerifiedCheckpointSummary(
+            checkpoint.clone(),
+            ValidatorQuorumSignatureInfo {
+                epoch: checkpoint.epoch,
+                signatures: signatures.clone().into_iter().collect(),
+            },
+        );
+
+        self.validator_store
+            .fullnode_store()
+            .insert_signed_checkpoint(signed)?;
+        self.pending_checkpoints
+            .retain(|pending_round, _| *pending_round > round);
+
+        info!(
+            "Checkpoint {} of epoch {} signed by a quorum of validators",
+            round.1, round.0
+        );
+
+        Ok(())
+    }
+
+    /// Returns the round of the latest checkpoint signed by a quorum of validators
+    fn latest_checkpoint_round(
+        &self,
+    ) -> Result<Option<CheckpointRound>, Box<dyn std::error::Error>> {
+        Ok(self
+            .validator_store
+            .fullnode_store()
+            .get_latest_signed_checkpoint()?
+            .map(|VerifiedCheckpointSummary(checkpoint, _)| {
+                (checkpoint.epoch, checkpoint.sequence_number)
+            }))
+    }
+
+    /// Returns the validators expected to sign the checkpoint of the given epoch
+    fn checkpoint_validators(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<Validators, Box<dyn std::error::Error>> {
+        let epoch_validators_store = self.validator_store.epoch_validators_store();
+
+        // The epoch may not be started locally yet, its validators being those of the latest epoch
+        let validators = match epoch_validators_store.get_validators(epoch_id)? {
+            Some(validators) => validators,
+            None => epoch_validators_store
+                .get_latest_validators()?
+                .map(|(_, validators)| validators)
+                .unwrap_or_default(),
+        };
+
+        Ok(validators)
+    }
+}
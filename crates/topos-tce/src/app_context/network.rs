@@ -5,12 +5,16 @@ use topos_tce_storage::errors::{InternalStorageError, StorageError};
 use tokio::spawn;
 
 use topos_metrics::CERTIFICATE_DELIVERY_LATENCY;
-use topos_p2p::Event as NetEvent;
+use topos_p2p::{Event as NetEvent, NetworkClient, PeerId};
 use topos_tce_broadcast::DoubleEchoCommand;
-use tracing::{debug, error, info, trace};
+use topos_tce_gatekeeper::{peer_binding_payload, GatekeeperClient, GatekeeperError};
+use tracing::{debug, error, info, trace, warn};
 
-use topos_core::api::grpc::tce::v1::{double_echo_request, DoubleEchoRequest, Echo, Gossip, Ready};
-use topos_core::uci;
+use topos_core::api::grpc::tce::v1::{
+    double_echo_request, DoubleEchoRequest, Echo, Gossip, PeerBinding, Ready,
+};
+use topos_core::types::{Signature, ValidatorId};
+use topos_core::uci::{self, CertificateId};
 
 use crate::AppContext;
 
@@ -32,6 +36,15 @@ impl AppContext {
                         certificate: Some(certificate),
                     }) => match uci::Certificate::try_from(certificate) {
                         Ok(cert) => {
+                            if let Err(error) = self.certificate_validator.validate(&cert) {
+                                warn!(
+                                    "Rejecting the certificate {} gossiped by {}: {}",
+                                    cert.id, from, error
+                                );
+
+                                return;
+                            }
+
                             if let hash_map::Entry::Vacant(entry) =
                                 self.delivery_latency.entry(cert.id)
                             {
@@ -109,8 +122,11 @@ impl AppContext {
                         certificate_id: Some(certificate_id),
                         signature: Some(signature),
                         validator_id: Some(validator_id),
+                        peer_binding,
                     }) => {
                         let channel = self.tce_cli.get_double_echo_channel();
+                        let gatekeeper = self.gatekeeper.clone();
+                        let network_client = self.network_client.clone();
                         spawn(async move {
                             let certificate_id = certificate_id.clone().try_into().map_err(|e| {
                                 error!(
@@ -136,9 +152,24 @@ impl AppContext {
                                     validator_id = validator_id
                                 );
 
+                                let signature = signature.into();
+                                if !admit_publisher(
+                                    &gatekeeper,
+                                    &network_client,
+                                    from,
+                                    &certificate_id,
+                                    validator_id,
+                                    signature,
+                                    peer_binding,
+                                )
+                                .await
+                                {
+                                    return;
+                                }
+
                                 if let Err(e) = channel
                                     .send(DoubleEchoCommand::Echo {
-                                        signature: signature.into(),
+                                        signature,
                                         certificate_id,
                                         validator_id,
                                     })
@@ -155,8 +186,11 @@ impl AppContext {
                         certificate_id: Some(certificate_id),
                         signature: Some(signature),
                         validator_id: Some(validator_id),
+                        peer_binding,
                     }) => {
                         let channel = self.tce_cli.get_double_echo_channel();
+                        let gatekeeper = self.gatekeeper.clone();
+                        let network_client = self.network_client.clone();
                         spawn(async move {
                             let certificate_id = certificate_id.clone().try_into().map_err(|e| {
                                 error!(
@@ -181,9 +215,25 @@ impl AppContext {
                                     certificate_id = certificate_id,
                                     validator_id = validator_id
                                 );
+
+                                let signature = signature.into();
+                                if !admit_publisher(
+                                    &gatekeeper,
+                                    &network_client,
+                                    from,
+                                    &certificate_id,
+                                    validator_id,
+                                    signature,
+                                    peer_binding,
+                                )
+                                .await
+                                {
+                                    return;
+                                }
+
                                 if let Err(e) = channel
                                     .send(DoubleEchoCommand::Ready {
-                                        signature: signature.into(),
+                                        signature,
                                         certificate_id,
                                         validator_id,
                                     })
@@ -196,9 +246,94 @@ impl AppContext {
                             }
                         });
                     }
+                    double_echo_request::Request::Checkpoint(checkpoint) => {
+                        trace!("Received signed checkpoint from: {from}");
+
+                        self.on_checkpoint_signature(checkpoint).await;
+                    }
                     _ => {}
                 }
             }
         }
     }
 }
+
+/// Check that the publisher of an Echo or Ready is acting for a validator of the current epoch,
+/// denying it from the echo and ready topics otherwise.
+///
+/// The peer is bound to the validator only once the message and the binding of the peer are
+/// verified to be signed by the validator, a peer republishing the messages of a validator not
+/// being able to act for it.
+async fn admit_publisher(
+    gatekeeper: &GatekeeperClient,
+    network_client: &NetworkClient,
+    from: PeerId,
+    certificate_id: &CertificateId,
+    validator_id: ValidatorId,
+    signature: Signature,
+    peer_binding: Option<PeerBinding>,
+) -> bool {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(certificate_id.as_array());
+    payload.extend_from_slice(validator_id.as_bytes());
+
+    if signature
+        .verify(payload.as_slice(), validator_id.address())
+        .is_err()
+    {
+        debug!("Dropping message of {from} with an invalid signature of {validator_id}");
+
+        return false;
+    }
+
+    let Some(PeerBinding {
+        epoch_id,
+        signature: Some(binding_signature),
+    }) = peer_binding
+    else {
+        debug!("Dropping message of {from} without a binding to {validator_id}");
+
+        return false;
+    };
+
+    let binding_signature: Signature = binding_signature.into();
+    if binding_signature
+        .verify(
+            peer_binding_payload(&from, epoch_id).as_slice(),
+            validator_id.address(),
+        )
+        .is_err()
+    {
+        debug!("Dropping message of {from} with an invalid binding to {validator_id}");
+
+        return false;
+    }
+
+    match gatekeeper.bind_peer(from, validator_id, epoch_id).await {
+        Ok(()) | Err(GatekeeperError::NoUpdate) => true,
+        Err(GatekeeperError::UnknownValidator(_)) => {
+            debug!("Denying {from} publishing on behalf of {validator_id}, not a validator");
+
+            if let Err(error) = network_client
+                .update_denied_peers(vec![from], Vec::new())
+                .await
+            {
+                error!("Unable to deny {from} from the echo and ready topics: {error}");
+            }
+
+            false
+        }
+        Err(GatekeeperError::AlreadyBound(_)) => {
+            // Not denied, the peer may be bound to the validator again by a later binding
+            warn!("Dropping message of {from} on behalf of {validator_id}, bound to another peer");
+
+            false
+        }
+        Err(error) => {
+            // The double echo still checks the validator and its signature
+            warn!("Unable to check the admission of {from}: {error}");
+
+            true
+        }
+    }
+}
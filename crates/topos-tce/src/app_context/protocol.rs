@@ -6,6 +6,8 @@ use crate::AppContext;
 
 impl AppContext {
     pub async fn on_protocol_event(&mut self, evt: ProtocolEvents) {
+        self.api_client.dispatch_protocol_event(&evt);
+
         match evt {
             ProtocolEvents::Broadcast { certificate_id } => {
                 info!("Broadcasting certificate {}", certificate_id);
@@ -41,6 +43,7 @@ impl AppContext {
                         certificate_id: Some(certificate_id.into()),
                         signature: Some(signature.into()),
                         validator_id: Some(validator_id.into()),
+                        peer_binding: self.peer_binding.clone(),
                     })),
                 };
 
@@ -63,6 +66,7 @@ impl AppContext {
                         certificate_id: Some(certificate_id.into()),
                         signature: Some(signature.into()),
                         validator_id: Some(validator_id.into()),
+                        peer_binding: self.peer_binding.clone(),
                     })),
                 };
 
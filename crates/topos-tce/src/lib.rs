@@ -1,6 +1,8 @@
+use chrono::DateTime;
 use futures::{Future, StreamExt};
 use opentelemetry::global;
 use std::process::ExitStatus;
+use std::time::Duration;
 use std::{future::IntoFuture, sync::Arc};
 use tokio::{
     spawn,
@@ -8,17 +10,22 @@ use tokio::{
 };
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_util::sync::CancellationToken;
-use topos_config::tce::TceConfig;
+use topos_clock::{Clock, TimeClock};
+use topos_config::tce::{broadcast::ReliableBroadcastParams, TceConfig};
 use topos_core::api::grpc::tce::v1::synchronizer_service_server::SynchronizerServiceServer;
+use topos_core::uci::CertificateValidator;
 use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
 use topos_p2p::{
     utils::{local_key_pair, local_key_pair_from_slice},
     GrpcContext, GrpcRouter,
 };
 use topos_tce_broadcast::{ReliableBroadcastClient, ReliableBroadcastConfig};
-use topos_tce_storage::{store::ReadStore, validator::ValidatorStore, StorageClient};
+use topos_tce_gatekeeper::GatekeeperError;
+use topos_tce_storage::{
+    retention::RetentionPolicy, store::ReadStore, validator::ValidatorStore, StorageClient,
+};
 use topos_tce_synchronizer::SynchronizerService;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 mod app_context;
 pub mod events;
@@ -92,20 +99,53 @@ pub async fn run(
     boot_peers.retain(|(p, _)| *p != peer_id);
     let is_validator = config.validators.contains(&validator_id);
 
+    // The configuration is checked before anything is started, nothing being left running if
+    // it is invalid
+    if config.retention.interval_seconds == 0 {
+        return Err(Box::from(
+            "Retention interval must be greater than 0".to_string(),
+        ));
+    }
+
+    if config.epoch.duration_seconds == 0 {
+        return Err(Box::from(
+            "Epoch duration must be greater than 0".to_string(),
+        ));
+    }
+
+    if config.epoch.checkpoint_interval_seconds == 0 {
+        return Err(Box::from(
+            "Checkpoint interval must be greater than 0".to_string(),
+        ));
+    }
+
+    let genesis_timestamp = config.epoch.genesis_timestamp.ok_or_else(|| {
+        "The epoch genesis timestamp must be set, either in the epoch configuration or in the \
+         genesis file"
+            .to_string()
+    })?;
+    let genesis = DateTime::from_timestamp(genesis_timestamp, 0)
+        .ok_or_else(|| format!("Invalid epoch genesis timestamp: {genesis_timestamp}"))?;
+    let clock = TimeClock::new(genesis, config.epoch.duration_seconds)?
+        .with_checkpoint_interval(config.epoch.checkpoint_interval_seconds);
+
     // Preboot phase - stop
     // Healthiness phase - start
     debug!("Starting the Storage");
-    let path = if let StorageConfiguration::RocksDB(Some(ref path)) = config.storage {
-        path
-    } else {
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("Unsupported storage type {:?}", config.storage),
-        )));
-    };
-
-    let validator_store = ValidatorStore::new(path)
-        .map_err(|error| format!("Unable to create validator store: {error}"))?;
+    let validator_store = match config.storage {
+        StorageConfiguration::RocksDB(Some(ref path)) => ValidatorStore::new(path),
+        StorageConfiguration::RAM => {
+            warn!("Storage is kept in memory, nothing will be persisted");
+            ValidatorStore::new_in_memory()
+        }
+        StorageConfiguration::RocksDB(None) => {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Unsupported storage type {:?}", config.storage),
+            )));
+        }
+    }
+    .map_err(|error| format!("Unable to create validator store: {error}"))?;
 
     let fullnode_store = validator_store.fullnode_store();
 
@@ -159,6 +199,8 @@ pub async fn run(
             .with_shutdown(shutdown.0.child_token())
             .with_store(validator_store.clone())
             .with_network_client(network_client.clone())
+            .with_validators(config.validators.clone())
+            .with_delivery_threshold(config.tce_params.delivery_threshold as u64)
             .build()?;
 
     debug!("Synchronizer created");
@@ -170,8 +212,15 @@ pub async fn run(
         .with_peer_id(peer_id.to_string())
         .with_broadcast_stream(broadcast_receiver.resubscribe())
         .serve_grpc_addr(config.grpc_api_addr)
+        .serve_console_addr(config.console_api_addr)
         .serve_graphql_addr(config.graphql_api_addr)
+        .serve_rest_addr(config.rest_api_addr)
         .serve_metrics_addr(config.metrics_api_addr)
+        .with_client_limits(topos_tce_api::ClientLimits {
+            submissions_per_second: config.limits.submissions_per_second,
+            max_streams_per_peer: config.limits.max_streams_per_peer,
+            max_pending_certificates: config.limits.max_pending_certificates,
+        })
         .store(validator_store.clone())
         .storage(storage_client.clone())
         .build_and_launch()
@@ -189,12 +238,49 @@ pub async fn run(
 
     debug!("Starting reliable broadcast");
 
+    // Resume from the latest known epoch, the genesis validators defining the epoch 0
+    let epoch_validators_store = validator_store.epoch_validators_store();
+    let (epoch_id, validators, tce_params) = match epoch_validators_store
+        .get_latest_validators()
+        .map_err(|error| format!("Unable to get the latest epoch validators: {error}"))?
+    {
+        Some((epoch_id, validators)) if epoch_id > 0 => {
+            let tce_params = ReliableBroadcastParams::new(validators.len());
+
+            (epoch_id, validators, tce_params)
+        }
+        _ => {
+            epoch_validators_store
+                .insert_validators(0, config.validators.clone())
+                .map_err(|error| format!("Unable to persist the genesis validators: {error}"))?;
+
+            (0, config.validators.clone(), config.tce_params.clone())
+        }
+    };
+
+    match gatekeeper_client
+        .update_epoch(epoch_id, validators.clone())
+        .await
+    {
+        Ok(_) | Err(GatekeeperError::NoUpdate) => {}
+        Err(error) => return Err(format!("Unable to initialize the gatekeeper: {error}").into()),
+    }
+
+    info!(
+        "Reliable broadcast starting at epoch {} with {} validators",
+        epoch_id,
+        validators.len()
+    );
+
+    api_client.set_epoch(epoch_id).await;
+
     let (tce_cli, tce_stream) = ReliableBroadcastClient::new(
         ReliableBroadcastConfig {
-            tce_params: config.tce_params.clone(),
+            tce_params,
+            epoch_id,
             validator_id,
-            validators: config.validators.clone(),
-            message_signer,
+            validators,
+            message_signer: message_signer.clone(),
         },
         validator_store.clone(),
         broadcast_sender,
@@ -204,8 +290,28 @@ pub async fn run(
     debug!("Reliable broadcast started");
 
     spawn(synchronizer_runtime.into_future());
+
+    debug!("Starting the storage pruning");
+    spawn(prune_storage(
+        validator_store.clone(),
+        RetentionPolicy {
+            precedence_pool_ttl: config
+                .retention
+                .precedence_pool_ttl_seconds
+                .map(Duration::from_secs),
+            proofs_per_source_stream: config.retention.proofs_per_source_stream,
+        },
+        Duration::from_secs(config.retention.interval_seconds),
+        shutdown.0.child_token(),
+    ));
+
+    debug!("Starting the epoch clock");
+    let current_epoch = clock.epoch_ref();
+    let clock_stream = clock.spawn()?;
+    debug!("Epoch clock started");
+
     // setup transport-tce-storage-api connector
-    let (app_context, _tce_stream) = AppContext::new(
+    let (mut app_context, _tce_stream) = AppContext::new(
         is_validator,
         storage_client,
         tce_cli,
@@ -214,7 +320,12 @@ pub async fn run(
         gatekeeper_client,
         validator_store,
         ctx,
+        message_signer,
+        CertificateValidator::default()
+            .with_signature_verification(config.validation.verify_signature)
+            .with_max_proof_size(config.validation.max_proof_size),
     );
+    app_context.sign_peer_binding(current_epoch.load(std::sync::atomic::Ordering::Relaxed));
 
     Ok(app_context.run(
         event_stream,
@@ -222,6 +333,29 @@ pub async fn run(
         api_stream,
         synchronizer_stream,
         BroadcastStream::new(broadcast_receiver).filter_map(|v| futures::future::ready(v.ok())),
+        BroadcastStream::new(clock_stream).filter_map(|v| futures::future::ready(v.ok())),
         shutdown,
     ))
 }
+
+/// Prune the storage on every `interval` according to the retention `policy`
+async fn prune_storage(
+    validator_store: Arc<ValidatorStore>,
+    policy: RetentionPolicy,
+    interval: Duration,
+    shutdown: CancellationToken,
+) {
+    let mut interval = tokio::time::interval(interval);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match validator_store.prune(&policy).await {
+                    Ok(summary) => debug!("Storage pruned: {summary:?}"),
+                    Err(error) => error!("Unable to prune the storage: {error}"),
+                }
+            }
+            _ = shutdown.cancelled() => break,
+        }
+    }
+}
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use rstest::rstest;
 use test_log::test;
 use tokio::sync::{mpsc, oneshot};
+use topos_core::uci::{CertificateValidator, ValidationError, VerifierRegistry};
 use topos_crypto::messages::MessageSigner;
+use topos_tce_api::CertificateStatus;
 use topos_tce_storage::{store::WriteStore, types::PendingResult};
 use topos_test_sdk::{
     certificates::create_certificate_chain,
@@ -102,3 +104,147 @@ async fn handle_certificate_already_delivered(
 
     assert!(matches!(response, Ok(Ok(PendingResult::AlreadyDelivered))));
 }
+
+#[rstest]
+#[test(tokio::test)]
+async fn handle_certificate_chain_submission(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, _, _) = setup_test.await;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+
+    let mut self_target = certificates[2].certificate.clone();
+    self_target.target_subnets = vec![SOURCE_SUBNET_ID_1];
+
+    let (sender, receiver) = oneshot::channel();
+
+    context
+        .on_api_event(topos_tce_api::RuntimeEvent::CertificatesSubmitted {
+            certificates: vec![
+                certificates[0].certificate.clone(),
+                certificates[1].certificate.clone(),
+                self_target,
+            ],
+            sender,
+        })
+        .await;
+
+    let results = receiver.await.unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0], Ok(PendingResult::InPending(_))));
+    assert!(matches!(results[1], Ok(PendingResult::AwaitPrecedence)));
+    assert!(matches!(
+        results[2],
+        Err(topos_tce_api::RuntimeError::InvalidCertificate(
+            ValidationError::SelfTarget
+        ))
+    ));
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn reject_certificate_with_invalid_proof(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, _, _) = setup_test.await;
+    context.certificate_validator = CertificateValidator::default()
+        .with_signature_verification(false)
+        .with_verifiers(VerifierRegistry::empty());
+
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1);
+    let certificate = certificates.pop().unwrap().certificate;
+    let certificate_id = certificate.id;
+
+    let (sender, receiver) = oneshot::channel();
+
+    context
+        .on_api_event(topos_tce_api::RuntimeEvent::CertificateSubmitted {
+            certificate: Box::new(certificate),
+            sender,
+        })
+        .await;
+
+    let response = receiver.await;
+
+    assert!(matches!(
+        response,
+        Ok(Err(topos_tce_api::RuntimeError::InvalidCertificate(
+            ValidationError::InvalidProof(_)
+        )))
+    ));
+    assert!(context
+        .validator_store
+        .get_pending_id(&certificate_id)
+        .unwrap()
+        .is_none());
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn get_certificate_status(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, _, _) = setup_test.await;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 4);
+
+    context
+        .validator_store
+        .insert_certificate_delivered(&certificates[0])
+        .await
+        .unwrap();
+    context
+        .validator_store
+        .insert_pending_certificate(&certificates[1].certificate)
+        .await
+        .unwrap();
+    context
+        .validator_store
+        .insert_pending_certificate(&certificates[3].certificate)
+        .await
+        .unwrap();
+
+    let mut statuses = Vec::new();
+    for certificate in &certificates {
+        let (sender, receiver) = oneshot::channel();
+
+        context
+            .on_api_event(topos_tce_api::RuntimeEvent::GetCertificateStatus {
+                certificate_id: certificate.certificate.id,
+                sender,
+            })
+            .await;
+
+        statuses.push(receiver.await.unwrap().unwrap());
+    }
+
+    assert!(matches!(
+        &statuses[0],
+        Some(CertificateStatus::Delivered(positions))
+            if positions.source == certificates[0].proof_of_delivery.delivery_position
+                && positions.targets.contains_key(&TARGET_SUBNET_ID_1)
+    ));
+    // The pending certificate can be picked up by the broadcast at any time
+    assert!(matches!(
+        statuses[1],
+        Some(CertificateStatus::Pending { .. }) | Some(CertificateStatus::InBroadcast { .. })
+    ));
+    assert!(statuses[2].is_none());
+    assert!(matches!(
+        statuses[3],
+        Some(CertificateStatus::AwaitingPrecedence { prev_id })
+            if prev_id == certificates[2].certificate.id
+    ));
+}
@@ -0,0 +1,259 @@
+use std::{collections::HashSet, sync::Arc};
+
+use rstest::rstest;
+use test_log::test;
+use tokio::sync::mpsc;
+use topos_clock::Event as ClockEvent;
+use topos_core::types::stream::Position;
+use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
+use topos_tce_storage::{
+    store::WriteStore,
+    types::{CheckpointSummary, ValidatorQuorumSignatureInfo, VerifiedCheckpointSummary},
+    SourceHead,
+};
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
+};
+
+use crate::{app_context::epoch::checkpoint_proposer, AppContext};
+
+use super::setup_test;
+
+/// Register the node and three other validators in the genesis validator set
+fn register_validators(
+    context: &AppContext,
+    message_signer: &Arc<MessageSigner>,
+) -> (Vec<Arc<MessageSigner>>, HashSet<ValidatorId>) {
+    let signers: Vec<_> = (1..=3u8)
+        .map(|i| Arc::new(MessageSigner::new(&[i; 32]).unwrap()))
+        .collect();
+    let validators: HashSet<_> = signers
+        .iter()
+        .chain(std::iter::once(message_signer))
+        .map(|signer| ValidatorId::from(signer.public_address))
+        .collect();
+    context
+        .validator_store
+        .epoch_validators_store()
+        .insert_validators(0, validators.clone())
+        .unwrap();
+
+    (signers, validators)
+}
+
+fn signed_by(checkpoint: &CheckpointSummary, signer: &MessageSigner) -> VerifiedCheckpointSummary {
+    VerifiedCheckpointSummary(
+        checkpoint.clone(),
+        ValidatorQuorumSignatureInfo {
+            epoch: checkpoint.epoch,
+            signatures: vec![(
+                signer.public_address.into(),
+                signer.sign_message(&checkpoint.payload()).unwrap(),
+            )],
+        },
+    )
+}
+
+fn clock_event(epoch: u64, sequence_number: usize) -> ClockEvent {
+    if sequence_number == 0 {
+        ClockEvent::EpochChange(epoch)
+    } else {
+        ClockEvent::Checkpoint {
+            epoch,
+            sequence_number: sequence_number as u64,
+        }
+    }
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn proposed_checkpoint_is_persisted_once_reaching_the_quorum(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, mut p2p_receiver, message_signer) = setup_test.await;
+    context.is_validator = true;
+
+    let (signers, validators) = register_validators(&context, &message_signer);
+    let validator_id = ValidatorId::from(message_signer.public_address);
+    let sequence_number = (0..validators.len())
+        .find(|sequence_number| {
+            checkpoint_proposer(&validators, (1, *sequence_number)) == Some(validator_id)
+        })
+        .unwrap();
+
+    context
+        .on_clock_event(clock_event(1, sequence_number))
+        .await;
+
+    assert!(matches!(
+        p2p_receiver.try_recv(),
+        Ok(topos_p2p::Command::Gossip { topic, .. }) if topic == topos_p2p::TOPOS_CHECKPOINT
+    ));
+
+    // The checkpoint is only signed by the node itself
+    let fullnode_store = context.validator_store.fullnode_store();
+    assert!(fullnode_store
+        .get_latest_signed_checkpoint()
+        .unwrap()
+        .is_none());
+
+    let (checkpoint, _) = context.pending_checkpoints[&(1, sequence_number)]
+        .signatures
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    for signer in signers.iter().take(2) {
+        context
+            .on_checkpoint_signature(signed_by(&checkpoint, signer).into())
+            .await;
+    }
+
+    let VerifiedCheckpointSummary(signed, quorum) = fullnode_store
+        .get_latest_signed_checkpoint()
+        .unwrap()
+        .unwrap();
+    assert_eq!(signed, checkpoint);
+    assert_eq!(quorum.signatures.len(), 3);
+    assert!(context.pending_checkpoints.is_empty());
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn proposal_is_signed_once_its_heads_are_delivered(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, mut p2p_receiver, message_signer) = setup_test.await;
+    context.is_validator = true;
+
+    let (signers, validators) = register_validators(&context, &message_signer);
+    let validator_id = ValidatorId::from(message_signer.public_address);
+
+    // Neither the round of the proposal nor the following one are proposed by the node
+    let sequence_number = (1..=validators.len())
+        .find(|sequence_number| {
+            checkpoint_proposer(&validators, (1, *sequence_number)) != Some(validator_id)
+                && checkpoint_proposer(&validators, (1, sequence_number + 1)) != Some(validator_id)
+        })
+        .unwrap();
+    let proposer = checkpoint_proposer(&validators, (1, sequence_number)).unwrap();
+    let (proposer, others): (Vec<_>, Vec<_>) = signers
+        .iter()
+        .partition(|signer| ValidatorId::from(signer.public_address) == proposer);
+
+    let certificate = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+        .pop()
+        .unwrap();
+    let checkpoint = CheckpointSummary::new(
+        1,
+        sequence_number,
+        vec![SourceHead {
+            certificate_id: certificate.certificate.id,
+            subnet_id: SOURCE_SUBNET_ID_1,
+            position: Position::ZERO,
+        }],
+        Vec::new(),
+    );
+
+    context
+        .on_checkpoint_signature(signed_by(&checkpoint, proposer[0]).into())
+        .await;
+
+    // The heads of the proposal aren't delivered locally
+    assert!(p2p_receiver.try_recv().is_err());
+    assert_eq!(
+        context.pending_checkpoints[&(1, sequence_number)].proposal,
+        Some(checkpoint.clone())
+    );
+
+    context
+        .validator_store
+        .insert_certificate_delivered(&certificate)
+        .await
+        .unwrap();
+
+    // The proposal is checked again on the next round
+    context
+        .on_clock_event(clock_event(1, sequence_number + 1))
+        .await;
+
+    assert!(matches!(
+        p2p_receiver.try_recv(),
+        Ok(topos_p2p::Command::Gossip { topic, .. }) if topic == topos_p2p::TOPOS_CHECKPOINT
+    ));
+
+    context
+        .on_checkpoint_signature(signed_by(&checkpoint, others[0]).into())
+        .await;
+
+    let VerifiedCheckpointSummary(signed, quorum) = context
+        .validator_store
+        .fullnode_store()
+        .get_latest_signed_checkpoint()
+        .unwrap()
+        .unwrap();
+    assert_eq!(signed, checkpoint);
+    assert!(quorum
+        .signatures
+        .iter()
+        .any(|(signer, _)| *signer == validator_id));
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn validator_signs_a_single_checkpoint_per_round(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, _, message_signer) = setup_test.await;
+
+    let (signers, _) = register_validators(&context, &message_signer);
+
+    for position in 0..10u64 {
+        let checkpoint = CheckpointSummary::new(
+            1,
+            0,
+            vec![SourceHead {
+                certificate_id: topos_test_sdk::constants::CERTIFICATE_ID_1,
+                subnet_id: SOURCE_SUBNET_ID_1,
+                position: position.into(),
+            }],
+            Vec::new(),
+        );
+
+        context
+            .on_checkpoint_signature(signed_by(&checkpoint, &signers[0]).into())
+            .await;
+    }
+
+    assert_eq!(context.pending_checkpoints[&(1, 0)].signatures.len(), 1);
+
+    // Signatures are counted per round, the validators signing each round once
+    for (sequence_number, signer) in [(0, &signers[1]), (1, &signers[2]), (1, &signers[0])] {
+        let checkpoint = CheckpointSummary::new(1, sequence_number, Vec::new(), Vec::new());
+
+        context
+            .on_checkpoint_signature(signed_by(&checkpoint, signer).into())
+            .await;
+    }
+
+    assert_eq!(context.pending_checkpoints.len(), 2);
+    assert!(context
+        .validator_store
+        .fullnode_store()
+        .get_latest_signed_checkpoint()
+        .unwrap()
+        .is_none());
+}
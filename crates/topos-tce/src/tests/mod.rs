@@ -12,7 +12,7 @@ use topos_p2p::{utils::GrpcOverP2P, NetworkClient};
 use topos_tce_broadcast::{ReliableBroadcastClient, ReliableBroadcastConfig};
 use topos_tce_storage::{validator::ValidatorStore, StorageClient};
 use topos_test_sdk::{
-    certificates::create_certificate_chain,
+    certificates::{certificate_validator, create_certificate_chain},
     constants::{CERTIFICATE_ID_1, SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
     storage::create_validator_store,
     tce::public_api::{create_public_api, PublicApiContext},
@@ -21,6 +21,7 @@ use topos_test_sdk::{
 use crate::AppContext;
 
 mod api;
+mod epoch;
 mod network;
 
 #[rstest]
@@ -107,6 +108,7 @@ pub async fn setup_test(
     let (tce_cli, _) = ReliableBroadcastClient::new(
         ReliableBroadcastConfig {
             tce_params: topos_config::tce::broadcast::ReliableBroadcastParams::default(),
+            epoch_id: 0,
             validator_id,
             validators: HashSet::new(),
             message_signer: message_signer.clone(),
@@ -130,7 +132,8 @@ pub async fn setup_test(
     let (api_context, _api_stream) = create_public_api.await;
     let api_client = api_context.client;
 
-    let (gatekeeper_client, _) = Gatekeeper::builder().into_future().await.unwrap();
+    let (gatekeeper_client, gatekeeper) = Gatekeeper::builder().into_future().await.unwrap();
+    tokio::spawn(gatekeeper.into_future());
 
     let (context, _) = AppContext::new(
         is_validator,
@@ -141,6 +144,8 @@ pub async fn setup_test(
         gatekeeper_client,
         validator_store,
         api_context.api_context.unwrap(),
+        message_signer.clone(),
+        certificate_validator(),
     );
 
     (context, p2p_receiver, message_signer)
@@ -1,12 +1,15 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use libp2p::PeerId;
 use prost::Message;
 use rstest::rstest;
 use test_log::test;
 use tokio::sync::mpsc;
-use topos_core::api::grpc::tce::v1::{double_echo_request, DoubleEchoRequest, Echo, Gossip, Ready};
+use topos_core::api::grpc::tce::v1::{
+    double_echo_request, DoubleEchoRequest, Echo, Gossip, PeerBinding, Ready,
+};
 use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
+use topos_tce_gatekeeper::peer_binding_payload;
 use topos_tce_storage::store::WriteStore;
 use topos_test_sdk::{
     certificates::create_certificate_chain,
@@ -17,6 +20,18 @@ use crate::AppContext;
 
 use super::setup_test;
 
+fn peer_binding(signer: &MessageSigner, peer: &PeerId, epoch_id: u64) -> PeerBinding {
+    PeerBinding {
+        epoch_id,
+        signature: Some(
+            signer
+                .sign_message(&peer_binding_payload(peer, epoch_id))
+                .unwrap()
+                .into(),
+        ),
+    }
+}
+
 #[rstest]
 #[test(tokio::test)]
 async fn handle_gossip(
@@ -62,6 +77,7 @@ async fn handle_echo(
             certificate_id: Some(certificate.id.into()),
             signature: Some(message_signer.sign_message(&[]).ok().unwrap().into()),
             validator_id: Some(validator_id.into()),
+            peer_binding: None,
         })),
     };
     context
@@ -91,6 +107,7 @@ async fn handle_ready(
             certificate_id: Some(certificate.id.into()),
             signature: Some(message_signer.sign_message(&[]).ok().unwrap().into()),
             validator_id: Some(validator_id.into()),
+            peer_binding: None,
         })),
     };
     context
@@ -133,3 +150,107 @@ async fn handle_already_delivered(
         })
         .await;
 }
+
+#[rstest]
+#[test(tokio::test)]
+async fn deny_echo_from_non_validator(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, mut p2p_receiver, message_signer) = setup_test.await;
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1);
+    let certificate = certificates.pop().unwrap().certificate;
+    let validator_id: ValidatorId = message_signer.public_address.into();
+    let from = PeerId::random();
+
+    let mut payload = Vec::new();
+    payload.extend_from_slice(certificate.id.as_array());
+    payload.extend_from_slice(validator_id.as_bytes());
+
+    let msg = DoubleEchoRequest {
+        request: Some(double_echo_request::Request::Echo(Echo {
+            certificate_id: Some(certificate.id.into()),
+            signature: Some(message_signer.sign_message(&payload).ok().unwrap().into()),
+            validator_id: Some(validator_id.into()),
+            peer_binding: Some(peer_binding(&message_signer, &from, 0)),
+        })),
+    };
+    context
+        .on_net_event(topos_p2p::Event::Gossip {
+            from,
+            data: msg.encode_to_vec(),
+        })
+        .await;
+
+    let command = tokio::time::timeout(Duration::from_secs(1), p2p_receiver.recv())
+        .await
+        .expect("No command received")
+        .unwrap();
+
+    assert!(matches!(
+        command,
+        topos_p2p::Command::UpdateDeniedPeers { denied, allowed }
+            if denied == vec![from] && allowed.is_empty()
+    ));
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn republished_echo_does_not_bind_the_republisher(
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (mut context, _, message_signer) = setup_test.await;
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1);
+    let certificate = certificates.pop().unwrap().certificate;
+    let validator_id: ValidatorId = message_signer.public_address.into();
+    let (publisher, republisher) = (PeerId::random(), PeerId::random());
+
+    _ = context
+        .gatekeeper
+        .update_epoch(0, HashSet::from([validator_id]))
+        .await;
+
+    let mut payload = Vec::new();
+    payload.extend_from_slice(certificate.id.as_array());
+    payload.extend_from_slice(validator_id.as_bytes());
+
+    let msg = DoubleEchoRequest {
+        request: Some(double_echo_request::Request::Echo(Echo {
+            certificate_id: Some(certificate.id.into()),
+            signature: Some(message_signer.sign_message(&payload).ok().unwrap().into()),
+            validator_id: Some(validator_id.into()),
+            peer_binding: Some(peer_binding(&message_signer, &publisher, 0)),
+        })),
+    };
+
+    // The republisher relays the message of the validator before its legitimate publisher
+    for from in [republisher, publisher] {
+        context
+            .on_net_event(topos_p2p::Event::Gossip {
+                from,
+                data: msg.encode_to_vec(),
+            })
+            .await;
+    }
+
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while !context.gatekeeper.is_peer_allowed(publisher).await.unwrap() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The publisher isn't bound to the validator");
+
+    assert!(!context
+        .gatekeeper
+        .is_peer_allowed(republisher)
+        .await
+        .unwrap());
+}
@@ -3,13 +3,40 @@ use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::{SpanExporterBuilder, WithExportConfig};
 use opentelemetry_sdk::trace::{BatchConfigBuilder, BatchSpanProcessor, SpanLimits};
 use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::Sampler, Resource};
+use std::sync::OnceLock;
 use std::time::Duration;
 use tracing::Level;
 use tracing_subscriber::util::TryInitError;
 use tracing_subscriber::{
-    prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
+    prelude::__tracing_subscriber_SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Layer,
+    Registry,
 };
 
+/// Handle on the filter of the log layer, used to change the log level at runtime
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogFilterError {
+    #[error("The tracing hasn't been set up")]
+    NotInitialized,
+
+    #[error("Invalid log filter: {0}")]
+    InvalidFilter(#[from] tracing_subscriber::filter::ParseError),
+
+    #[error("Unable to reload the log filter: {0}")]
+    Reload(#[from] reload::Error),
+}
+
+/// Replace the filter of the log layer, using the `RUST_LOG` syntax (e.g. `warn,topos=debug`)
+pub fn set_log_filter(filter: &str) -> Result<(), LogFilterError> {
+    let filter = EnvFilter::try_new(filter)?;
+    let handle = LOG_FILTER.get().ok_or(LogFilterError::NotInitialized)?;
+
+    handle.reload(filter)?;
+
+    Ok(())
+}
+
 fn verbose_to_level(verbose: u8) -> Level {
     match verbose {
         0 => Level::ERROR,
@@ -71,6 +98,9 @@ pub fn setup_tracing(
 
     let ansi = !no_color;
 
+    let (log_filter, log_filter_handle) = reload::Layer::new(create_filter(verbose));
+    _ = LOG_FILTER.set(log_filter_handle);
+
     layers.push(
         match std::env::var("TOPOS_LOG_FORMAT")
             .map(|f| f.to_lowercase())
@@ -80,17 +110,17 @@ pub fn setup_tracing(
             Ok("json") => tracing_subscriber::fmt::layer()
                 .json()
                 .with_ansi(ansi)
-                .with_filter(create_filter(verbose))
+                .with_filter(log_filter)
                 .boxed(),
             Ok("pretty") => tracing_subscriber::fmt::layer()
                 .pretty()
                 .with_ansi(ansi)
-                .with_filter(create_filter(verbose))
+                .with_filter(log_filter)
                 .boxed(),
             _ => tracing_subscriber::fmt::layer()
                 .compact()
                 .with_ansi(ansi)
-                .with_filter(create_filter(verbose))
+                .with_filter(log_filter)
                 .boxed(),
         },
     );
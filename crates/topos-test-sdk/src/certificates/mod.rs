@@ -6,13 +6,30 @@ use topos_core::{
         stream::CertificateSourceStreamPosition, stream::Position, CertificateDelivered,
         ProofOfDelivery,
     },
-    uci::{Certificate, CertificateId, SubnetId, INITIAL_CERTIFICATE_ID},
+    uci::{
+        Certificate, CertificateId, CertificateValidator, SignatureOnlyVerifier, SubnetId,
+        VerifierRegistry, INITIAL_CERTIFICATE_ID, SECP256K1_SIGNATURE_VERIFIER,
+    },
 };
 
 use crate::constants::PREV_CERTIFICATE_ID;
 use crate::constants::SOURCE_SUBNET_ID_1;
 use crate::constants::TARGET_SUBNET_ID_1;
 
+/// Registry accepting every certificate, as the certificates created for tests aren't signed
+#[fixture]
+pub fn verifier_registry() -> VerifierRegistry {
+    VerifierRegistry::empty().with_verifier(SECP256K1_SIGNATURE_VERIFIER, SignatureOnlyVerifier)
+}
+
+/// Validation pipeline skipping the signature and proof verification
+#[fixture]
+pub fn certificate_validator() -> CertificateValidator {
+    CertificateValidator::default()
+        .with_signature_verification(false)
+        .with_verifiers(verifier_registry())
+}
+
 #[fixture]
 pub fn create_certificate(
     #[default(SOURCE_SUBNET_ID_1)] source_subnet: SubnetId,
@@ -4,9 +4,7 @@ use std::sync::Arc;
 
 use topos_core::types::CertificateDelivered;
 use topos_tce_storage::{
-    epoch::EpochValidatorsStore, epoch::ValidatorPerEpochStore, fullnode::FullNodeStore,
-    index::IndexTables, store::WriteStore, validator::ValidatorPerpetualTables,
-    validator::ValidatorStore, StorageClient,
+    fullnode::FullNodeStore, store::WriteStore, validator::ValidatorStore, StorageClient,
 };
 
 use crate::folder_name;
@@ -32,11 +30,10 @@ pub async fn create_validator_store(
     certificates: &[CertificateDelivered],
     #[future] create_fullnode_store: Arc<FullNodeStore>,
 ) -> Arc<ValidatorStore> {
-    let temp_dir = create_folder::default();
     let fullnode_store = create_fullnode_store.await;
 
     let store =
-        ValidatorStore::open(&temp_dir, fullnode_store).expect("Unable to create validator store");
+        ValidatorStore::open_in_memory(fullnode_store).expect("Unable to create validator store");
 
     store
         .insert_certificates_delivered(certificates)
@@ -49,30 +46,12 @@ pub async fn create_validator_store(
 pub async fn create_validator_store_with_fullnode(
     fullnode_store: Arc<FullNodeStore>,
 ) -> Arc<ValidatorStore> {
-    ValidatorStore::open(&create_folder::default(), fullnode_store)
-        .expect("Unable to create validator store")
+    ValidatorStore::open_in_memory(fullnode_store).expect("Unable to create validator store")
 }
 
 #[fixture(certificates = &[])]
 pub async fn create_fullnode_store(certificates: &[CertificateDelivered]) -> Arc<FullNodeStore> {
-    let temp_dir = create_folder::default();
-
-    let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(&temp_dir));
-    let index_tables = Arc::new(IndexTables::open(&temp_dir));
-
-    let validators_store =
-        EpochValidatorsStore::new(&temp_dir).expect("Unable to create EpochValidators store");
-
-    let epoch_store =
-        ValidatorPerEpochStore::new(0, &temp_dir).expect("Unable to create Per epoch store");
-
-    let store = FullNodeStore::open(
-        epoch_store,
-        validators_store,
-        perpetual_tables,
-        index_tables,
-    )
-    .expect("Unable to create full node store");
+    let store = FullNodeStore::new_in_memory().expect("Unable to create full node store");
 
     store
         .insert_certificates_delivered(certificates)
@@ -27,6 +27,7 @@ use topos_core::api::grpc::tce::v1::{
 };
 use topos_core::api::grpc::tce::v1::{
     CheckpointRequest, CheckpointResponse, FetchCertificatesRequest, FetchCertificatesResponse,
+    SignedCheckpointRequest, SignedCheckpointResponse,
 };
 use topos_core::api::grpc::tce::v1::{StatusRequest, StatusResponse};
 use topos_core::types::CertificateDelivered;
@@ -35,6 +36,7 @@ use topos_core::uci::SubnetId;
 use topos_crypto::messages::MessageSigner;
 use topos_p2p::{error::P2PError, Event, GrpcRouter, NetworkClient, Runtime};
 use topos_tce::{events::Events, AppContext};
+use topos_tce_gatekeeper::GatekeeperError;
 use topos_tce_storage::StorageClient;
 use topos_tce_synchronizer::SynchronizerService;
 use tracing::info;
@@ -44,6 +46,7 @@ use self::p2p::{bootstrap_network, create_network_worker};
 use self::protocol::{create_reliable_broadcast_client, create_reliable_broadcast_params};
 use self::public_api::create_public_api;
 use self::synchronizer::create_synchronizer;
+use crate::certificates::certificate_validator;
 use crate::crypto::message_signer;
 use crate::p2p::local_peer;
 use crate::storage::create_fullnode_store;
@@ -201,6 +204,13 @@ impl GrpcSynchronizerService for DummyService {
     ) -> Result<Response<CheckpointResponse>, Status> {
         Err(Status::unimplemented("fetch_checkpoint"))
     }
+
+    async fn fetch_signed_checkpoint(
+        &self,
+        _request: Request<SignedCheckpointRequest>,
+    ) -> Result<Response<SignedCheckpointResponse>, Status> {
+        Err(Status::unimplemented("fetch_signed_checkpoint"))
+    }
 }
 
 pub fn create_dummy_router() -> Router {
@@ -252,8 +262,8 @@ pub async fn start_node(
     let (sender, receiver) = broadcast::channel(100);
     let (tce_cli, tce_stream) = create_reliable_broadcast_client(
         validator_id,
-        validators,
-        message_signer,
+        validators.clone(),
+        message_signer.clone(),
         create_reliable_broadcast_params(peers.len()),
         validator_store.clone(),
         sender,
@@ -272,6 +282,12 @@ pub async fn start_node(
     .await;
 
     let (gatekeeper_client, gatekeeper_join_handle) = create_gatekeeper().await.unwrap();
+    if let Err(error) = gatekeeper_client.update_epoch(0, validators).await {
+        assert!(
+            matches!(error, GatekeeperError::NoUpdate),
+            "Unable to set the validators of the gatekeeper: {error}"
+        );
+    }
 
     let (synchronizer_stream, synchronizer_join_handle) = create_synchronizer(
         gatekeeper_client.clone(),
@@ -281,7 +297,7 @@ pub async fn start_node(
     .in_current_span()
     .await;
 
-    let (app, event_stream) = AppContext::new(
+    let (mut app, event_stream) = AppContext::new(
         is_validator,
         storage_client,
         tce_cli,
@@ -290,7 +306,11 @@ pub async fn start_node(
         gatekeeper_client,
         validator_store,
         api_context.api_context.unwrap(),
+        message_signer,
+        certificate_validator(),
     );
+    // The nodes stay in the genesis epoch, their clock being pending
+    app.sign_peer_binding(0);
 
     let shutdown_token = CancellationToken::new();
     let shutdown_cloned = shutdown_token.clone();
@@ -304,6 +324,7 @@ pub async fn start_node(
             api_stream,
             synchronizer_stream,
             BroadcastStream::new(receiver).filter_map(|v| futures::future::ready(v.ok())),
+            futures::stream::pending(),
             (shutdown_token, shutdown_sender),
         )
         .in_current_span(),
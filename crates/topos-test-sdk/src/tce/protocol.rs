@@ -24,6 +24,7 @@ pub async fn create_reliable_broadcast_client(
 ) {
     let config = ReliableBroadcastConfig {
         tce_params,
+        epoch_id: 0,
         validator_id,
         validators,
         message_signer,
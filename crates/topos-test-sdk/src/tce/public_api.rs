@@ -47,12 +47,14 @@ pub async fn create_public_api(
     let storage_client = storage_client.await;
     let store = create_validator_store.await;
     let grpc_addr = get_available_addr();
+    let console_addr = get_available_addr();
     let graphql_addr = get_available_addr();
     let metrics_addr = get_available_addr();
 
     let api_port = grpc_addr.port();
 
     let api_endpoint = format!("http://0.0.0.0:{api_port}");
+    let console_endpoint = format!("http://0.0.0.0:{}", console_addr.port());
     warn!("API endpoint: {}", api_endpoint);
     warn!("gRPC endpoint: {}", grpc_addr);
     warn!("GraphQL endpoint: {}", graphql_addr);
@@ -61,6 +63,7 @@ pub async fn create_public_api(
     let (client, stream, ctx) = topos_tce_api::Runtime::builder()
         .with_broadcast_stream(broadcast_stream)
         .serve_grpc_addr(grpc_addr)
+        .serve_console_addr(console_addr)
         .serve_graphql_addr(graphql_addr)
         .serve_metrics_addr(metrics_addr)
         .store(store)
@@ -73,7 +76,7 @@ pub async fn create_public_api(
         .unwrap()
         .connect_lazy();
 
-    let console_channel = channel::Endpoint::from_str(&api_endpoint)
+    let console_channel = channel::Endpoint::from_str(&console_endpoint)
         .unwrap()
         .connect_lazy();
 
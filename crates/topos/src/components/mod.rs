@@ -1,3 +1,4 @@
 pub(crate) mod node;
 pub(crate) mod regtest;
 pub(crate) mod setup;
+pub(crate) mod tce;
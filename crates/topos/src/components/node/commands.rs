@@ -4,16 +4,18 @@ use clap::{Args, Subcommand};
 use serde::Serialize;
 
 mod init;
+mod snapshot;
 mod status;
 mod up;
 
 pub(crate) use init::Init;
+pub(crate) use snapshot::{Snapshot, SnapshotCommands};
 pub(crate) use status::Status;
 pub(crate) use up::Up;
 
 #[derive(Args, Debug, Serialize)]
 pub(crate) struct NodeArgument {
-    #[clap(short, long, default_value = "http://[::1]:1340")]
+    #[clap(short, long, default_value = "http://127.0.0.1:1341")]
     pub(crate) node: String,
 }
 
@@ -47,6 +49,7 @@ pub(crate) enum NodeCommands {
     Up(Box<Up>),
     Init(Box<Init>),
     Status(Status),
+    Snapshot(Box<Snapshot>),
 }
 
 #[cfg(test)]
@@ -57,5 +60,6 @@ mod tests {
     fn test_run() {
         assert!(NodeCommands::has_subcommand("up"));
         assert!(NodeCommands::has_subcommand("init"));
+        assert!(NodeCommands::has_subcommand("snapshot"));
     }
 }
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+#[derive(Args, Debug, Serialize)]
+#[command(
+    about = "Export or import a snapshot of the delivered certificates of a stopped node, to \
+             bootstrap another node without syncing from genesis"
+)]
+pub(crate) struct Snapshot {
+    /// Name to identify your node
+    #[arg(
+        long,
+        env = "TOPOS_NODE_NAME",
+        default_value = "default",
+        global = true
+    )]
+    pub(crate) name: String,
+
+    #[command(subcommand)]
+    pub(crate) subcommands: SnapshotCommands,
+}
+
+#[derive(Subcommand, Debug, Serialize)]
+pub(crate) enum SnapshotCommands {
+    Export(ExportSnapshot),
+    Import(ImportSnapshot),
+}
+
+#[derive(Args, Debug, Serialize)]
+#[command(about = "Export the delivered certificates and their streams to a snapshot file")]
+pub(crate) struct ExportSnapshot {
+    /// Path of the snapshot file to create
+    #[arg(long)]
+    pub(crate) output: PathBuf,
+}
+
+#[derive(Args, Debug, Serialize)]
+#[command(
+    about = "Import a snapshot file in an empty node, once its proofs of delivery are verified \
+             against the validators of the genesis file"
+)]
+pub(crate) struct ImportSnapshot {
+    /// Path of the snapshot file to import
+    #[arg(long)]
+    pub(crate) input: PathBuf,
+
+    /// Minimum number of Ready messages required to consider a certificate as delivered.
+    /// Derived from the size of the validator set if not provided
+    #[arg(long)]
+    pub(crate) threshold: Option<u64>,
+}
@@ -5,13 +5,15 @@ use std::{
 use std::{path::Path, sync::Arc};
 use tokio::sync::Mutex;
 use tonic::transport::{Channel, Endpoint};
+use topos_tce_storage::fullnode::FullNodeStore;
 use topos_telemetry::tracing::setup_tracing;
 use tower::Service;
 use tracing::error;
 
-use self::commands::{NodeCommand, NodeCommands};
+use self::commands::{NodeCommand, NodeCommands, SnapshotCommands};
 use topos_config::node::NodeConfig;
 use topos_config::{edge::command::BINARY_NAME, Config};
+use topos_config::{genesis::Genesis, tce::broadcast::ReliableBroadcastParams};
 use topos_core::api::grpc::tce::v1::console_service_client::ConsoleServiceClient;
 
 pub(crate) mod commands;
@@ -154,6 +156,70 @@ pub(crate) async fn handle_command(
             let exit_code = i32::from(!(node_service.call(status).await?));
             std::process::exit(exit_code);
         }
+        Some(NodeCommands::Snapshot(cmd)) => {
+            _ = setup_tracing(verbose, no_color, None, None, env!("TOPOS_VERSION"));
+
+            let config = NodeConfig::try_from::<()>(&home, &cmd.name, None)?;
+            let Some(tce_config) = config.tce.as_ref() else {
+                println!("Node {} isn't running a TCE", cmd.name);
+                std::process::exit(1);
+            };
+
+            // The node needs to be stopped, RocksDB allowing a single process to open the store
+            let store = FullNodeStore::new(&tce_config.db_path)?;
+
+            match cmd.subcommands {
+                SnapshotCommands::Export(export) => {
+                    let summary = store.export_snapshot(&export.output).await?;
+
+                    println!(
+                        "Exported {} certificates from {} source subnets to {}",
+                        summary.certificates,
+                        summary.source_heads.len(),
+                        export.output.display()
+                    );
+                }
+                SnapshotCommands::Import(import) => {
+                    let genesis_validators = Genesis::new(&config.genesis_path)?.validators()?;
+                    let threshold = import.threshold.unwrap_or(
+                        ReliableBroadcastParams::new(genesis_validators.len()).delivery_threshold
+                            as u64,
+                    );
+
+                    // The proofs are verified against the validators of every known epoch, the
+                    // genesis ones first
+                    let mut validators = vec![(genesis_validators, threshold)];
+                    validators.extend(
+                        store
+                            .epoch_validators_store()
+                            .get_all_validators()?
+                            .into_iter()
+                            .filter(|(epoch_id, _)| *epoch_id > 0)
+                            .map(|(_, validators)| {
+                                let threshold = ReliableBroadcastParams::new(validators.len())
+                                    .delivery_threshold
+                                    as u64;
+
+                                (validators, threshold)
+                            }),
+                    );
+
+                    match store.import_snapshot(&import.input, &validators).await {
+                        Ok(summary) => println!(
+                            "Imported {} certificates from {} source subnets",
+                            summary.certificates,
+                            summary.source_heads.len()
+                        ),
+                        Err(error) => {
+                            println!("Snapshot {} is rejected: {error}", import.input.display());
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
         None => Ok(()),
     }
 }
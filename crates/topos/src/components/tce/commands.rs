@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+mod db;
+mod verify_delivery;
+
+pub(crate) use db::{Db, DbCommands};
+pub(crate) use verify_delivery::VerifyDelivery;
+
+/// Utility to inspect the TCE data (e.g., verify the delivery of a certificate, migrate the
+/// database)
+#[derive(Args, Debug)]
+pub(crate) struct TceCommand {
+    #[clap(from_global)]
+    pub(crate) verbose: u8,
+
+    #[clap(from_global)]
+    pub(crate) no_color: bool,
+
+    #[clap(from_global)]
+    pub(crate) home: PathBuf,
+
+    #[clap(subcommand)]
+    pub(crate) subcommands: Option<TceCommands>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum TceCommands {
+    VerifyDelivery(Box<VerifyDelivery>),
+    Db(Box<Db>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        assert!(TceCommands::has_subcommand("verify-delivery"));
+        assert!(TceCommands::has_subcommand("db"));
+    }
+}
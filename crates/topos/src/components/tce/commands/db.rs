@@ -0,0 +1,43 @@
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug)]
+#[command(about = "Manage the TCE database of a stopped node")]
+pub(crate) struct Db {
+    /// Name to identify your node
+    #[arg(
+        long,
+        env = "TOPOS_NODE_NAME",
+        default_value = "default",
+        global = true
+    )]
+    pub(crate) name: String,
+
+    #[command(subcommand)]
+    pub(crate) subcommands: DbCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum DbCommands {
+    Migrate(Migrate),
+    Check(Check),
+}
+
+#[derive(Args, Debug)]
+#[command(about = "Migrate the database to the storage schema of this version of the node")]
+pub(crate) struct Migrate {
+    /// Report the migrations to apply without changing the database
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Check that the streams, the certificates and the indexes of the database are \
+             consistent"
+)]
+pub(crate) struct Check {
+    /// Rebuild the source heads, the target streams and the target source lists from the
+    /// delivered certificates if they don't match
+    #[arg(long)]
+    pub(crate) repair: bool,
+}
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Verify offline that a certificate has been delivered, by checking its proof of \
+             delivery against the validators of a genesis file"
+)]
+pub(crate) struct VerifyDelivery {
+    /// Path to the JSON file containing the delivered certificate and its proof of delivery.
+    /// The standard input is read if not provided
+    #[arg(long)]
+    pub(crate) input: Option<PathBuf>,
+
+    /// Path to the genesis file defining the validator set
+    #[arg(long, env = "TOPOS_GENESIS_PATH")]
+    pub(crate) genesis: PathBuf,
+
+    /// Minimum number of Ready messages required to consider the certificate as delivered.
+    /// Can't be lower than the quorum of the validator set, which is used if not provided
+    #[arg(long)]
+    pub(crate) threshold: Option<u64>,
+}
@@ -0,0 +1,146 @@
+use std::fs::File;
+
+use topos_config::{genesis::Genesis, node::NodeConfig, tce::broadcast::ReliableBroadcastParams};
+use topos_core::types::CertificateDelivered;
+use topos_tce_storage::{fullnode::FullNodeStore, integrity::IntegrityReport, migration};
+use topos_telemetry::tracing::setup_tracing;
+
+use self::commands::{DbCommands, TceCommand, TceCommands};
+
+pub(crate) mod commands;
+
+pub(crate) async fn handle_command(
+    TceCommand {
+        verbose,
+        no_color,
+        home,
+        subcommands,
+    }: TceCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match subcommands {
+        Some(TceCommands::VerifyDelivery(cmd)) => {
+            _ = setup_tracing(verbose, no_color, None, None, env!("TOPOS_VERSION"));
+
+            let delivered: CertificateDelivered = match cmd.input {
+                Some(path) => serde_json::from_reader(File::open(path)?)?,
+                None => serde_json::from_reader(std::io::stdin())?,
+            };
+
+            let validators = Genesis::new(&cmd.genesis)?.validators()?;
+            let quorum = ReliableBroadcastParams::new(validators.len()).delivery_threshold as u64;
+            let threshold = cmd.threshold.unwrap_or(quorum);
+
+            if threshold < quorum {
+                println!(
+                    "The threshold {threshold} is lower than the quorum of the {} validators \
+                     ({quorum})",
+                    validators.len()
+                );
+                std::process::exit(1);
+            }
+
+            let certificate_id = delivered.certificate.id;
+            match delivered.verify(&validators, threshold) {
+                Ok(()) => {
+                    println!(
+                        "Certificate {} is delivered: {} valid Ready messages out of {} \
+                         validators (threshold: {})",
+                        certificate_id,
+                        delivered.proof_of_delivery.readies.len(),
+                        validators.len(),
+                        threshold
+                    );
+
+                    Ok(())
+                }
+                Err(error) => {
+                    println!("Certificate {certificate_id} is not delivered: {error}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(TceCommands::Db(cmd)) => {
+            _ = setup_tracing(verbose, no_color, None, None, env!("TOPOS_VERSION"));
+
+            let config = NodeConfig::try_from::<()>(&home, &cmd.name, None)?;
+            let Some(tce_config) = config.tce.as_ref() else {
+                println!("Node {} isn't running a TCE", cmd.name);
+                std::process::exit(1);
+            };
+
+            if !tce_config.db_path.exists() {
+                println!("No database found at {}", tce_config.db_path.display());
+                std::process::exit(1);
+            }
+
+            match cmd.subcommands {
+                DbCommands::Migrate(migrate) => {
+                    let report = migration::migrate(&tce_config.db_path, migrate.dry_run)?;
+
+                    if report.steps.is_empty() {
+                        println!("Database schema is up to date at version {}", report.from);
+                    } else {
+                        println!(
+                            "{} the database schema from version {} to {}",
+                            if report.dry_run {
+                                "Would migrate"
+                            } else {
+                                "Migrated"
+                            },
+                            report.from,
+                            report.to
+                        );
+
+                        for step in &report.steps {
+                            println!(
+                                "  - version {}: {} ({} entries)",
+                                step.version, step.description, step.changes
+                            );
+                        }
+                    }
+                }
+                DbCommands::Check(check) => {
+                    let store = FullNodeStore::new(&tce_config.db_path)?;
+                    let mut report = store.check_integrity()?;
+                    print_integrity_report(&report);
+
+                    if check.repair && report.issues.iter().any(|issue| issue.is_repairable()) {
+                        report = store.repair_indexes().await?;
+                        println!("Indexes rebuilt");
+                        print_integrity_report(&report);
+                    }
+
+                    if !report.is_consistent() {
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+fn print_integrity_report(report: &IntegrityReport) {
+    println!(
+        "Checked {} certificates, {} source stream entries and {} target stream entries",
+        report.certificates, report.source_stream_entries, report.target_stream_entries
+    );
+
+    if report.is_consistent() {
+        println!("No issue found");
+    } else {
+        println!("{} issues found:", report.issues.len());
+        for issue in &report.issues {
+            println!(
+                "  - {issue}{}",
+                if issue.is_repairable() {
+                    " (repairable)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+}
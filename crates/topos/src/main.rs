@@ -16,5 +16,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ToposCommand::Setup(cmd) => components::setup::handle_command(cmd).await,
         ToposCommand::Node(cmd) => components::node::handle_command(cmd).await,
         ToposCommand::Regtest(cmd) => components::regtest::handle_command(cmd).await,
+        ToposCommand::Tce(cmd) => components::tce::handle_command(cmd).await,
     }
 }
@@ -4,6 +4,7 @@ use std::{ffi::OsString, path::PathBuf};
 use crate::components::node::commands::NodeCommand;
 use crate::components::regtest::commands::RegtestCommand;
 use crate::components::setup::commands::SetupCommand;
+use crate::components::tce::commands::TceCommand;
 
 pub(crate) mod input_format;
 
@@ -50,4 +51,5 @@ pub(crate) enum ToposCommand {
     Setup(SetupCommand),
     Node(NodeCommand),
     Regtest(RegtestCommand),
+    Tce(TceCommand),
 }
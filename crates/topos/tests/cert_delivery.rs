@@ -147,7 +147,9 @@ async fn cert_delivery() {
                     target_subnet_ids: vec![in_stream_subnet_id.into()],
                     positions: Vec::new()
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                max_unacknowledged: 0,
+                target_subnet_filter: vec![],
             }.into();
         };
 
@@ -375,7 +377,9 @@ async fn assert_certificate_full_delivery(
                         target_subnet_ids: vec![[2u8; SUBNET_ID_LENGTH].into()],
                         positions: vec![]
                     }),
-                    source_checkpoint: None
+                    source_checkpoint: None,
+                    max_unacknowledged: 0,
+                    target_subnet_filter: vec![],
                 }.into()
             };
 
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use tempfile::tempdir;
+use topos_core::types::{CertificateDelivered, ProofOfDelivery, ValidatorId};
+use topos_crypto::messages::MessageSigner;
+use topos_test_sdk::certificates::create_certificate_chain;
+use topos_test_sdk::constants::{SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1};
+
+fn genesis_example_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../topos-config/assets/genesis-example.json")
+}
+
+/// RLP encoding of a list of less than 256 bytes
+fn rlp_list(payload: Vec<u8>) -> Vec<u8> {
+    let mut list = if payload.len() < 56 {
+        vec![0xc0 + payload.len() as u8]
+    } else {
+        vec![0xf8, payload.len() as u8]
+    };
+    list.extend(payload);
+
+    list
+}
+
+/// Write a genesis file whose `extraData` lists the validators of the given signers, each one
+/// with an empty BLS key
+fn write_genesis(path: &Path, signers: &[MessageSigner]) -> Result<(), Box<dyn std::error::Error>> {
+    let validators = signers
+        .iter()
+        .flat_map(|signer| {
+            let mut validator = vec![0x94];
+            validator.extend_from_slice(signer.public_address.as_bytes());
+            validator.push(0x80);
+
+            rlp_list(validator)
+        })
+        .collect();
+
+    let mut extra_data = vec![0; 32];
+    extra_data.extend(rlp_list(rlp_list(validators)));
+
+    std::fs::write(
+        path,
+        serde_json::to_vec(&serde_json::json!({
+            "genesis": { "extraData": format!("0x{}", hex::encode(extra_data)) }
+        }))?,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn tce_verify_delivery_help_display() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("topos")?;
+    cmd.arg("tce").arg("verify-delivery").arg("-h");
+
+    let output = cmd.assert().success();
+
+    let result: &str = std::str::from_utf8(&output.get_output().stdout)?;
+
+    assert!(result.contains("Usage: topos tce verify-delivery [OPTIONS] --genesis <GENESIS>"));
+
+    Ok(())
+}
+
+#[test]
+fn tce_verify_delivery_without_quorum() -> Result<(), Box<dyn std::error::Error>> {
+    let certificate: CertificateDelivered =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+            .pop()
+            .unwrap();
+
+    let tmp = tempdir()?;
+    let input = tmp.path().join("certificate.json");
+    std::fs::write(&input, serde_json::to_vec(&certificate)?)?;
+
+    let mut cmd = Command::cargo_bin("topos")?;
+    cmd.arg("tce")
+        .arg("verify-delivery")
+        .arg("--genesis")
+        .arg(genesis_example_path())
+        .arg("--input")
+        .arg(&input);
+
+    let output = cmd.assert().failure();
+
+    let result: &str = std::str::from_utf8(&output.get_output().stdout)?;
+
+    assert!(result.contains(&format!(
+        "Certificate {} is not delivered",
+        certificate.certificate.id
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn tce_verify_delivery_with_quorum() -> Result<(), Box<dyn std::error::Error>> {
+    let signers = (1..=4)
+        .map(|i| MessageSigner::new(&[i; 32]))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut certificate: CertificateDelivered =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+            .pop()
+            .unwrap();
+    let proof = &mut certificate.proof_of_delivery;
+    proof.threshold = 3;
+    for signer in &signers[..3] {
+        let validator_id = ValidatorId::from(signer.public_address);
+        let payload = ProofOfDelivery::ready_payload(&proof.certificate_id, &validator_id);
+        proof
+            .readies
+            .push((validator_id, signer.sign_message(&payload)?));
+    }
+
+    let tmp = tempdir()?;
+    let genesis = tmp.path().join("genesis.json");
+    write_genesis(&genesis, &signers)?;
+    let input = tmp.path().join("certificate.json");
+    std::fs::write(&input, serde_json::to_vec(&certificate)?)?;
+
+    let mut cmd = Command::cargo_bin("topos")?;
+    cmd.arg("tce")
+        .arg("verify-delivery")
+        .arg("--genesis")
+        .arg(&genesis)
+        .arg("--input")
+        .arg(&input);
+
+    let output = cmd.assert().success();
+
+    let result: &str = std::str::from_utf8(&output.get_output().stdout)?;
+
+    assert!(result.contains(&format!(
+        "Certificate {} is delivered: 3 valid Ready messages out of 4 validators (threshold: 3)",
+        certificate.certificate.id
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn tce_verify_delivery_below_quorum_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    let certificate: CertificateDelivered =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1)
+            .pop()
+            .unwrap();
+
+    let tmp = tempdir()?;
+    let input = tmp.path().join("certificate.json");
+    std::fs::write(&input, serde_json::to_vec(&certificate)?)?;
+
+    let mut cmd = Command::cargo_bin("topos")?;
+    cmd.arg("tce")
+        .arg("verify-delivery")
+        .arg("--genesis")
+        .arg(genesis_example_path())
+        .arg("--input")
+        .arg(&input)
+        .arg("--threshold")
+        .arg("0");
+
+    let output = cmd.assert().failure();
+
+    let result: &str = std::str::from_utf8(&output.get_output().stdout)?;
+
+    assert!(result.contains("The threshold 0 is lower than the quorum of the 4 validators (3)"));
+
+    Ok(())
+}